@@ -13,7 +13,11 @@ use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Through
 use digest::{crypto_common::generic_array::ArrayLength, Digest, DynDigest, OutputSizeUser};
 use jf_primitives::{
     pcs::{checked_fft_size, prelude::UnivariateKzgPCS, PolynomialCommitmentScheme},
-    vid::{advz::Advz, VidScheme},
+    vid::{
+        advz::{payload_prover::SmallRangeProof, Advz},
+        payload_prover::PayloadProver,
+        VidScheme,
+    },
 };
 use sha2::Sha256;
 
@@ -112,6 +116,48 @@ where
         }
         grp.finish();
 
+        // 100 small proofs, each computed from scratch
+        const NUM_PROOFS: usize = 100;
+        let mut grp = c.benchmark_group(benchmark_group_name("small_proofs"));
+        for (poly_degree, num_storage_nodes) in vid_sizes_iter.clone() {
+            let advz = Advz::<E, H>::new(poly_degree, num_storage_nodes, &srs).unwrap();
+            grp.bench_with_input(
+                BenchmarkId::from_parameter(num_storage_nodes),
+                &num_storage_nodes,
+                |b, _| {
+                    b.iter(|| {
+                        for i in 0..NUM_PROOFS {
+                            let start = i * 97 % (poly_degree * 31 - 10);
+                            let _: SmallRangeProof<_> = advz
+                                .payload_proof(&payload_bytes, start..start + 10)
+                                .unwrap();
+                        }
+                    });
+                },
+            );
+        }
+        grp.finish();
+
+        // the same 100 small proofs via the offline/online split
+        let mut grp = c.benchmark_group(benchmark_group_name("small_proofs_offline_online"));
+        for (poly_degree, num_storage_nodes) in vid_sizes_iter.clone() {
+            let advz = Advz::<E, H>::new(poly_degree, num_storage_nodes, &srs).unwrap();
+            grp.bench_with_input(
+                BenchmarkId::from_parameter(num_storage_nodes),
+                &num_storage_nodes,
+                |b, _| {
+                    b.iter(|| {
+                        let offline = advz.offline_precompute(&payload_bytes).unwrap();
+                        for i in 0..NUM_PROOFS {
+                            let start = i * 97 % (poly_degree * 31 - 10);
+                            advz.online_proof(&offline, start..start + 10).unwrap();
+                        }
+                    });
+                },
+            );
+        }
+        grp.finish();
+
         // recover
         let mut grp = c.benchmark_group(benchmark_group_name("recover"));
         grp.throughput(Throughput::Bytes(len as u64));
@@ -132,6 +178,49 @@ where
             );
         }
         grp.finish();
+
+        // recover without parallelism across polynomials, for comparison with
+        // `recover` if the `parallel` feature is enabled
+        let mut grp = c.benchmark_group(benchmark_group_name("recover_serial"));
+        grp.throughput(Throughput::Bytes(len as u64));
+        for (poly_degree, num_storage_nodes) in vid_sizes_iter.clone() {
+            let mut advz = Advz::<E, H>::new(poly_degree, num_storage_nodes, &srs).unwrap();
+            let disperse = advz.disperse(&payload_bytes).unwrap();
+            let (shares, common) = (disperse.shares, disperse.common);
+            advz.set_parallel_threshold(usize::MAX);
+            grp.bench_with_input(
+                BenchmarkId::from_parameter(num_storage_nodes),
+                &num_storage_nodes,
+                |b, _| {
+                    b.iter(|| {
+                        advz.recover_payload(&shares[..poly_degree], &common)
+                            .unwrap()
+                    });
+                },
+            );
+        }
+        grp.finish();
+
+        // recover from shares in columnar layout
+        let mut grp = c.benchmark_group(benchmark_group_name("recover_columns"));
+        grp.throughput(Throughput::Bytes(len as u64));
+        for (poly_degree, num_storage_nodes) in vid_sizes_iter.clone() {
+            let advz = Advz::<E, H>::new(poly_degree, num_storage_nodes, &srs).unwrap();
+            let (disperse, mut share_columns) = advz.disperse_columns(&payload_bytes).unwrap();
+            share_columns.truncate(poly_degree);
+            let common = disperse.common;
+            grp.bench_with_input(
+                BenchmarkId::from_parameter(num_storage_nodes),
+                &num_storage_nodes,
+                |b, _| {
+                    b.iter(|| {
+                        advz.recover_payload_columns(&share_columns, &common)
+                            .unwrap()
+                    });
+                },
+            );
+        }
+        grp.finish();
     }
 }
 
@@ -10,7 +10,10 @@ use super::{
     DigestAlgorithm, Element, Index, LookupResult, MerkleCommitment, NodeValue, ToTraversalPath,
 };
 use crate::errors::{PrimitivesError, VerificationResult};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use ark_std::{
     borrow::Borrow, boxed::Box, format, iter::Peekable, string::ToString, vec, vec::Vec,
 };
@@ -72,6 +75,160 @@ where
     }
 }
 
+impl<E, I, T> MerkleNode<E, I, T>
+where
+    E: Element + CanonicalSerialize,
+    I: Index + CanonicalSerialize,
+    T: NodeValue,
+{
+    // recursion through a fixed writer type, as `&mut W` at each level would
+    // not terminate monomorphization
+    fn serialize_node<W: Write>(
+        &self,
+        writer: &mut W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        match self {
+            Self::Empty => 0u8.serialize_with_mode(writer, compress),
+            Self::Branch { value, children } => {
+                1u8.serialize_with_mode(&mut *writer, compress)?;
+                value.serialize_with_mode(&mut *writer, compress)?;
+                (children.len() as u64).serialize_with_mode(&mut *writer, compress)?;
+                for child in children.iter() {
+                    child.serialize_node(writer, compress)?;
+                }
+                Ok(())
+            },
+            Self::Leaf { value, pos, elem } => {
+                2u8.serialize_with_mode(&mut *writer, compress)?;
+                value.serialize_with_mode(&mut *writer, compress)?;
+                pos.serialize_with_mode(&mut *writer, compress)?;
+                elem.serialize_with_mode(writer, compress)
+            },
+            Self::ForgettenSubtree { value } => {
+                3u8.serialize_with_mode(&mut *writer, compress)?;
+                value.serialize_with_mode(writer, compress)
+            },
+        }
+    }
+}
+
+impl<E, I, T> MerkleNode<E, I, T>
+where
+    E: Element + CanonicalDeserialize,
+    I: Index + CanonicalDeserialize,
+    T: NodeValue,
+{
+    // same as `serialize_node`
+    fn deserialize_node<R: Read>(
+        reader: &mut R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        match u8::deserialize_with_mode(&mut *reader, compress, validate)? {
+            0 => Ok(Self::Empty),
+            1 => {
+                let value = T::deserialize_with_mode(&mut *reader, compress, validate)?;
+                let num_children = u64::deserialize_with_mode(&mut *reader, compress, validate)?;
+                // no preallocation: `num_children` is untrusted
+                let mut children = Vec::new();
+                for _ in 0..num_children {
+                    children.push(Box::new(Self::deserialize_node(
+                        reader, compress, validate,
+                    )?));
+                }
+                Ok(Self::Branch { value, children })
+            },
+            2 => Ok(Self::Leaf {
+                value: T::deserialize_with_mode(&mut *reader, compress, validate)?,
+                pos: I::deserialize_with_mode(&mut *reader, compress, validate)?,
+                elem: E::deserialize_with_mode(reader, compress, validate)?,
+            }),
+            3 => Ok(Self::ForgettenSubtree {
+                value: T::deserialize_with_mode(reader, compress, validate)?,
+            }),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+// The canonical encoding of a node is a tag byte followed by its fields:
+// `0` for `Empty`, `1` for `Branch` followed by the value and the number of
+// children then each child, `2` for `Leaf` followed by the value, position
+// and element, and `3` for `ForgettenSubtree` followed by the value.
+impl<E, I, T> CanonicalSerialize for MerkleNode<E, I, T>
+where
+    E: Element + CanonicalSerialize,
+    I: Index + CanonicalSerialize,
+    T: NodeValue,
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.serialize_node(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        1 + match self {
+            Self::Empty => 0,
+            Self::Branch { value, children } => {
+                value.serialized_size(compress)
+                    + 0u64.serialized_size(compress)
+                    + children
+                        .iter()
+                        .map(|child| child.serialized_size(compress))
+                        .sum::<usize>()
+            },
+            Self::Leaf { value, pos, elem } => {
+                value.serialized_size(compress)
+                    + pos.serialized_size(compress)
+                    + elem.serialized_size(compress)
+            },
+            Self::ForgettenSubtree { value } => value.serialized_size(compress),
+        }
+    }
+}
+
+impl<E, I, T> Valid for MerkleNode<E, I, T>
+where
+    E: Element + Valid,
+    I: Index + Valid,
+    T: NodeValue,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        match self {
+            Self::Empty => Ok(()),
+            Self::Branch { value, children } => {
+                value.check()?;
+                children.iter().try_for_each(|child| child.check())
+            },
+            Self::Leaf { value, pos, elem } => {
+                value.check()?;
+                pos.check()?;
+                elem.check()
+            },
+            Self::ForgettenSubtree { value } => value.check(),
+        }
+    }
+}
+
+impl<E, I, T> CanonicalDeserialize for MerkleNode<E, I, T>
+where
+    E: Element + CanonicalDeserialize,
+    I: Index + CanonicalDeserialize,
+    T: NodeValue,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Self::deserialize_node(&mut reader, compress, validate)
+    }
+}
+
 pub type MerklePath<E, I, T> = Vec<MerkleNode<E, I, T>>;
 
 /// A merkle commitment consists a root hash value, a tree height and number of
@@ -174,6 +331,60 @@ where
     }
 }
 
+// The canonical encoding of a proof is its position followed by its path.
+impl<E, I, T, Arity> CanonicalSerialize for MerkleProof<E, I, T, Arity>
+where
+    E: Element + CanonicalSerialize,
+    I: Index + CanonicalSerialize,
+    T: NodeValue,
+    Arity: Unsigned,
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.pos.serialize_with_mode(&mut writer, compress)?;
+        self.proof.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.pos.serialized_size(compress) + self.proof.serialized_size(compress)
+    }
+}
+
+impl<E, I, T, Arity> Valid for MerkleProof<E, I, T, Arity>
+where
+    E: Element + Valid,
+    I: Index + Valid,
+    T: NodeValue,
+    Arity: Unsigned + Sync,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.pos.check()?;
+        self.proof.check()
+    }
+}
+
+impl<E, I, T, Arity> CanonicalDeserialize for MerkleProof<E, I, T, Arity>
+where
+    E: Element + CanonicalDeserialize,
+    I: Index + CanonicalDeserialize,
+    T: NodeValue,
+    Arity: Unsigned + Sync,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self::new(
+            I::deserialize_with_mode(&mut reader, compress, validate)?,
+            MerklePath::deserialize_with_mode(&mut reader, compress, validate)?,
+        ))
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub(crate) fn build_tree_internal<E, H, I, Arity, T>(
     height: usize,
@@ -194,6 +194,7 @@ mod mt_tests {
     use ark_ed_on_bls12_377::Fq as Fq377;
     use ark_ed_on_bls12_381::Fq as Fq381;
     use ark_ed_on_bn254::Fq as Fq254;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
     use hashbrown::HashMap;
     use num_bigint::BigUint;
     use typenum::U3;
@@ -514,5 +515,19 @@ mod mt_tests {
             *node,
             bincode::deserialize(&bincode::serialize(node).unwrap()).unwrap()
         );
+
+        for proof in [&mem_proof, &non_mem_proof] {
+            let mut bytes = Vec::new();
+            proof.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(bytes.len(), proof.compressed_size());
+            assert_eq!(
+                *proof,
+                CanonicalDeserialize::deserialize_compressed(bytes.as_slice()).unwrap()
+            );
+            assert!(
+                <MerkleProof<F, F, F, U3>>::deserialize_compressed(&bytes[..bytes.len() - 1])
+                    .is_err()
+            );
+        }
     }
 }
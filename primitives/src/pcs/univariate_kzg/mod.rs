@@ -294,24 +294,15 @@ impl<E: Pairing> PolynomialCommitmentScheme for UnivariateKzgPCS<E> {
     ///
     /// Only accept `polynomial` with power-of-two degree, no constraint on the
     /// size of `points`
+    ///
+    /// With the `parallel` feature the points are processed concurrently. See
+    /// [`UnivariateKzgPCS::multi_open_serial`] for a serial alternative.
     fn multi_open(
         prover_param: impl Borrow<UnivariateProverParam<E>>,
         polynomial: &Self::Polynomial,
         points: &[Self::Point],
     ) -> Result<(Vec<Self::Proof>, Vec<Self::Evaluation>), PCSError> {
-        let h_poly = Self::compute_h_poly_in_fk23(prover_param, &polynomial.coeffs)?;
-        let proofs: Vec<_> = h_poly
-            .batch_evaluate(points)
-            .into_iter()
-            .map(|g| UnivariateKzgProof {
-                proof: g.into_affine(),
-            })
-            .collect();
-
-        // Evaluate at all points
-        let evals =
-            GeneralDensePolynomial::from_coeff_slice(&polynomial.coeffs).batch_evaluate(points);
-        Ok((proofs, evals))
+        Self::multi_open_internal(prover_param, polynomial, points, true)
     }
 }
 
@@ -349,11 +340,67 @@ impl<E: Pairing> UnivariatePCS for UnivariateKzgPCS<E> {
     }
 }
 
+impl<E: Pairing> UnivariateKzgPCS<E> {
+    /// Deterministically generate an SRS of degree `supported_degree` from
+    /// `seed`.
+    ///
+    /// Anyone who knows `seed` also knows the trapdoor, so identical test
+    /// vectors can be reproduced across machines.
+    ///
+    /// WARNING: THIS FUNCTION IS FOR TESTING PURPOSE ONLY.
+    /// THE OUTPUT SRS IS INSECURE AND SHOULD NOT BE USED IN PRODUCTION.
+    #[cfg(any(test, feature = "test-srs"))]
+    pub fn gen_srs_from_seed(
+        seed: [u8; 32],
+        supported_degree: usize,
+    ) -> Result<UnivariateUniversalParams<E>, PCSError> {
+        use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+        Self::gen_srs_for_testing(&mut ChaCha20Rng::from_seed(seed), supported_degree)
+    }
+}
+
 impl<E, F> UnivariateKzgPCS<E>
 where
     E: Pairing<ScalarField = F>,
     F: FftField,
 {
+    /// Same as [`PolynomialCommitmentScheme::multi_open`] except that the
+    /// points are processed serially even with the `parallel` feature.
+    ///
+    /// Useful for callers that already parallelize at a coarser level, such as
+    /// across polynomials.
+    pub fn multi_open_serial(
+        prover_param: impl Borrow<UnivariateProverParam<E>>,
+        polynomial: &DensePolynomial<F>,
+        points: &[F],
+    ) -> Result<(Vec<UnivariateKzgProof<E>>, Vec<F>), PCSError> {
+        Self::multi_open_internal(prover_param, polynomial, points, false)
+    }
+
+    fn multi_open_internal(
+        prover_param: impl Borrow<UnivariateProverParam<E>>,
+        polynomial: &DensePolynomial<F>,
+        points: &[F],
+        parallel: bool,
+    ) -> Result<(Vec<UnivariateKzgProof<E>>, Vec<F>), PCSError> {
+        let h_poly = Self::compute_h_poly_in_fk23(prover_param, &polynomial.coeffs)?;
+
+        // each point is independent: the proof is an evaluation of `h_poly`
+        let open = |point: &F| {
+            (
+                UnivariateKzgProof {
+                    proof: h_poly.evaluate(point).into_affine(),
+                },
+                polynomial.evaluate(point),
+            )
+        };
+        Ok(if parallel {
+            parallelizable_slice_iter(points).map(open).unzip()
+        } else {
+            points.iter().map(open).unzip()
+        })
+    }
+
     // Sec 2.2. of <https://eprint.iacr.org/2023/033>
     fn compute_h_poly_in_fk23(
         prover_param: impl Borrow<UnivariateProverParam<E>>,
@@ -609,4 +656,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn multi_open_parallel_matches_serial() -> Result<(), PCSError> {
+        type E = Bls12_381;
+        type Fr = ark_bls12_381::Fr;
+
+        let mut rng = test_rng();
+        let degree = 16;
+        let pp = UnivariateKzgPCS::<E>::gen_srs_for_testing(&mut rng, 2 * degree)?;
+        let (ck, _) = UnivariateKzgPCS::<E>::trim_fft_size(&pp, degree)?;
+        let poly = <DensePolynomial<Fr> as DenseUVPolynomial<Fr>>::rand(degree, &mut rng);
+
+        for num_points in [0, 1, 64] {
+            let points: Vec<Fr> = (0..num_points).map(|_| Fr::rand(&mut rng)).collect();
+            assert_eq!(
+                UnivariateKzgPCS::<E>::multi_open(&ck, &poly, &points)?,
+                UnivariateKzgPCS::<E>::multi_open_serial(&ck, &poly, &points)?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn gen_srs_from_seed() -> Result<(), PCSError> {
+        type E = Bls12_381;
+        let degree = 16;
+        let seed = [7u8; 32];
+
+        let pp = UnivariateKzgPCS::<E>::gen_srs_from_seed(seed, degree)?;
+        assert_eq!(pp, UnivariateKzgPCS::<E>::gen_srs_from_seed(seed, degree)?);
+
+        let mut other_seed = seed;
+        other_seed[0] ^= 1;
+        assert_ne!(
+            pp,
+            UnivariateKzgPCS::<E>::gen_srs_from_seed(other_seed, degree)?
+        );
+        Ok(())
+    }
 }
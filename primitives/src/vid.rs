@@ -6,8 +6,9 @@
 
 //! Trait and implementation for a Verifiable Information Retrieval (VID).
 /// See <https://arxiv.org/abs/2111.12323> section 1.3--1.4 for intro to VID semantics.
+use crate::pcs::prelude::PCSError;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{error::Error, fmt::Debug, hash::Hash, string::String, vec::Vec};
+use ark_std::{error::Error, fmt::Debug, format, hash::Hash, string::String, vec, vec::Vec};
 use displaydoc::Display;
 use serde::{Deserialize, Serialize};
 
@@ -77,6 +78,8 @@ pub trait VidScheme {
 )]
 pub struct VidDisperse<V: VidScheme + ?Sized> {
     /// VID disperse shares to send to the storage nodes.
+    ///
+    /// Ordered by storage node index so that serialization is deterministic.
     pub shares: Vec<V::Share>,
     /// VID common data to send to all storage nodes.
     pub common: V::Common,
@@ -84,6 +87,164 @@ pub struct VidDisperse<V: VidScheme + ?Sized> {
     pub commit: V::Commit,
 }
 
+/// Magic bytes identifying a concrete instantiation of a [`VidScheme`].
+///
+/// Written into the blob produced by [`VidDisperse::to_bytes`] so that a blob
+/// for one instantiation (eg. one curve) fails to deserialize as another
+/// instead of producing garbage.
+pub trait VidSchemeMagic {
+    /// Magic bytes for `Self`.
+    fn magic() -> [u8; 4];
+}
+
+impl<V> VidDisperse<V>
+where
+    V: VidScheme + VidSchemeMagic + ?Sized,
+    V::Share: CanonicalSerialize + CanonicalDeserialize,
+    V::Commit: AsRef<[u8]> + AsMut<[u8]> + Default,
+{
+    /// Version byte at the start of the blob produced by
+    /// [`VidDisperse::to_bytes`].
+    ///
+    /// Version 1 framed `bincode` encodings and is no longer supported.
+    pub const BYTES_VERSION: u8 = 2;
+
+    /// Serialize `self` into a single blob suitable for storage in one file or
+    /// DB value.
+    ///
+    /// The blob is the version byte [`VidDisperse::BYTES_VERSION`] followed
+    /// by the magic bytes [`VidSchemeMagic::magic`], length-framed `commit`,
+    /// `common`, the number of shares and length-framed `shares`. Lengths are
+    /// little-endian `u64`. The `commit` frame holds its raw bytes and every
+    /// other frame a compressed [`CanonicalSerialize`] encoding, so the blob
+    /// can be read without this crate.
+    pub fn to_bytes(&self) -> VidResult<Vec<u8>> {
+        let mut bytes = vec![Self::BYTES_VERSION];
+        bytes.extend_from_slice(&V::magic());
+        write_frame(&mut bytes, self.commit.as_ref());
+        write_frame(&mut bytes, &serialize(&self.common)?);
+        bytes.extend_from_slice(&(self.shares.len() as u64).to_le_bytes());
+        for share in self.shares.iter() {
+            write_frame(&mut bytes, &serialize(share)?);
+        }
+        Ok(bytes)
+    }
+
+    /// Deserialize a blob produced by [`VidDisperse::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `bytes` is truncated, has trailing
+    /// data, has an unsupported version, was produced by a different
+    /// instantiation of [`VidScheme`] or is otherwise malformed.
+    pub fn from_bytes(mut bytes: &[u8]) -> VidResult<Self> {
+        let version = *bytes
+            .first()
+            .ok_or_else(|| VidError::Argument("empty blob".into()))?;
+        if version != Self::BYTES_VERSION {
+            return Err(VidError::Argument(format!(
+                "unsupported blob version {}, expected {}",
+                version,
+                Self::BYTES_VERSION
+            )));
+        }
+        bytes = &bytes[1..];
+
+        if bytes.len() < 4 {
+            return Err(VidError::Argument("truncated blob".into()));
+        }
+        let (magic, rest) = bytes.split_at(4);
+        if magic != V::magic() {
+            return Err(VidError::Argument(format!(
+                "blob magic {:02x?} differs from expected {:02x?}: blob is for a different VID scheme instantiation",
+                magic,
+                V::magic()
+            )));
+        }
+        bytes = rest;
+
+        let commit_frame = read_frame(&mut bytes)?;
+        let mut commit = V::Commit::default();
+        if commit_frame.len() != commit.as_ref().len() {
+            return Err(VidError::Argument(format!(
+                "commit frame len {} differs from commit len {}",
+                commit_frame.len(),
+                commit.as_ref().len()
+            )));
+        }
+        commit.as_mut().copy_from_slice(commit_frame);
+        let common = deserialize(read_frame(&mut bytes)?)?;
+        let num_shares = read_len(&mut bytes)?;
+        let mut shares = Vec::new();
+        for _ in 0..num_shares {
+            shares.push(deserialize(read_frame(&mut bytes)?)?);
+        }
+        if !bytes.is_empty() {
+            return Err(VidError::Argument(format!(
+                "{} trailing bytes in blob",
+                bytes.len()
+            )));
+        }
+        Ok(Self {
+            shares,
+            common,
+            commit,
+        })
+    }
+}
+
+fn write_frame(bytes: &mut Vec<u8>, frame: &[u8]) {
+    bytes.extend_from_slice(&(frame.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(frame);
+}
+
+fn read_len(bytes: &mut &[u8]) -> VidResult<usize> {
+    const LEN_BYTES: usize = core::mem::size_of::<u64>();
+    if bytes.len() < LEN_BYTES {
+        return Err(VidError::Argument("truncated blob".into()));
+    }
+    let (len, rest) = bytes.split_at(LEN_BYTES);
+    *bytes = rest;
+    usize::try_from(u64::from_le_bytes(
+        len.try_into().expect("slice len should equal LEN_BYTES"),
+    ))
+    .map_err(|_| VidError::Argument("frame len overflows usize".into()))
+}
+
+fn read_frame<'a>(bytes: &mut &'a [u8]) -> VidResult<&'a [u8]> {
+    let len = read_len(bytes)?;
+    if bytes.len() < len {
+        return Err(VidError::Argument("truncated blob".into()));
+    }
+    let (frame, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(frame)
+}
+
+fn serialize<T>(value: &T) -> VidResult<Vec<u8>>
+where
+    T: CanonicalSerialize,
+{
+    let mut frame = Vec::with_capacity(value.compressed_size());
+    value.serialize_compressed(&mut frame).map_err(vid)?;
+    Ok(frame)
+}
+
+fn deserialize<T>(mut frame: &[u8]) -> VidResult<T>
+where
+    T: CanonicalDeserialize,
+{
+    let value = T::deserialize_compressed(&mut frame)
+        .map_err(|e| VidError::Argument(format!("malformed blob: {}", e)))?;
+    if !frame.is_empty() {
+        return Err(VidError::Argument(format!(
+            "{} trailing bytes in blob frame",
+            frame.len()
+        )));
+    }
+    Ok(value)
+}
+
 pub mod payload_prover;
 
 pub mod advz; // instantiation of `VidScheme`
@@ -97,9 +258,24 @@ pub enum VidError {
     Argument(String),
     /// internal error: {0}
     Internal(anyhow::Error),
+    /// polynomial commitment scheme error: {0}
+    Pcs(PCSError),
 }
 
-impl Error for VidError {}
+impl Error for VidError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Pcs(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<PCSError> for VidError {
+    fn from(e: PCSError) -> Self {
+        Self::Pcs(e)
+    }
+}
 
 /// Convenience wrapper to convert any error into a [`VidError`].
 ///
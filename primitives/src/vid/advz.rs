@@ -8,47 +8,206 @@
 //!
 //! `advz` named for the authors Alhaddad-Duan-Varia-Zhang.
 
-use super::{vid, VidDisperse, VidError, VidResult, VidScheme};
+use super::{vid, VidDisperse, VidError, VidResult, VidScheme, VidSchemeMagic};
 use crate::{
     alloc::string::ToString,
+    crhf::{VariableLengthRescueCRHF, CRHF},
     merkle_tree::{
-        hasher::{HasherDigest, HasherMerkleTree},
-        MerkleCommitment, MerkleTreeScheme,
+        hasher::{HasherDigest, HasherDigestAlgorithm, HasherMerkleTree, HasherNode},
+        DigestAlgorithm, MerkleCommitment, MerkleTreeScheme,
     },
     pcs::{
-        prelude::UnivariateKzgPCS, PolynomialCommitmentScheme, StructuredReferenceString,
-        UnivariatePCS,
+        checked_fft_size,
+        prelude::{UnivariateKzgPCS, UnivariateKzgProof},
+        PolynomialCommitmentScheme, StructuredReferenceString, UnivariatePCS,
     },
     reed_solomon_code::reed_solomon_erasure_decode_rou,
+    rescue::RescueParameter,
 };
-use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_ff::{Field, PrimeField};
+use ark_ec::{
+    pairing::Pairing, scalar_mul::variable_base::VariableBaseMSM, AffineRepr, CurveGroup,
+};
+use ark_ff::{BigInteger, FftField, Field, PrimeField};
 use ark_poly::{DenseUVPolynomial, EvaluationDomain, Radix2EvaluationDomain};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use ark_std::{
     borrow::Borrow,
-    end_timer, format,
+    end_timer,
+    fmt::{self, Debug, Formatter},
+    format,
     marker::PhantomData,
-    ops::{Add, Mul},
+    ops::{Add, Mul, Range},
+    rand::{CryptoRng, RngCore},
     start_timer, vec,
     vec::Vec,
-    Zero,
+    One, UniformRand, Zero,
 };
-use bytes_to_field::{bytes_to_field, field_to_bytes};
+use bytes_to_field::{bytes_to_field, elem_byte_capacity, field_to_bytes};
 use derivative::Derivative;
 use digest::crypto_common::Output;
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use jf_utils::canonical;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 mod bytes_to_field;
 pub mod payload_prover;
 
+pub use bytes_to_field::{
+    bytes_to_field_packed, bytes_to_field_tagged, field_to_bytes_packed, field_to_bytes_tagged,
+    packed_elem_bit_capacity, tagged_elem_byte_capacity,
+};
+
+/// Default for [`Advz::set_parallel_threshold`].
+pub const DEFAULT_PARALLEL_THRESHOLD: usize = 4;
+
+/// Which data each [`Share`] carries, set via [`Advz::set_share_encoding`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ShareEncoding {
+    /// Each share carries its evals, the KZG aggregate proof and the merkle
+    /// path of its evals under `all_evals_digest`.
+    #[default]
+    Full,
+    /// Same as [`ShareEncoding::Full`] except that the merkle path is
+    /// omitted. Instead [`Common`] carries the leaf digests of the merkle
+    /// tree, from which [`VidScheme::verify_share`] authenticates the evals
+    /// of each share against `all_evals_digest`.
+    ///
+    /// Each share saves a merkle path at the cost of one digest per storage
+    /// node in `common`, so this pays off when `common` is broadcast
+    /// separately.
+    Minimal,
+}
+
+/// How payload bytes are packed into field elements, set via
+/// [`Advz::set_field_packing`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum FieldPacking {
+    /// Each field element holds a whole number of bytes, see
+    /// [`bytes_to_field`].
+    #[default]
+    Bytes,
+    /// Payload bits are packed densely across field element boundaries, see
+    /// [`bytes_to_field_packed`]. Large payloads encode into fewer
+    /// polynomials.
+    Bits,
+}
+
+/// Format of the payload commitment [`VidScheme::Commit`], fixed at
+/// construction via [`Advz::new_with_commit_version`].
+///
+/// Commitments are consensus-visible: a commitment computed under one
+/// version does not verify under another, so all parties must agree on the
+/// version. To migrate, switch every party to the new version at an agreed
+/// point, eg. a block height, and keep verifying commitments made before
+/// that point under the old version.
+///
+/// In the formats below `H` is the hash of the [`Advz`] instance, `||` is
+/// concatenation, `c_i` is the uncompressed encoding of the commitment to
+/// polynomial `i` and `d` is the digest of the header given to
+/// [`Advz::disperse_with_header`], omitted if there is none.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum CommitVersion {
+    /// `H(c_0 || ... || c_{n-1} || d)`, the original format. The default, so
+    /// that commitments published before versioning remain valid.
+    #[default]
+    V0 = 0,
+    /// `H(H(c_0) || ... || H(c_{n-1}) || d)`, which can be checked against a
+    /// [`CompactCommon`].
+    V1 = 1,
+    /// `H(v || len)` where `v` is the [`CommitVersion::V1`] commitment and
+    /// `len` is the payload byte length as a little-endian `u64`, so that
+    /// the commitment binds the payload byte length. Required by
+    /// [`Advz::length_proof`] and [`Advz::reconstruct_common`].
+    V2 = 2,
+    /// `r`, the root of the merkle tree whose leaves are the `c_i`, as
+    /// returned by [`Advz::poly_commits_merkle_root`], so that a single
+    /// polynomial commitment can be proved against the commitment via
+    /// [`Advz::verify_poly_commit_inclusion`]. Does not support a header.
+    V3 = 3,
+    /// The canonical encoding of the field element returned by
+    /// [`Advz::poly_commits_hash_field`], so that the commitment can be
+    /// carried through a recursive proof. Selected via
+    /// [`Advz::new_with_field_commit`] rather than
+    /// [`Advz::new_with_commit_version`]. Does not support a header.
+    V4 = 4,
+}
+
+/// A fixed permutation of the elements of each polynomial, set via
+/// [`Advz::set_permutation`].
+///
+/// Maps the logical offset of an element within its polynomial to its
+/// physical offset: the point of the evaluation domain at which the element
+/// is encoded.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Permutation {
+    // `physical[i]` is the physical offset of logical offset `i`
+    physical: Vec<usize>,
+    // inverse of `physical`
+    logical: Vec<usize>,
+}
+
+impl Permutation {
+    /// The permutation that maps logical offset `i` to physical offset
+    /// `physical[i]`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `physical` is not a permutation of
+    /// `0..physical.len()`.
+    pub fn new(physical: Vec<usize>) -> VidResult<Self> {
+        let mut logical = vec![usize::MAX; physical.len()];
+        for (i, p) in physical.iter().enumerate() {
+            match logical.get_mut(*p) {
+                Some(l) if *l == usize::MAX => *l = i,
+                _ => {
+                    return Err(VidError::Argument(format!(
+                        "physical offset {} out of bounds or repeated",
+                        p
+                    )))
+                },
+            }
+        }
+        Ok(Self { physical, logical })
+    }
+
+    /// The permutation that transposes a `rows` by `cols` matrix stored in
+    /// row-major order, so that each logical row is strided across the
+    /// physical offsets.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `rows * cols` overflows.
+    pub fn transpose(rows: usize, cols: usize) -> VidResult<Self> {
+        let len = rows
+            .checked_mul(cols)
+            .ok_or_else(|| VidError::Argument(format!("{} by {} matrix too large", rows, cols)))?;
+        Self::new((0..len).map(|i| (i % cols) * rows + i / cols).collect())
+    }
+
+    /// Number of elements permuted.
+    pub fn len(&self) -> usize {
+        self.physical.len()
+    }
+
+    /// Whether `self` permutes no elements.
+    pub fn is_empty(&self) -> bool {
+        self.physical.is_empty()
+    }
+}
+
 /// The [ADVZ VID scheme](https://eprint.iacr.org/2021/1500), a concrete impl for [`VidScheme`].
 ///
 /// - `E` is any [`Pairing`]
 /// - `H` is a [`digest::Digest`]-compatible hash function.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Debug, Derivative, Eq, PartialEq)]
+#[derivative(Clone(bound = ""))]
 pub struct Advz<E, H>
 where
     E: Pairing,
@@ -64,9 +223,76 @@ where
     // but that method consumes `other` and its doc is unclear.
     eval_domain: Radix2EvaluationDomain<KzgPoint<E>>,
 
+    // limit on the byte length of a payload proof range, if any
+    max_proof_range_bytes: Option<usize>,
+
+    // minimum number of polynomials for which per-polynomial work runs in
+    // parallel
+    parallel_threshold: usize,
+
+    // if set, `payload_verify_channel` sends results in request order
+    deterministic: bool,
+
+    // number of shares checked by `self_verify_disperse`, or all if `None`
+    self_verify_sample_size: Option<usize>,
+
+    // payloads are padded with zero polynomials up to this many polynomials
+    min_polynomials: usize,
+
+    share_encoding: ShareEncoding,
+
+    commit_version: CommitVersion,
+
+    // payload commitment under `CommitVersion::V4`, if set
+    field_commit: Option<FieldCommit<E>>,
+
+    // elements of each polynomial are encoded in this order, if any
+    permutation: Option<Permutation>,
+
+    field_packing: FieldPacking,
+
     _pd: PhantomData<H>,
 }
 
+/// The [`CommitVersion::V4`] payload commitment to the given poly commits.
+///
+/// Generic over the base field of the KZG commitment group, which [`Advz`]
+/// cannot name without bounds on `E`, so [`Advz::new_with_field_commit`] captures
+/// it as a function pointer.
+struct FieldCommit<E>(fn(&[KzgCommit<E>]) -> VidResult<Vec<u8>>)
+where
+    E: Pairing;
+
+impl<E> Clone for FieldCommit<E>
+where
+    E: Pairing,
+{
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+impl<E> Debug for FieldCommit<E>
+where
+    E: Pairing,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("FieldCommit")
+    }
+}
+
+// the function is fully determined by `E`
+impl<E> PartialEq for FieldCommit<E>
+where
+    E: Pairing,
+{
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<E> Eq for FieldCommit<E> where E: Pairing {}
+
 // [Nested associated type projection is overly conservative · Issue #38078 · rust-lang/rust](https://github.com/rust-lang/rust/issues/38078)
 // I want to do this but I cant:
 // type Kzg<E> = <UnivariateKzgPCS<E> as PolynomialCommitmentScheme>;
@@ -85,6 +311,13 @@ type KzgEvalsMerkleTreeNode<E, H> = <KzgEvalsMerkleTree<E, H> as MerkleTreeSchem
 type KzgEvalsMerkleTreeIndex<E, H> = <KzgEvalsMerkleTree<E, H> as MerkleTreeScheme>::Index;
 type KzgEvalsMerkleTreeProof<E, H> =
     <KzgEvalsMerkleTree<E, H> as MerkleTreeScheme>::MembershipProof;
+type PolyCommitsMerkleTree<E, H> = HasherMerkleTree<H, KzgCommit<E>>;
+type PolyCommitsMerkleTreeIndex<E, H> = <PolyCommitsMerkleTree<E, H> as MerkleTreeScheme>::Index;
+type PolyCommitsMerkleTreeProof<E, H> =
+    <PolyCommitsMerkleTree<E, H> as MerkleTreeScheme>::MembershipProof;
+type SharesMerkleTree<H> = HasherMerkleTree<H, HasherNode<H>>;
+type SharesMerkleTreeIndex<H> = <SharesMerkleTree<H> as MerkleTreeScheme>::Index;
+type SharesMerkleTreeProof<H> = <SharesMerkleTree<H> as MerkleTreeScheme>::MembershipProof;
 
 impl<E, H> Advz<E, H>
 where
@@ -94,30 +327,55 @@ where
     ///
     /// # Errors
     /// Return [`VidError::Argument`] if `num_storage_nodes <
-    /// payload_chunk_size`.
+    /// payload_chunk_size` or if `num_storage_nodes` exceeds the size of the
+    /// largest evaluation domain supported by the scalar field of `E`.
     pub fn new(
         payload_chunk_size: usize,
         num_storage_nodes: usize,
         srs: impl Borrow<KzgSrs<E>>,
     ) -> VidResult<Self> {
-        if num_storage_nodes < payload_chunk_size {
-            return Err(VidError::Argument(format!(
-                "payload_chunk_size {} exceeds num_storage_nodes {}",
-                payload_chunk_size, num_storage_nodes
-            )));
-        }
-        let (ck, vk) = UnivariateKzgPCS::trim_fft_size(srs, payload_chunk_size - 1).map_err(vid)?;
-        let multi_open_domain = UnivariateKzgPCS::<E>::multi_open_rou_eval_domain(
-            payload_chunk_size - 1,
+        Self::new_coset(
+            payload_chunk_size,
             num_storage_nodes,
+            KzgEval::<E>::one(),
+            srs,
         )
-        .map_err(vid)?;
+    }
+
+    /// Same as [`Advz::new`] except that payload polynomials and storage node
+    /// shares are evaluated over the coset of the usual roots of unity given
+    /// by `coset_offset`.
+    ///
+    /// Useful to avoid evaluating at roots of unity used elsewhere. An
+    /// instance built with `coset_offset` equal to one is the same as one
+    /// built by [`Advz::new`]. Commitments and proofs made with one coset do
+    /// not verify against another.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] under the conditions listed at
+    /// [`Advz::new`] or if `coset_offset` is zero.
+    pub fn new_coset(
+        payload_chunk_size: usize,
+        num_storage_nodes: usize,
+        coset_offset: KzgEval<E>,
+        srs: impl Borrow<KzgSrs<E>>,
+    ) -> VidResult<Self> {
+        if coset_offset.is_zero() {
+            return Err(VidError::Argument("coset offset is zero".to_string()));
+        }
+        let multi_open_domain =
+            Self::multi_open_domain(payload_chunk_size, num_storage_nodes, coset_offset)?;
+        let (ck, vk) =
+            UnivariateKzgPCS::trim_fft_size(srs, payload_chunk_size - 1).map_err(VidError::Pcs)?;
         let eval_domain = Radix2EvaluationDomain::new(payload_chunk_size).ok_or_else(|| {
             VidError::Internal(anyhow::anyhow!(
                 "fail to construct doman of size {}",
                 payload_chunk_size
             ))
         })?;
+        let eval_domain = eval_domain
+            .get_coset(coset_offset)
+            .ok_or_else(|| VidError::Internal(anyhow::anyhow!("fail to construct coset")))?;
 
         // TODO TEMPORARY: enforce power-of-2 chunk size
         // Remove this restriction after we get KZG in eval form
@@ -137,13 +395,315 @@ where
             vk,
             multi_open_domain,
             eval_domain,
+            max_proof_range_bytes: None,
+            parallel_threshold: DEFAULT_PARALLEL_THRESHOLD,
+            deterministic: false,
+            self_verify_sample_size: None,
+            min_polynomials: 0,
+            share_encoding: ShareEncoding::default(),
+            commit_version: CommitVersion::default(),
+            field_commit: None,
+            permutation: None,
+            field_packing: FieldPacking::default(),
             _pd: Default::default(),
         })
     }
+
+    /// Same as [`Advz::new`] except that payload commitments are computed
+    /// under `commit_version` instead of [`CommitVersion::V0`], for the
+    /// lifetime of the instance.
+    ///
+    /// Affects every method that computes or checks a payload commitment. See
+    /// [`CommitVersion`] for migration between versions.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] under the conditions listed at
+    /// [`Advz::new`] or if `commit_version` is [`CommitVersion::V4`], which
+    /// is selected via [`Advz::new_with_field_commit`] instead.
+    pub fn new_with_commit_version(
+        payload_chunk_size: usize,
+        num_storage_nodes: usize,
+        srs: impl Borrow<KzgSrs<E>>,
+        commit_version: CommitVersion,
+    ) -> VidResult<Self> {
+        if commit_version == CommitVersion::V4 {
+            return Err(VidError::Argument(
+                "commit version V4 requires Advz::new_with_field_commit".to_string(),
+            ));
+        }
+        let mut advz = Self::new(payload_chunk_size, num_storage_nodes, srs)?;
+        advz.commit_version = commit_version;
+        Ok(advz)
+    }
+
+    /// Same as [`Advz::new`] except that payload commitments are computed
+    /// under [`CommitVersion::V4`]: the Rescue hash
+    /// [`Advz::poly_commits_hash_field`] over the base field `F` of the KZG
+    /// commitment group, encoded canonically.
+    ///
+    /// Recover the field element from a payload commitment via
+    /// [`Advz::commit_field`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] under the conditions listed at
+    /// [`Advz::new`] or if the canonical encoding of `F` is not as long as a
+    /// digest of `H`.
+    pub fn new_with_field_commit<F>(
+        payload_chunk_size: usize,
+        num_storage_nodes: usize,
+        srs: impl Borrow<KzgSrs<E>>,
+    ) -> VidResult<Self>
+    where
+        F: RescueParameter,
+        E::G1Affine: AffineRepr<BaseField = F>,
+        H: HasherDigest,
+    {
+        let digest_len = Output::<H>::default().len();
+        if F::zero().compressed_size() != digest_len {
+            return Err(VidError::Argument(format!(
+                "field element byte len {} differs from digest byte len {}",
+                F::zero().compressed_size(),
+                digest_len
+            )));
+        }
+        let mut advz = Self::new(payload_chunk_size, num_storage_nodes, srs)?;
+        advz.field_commit = Some(FieldCommit(Self::field_commit_bytes::<F>));
+        advz.commit_version = CommitVersion::V4;
+        Ok(advz)
+    }
+
+    /// Same as `self` except for `num_storage_nodes`, as needed to verify
+    /// shares from [`Advz::reencode_shares`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] under the conditions on
+    /// `num_storage_nodes` listed at [`Advz::new`].
+    pub fn with_num_storage_nodes(&self, num_storage_nodes: usize) -> VidResult<Self> {
+        let mut advz = self.clone();
+        advz.multi_open_domain = Self::multi_open_domain(
+            self.payload_chunk_size,
+            num_storage_nodes,
+            self.eval_domain.coset_offset(),
+        )?;
+        advz.num_storage_nodes = num_storage_nodes;
+        Ok(advz)
+    }
+
+    /// Domain at whose points storage node shares are evaluated.
+    fn multi_open_domain(
+        payload_chunk_size: usize,
+        num_storage_nodes: usize,
+        coset_offset: KzgEval<E>,
+    ) -> VidResult<Radix2EvaluationDomain<KzgPoint<E>>> {
+        if num_storage_nodes < payload_chunk_size {
+            return Err(VidError::Argument(format!(
+                "payload_chunk_size {} exceeds num_storage_nodes {}",
+                payload_chunk_size, num_storage_nodes
+            )));
+        }
+        // each storage node needs a distinct evaluation point
+        if let Some(max_domain_size) = 1usize.checked_shl(KzgEval::<E>::TWO_ADICITY) {
+            if num_storage_nodes > max_domain_size {
+                return Err(VidError::Argument(format!(
+                    "num_storage_nodes {} exceeds max evaluation domain size {}",
+                    num_storage_nodes, max_domain_size
+                )));
+            }
+        }
+        let multi_open_domain = UnivariateKzgPCS::<E>::multi_open_rou_eval_domain(
+            payload_chunk_size - 1,
+            num_storage_nodes,
+        )
+        .map_err(VidError::Pcs)?;
+        let multi_open_domain = multi_open_domain
+            .get_coset(coset_offset)
+            .ok_or_else(|| VidError::Internal(anyhow::anyhow!("fail to construct coset")))?;
+        if multi_open_domain.size() < num_storage_nodes {
+            return Err(VidError::Argument(format!(
+                "evaluation domain size {} too small for num_storage_nodes {}",
+                multi_open_domain.size(),
+                num_storage_nodes
+            )));
+        }
+        Ok(multi_open_domain)
+    }
+
+    /// Limit the byte length of ranges accepted by payload proof and
+    /// verification methods such as [`PayloadProver::payload_proof`] and
+    /// [`PayloadProver::payload_verify`]. `None` means unlimited, which is the
+    /// default.
+    ///
+    /// Protects a public proving or verification service from requests that
+    /// force expensive cryptographic work for huge ranges. Proofs for
+    /// explicit element indices count each element as its full byte capacity.
+    ///
+    /// [`PayloadProver::payload_proof`]: super::payload_prover::PayloadProver::payload_proof
+    /// [`PayloadProver::payload_verify`]: super::payload_prover::PayloadProver::payload_verify
+    pub fn set_max_proof_range_bytes(&mut self, max_proof_range_bytes: Option<usize>) {
+        self.max_proof_range_bytes = max_proof_range_bytes;
+    }
+
+    /// Set the minimum number of polynomials for which per-polynomial work
+    /// such as evaluation and commitment runs in parallel. Smaller inputs are
+    /// processed serially on the calling thread, where the overhead of
+    /// spawning tasks would outweigh the gain. Defaults to
+    /// [`DEFAULT_PARALLEL_THRESHOLD`].
+    ///
+    /// Has no effect unless the `parallel` feature is enabled.
+    pub fn set_parallel_threshold(&mut self, parallel_threshold: usize) {
+        self.parallel_threshold = parallel_threshold;
+    }
+
+    /// Make [`Advz::payload_verify_channel`] send its results in request
+    /// order, for consensus-critical use. Off by default.
+    ///
+    /// Under the `parallel` feature that method verifies requests
+    /// concurrently and sends each result as soon as it is ready, so the
+    /// order of results varies from run to run. With `deterministic` set it
+    /// verifies one request at a time instead.
+    ///
+    /// # Determinism
+    ///
+    /// Other verification needs no such mode. Payload verification such as
+    /// [`PayloadProver::payload_verify`] uses no floating point arithmetic,
+    /// never depends on the iteration order of a hash-ordered collection and
+    /// collects parallel work in input order, so its result and its error
+    /// messages depend only on its inputs, whatever the build or target.
+    /// Randomized batch verifiers such as [`Advz::payload_verify_batch`] are
+    /// deterministic only if given a deterministic `rng`.
+    ///
+    /// [`PayloadProver::payload_verify`]: super::payload_prover::PayloadProver::payload_verify
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
+    /// Set the number of shares checked by [`Advz::self_verify_disperse`].
+    /// `None` means all shares, which is the default.
+    pub fn set_self_verify_sample_size(&mut self, self_verify_sample_size: Option<usize>) {
+        self.self_verify_sample_size = self_verify_sample_size;
+    }
+
+    /// Pad every dispersed payload with zero polynomials up to at least
+    /// `min_polynomials` polynomials, so that commitments have the same
+    /// structure and proving cost is predictable across payload sizes.
+    /// Defaults to zero, ie. no padding.
+    ///
+    /// Padding is not part of the payload: `common` records the real payload
+    /// byte length, recovery returns only the real payload and payload proofs
+    /// reject ranges beyond it. Padding changes the payload commitment, so
+    /// all parties must agree on `min_polynomials`.
+    pub fn set_min_polynomials(&mut self, min_polynomials: usize) {
+        self.min_polynomials = min_polynomials;
+    }
+
+    /// Set which data each share carries. Defaults to
+    /// [`ShareEncoding::Full`].
+    ///
+    /// [`ShareEncoding::Minimal`] reduces per-share bandwidth when `common` is
+    /// broadcast separately. It affects the shares and common data produced
+    /// by [`VidScheme::disperse`] but not share verification, which accepts a
+    /// share without a merkle path only if `common` carries the leaf digests
+    /// that authenticate its evals.
+    pub fn set_share_encoding(&mut self, share_encoding: ShareEncoding) {
+        self.share_encoding = share_encoding;
+    }
+
+    /// Set the order in which the elements of each polynomial are encoded.
+    /// `None`, the default, means logical order.
+    ///
+    /// Supports encodings that interleave data before dispersal: logical
+    /// element `i` of each polynomial is encoded at physical offset
+    /// `permutation[i]`, applied consistently by [`VidScheme::disperse`],
+    /// payload recovery and the payload proof methods, which all address
+    /// payload bytes by their logical offset. See
+    /// [`Advz::physical_byte_offset`]. The permutation changes the payload
+    /// commitment, so all parties must agree on it.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if the length of `permutation` differs
+    /// from `payload_chunk_size`.
+    pub fn set_permutation(&mut self, permutation: Option<Permutation>) -> VidResult<()> {
+        if let Some(permutation) = &permutation {
+            if permutation.len() != self.payload_chunk_size {
+                return Err(VidError::Argument(format!(
+                    "permutation len {} differs from payload_chunk_size {}",
+                    permutation.len(),
+                    self.payload_chunk_size
+                )));
+            }
+        }
+        self.permutation = permutation;
+        Ok(())
+    }
+
+    /// Set how payload bytes are packed into field elements. Defaults to
+    /// [`FieldPacking::Bytes`].
+    ///
+    /// Affects dispersal, recovery and payload proofs. The encoding changes
+    /// the payload commitment, so all parties must agree on it. Payload
+    /// proofs for byte ranges work under either packing, except for
+    /// [`Advz::payload_proof_strided`], [`Advz::payload_verify_stream`],
+    /// [`Advz::payload_verify_spec_compliant`] and
+    /// [`Advz::prove_common_prefix`], whose formats assume byte-aligned
+    /// elements.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if under [`FieldPacking::Bits`] a
+    /// polynomial would not hold a whole number of bytes.
+    pub fn set_field_packing(&mut self, field_packing: FieldPacking) -> VidResult<()> {
+        if field_packing == FieldPacking::Bits
+            && (self.payload_chunk_size * packed_elem_bit_capacity::<KzgEval<E>>()) % 8 != 0
+        {
+            return Err(VidError::Argument(format!(
+                "payload_chunk_size {} of bit-packed elems of {} bits is not a whole number of bytes",
+                self.payload_chunk_size,
+                packed_elem_bit_capacity::<KzgEval<E>>()
+            )));
+        }
+        self.field_packing = field_packing;
+        Ok(())
+    }
+
+    /// Return the minimum degree of an SRS suitable for [`Advz::new`] with
+    /// the given `payload_chunk_size`.
+    ///
+    /// Polynomials have degree `payload_chunk_size - 1` but FFT-based
+    /// multi-opening requires the SRS to support the next power of two
+    /// strictly greater than that degree.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `payload_chunk_size` is zero or too
+    /// large.
+    pub fn required_srs_degree(payload_chunk_size: usize) -> VidResult<usize> {
+        if payload_chunk_size == 0 {
+            return Err(VidError::Argument(
+                "payload_chunk_size must be positive".to_string(),
+            ));
+        }
+        checked_fft_size(payload_chunk_size - 1)
+            .map_err(|e| VidError::Argument(format!("invalid payload_chunk_size: {}", e)))
+    }
+
+    /// Enumerate the field elements of `payload` with their element indices.
+    ///
+    /// Element `i` encodes payload bytes `i * c..(i + 1) * c`, where `c` is
+    /// the byte capacity of a field element, and belongs to polynomial
+    /// `i / payload_chunk_size`. The final element is zero-padded if the
+    /// payload length is not a multiple of `c`, exactly as in
+    /// [`VidScheme::disperse`] under the default [`FieldPacking::Bytes`].
+    pub fn enumerate_elems<I>(payload: I) -> impl Iterator<Item = (usize, KzgEval<E>)>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<u8>,
+    {
+        bytes_to_field::<_, KzgEval<E>>(payload).enumerate()
+    }
 }
 
 /// The [`VidScheme::Share`] type for [`Advz`].
-#[derive(Derivative, Deserialize, Serialize)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Derivative, Deserialize, Serialize)]
 #[serde(bound = "Output<H>: Serialize + for<'a> Deserialize<'a>")]
 #[derivative(
     Clone(bound = ""),
@@ -165,11 +725,66 @@ where
     #[serde(with = "canonical")]
     aggregate_proof: KzgProof<E>,
 
-    evals_proof: KzgEvalsMerkleTreeProof<E, H>,
+    // `None` under `ShareEncoding::Minimal`
+    evals_proof: Option<KzgEvalsMerkleTreeProof<E, H>>,
+}
+
+/// The evaluations held by a set of [`Share`]s in columnar layout: the
+/// evaluations of each polynomial across storage nodes are contiguous.
+///
+/// [`VidScheme::recover_payload`] gathers the evaluations of each polynomial
+/// from every share. A disperser that keeps the payload for later recovery
+/// can instead choose columnar layout at dispersal via
+/// [`Advz::disperse_columns`] and recover via
+/// [`Advz::recover_payload_columns`], which reads each polynomial's
+/// evaluations contiguously. Shares collected from storage nodes can be
+/// rearranged via [`Advz::share_columns`].
+#[derive(Derivative, Deserialize, Serialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    Hash(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct ShareColumns<E>
+where
+    E: Pairing,
+{
+    /// Storage node index of each share.
+    indices: Vec<usize>,
+
+    /// `columns[i][j]` is the evaluation of polynomial `i` for storage node
+    /// `indices[j]`.
+    #[serde(with = "canonical")]
+    columns: Vec<Vec<KzgEval<E>>>,
+}
+
+impl<E> ShareColumns<E>
+where
+    E: Pairing,
+{
+    /// Number of shares in `self`.
+    pub fn num_shares(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Keep only the first `num_shares` shares, eg. the number needed for
+    /// recovery. No-op if `num_shares` is at least [`ShareColumns::num_shares`].
+    pub fn truncate(&mut self, num_shares: usize) {
+        self.indices.truncate(num_shares);
+        for column in self.columns.iter_mut() {
+            column.truncate(num_shares);
+        }
+    }
 }
 
 /// The [`VidScheme::Common`] type for [`Advz`].
-#[derive(CanonicalSerialize, CanonicalDeserialize, Derivative, Deserialize, Serialize)]
+///
+/// The canonical serialization starts with [`COMMON_FORMAT_V1`]. Common data
+/// serialized before the format was versioned still deserializes, without
+/// the parameters of its `Advz` instance or a header digest.
+#[derive(Derivative, Deserialize, Serialize)]
 #[derivative(
     Clone(bound = ""),
     Debug(bound = ""),
@@ -189,482 +804,5790 @@ where
     all_evals_digest: KzgEvalsMerkleTreeNode<E, H>,
 
     bytes_len: usize,
+
+    // digest of the header bound into the payload commitment, if any
+    #[serde(with = "canonical")]
+    header_digest: Option<HasherNode<H>>,
+
+    // leaf digests of the merkle tree with root `all_evals_digest` under
+    // `ShareEncoding::Minimal`, `None` otherwise
+    #[serde(with = "canonical")]
+    evals_leaf_digests: Option<Vec<KzgEvalsMerkleTreeNode<E, H>>>,
+
+    // parameters of the `Advz` instance that produced `self`, or `0` if not
+    // recorded by the serialization format
+    payload_chunk_size: usize,
+    num_storage_nodes: usize,
 }
 
-impl<E, H> VidScheme for Advz<E, H>
+/// Tag at the start of the canonical serialization of [`Common`] that
+/// records the parameters of its `Advz` instance, its header digest and its
+/// evals leaf digests.
+///
+/// Earlier common data starts with the number of poly commits instead,
+/// which never equals this tag.
+pub const COMMON_FORMAT_V1: u64 = u64::MAX;
+
+impl<E, H> CanonicalSerialize for Common<E, H>
 where
     E: Pairing,
     H: HasherDigest,
 {
-    type Commit = Output<H>;
-    type Share = Share<E, H>;
-    type Common = Common<E, H>;
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        COMMON_FORMAT_V1.serialize_with_mode(&mut writer, compress)?;
+        self.poly_commits
+            .serialize_with_mode(&mut writer, compress)?;
+        self.all_evals_digest
+            .serialize_with_mode(&mut writer, compress)?;
+        self.bytes_len.serialize_with_mode(&mut writer, compress)?;
+        self.header_digest
+            .serialize_with_mode(&mut writer, compress)?;
+        self.payload_chunk_size
+            .serialize_with_mode(&mut writer, compress)?;
+        self.num_storage_nodes
+            .serialize_with_mode(&mut writer, compress)?;
+        self.evals_leaf_digests
+            .serialize_with_mode(&mut writer, compress)
+    }
 
-    fn commit_only<B>(&self, payload: B) -> VidResult<Self::Commit>
-    where
-        B: AsRef<[u8]>,
-    {
-        let payload = payload.as_ref();
-        let commit_time = start_timer!(|| format!(
-            "VID commit_only {} payload bites to {} nodes",
-            payload.len(),
-            self.num_storage_nodes
-        ));
+    fn serialized_size(&self, compress: Compress) -> usize {
+        COMMON_FORMAT_V1.serialized_size(compress)
+            + self.poly_commits.serialized_size(compress)
+            + self.all_evals_digest.serialized_size(compress)
+            + self.bytes_len.serialized_size(compress)
+            + self.header_digest.serialized_size(compress)
+            + self.payload_chunk_size.serialized_size(compress)
+            + self.num_storage_nodes.serialized_size(compress)
+            + self.evals_leaf_digests.serialized_size(compress)
+    }
+}
 
-        // Can't use `Self::poly_commits_hash()`` here because `P::commit()`` returns
-        // `Result<P::Commitment,_>`` instead of `P::Commitment`.
-        // There's probably an idiomatic way to do this using eg.
-        // itertools::process_results() but the code is unreadable.
-        let mut hasher = H::new();
-        let elems_iter = bytes_to_field::<_, KzgEval<E>>(payload);
-        for evals_iter in elems_iter.chunks(self.payload_chunk_size).into_iter() {
-            let poly = self.polynomial(evals_iter);
-            let commitment = UnivariateKzgPCS::commit(&self.ck, &poly).map_err(vid)?;
-            commitment
-                .serialize_uncompressed(&mut hasher)
-                .map_err(vid)?;
-        }
-        end_timer!(commit_time);
-        Ok(hasher.finalize())
+impl<E, H> Valid for Common<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.poly_commits.check()
     }
+}
 
-    fn disperse<B>(&self, payload: B) -> VidResult<VidDisperse<Self>>
-    where
+impl<E, H> CanonicalDeserialize for Common<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let tag = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+        if tag != COMMON_FORMAT_V1 {
+            // unversioned: `tag` is the number of poly commits
+            let poly_commits = (0..tag)
+                .map(|_| KzgCommit::<E>::deserialize_with_mode(&mut reader, compress, validate))
+                .collect::<Result<_, _>>()?;
+            return Ok(Self {
+                poly_commits,
+                all_evals_digest: CanonicalDeserialize::deserialize_with_mode(
+                    &mut reader,
+                    compress,
+                    validate,
+                )?,
+                bytes_len: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+                header_digest: None,
+                evals_leaf_digests: None,
+                payload_chunk_size: 0,
+                num_storage_nodes: 0,
+            });
+        }
+        Ok(Self {
+            poly_commits: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            all_evals_digest: CanonicalDeserialize::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            bytes_len: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            header_digest: Option::deserialize_with_mode(&mut reader, compress, validate)?,
+            payload_chunk_size: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            num_storage_nodes: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            evals_leaf_digests: Option::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+/// A compressed form of [`Common`] that holds a hash of each polynomial
+/// commitment instead of the commitment itself.
+///
+/// Computed by [`Advz::compact_common`]. Verify payload proofs against a
+/// [`CompactCommon`] via [`payload_prover::PayloadVerifyCompact`].
+#[derive(CanonicalSerialize, CanonicalDeserialize, Derivative, Deserialize, Serialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    Hash(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct CompactCommon<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    #[serde(with = "canonical")]
+    poly_commit_hashes: Vec<HasherNode<H>>,
+
+    #[serde(with = "canonical")]
+    all_evals_digest: KzgEvalsMerkleTreeNode<E, H>,
+
+    bytes_len: usize,
+
+    // digest of the header bound into the payload commitment, if any
+    #[serde(with = "canonical")]
+    header_digest: Option<HasherNode<H>>,
+
+    // parameters of the `Advz` instance that produced `self`
+    payload_chunk_size: usize,
+    num_storage_nodes: usize,
+}
+
+/// A proof that a payload commitment binds a given payload byte length.
+///
+/// Computed by [`Advz::length_proof`], verified by [`Advz::verify_length`].
+/// The byte length is the last item of the payload commitment preimage, so
+/// the proof need only carry the digest of everything before it.
+#[derive(CanonicalSerialize, CanonicalDeserialize, Derivative, Deserialize, Serialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    Hash(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct LengthProof<H>
+where
+    H: HasherDigest,
+{
+    bytes_len: usize,
+
+    // digest of the poly commit hashes and header digest
+    #[serde(with = "canonical")]
+    prefix_digest: HasherNode<H>,
+}
+
+/// Output of [`Advz::disperse_hiding`].
+///
+/// Publish and disperse [`HidingDisperse::disperse`] as usual. Keep the
+/// blinds private until the payload is revealed.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct HidingDisperse<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Disperse data for the blinded polynomials. The shares, common data
+    /// and payload commitment reveal nothing about the payload besides its
+    /// byte length. Shares verify against `common` and `commit` as usual.
+    pub disperse: VidDisperse<Advz<E, H>>,
+    blinds: Vec<Blind>,
+}
+
+/// The blind of one polynomial in a [`HidingDisperse`]: the seed of the
+/// random polynomial added to it.
+///
+/// Obtained via [`HidingDisperse::reveal_blind`]. Used to de-hide the
+/// commitment via [`Advz::dehide`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Blind([u8; 32]);
+
+impl<E, H> HidingDisperse<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Reveal the blind of polynomial commitment `poly_index`, so that payload
+    /// proofs for ranges in that polynomial can be verified via
+    /// [`Advz::dehide`]. Other polynomials stay hidden.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `poly_index` is out of bounds.
+    pub fn reveal_blind(&self, poly_index: usize) -> VidResult<Blind> {
+        self.blinds.get(poly_index).copied().ok_or_else(|| {
+            VidError::Argument(format!(
+                "poly index {} out of bounds for {} blinds",
+                poly_index,
+                self.blinds.len()
+            ))
+        })
+    }
+}
+
+/// Output of [`Advz::disperse_heterogeneous`], in which each polynomial has
+/// its own number of storage nodes.
+#[derive(Derivative, Deserialize, Serialize)]
+#[serde(bound = "Output<H>: Serialize + for<'a> Deserialize<'a>")]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct HeterogeneousDisperse<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// One share for each of the `num_storage_nodes` storage nodes of the
+    /// [`Advz`] instance.
+    pub shares: Vec<HeterogeneousShare<E>>,
+    /// `poly_num_storage_nodes[i]` is the number of storage nodes for
+    /// polynomial `i`.
+    pub poly_num_storage_nodes: Vec<usize>,
+    /// Polynomial commitments, equal to those of [`VidScheme::disperse`].
+    #[serde(with = "canonical")]
+    pub poly_commits: Vec<KzgCommit<E>>,
+    /// Payload commitment, equal to that of [`VidScheme::disperse`].
+    pub commit: Output<H>,
+    /// Byte length of the payload, needed by
+    /// [`Advz::recover_payload_heterogeneous`].
+    pub bytes_len: usize,
+}
+
+/// A share of a [`HeterogeneousDisperse`].
+///
+/// Holds an eval for each polynomial dispersed to this storage node, and
+/// `None` for every other polynomial, together with a KZG proof for those
+/// evals. Verify via [`Advz::verify_share_heterogeneous`].
+#[derive(Derivative, Deserialize, Serialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct HeterogeneousShare<E>
+where
+    E: Pairing,
+{
+    index: usize,
+
+    #[serde(with = "canonical")]
+    evals: Vec<Option<KzgEval<E>>>,
+
+    #[serde(with = "canonical")]
+    aggregate_proof: KzgProof<E>,
+}
+
+/// Polynomial commitments and storage node evaluations for one segment of a
+/// payload, as computed by [`Advz::disperse_partial`].
+///
+/// Combine the segments of a payload into a [`VidDisperse`] via
+/// [`Advz::merge_commitments`].
+#[derive(Derivative, Deserialize, Serialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct PartialDisperse<E>
+where
+    E: Pairing,
+{
+    byte_offset: usize,
+    bytes_len: usize,
+
+    #[serde(with = "canonical")]
+    polys: Vec<KzgPolynomial<E>>,
+
+    #[serde(with = "canonical")]
+    poly_commits: Vec<KzgCommit<E>>,
+
+    /// `all_storage_node_evals[i]` holds the evals for storage node `i`.
+    #[serde(with = "canonical")]
+    all_storage_node_evals: Vec<Vec<KzgEval<E>>>,
+}
+
+impl<E> PartialDisperse<E>
+where
+    E: Pairing,
+{
+    /// The byte range of the payload segment covered by `self`.
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_offset..self.byte_offset + self.bytes_len
+    }
+
+    /// Number of polynomials committed in `self`.
+    pub fn num_polys(&self) -> usize {
+        self.poly_commits.len()
+    }
+}
+
+/// Output of [`Advz::disperse_with_deadline`].
+#[cfg(feature = "std")]
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub enum DeadlineDisperse<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Every polynomial was committed before the deadline.
+    Complete(VidDisperse<Advz<E, H>>),
+    /// The deadline passed after committing the polynomials of the payload
+    /// prefix [`PartialDisperse::byte_range`].
+    Partial(PartialDisperse<E>),
+}
+
+/// Proof that a polynomial commitment has degree less than
+/// `payload_chunk_size`, as computed by [`Advz::degree_bound_proof`].
+///
+/// Holds commitments to the polynomial multiplied by `X^k` for each `k` from
+/// one up to the largest shift supported by the SRS. Each is checked against
+/// its predecessor by a pairing, so the last one can be computed only if the
+/// polynomial has small enough degree.
+#[derive(Derivative, Deserialize, Serialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct DegreeBoundProof<E>
+where
+    E: Pairing,
+{
+    #[serde(with = "canonical")]
+    shifted_commits: Vec<KzgCommit<E>>,
+}
+
+/// Accumulator for the payload commitment that accepts polynomial
+/// commitments in any order.
+///
+/// Useful for a distributed prover whose polynomial commitments complete out
+/// of order. [`CommitAccumulator::finalize`] yields the same payload commitment as
+/// [`VidScheme::commit_only`] once every index in `0..num_polys` is present.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct CommitAccumulator<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    poly_commits: Vec<Option<KzgCommit<E>>>,
+    commit_version: CommitVersion,
+    field_commit: Option<FieldCommit<E>>,
+    _pd: PhantomData<H>,
+}
+
+impl<E, H> CommitAccumulator<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// An empty accumulator for a payload of `num_polys` polynomials,
+    /// including any padding polynomials as per
+    /// [`Advz::set_min_polynomials`], committed under `commit_version`.
+    ///
+    /// Under [`CommitVersion::V4`] use [`Advz::commit_accumulator`] instead,
+    /// without which [`CommitAccumulator::finalize`] fails.
+    pub fn new(num_polys: usize, commit_version: CommitVersion) -> Self {
+        Self {
+            poly_commits: vec![None; num_polys],
+            commit_version,
+            field_commit: None,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Insert the commitment to polynomial `index`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `index` is out of bounds or already
+    /// present.
+    pub fn insert(&mut self, index: usize, poly_commit: &KzgCommit<E>) -> VidResult<()> {
+        let num_polys = self.poly_commits.len();
+        let slot = self.poly_commits.get_mut(index).ok_or_else(|| {
+            VidError::Argument(format!(
+                "poly index {} out of bounds for {} polys",
+                index, num_polys
+            ))
+        })?;
+        if slot.is_some() {
+            return Err(VidError::Argument(format!(
+                "poly index {} already present",
+                index
+            )));
+        }
+        *slot = Some(*poly_commit);
+        Ok(())
+    }
+
+    /// Number of polynomial commitments inserted so far.
+    pub fn len(&self) -> usize {
+        self.poly_commits
+            .iter()
+            .filter(|poly_commit| poly_commit.is_some())
+            .count()
+    }
+
+    /// Whether no polynomial commitment has been inserted.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The payload commitment for a payload of `bytes_len` bytes, bound to
+    /// `header` as per [`Advz::disperse_with_header`] if given.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if any index in `0..num_polys` is
+    /// missing.
+    pub fn finalize(
+        self,
+        header: Option<&[u8]>,
+        bytes_len: usize,
+    ) -> VidResult<<Advz<E, H> as VidScheme>::Commit> {
+        let poly_commits = self
+            .poly_commits
+            .into_iter()
+            .enumerate()
+            .map(|(index, poly_commit)| {
+                poly_commit
+                    .ok_or_else(|| VidError::Argument(format!("poly index {} missing", index)))
+            })
+            .collect::<VidResult<Vec<_>>>()?;
+        let header_digest = header.map(|header| HasherNode::from(H::digest(header)));
+        Advz::<E, H>::versioned_poly_commits_hash(
+            self.commit_version,
+            self.field_commit.as_ref(),
+            poly_commits.iter(),
+            header_digest.as_ref(),
+            bytes_len,
+        )
+    }
+}
+
+impl<E, H> VidScheme for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    type Commit = Output<H>;
+    type Share = Share<E, H>;
+    type Common = Common<E, H>;
+
+    fn commit_only<B>(&self, payload: B) -> VidResult<Self::Commit>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let commit_time = start_timer!(|| format!(
+            "VID commit_only {} payload bites to {} nodes",
+            payload.len(),
+            self.num_storage_nodes
+        ));
+
+        let mut poly_commits = self
+            .payload_polys(payload)
+            .iter()
+            .map(|poly| UnivariateKzgPCS::commit(&self.ck, poly).map_err(VidError::Pcs))
+            .collect::<VidResult<Vec<_>>>()?;
+        if poly_commits.len() < self.min_polynomials {
+            poly_commits.resize(
+                self.min_polynomials,
+                KzgCommit::<E>::from(E::G1Affine::zero()),
+            );
+        }
+        let commit = self.poly_commits_hash(poly_commits.iter(), None, payload.len())?;
+        end_timer!(commit_time);
+        Ok(commit)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "disperse",
+            level = "debug",
+            skip_all,
+            fields(
+                payload_byte_len = payload.as_ref().len(),
+                num_storage_nodes = self.num_storage_nodes
+            )
+        )
+    )]
+    fn disperse<B>(&self, payload: B) -> VidResult<VidDisperse<Self>>
+    where
         B: AsRef<[u8]>,
     {
         let payload = payload.as_ref();
-        let payload_len = payload.len();
         let disperse_time = start_timer!(|| format!(
             "VID disperse {} payload bytes to {} nodes",
-            payload_len, self.num_storage_nodes
+            payload.len(),
+            self.num_storage_nodes
         ));
+        let partial = self.disperse_partial(payload, 0)?;
+        let disperse = self.disperse_from_parts(
+            partial.polys,
+            partial.poly_commits,
+            partial.all_storage_node_evals,
+            partial.bytes_len,
+            None,
+        );
+        end_timer!(disperse_time);
+        disperse
+    }
+
+    fn verify_share(
+        &self,
+        share: &Self::Share,
+        common: &Self::Common,
+        commit: &Self::Commit,
+    ) -> VidResult<Result<(), ()>> {
+        // check arguments
+        self.check_common_params(common)?;
+        if share.evals.len() != common.poly_commits.len() {
+            return Err(VidError::Argument(format!(
+                "(share eval, common poly commit) lengths differ ({},{})",
+                share.evals.len(),
+                common.poly_commits.len()
+            )));
+        }
+        if share.index >= self.num_storage_nodes {
+            return Ok(Err(())); // not an arg error
+        }
+
+        // check `common` against `commit`
+        let commit_rebuilt = self.common_commit(common)?;
+        if commit_rebuilt != *commit {
+            return Err(VidError::Argument(
+                "commit inconsistent with common".to_string(),
+            ));
+        }
+
+        if !self.check_share_evals_proof(share, common)? {
+            return Ok(Err(()));
+        }
+        self.verify_share_aggregate_proof(share, common, commit)
+    }
+
+    fn recover_payload(&self, shares: &[Self::Share], common: &Self::Common) -> VidResult<Vec<u8>> {
+        self.check_common_params(common)?;
+        self.check_enough_shares(shares.iter().map(|s| s.index))?;
+
+        let num_polys = Self::shares_num_polys(shares)?;
+
+        // polys are decoded independently, possibly in parallel
+        let poly_indices: Vec<usize> = (0..num_polys).collect();
+        let (payload_chunk_size, multi_open_domain, eval_domain) = (
+            self.payload_chunk_size,
+            &self.multi_open_domain,
+            &self.eval_domain,
+        );
+        let elems = self
+            .maybe_par_map(&poly_indices, |i| {
+                Self::decode_poly_elems(
+                    payload_chunk_size,
+                    multi_open_domain,
+                    eval_domain,
+                    shares.iter().map(|s| (s.index, s.evals[*i])),
+                )
+            })
+            .into_iter()
+            .collect::<VidResult<Vec<_>>>()?
+            .into_iter()
+            .flat_map(|poly_elems| self.to_logical_order(poly_elems))
+            .collect::<Vec<_>>();
+        assert_eq!(elems.len(), num_polys * self.payload_chunk_size);
+
+        let mut payload: Vec<_> = self.elems_to_bytes(elems).collect();
+        payload.truncate(common.bytes_len);
+        Ok(payload)
+    }
+}
+
+impl<E, H> VidSchemeMagic for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Derived from the moduli of the scalar field and of the base field of
+    /// `E`, so that instantiations over different curves differ.
+    fn magic() -> [u8; 4] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"advz");
+        hasher.update(E::ScalarField::MODULUS.to_bytes_le());
+        for limb in <<E::G1Affine as AffineRepr>::BaseField as Field>::characteristic() {
+            hasher.update(limb.to_le_bytes());
+        }
+        let digest = hasher.finalize();
+        [digest[0], digest[1], digest[2], digest[3]]
+    }
+}
+
+impl<E, H> Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Verify the merkle authentication path of `share` against
+    /// `common.all_evals_digest`.
+    ///
+    /// This check binds `share.evals` into the merkle root in `common`. It does
+    /// not check `share.evals` against the polynomial commitments in `common`.
+    /// For that, see [`Advz::verify_share_aggregate_proof`].
+    ///
+    /// Return value has the same semantics as [`VidScheme::verify_share`].
+    pub fn verify_share_evals_proof(
+        &self,
+        share: &<Self as VidScheme>::Share,
+        common: &<Self as VidScheme>::Common,
+    ) -> VidResult<Result<(), ()>> {
+        if share.index >= self.num_storage_nodes {
+            return Ok(Err(())); // not an arg error
+        }
+
+        let evals_proof = match &share.evals_proof {
+            Some(evals_proof) => evals_proof,
+            None => return Ok(Err(())),
+        };
+
+        // the authenticated leaf must be the evals claimed by this share
+        if evals_proof.elem() != Some(&share.evals) {
+            return Ok(Err(()));
+        }
+
+        KzgEvalsMerkleTree::<E, H>::verify(
+            common.all_evals_digest,
+            &KzgEvalsMerkleTreeIndex::<E, H>::from(share.index as u64),
+            evals_proof,
+        )
+        .map_err(vid)
+    }
+
+    /// Same as [`Advz::verify_share_evals_proof`] except that a share without
+    /// a merkle path is authenticated via the leaf digests in `common`, if
+    /// any, as dispersed under [`ShareEncoding::Minimal`].
+    fn check_share_evals_proof(
+        &self,
+        share: &<Self as VidScheme>::Share,
+        common: &<Self as VidScheme>::Common,
+    ) -> VidResult<bool> {
+        let leaf_digests = match (&share.evals_proof, &common.evals_leaf_digests) {
+            (None, Some(leaf_digests)) => leaf_digests,
+            _ => return Ok(self.verify_share_evals_proof(share, common)?.is_ok()),
+        };
+        if share.index >= self.num_storage_nodes || leaf_digests.len() != self.num_storage_nodes {
+            return Ok(false);
+        }
+        Ok(
+            leaf_digests[share.index] == Self::evals_leaf_digest(share.index, &share.evals)?
+                && Self::all_evals_root(leaf_digests)? == common.all_evals_digest,
+        )
+    }
+
+    /// Leaf digest of storage node `index` in the merkle tree of
+    /// [`Advz::all_evals_merkle_tree`].
+    fn evals_leaf_digest(
+        index: usize,
+        evals: &Vec<KzgEval<E>>,
+    ) -> VidResult<KzgEvalsMerkleTreeNode<E, H>> {
+        <HasherDigestAlgorithm as DigestAlgorithm<_, _, _>>::digest_leaf(
+            &KzgEvalsMerkleTreeIndex::<E, H>::from(index as u64),
+            evals,
+        )
+        .map_err(vid)
+    }
+
+    /// Root of the merkle tree of [`Advz::all_evals_merkle_tree`] computed
+    /// from its leaf digests.
+    fn all_evals_root(
+        leaf_digests: &[KzgEvalsMerkleTreeNode<E, H>],
+    ) -> VidResult<KzgEvalsMerkleTreeNode<E, H>> {
+        let arity = KzgEvalsMerkleTree::<E, H>::ARITY;
+        let mut nodes = leaf_digests.to_vec();
+        for _ in 0..Self::all_evals_tree_height(leaf_digests.len())? {
+            nodes = nodes
+                .chunks(arity)
+                .map(|children| {
+                    // missing children are empty subtrees
+                    let mut children = children.to_vec();
+                    children.resize(arity, KzgEvalsMerkleTreeNode::<E, H>::default());
+                    <HasherDigestAlgorithm as DigestAlgorithm<
+                        Vec<KzgEval<E>>,
+                        KzgEvalsMerkleTreeIndex<E, H>,
+                        _,
+                    >>::digest(&children)
+                })
+                .collect::<Result<_, _>>()
+                .map_err(vid)?;
+        }
+        nodes
+            .pop()
+            .ok_or_else(|| VidError::Argument("no leaf digests".to_string()))
+    }
+
+    /// Leaf digests carried by [`Common`] for `all_storage_node_evals` under
+    /// `self.share_encoding`.
+    fn evals_leaf_digests(
+        &self,
+        all_storage_node_evals: &[Vec<KzgEval<E>>],
+    ) -> VidResult<Option<Vec<KzgEvalsMerkleTreeNode<E, H>>>> {
+        match self.share_encoding {
+            ShareEncoding::Full => Ok(None),
+            ShareEncoding::Minimal => all_storage_node_evals
+                .iter()
+                .enumerate()
+                .map(|(index, evals)| Self::evals_leaf_digest(index, evals))
+                .collect::<VidResult<_>>()
+                .map(Some),
+        }
+    }
+
+    /// The merkle path of storage node `index` in `all_evals_commit` as
+    /// carried by its share under `self.share_encoding`.
+    fn share_evals_proof(
+        &self,
+        all_evals_commit: &KzgEvalsMerkleTree<E, H>,
+        index: usize,
+    ) -> VidResult<Option<KzgEvalsMerkleTreeProof<E, H>>> {
+        match self.share_encoding {
+            ShareEncoding::Full => Ok(Some(
+                all_evals_commit
+                    .lookup(KzgEvalsMerkleTreeIndex::<E, H>::from(index as u64))
+                    .expect_ok()
+                    .map_err(vid)?
+                    .1,
+            )),
+            ShareEncoding::Minimal => Ok(None),
+        }
+    }
+
+    /// Verify the KZG aggregate proof of `share` against the polynomial
+    /// commitments in `common`.
+    ///
+    /// This check does not look at the merkle authentication path of `share`.
+    /// For that, see [`Advz::verify_share_evals_proof`].
+    ///
+    /// Return value has the same semantics as [`VidScheme::verify_share`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `share` and `common` have inconsistent
+    /// lengths.
+    pub fn verify_share_aggregate_proof(
+        &self,
+        share: &<Self as VidScheme>::Share,
+        common: &<Self as VidScheme>::Common,
+        commit: &<Self as VidScheme>::Commit,
+    ) -> VidResult<Result<(), ()>> {
+        if share.evals.len() != common.poly_commits.len() {
+            return Err(VidError::Argument(format!(
+                "(share eval, common poly commit) lengths differ ({},{})",
+                share.evals.len(),
+                common.poly_commits.len()
+            )));
+        }
+        if share.index >= self.num_storage_nodes {
+            return Ok(Err(())); // not an arg error
+        }
+
+        let pseudorandom_scalar = Self::pseudorandom_scalar(common, commit)?;
+
+        // Compute aggregate polynomial [commitment|evaluation]
+        // as a pseudorandom linear combo of [commitments|evaluations]
+        // via evaluation of the polynomial whose coefficients are
+        // [commitments|evaluations] and whose input point is the pseudorandom
+        // scalar.
+        let aggregate_poly_commit = KzgCommit::<E>::from(
+            polynomial_eval(
+                common
+                    .poly_commits
+                    .iter()
+                    .map(|x| CurveMultiplier(x.as_ref())),
+                pseudorandom_scalar,
+            )
+            .into(),
+        );
+        let aggregate_eval =
+            polynomial_eval(share.evals.iter().map(FieldMultiplier), pseudorandom_scalar);
+
+        // verify aggregate proof
+        Ok(UnivariateKzgPCS::verify(
+            &self.vk,
+            &aggregate_poly_commit,
+            &self.multi_open_domain.element(share.index),
+            &aggregate_eval,
+            &share.aggregate_proof,
+        )
+        .map_err(VidError::Pcs)?
+        .then_some(())
+        .ok_or(()))
+    }
+
+    /// Return whether `share` belongs to storage node `claimed_index`.
+    ///
+    /// A share is tied to its storage node by the evaluation point
+    /// `multi_open_domain.element(index)` at which its aggregate proof opens
+    /// and by the position of its evals in the merkle tree committed to in
+    /// `common`. Both are checked at `claimed_index`, as are the evals against
+    /// `common`, so a node cannot pass off another node's share under a false
+    /// index even by relabeling it.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the same conditions as
+    /// [`VidScheme::verify_share`] except those involving the commit, which is
+    /// recomputed from `common`.
+    pub fn verify_share_index(
+        &self,
+        share: &<Self as VidScheme>::Share,
+        claimed_index: usize,
+        common: &<Self as VidScheme>::Common,
+    ) -> VidResult<bool> {
+        self.check_common_params(common)?;
+        if share.evals.len() != common.poly_commits.len() {
+            return Err(VidError::Argument(format!(
+                "(share eval, common poly commit) lengths differ ({},{})",
+                share.evals.len(),
+                common.poly_commits.len()
+            )));
+        }
+        if share.index != claimed_index || claimed_index >= self.num_storage_nodes {
+            return Ok(false);
+        }
+        let commit = self.common_commit(common)?;
+        Ok(self.check_share_evals_proof(share, common)?
+            && self
+                .verify_share_aggregate_proof(share, common, &commit)?
+                .is_ok())
+    }
+
+    /// Prove that the polynomial at `poly_index` in the dispersal of `payload`
+    /// has degree less than `payload_chunk_size`.
+    ///
+    /// Verify via [`Advz::verify_degree_bound`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `poly_index` is out of bounds for
+    /// `payload`.
+    pub fn degree_bound_proof<B>(
+        &self,
+        payload: B,
+        poly_index: usize,
+    ) -> VidResult<DegreeBoundProof<E>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        poly_index
+            .checked_mul(self.poly_byte_len())
+            .filter(|start| *start < payload.len())
+            .ok_or_else(|| {
+                VidError::Argument(format!(
+                    "poly index {} out of bounds for payload len {}",
+                    poly_index,
+                    payload.len()
+                ))
+            })?;
+        let poly = self.polynomial(self.poly_elems(payload, poly_index).into_iter());
+        self.degree_bound_proof_for_poly(&poly)
+    }
+
+    /// Verify a [`DegreeBoundProof`] for the polynomial commitment at
+    /// `poly_index` in `common`.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `common` is inconsistent with `commit`
+    /// or with the parameters of `self`, if `poly_index` is out of bounds, or
+    /// if `proof` has the wrong length.
+    pub fn verify_degree_bound(
+        &self,
+        common: &<Self as VidScheme>::Common,
+        commit: &<Self as VidScheme>::Commit,
+        poly_index: usize,
+        proof: &DegreeBoundProof<E>,
+    ) -> VidResult<Result<(), ()>> {
+        self.check_common_params(common)?;
+        if *commit != self.common_commit(common)? {
+            return Err(VidError::Argument(
+                "commit inconsistent with common".to_string(),
+            ));
+        }
+        let poly_commit = common.poly_commits.get(poly_index).ok_or_else(|| {
+            VidError::Argument(format!(
+                "poly index {} out of bounds for {} poly commits",
+                poly_index,
+                common.poly_commits.len()
+            ))
+        })?;
+        self.verify_degree_bound_for_commit(poly_commit, proof)
+    }
+
+    /// Same as [`VidScheme::disperse`] except that the evaluations of all
+    /// storage nodes are also returned in columnar layout.
+    ///
+    /// Dispersal evaluates each polynomial for all storage nodes, so the
+    /// columns are taken as computed rather than rearranged from the shares.
+    pub fn disperse_columns<B>(&self, payload: B) -> VidResult<(VidDisperse<Self>, ShareColumns<E>)>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let polys = self.payload_polys(payload);
+        let columns = self.all_poly_evals(&polys)?;
+        let all_storage_node_evals = self.storage_node_evals(&columns);
+        let poly_commits = self.commit_polys(&polys)?;
+        let disperse = self.disperse_from_parts(
+            polys,
+            poly_commits,
+            all_storage_node_evals,
+            payload.len(),
+            None,
+        )?;
+        Ok((
+            disperse,
+            ShareColumns {
+                indices: (0..self.num_storage_nodes).collect(),
+                columns,
+            },
+        ))
+    }
+
+    /// Rearrange the evaluations of `shares` into columnar layout.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `shares` is empty or if `shares` do
+    /// not have equal evals lengths.
+    pub fn share_columns(shares: &[<Self as VidScheme>::Share]) -> VidResult<ShareColumns<E>> {
+        let num_polys = Self::shares_num_polys(shares)?;
+        Ok(ShareColumns {
+            indices: shares.iter().map(|s| s.index).collect(),
+            columns: (0..num_polys)
+                .map(|i| shares.iter().map(|s| s.evals[i]).collect())
+                .collect(),
+        })
+    }
+
+    /// Same as [`VidScheme::recover_payload`] except that the shares are
+    /// given in columnar layout.
+    pub fn recover_payload_columns(
+        &self,
+        share_columns: &ShareColumns<E>,
+        common: &<Self as VidScheme>::Common,
+    ) -> VidResult<Vec<u8>> {
+        self.check_common_params(common)?;
+        self.check_enough_shares(share_columns.indices.iter().cloned())?;
+        if let Some((i, column)) = share_columns
+            .columns
+            .iter()
+            .enumerate()
+            .find(|(_, c)| c.len() != share_columns.indices.len())
+        {
+            return Err(VidError::Argument(format!(
+                "column {} len {} differs from number of shares {}",
+                i,
+                column.len(),
+                share_columns.indices.len()
+            )));
+        }
+
+        let (payload_chunk_size, multi_open_domain, eval_domain, indices) = (
+            self.payload_chunk_size,
+            &self.multi_open_domain,
+            &self.eval_domain,
+            &share_columns.indices,
+        );
+        let elems = self
+            .maybe_par_map(&share_columns.columns, |column| {
+                Self::decode_poly_elems(
+                    payload_chunk_size,
+                    multi_open_domain,
+                    eval_domain,
+                    indices.iter().cloned().zip(column.iter().cloned()),
+                )
+            })
+            .into_iter()
+            .collect::<VidResult<Vec<_>>>()?
+            .into_iter()
+            .flat_map(|poly_elems| self.to_logical_order(poly_elems))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            elems.len(),
+            share_columns.columns.len() * self.payload_chunk_size
+        );
+
+        let mut payload: Vec<_> = self.elems_to_bytes(elems).collect();
+        payload.truncate(common.bytes_len);
+        Ok(payload)
+    }
+
+    /// Check that the storage node indices of `shares` are distinct and less
+    /// than `num_storage_nodes`, as required by
+    /// [`VidScheme::recover_payload`].
+    ///
+    /// Does not verify the shares themselves.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] naming the first storage node index that
+    /// is out of bounds or duplicated.
+    pub fn validate_share_set(&self, shares: &[<Self as VidScheme>::Share]) -> VidResult<()> {
+        let mut first_seen = vec![None; self.num_storage_nodes];
+        for (i, share) in shares.iter().enumerate() {
+            match first_seen.get_mut(share.index) {
+                None => {
+                    return Err(VidError::Argument(format!(
+                        "share {} storage node index {} out of bounds for num_storage_nodes {}",
+                        i, share.index, self.num_storage_nodes
+                    )))
+                },
+                Some(Some(j)) => {
+                    return Err(VidError::Argument(format!(
+                        "duplicate storage node index {} in shares {} and {}",
+                        share.index, j, i
+                    )))
+                },
+                Some(seen) => *seen = Some(i),
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the output of [`VidScheme::disperse`] is internally
+    /// consistent before broadcasting it.
+    ///
+    /// Checks that there is one share per storage node, in index order, and
+    /// verifies a sample of shares against `disperse.common` and
+    /// `disperse.commit`. The sample size is set via
+    /// [`Advz::set_self_verify_sample_size`]; sampled shares are spread
+    /// evenly over all storage nodes.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if any check fails.
+    pub fn self_verify_disperse(&self, disperse: &VidDisperse<Self>) -> VidResult<()> {
+        let shares = &disperse.shares;
+        if shares.len() != self.num_storage_nodes {
+            return Err(VidError::Argument(format!(
+                "number of shares {} differs from number of storage nodes {}",
+                shares.len(),
+                self.num_storage_nodes
+            )));
+        }
+        if let Some((i, share)) = shares.iter().enumerate().find(|(i, s)| s.index != *i) {
+            return Err(VidError::Argument(format!(
+                "share {} has storage node index {}",
+                i, share.index
+            )));
+        }
+
+        let sample_size = self
+            .self_verify_sample_size
+            .map_or(shares.len(), |n| usize::min(n, shares.len()));
+        for i in (0..sample_size).map(|j| j * shares.len() / sample_size) {
+            if self
+                .verify_share(&shares[i], &disperse.common, &disperse.commit)?
+                .is_err()
+            {
+                return Err(VidError::Argument(format!(
+                    "share {} inconsistent with common",
+                    i
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify many shares at once, returning the validity of each share.
+    ///
+    /// Merkle paths are checked for each share but the KZG aggregate proofs of
+    /// all shares are checked together via a random linear combination with
+    /// randomizers drawn from `rng`. Only if that batched check fails are
+    /// the proofs checked individually to find the culprits. Shares whose
+    /// evals length is inconsistent with `common` are invalid.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `common` is inconsistent with `commit`
+    /// or with the parameters of `self`.
+    pub fn verify_all_shares<R>(
+        &self,
+        shares: &[<Self as VidScheme>::Share],
+        common: &<Self as VidScheme>::Common,
+        commit: &<Self as VidScheme>::Commit,
+        rng: &mut R,
+    ) -> VidResult<Vec<bool>>
+    where
+        R: RngCore + CryptoRng,
+    {
+        self.check_common_params(common)?;
+        if *commit != self.common_commit(common)? {
+            return Err(VidError::Argument(
+                "commit inconsistent with common".to_string(),
+            ));
+        }
+
+        // merkle paths
+        let mut valid = Vec::with_capacity(shares.len());
+        for share in shares {
+            valid.push(
+                share.evals.len() == common.poly_commits.len()
+                    && self.check_share_evals_proof(share, common)?,
+            );
+        }
+        let candidates: Vec<_> = shares
+            .iter()
+            .zip(valid.iter())
+            .enumerate()
+            .filter_map(|(i, (share, v))| v.then_some((i, share)))
+            .collect();
+        if candidates.is_empty() {
+            return Ok(valid);
+        }
+
+        // aggregate [commitment|evaluations], see `verify_share_aggregate_proof`
+        let pseudorandom_scalar = Self::pseudorandom_scalar(common, commit)?;
+        let aggregate_poly_commit = KzgCommit::<E>::from(
+            polynomial_eval(
+                common
+                    .poly_commits
+                    .iter()
+                    .map(|x| CurveMultiplier(x.as_ref())),
+                pseudorandom_scalar,
+            )
+            .into(),
+        );
+        let points: Vec<_> = candidates
+            .iter()
+            .map(|(_, share)| self.multi_open_domain.element(share.index))
+            .collect();
+        let aggregate_evals: Vec<_> = candidates
+            .iter()
+            .map(|(_, share)| {
+                polynomial_eval(share.evals.iter().map(FieldMultiplier), pseudorandom_scalar)
+            })
+            .collect();
+        let proofs: Vec<_> = candidates
+            .iter()
+            .map(|(_, share)| share.aggregate_proof.clone())
+            .collect();
+
+        if UnivariateKzgPCS::batch_verify(
+            &self.vk,
+            &vec![aggregate_poly_commit; candidates.len()],
+            &points,
+            &aggregate_evals,
+            &proofs,
+            rng,
+        )
+        .map_err(VidError::Pcs)?
+        {
+            return Ok(valid);
+        }
+
+        // batch failed: find the culprits
+        for ((i, _), (point, (eval, proof))) in candidates
+            .iter()
+            .zip(points.iter().zip(aggregate_evals.iter().zip(proofs.iter())))
+        {
+            valid[*i] =
+                UnivariateKzgPCS::verify(&self.vk, &aggregate_poly_commit, point, eval, proof)
+                    .map_err(VidError::Pcs)?;
+        }
+        Ok(valid)
+    }
+
+    /// Compute polynomial commitments and storage node evaluations for a
+    /// segment of a payload beginning at byte `byte_offset`.
+    ///
+    /// Independent workers can each call this method on a different segment.
+    /// The results are then combined via [`Advz::merge_commitments`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `byte_offset` is not aligned to a
+    /// polynomial boundary.
+    pub fn disperse_partial<B>(
+        &self,
+        payload_segment: B,
+        byte_offset: usize,
+    ) -> VidResult<PartialDisperse<E>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload_segment = payload_segment.as_ref();
+        if byte_offset % self.poly_byte_len() != 0 {
+            return Err(VidError::Argument(format!(
+                "byte offset {} not aligned to polynomial byte len {}",
+                byte_offset,
+                self.poly_byte_len()
+            )));
+        }
+
+        let polys = self.payload_polys(payload_segment);
+
+        self.partial_from_polys(polys, byte_offset, payload_segment.len())
+    }
+
+    /// Partition `payload` into polynomials of `payload_chunk_size`
+    /// elements each.
+    fn payload_polys(&self, payload: &[u8]) -> Vec<KzgPolynomial<E>> {
+        let bytes_to_polys_time = start_timer!(|| "encode payload bytes into polynomials");
+        let elems_iter = self.bytes_to_elems(payload);
+        let polys: Vec<_> = elems_iter
+            .chunks(self.payload_chunk_size)
+            .into_iter()
+            .map(|evals_iter| self.polynomial(evals_iter))
+            .collect();
+        end_timer!(bytes_to_polys_time);
+        polys
+    }
+
+    /// Same as [`Advz::disperse_partial`] given the polynomials of the payload
+    /// segment.
+    fn partial_from_polys(
+        &self,
+        polys: Vec<KzgPolynomial<E>>,
+        byte_offset: usize,
+        bytes_len: usize,
+    ) -> VidResult<PartialDisperse<E>> {
+        // evaluate polynomials
+        let all_storage_node_evals_timer = start_timer!(|| format!(
+            "compute all storage node evals for {} polynomials of degree {}",
+            polys.len(),
+            self.payload_chunk_size
+        ));
+        let all_storage_node_evals = self.storage_node_evals(&self.all_poly_evals(&polys)?);
+        end_timer!(all_storage_node_evals_timer);
+
+        let poly_commits_timer =
+            start_timer!(|| format!("compute {} KZG commitments", polys.len()));
+        let poly_commits = self.commit_polys(&polys)?;
+        end_timer!(poly_commits_timer);
+
+        Ok(PartialDisperse {
+            byte_offset,
+            bytes_len,
+            polys,
+            poly_commits,
+            all_storage_node_evals,
+        })
+    }
+
+    /// Evaluate each of `polys` for all storage nodes, so that
+    /// `all_poly_evals[i][j]` is the evaluation of `polys[i]` for storage node
+    /// `j`.
+    fn all_poly_evals(&self, polys: &[KzgPolynomial<E>]) -> VidResult<Vec<Vec<KzgEval<E>>>> {
+        let (num_storage_nodes, multi_open_domain) =
+            (self.num_storage_nodes, &self.multi_open_domain);
+        self.maybe_par_map(polys, |poly| {
+            UnivariateKzgPCS::<E>::multi_open_rou_evals(poly, num_storage_nodes, multi_open_domain)
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(VidError::Pcs)
+    }
+
+    /// Rearrange the output of [`Advz::all_poly_evals`] so that
+    /// `all_storage_node_evals[j]` holds the evals for storage node `j`.
+    fn storage_node_evals(&self, all_poly_evals: &[Vec<KzgEval<E>>]) -> Vec<Vec<KzgEval<E>>> {
+        let mut all_storage_node_evals =
+            vec![Vec::with_capacity(all_poly_evals.len()); self.num_storage_nodes];
+        for poly_evals in all_poly_evals {
+            for (storage_node_evals, poly_eval) in all_storage_node_evals.iter_mut().zip(poly_evals)
+            {
+                storage_node_evals.push(*poly_eval);
+            }
+        }
+
+        // sanity checks
+        assert_eq!(all_storage_node_evals.len(), self.num_storage_nodes);
+        for storage_node_evals in all_storage_node_evals.iter() {
+            assert_eq!(storage_node_evals.len(), all_poly_evals.len());
+        }
+
+        all_storage_node_evals
+    }
+
+    fn commit_polys(&self, polys: &[KzgPolynomial<E>]) -> VidResult<Vec<KzgCommit<E>>> {
+        let ck = &self.ck;
+        self.maybe_par_map(polys, |poly| UnivariateKzgPCS::commit(ck, poly))
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .map_err(VidError::Pcs)
+    }
+
+    /// Combine the [`PartialDisperse`]s of consecutive payload segments into a
+    /// [`VidDisperse`] for the whole payload.
+    ///
+    /// The result is identical to that of [`VidScheme::disperse`] on the
+    /// concatenated payload.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if
+    /// - `partials` are not ordered by byte offset, or leave a gap, or
+    /// - any segment other than the last does not end on a polynomial
+    ///   boundary, or
+    /// - any segment is inconsistent with `self.num_storage_nodes`.
+    pub fn merge_commitments(&self, partials: &[PartialDisperse<E>]) -> VidResult<VidDisperse<Self>>
+    where
+        H: HasherDigest,
+    {
+        let mut bytes_len = 0;
+        for (i, partial) in partials.iter().enumerate() {
+            if partial.byte_offset != bytes_len {
+                return Err(VidError::Argument(format!(
+                    "segment {} byte offset {} differs from expected {}",
+                    i, partial.byte_offset, bytes_len
+                )));
+            }
+            if i + 1 < partials.len() && partial.bytes_len % self.poly_byte_len() != 0 {
+                return Err(VidError::Argument(format!(
+                    "segment {} byte len {} not aligned to polynomial byte len {}",
+                    i,
+                    partial.bytes_len,
+                    self.poly_byte_len()
+                )));
+            }
+            if partial.all_storage_node_evals.len() != self.num_storage_nodes {
+                return Err(VidError::Argument(format!(
+                    "segment {} storage node evals len {} differs from num_storage_nodes {}",
+                    i,
+                    partial.all_storage_node_evals.len(),
+                    self.num_storage_nodes
+                )));
+            }
+            bytes_len += partial.bytes_len;
+        }
+
+        let num_polys = partials.iter().map(|p| p.polys.len()).sum();
+        let mut polys = Vec::with_capacity(num_polys);
+        let mut poly_commits = Vec::with_capacity(num_polys);
+        let mut all_storage_node_evals =
+            vec![Vec::with_capacity(num_polys); self.num_storage_nodes];
+        for partial in partials {
+            polys.extend_from_slice(&partial.polys);
+            poly_commits.extend_from_slice(&partial.poly_commits);
+            for (storage_node_evals, partial_evals) in all_storage_node_evals
+                .iter_mut()
+                .zip(partial.all_storage_node_evals.iter())
+            {
+                storage_node_evals.extend_from_slice(partial_evals);
+            }
+        }
+
+        self.disperse_from_parts(polys, poly_commits, all_storage_node_evals, bytes_len, None)
+    }
+
+    /// Split a payload of `payload_len` bytes into at most `num_segments`
+    /// consecutive segments, each to be dispersed independently via
+    /// [`Advz::disperse_partial`] and combined via
+    /// [`Advz::merge_commitments`].
+    ///
+    /// Every segment boundary is aligned to a polynomial boundary and
+    /// segments differ in length by at most one polynomial. The boundaries
+    /// depend only on `payload_len`, `num_segments` and the parameters of
+    /// `self`. There are fewer than `num_segments` segments only if the
+    /// payload has fewer polynomials. An empty payload is a single empty
+    /// segment.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `num_segments` is zero.
+    pub fn segment_boundaries(
+        &self,
+        payload_len: usize,
+        num_segments: usize,
+    ) -> VidResult<Vec<Range<usize>>> {
+        if num_segments == 0 {
+            return Err(VidError::Argument(
+                "num_segments must be positive".to_string(),
+            ));
+        }
+        let poly_byte_len = self.poly_byte_len();
+        let num_polys = payload_len / poly_byte_len + usize::from(payload_len % poly_byte_len != 0);
+        if num_polys == 0 {
+            return Ok(vec![Range::default()]);
+        }
+        let num_segments = num_segments.min(num_polys);
+        let (min_polys, num_longer) = (num_polys / num_segments, num_polys % num_segments);
+        let mut start = 0;
+        Ok((0..num_segments)
+            .map(|i| {
+                let segment_polys = min_polys + usize::from(i < num_longer);
+                let end = payload_len.min(start + segment_polys * poly_byte_len);
+                let segment = start..end;
+                start = end;
+                segment
+            })
+            .collect())
+    }
+
+    /// Same as [`VidScheme::disperse`] except that the payload arrives as a
+    /// sequence of arbitrarily sized chunks over `rx`, ending when the channel
+    /// is closed.
+    ///
+    /// Each polynomial is committed as soon as its bytes have arrived so that
+    /// no more than one polynomial's worth of raw payload is buffered. Chunks
+    /// are pulled from `rx` only when the current polynomial needs more bytes,
+    /// so a bounded [`std::sync::mpsc::sync_channel`] applies backpressure to
+    /// the sender.
+    ///
+    /// The result is identical to that of [`VidScheme::disperse`] on the
+    /// concatenated chunks.
+    #[cfg(feature = "std")]
+    pub fn disperse_stream(
+        &self,
+        rx: std::sync::mpsc::Receiver<Vec<u8>>,
+    ) -> VidResult<VidDisperse<Self>>
+    where
+        H: HasherDigest,
+    {
+        let poly_byte_len = self.poly_byte_len();
+        let mut partials = Vec::new();
+        let mut poly_bytes = Vec::with_capacity(poly_byte_len);
+        let mut byte_offset = 0;
+        for chunk in rx.iter() {
+            let mut chunk = chunk.as_slice();
+            while !chunk.is_empty() {
+                let take = usize::min(poly_byte_len - poly_bytes.len(), chunk.len());
+                poly_bytes.extend_from_slice(&chunk[..take]);
+                chunk = &chunk[take..];
+                if poly_bytes.len() == poly_byte_len {
+                    partials.push(self.disperse_partial(&poly_bytes, byte_offset)?);
+                    byte_offset += poly_byte_len;
+                    poly_bytes.clear();
+                }
+            }
+        }
+        if !poly_bytes.is_empty() || partials.is_empty() {
+            partials.push(self.disperse_partial(&poly_bytes, byte_offset)?);
+        }
+        self.merge_commitments(&partials)
+    }
+
+    /// Same as [`VidScheme::disperse`] except that work stops once `deadline`
+    /// has passed, trading availability for latency.
+    ///
+    /// Polynomials are committed one at a time in payload order and the
+    /// deadline is checked after each, so at least one polynomial is always
+    /// committed. If the deadline passes before the last one, the polynomials
+    /// completed so far are returned as [`DeadlineDisperse::Partial`]. The
+    /// caller may then give up or finish the rest of the payload via
+    /// [`Advz::disperse_partial`] and combine the two via
+    /// [`Advz::merge_commitments`].
+    ///
+    /// Unlike [`VidScheme::disperse`], polynomials are not processed in
+    /// parallel with each other.
+    #[cfg(feature = "std")]
+    pub fn disperse_with_deadline<B>(
+        &self,
+        payload: B,
+        deadline: std::time::Instant,
+    ) -> VidResult<DeadlineDisperse<E, H>>
+    where
+        B: AsRef<[u8]>,
+        H: HasherDigest,
+    {
+        let payload = payload.as_ref();
+        let poly_byte_len = self.poly_byte_len();
+        let mut done = PartialDisperse {
+            byte_offset: 0,
+            bytes_len: 0,
+            polys: Vec::new(),
+            poly_commits: Vec::new(),
+            all_storage_node_evals: vec![Vec::new(); self.num_storage_nodes],
+        };
+        while done.bytes_len < payload.len() {
+            let end = usize::min(done.bytes_len + poly_byte_len, payload.len());
+            let partial = self.disperse_partial(&payload[done.bytes_len..end], done.bytes_len)?;
+            done.polys.extend(partial.polys);
+            done.poly_commits.extend(partial.poly_commits);
+            for (storage_node_evals, partial_evals) in done
+                .all_storage_node_evals
+                .iter_mut()
+                .zip(partial.all_storage_node_evals)
+            {
+                storage_node_evals.extend(partial_evals);
+            }
+            done.bytes_len = end;
+            if done.bytes_len < payload.len() && std::time::Instant::now() >= deadline {
+                return Ok(DeadlineDisperse::Partial(done));
+            }
+        }
+        Ok(DeadlineDisperse::Complete(self.disperse_from_parts(
+            done.polys,
+            done.poly_commits,
+            done.all_storage_node_evals,
+            done.bytes_len,
+            None,
+        )?))
+    }
+
+    /// Same as [`VidScheme::disperse`] except that a digest of `header` is
+    /// bound into the payload commitment.
+    ///
+    /// Only `payload` is erasure-coded; payload proofs address `payload`
+    /// without `header`. Anyone holding the resulting commitment and common
+    /// data can check a claimed header via [`Advz::verify_header`], so a
+    /// client that verifies a payload proof also learns that the header is
+    /// authentic.
+    pub fn disperse_with_header<B>(&self, header: &[u8], payload: B) -> VidResult<VidDisperse<Self>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let partial = self.disperse_partial(payload, 0)?;
+        self.disperse_from_parts(
+            partial.polys,
+            partial.poly_commits,
+            partial.all_storage_node_evals,
+            partial.bytes_len,
+            Some(HasherNode::from(H::digest(header))),
+        )
+    }
+
+    /// Same as [`VidScheme::disperse`] except that `payload` is packed into
+    /// elements of `F`, an extension of the scalar field of `E`.
+    ///
+    /// Each element of `F` holds the byte capacity of a scalar field element
+    /// times the extension degree `d` of `F`, and occupies `d` consecutive
+    /// elements of a polynomial, one per coordinate, lowest first. This is
+    /// the layout of payload bytes packed into scalar field elements, so the
+    /// result agrees with [`VidScheme::disperse`] and shares, recovery and
+    /// payload proofs apply unchanged. Prove and verify whole elements of `F`
+    /// via [`Advz::ext_element_proof`] and [`Advz::verify_ext_element`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `d` does not divide
+    /// `payload_chunk_size`, so that an element of `F` would span two
+    /// polynomials, or if payload bytes are not packed via
+    /// [`FieldPacking::Bytes`].
+    pub fn disperse_ext<F, B>(&self, payload: B) -> VidResult<VidDisperse<Self>>
+    where
+        F: Field<BasePrimeField = KzgEval<E>>,
+        B: AsRef<[u8]>,
+    {
+        self.check_ext_degree::<F>()?;
+        let payload = payload.as_ref();
+        let polys: Vec<_> = bytes_to_field::<_, F>(payload)
+            .flat_map(|elem| elem.to_base_prime_field_elements())
+            .chunks(self.payload_chunk_size)
+            .into_iter()
+            .map(|evals_iter| self.polynomial(evals_iter))
+            .collect();
+        let partial = self.partial_from_polys(polys, 0, payload.len())?;
+        self.disperse_from_parts(
+            partial.polys,
+            partial.poly_commits,
+            partial.all_storage_node_evals,
+            partial.bytes_len,
+            None,
+        )
+    }
+
+    /// Reconstruct the common data for `commit` from a threshold set of
+    /// `shares`, for a node that never received it.
+    ///
+    /// Each polynomial is interpolated from `shares` and committed to. Shares
+    /// do not record the payload byte length, so the length bound into
+    /// `commit` is found among those consistent with the recovered payload.
+    /// Only [`CommitVersion::V2`] binds the length.
+    /// `header` is the header given to [`Advz::disperse_with_header`], if
+    /// any. A successful return implies that the result is consistent with
+    /// `commit`.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] under the conditions of
+    /// [`VidScheme::recover_payload`], if `shares` and `header` are
+    /// inconsistent with `commit` or if the commit version is not
+    /// [`CommitVersion::V2`].
+    pub fn reconstruct_common(
+        &self,
+        shares: &[<Self as VidScheme>::Share],
+        commit: &<Self as VidScheme>::Commit,
+        header: Option<&[u8]>,
+    ) -> VidResult<<Self as VidScheme>::Common> {
+        self.check_commit_version(&[CommitVersion::V2], "reconstruct_common")?;
+        self.check_enough_shares(shares.iter().map(|s| s.index))?;
+        let num_polys = Self::shares_num_polys(shares)?;
+
+        let poly_indices: Vec<usize> = (0..num_polys).collect();
+        let (payload_chunk_size, multi_open_domain, eval_domain) = (
+            self.payload_chunk_size,
+            &self.multi_open_domain,
+            &self.eval_domain,
+        );
+        let elems = self
+            .maybe_par_map(&poly_indices, |i| {
+                Self::decode_poly_elems(
+                    payload_chunk_size,
+                    multi_open_domain,
+                    eval_domain,
+                    shares.iter().map(|s| (s.index, s.evals[*i])),
+                )
+            })
+            .into_iter()
+            .collect::<VidResult<Vec<_>>>()?
+            .into_iter()
+            .flat_map(|poly_elems| self.to_logical_order(poly_elems))
+            .collect::<Vec<_>>();
+
+        // re-disperse the recovered payload including any padding, so that
+        // trailing zero polynomials are kept
+        let padded_payload: Vec<_> = self.elems_to_bytes(elems).collect();
+        let partial = self.disperse_partial(&padded_payload, 0)?;
+        let header_digest = header.map(|header| HasherNode::from(H::digest(header)));
+        let prefix_digest =
+            Self::poly_commits_prefix_digest(partial.poly_commits.iter(), header_digest.as_ref())?;
+
+        // the payload is followed only by zero padding
+        let min_bytes_len = padded_payload
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |pos| pos + 1);
+        let bytes_len = (min_bytes_len..=padded_payload.len())
+            .find(|bytes_len| Self::length_bound_commit(&prefix_digest, *bytes_len) == *commit)
+            .ok_or_else(|| {
+                VidError::Argument("shares and header inconsistent with commit".to_string())
+            })?;
+
+        Ok(Common {
+            poly_commits: partial.poly_commits,
+            all_evals_digest: Self::all_evals_merkle_tree(&partial.all_storage_node_evals)?
+                .commitment()
+                .digest(),
+            bytes_len,
+            header_digest,
+            evals_leaf_digests: self.evals_leaf_digests(&partial.all_storage_node_evals)?,
+            payload_chunk_size: self.payload_chunk_size,
+            num_storage_nodes: self.num_storage_nodes,
+        })
+    }
+
+    /// Re-encode the payload held by `shares` into shares for
+    /// `new_num_storage_nodes` storage nodes without changing the payload
+    /// commitment, for example when redundancy requirements change.
+    ///
+    /// `shares` and `common` are from dispersal with `self`. The payload is
+    /// recovered from `shares` and its polynomials are re-evaluated at the
+    /// points of [`Advz::with_num_storage_nodes`]. The result is identical to
+    /// dispersal of the payload with that instance, so its shares verify via
+    /// that instance against its `common` and the unchanged payload
+    /// commitment. Only `common` fields that depend on the shares change.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] under the conditions of
+    /// [`VidScheme::recover_payload`] and [`Advz::with_num_storage_nodes`],
+    /// or if `shares` are inconsistent with `common`.
+    pub fn reencode_shares(
+        &self,
+        shares: &[<Self as VidScheme>::Share],
+        common: &<Self as VidScheme>::Common,
+        new_num_storage_nodes: usize,
+    ) -> VidResult<VidDisperse<Self>> {
+        let advz = self.with_num_storage_nodes(new_num_storage_nodes)?;
+        let payload = self.recover_payload(shares, common)?;
+        let partial = advz.disperse_partial(&payload, 0)?;
+        let disperse = advz.disperse_from_parts(
+            partial.polys,
+            partial.poly_commits,
+            partial.all_storage_node_evals,
+            common.bytes_len,
+            common.header_digest,
+        )?;
+        if disperse.commit != self.common_commit(common)? {
+            return Err(VidError::Argument(
+                "shares inconsistent with common".to_string(),
+            ));
+        }
+        Ok(disperse)
+    }
+
+    /// Check that `header` is the header bound into `commit` by
+    /// [`Advz::disperse_with_header`].
+    ///
+    /// Return value has the same semantics as [`VidScheme::verify_share`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `common` is inconsistent with `commit`.
+    pub fn verify_header(
+        &self,
+        header: &[u8],
+        commit: &<Self as VidScheme>::Commit,
+        common: &<Self as VidScheme>::Common,
+    ) -> VidResult<Result<(), ()>> {
+        if *commit != self.common_commit(common)? {
+            return Err(VidError::Argument(
+                "commit inconsistent with common".to_string(),
+            ));
+        }
+        Ok(
+            if common.header_digest == Some(HasherNode::from(H::digest(header))) {
+                Ok(())
+            } else {
+                Err(())
+            },
+        )
+    }
+
+    /// Disperse each of `payloads` independently and commit to all of them at
+    /// once.
+    ///
+    /// Return one [`VidDisperse`] per payload, in order, and the aggregate
+    /// root [`Advz::multi_root`] of their common data. A range of the
+    /// concatenation of `payloads` may straddle several payloads; prove it via
+    /// [`Advz::payload_proof_cross`].
+    pub fn disperse_multi<B>(
+        &self,
+        payloads: &[B],
+    ) -> VidResult<(Vec<VidDisperse<Self>>, HasherNode<H>)>
+    where
+        B: AsRef<[u8]>,
+    {
+        let disperses = payloads
+            .iter()
+            .map(|payload| self.disperse(payload))
+            .collect::<VidResult<Vec<_>>>()?;
+        let commons: Vec<_> = disperses.iter().map(|d| d.common.clone()).collect();
+        let root = self.multi_root(&commons)?;
+        Ok((disperses, root))
+    }
+
+    /// Aggregate root of several payloads given their common data in order,
+    /// as returned by [`Advz::disperse_multi`].
+    ///
+    /// Binds the commitment and byte length of each payload so that a range
+    /// of the concatenation of the payloads is well-defined under the root.
+    pub fn multi_root(&self, commons: &[<Self as VidScheme>::Common]) -> VidResult<HasherNode<H>> {
+        let mut hasher = H::new();
+        hasher.update(b"advz multi");
+        hasher.update((commons.len() as u64).to_le_bytes());
+        for common in commons {
+            hasher.update(self.common_commit(common)?);
+            hasher.update((common.bytes_len as u64).to_le_bytes());
+        }
+        Ok(HasherNode::from(hasher.finalize()))
+    }
+
+    /// Same as [`VidScheme::disperse`] except that polynomial `i` is
+    /// dispersed to only the first `poly_num_storage_nodes[i]` storage nodes,
+    /// eg. for more redundancy for header polynomials.
+    ///
+    /// Polynomials beyond the end of `poly_num_storage_nodes` are dispersed
+    /// to all `num_storage_nodes` storage nodes. Each polynomial still needs
+    /// `payload_chunk_size` of its storage nodes for recovery via
+    /// [`Advz::recover_payload_heterogeneous`], so redundancy varies per
+    /// polynomial. Polynomial commitments and the payload commitment are the
+    /// same as those of [`VidScheme::disperse`].
+    ///
+    /// Each share carries a KZG opening of a pseudorandom linear combination
+    /// of the polynomials dispersed to its storage node. The combination is
+    /// derived from the commitment and the share itself, because unlike
+    /// [`VidScheme::Common`] there is no merkle root that fixes every eval in
+    /// advance. Proving thus costs one opening per storage node.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `poly_num_storage_nodes` is longer
+    /// than the number of polynomials or if any entry is less than
+    /// `payload_chunk_size` or more than `num_storage_nodes`.
+    pub fn disperse_heterogeneous<B>(
+        &self,
+        payload: B,
+        poly_num_storage_nodes: &[usize],
+    ) -> VidResult<HeterogeneousDisperse<E, H>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let mut partial = self.disperse_partial(payload, 0)?;
+
+        // the zero polynomial has zero commitment and zero evals
+        let num_polys = usize::max(partial.poly_commits.len(), self.min_polynomials);
+        partial
+            .poly_commits
+            .resize(num_polys, KzgCommit::<E>::from(E::G1Affine::zero()));
+        for storage_node_evals in partial.all_storage_node_evals.iter_mut() {
+            storage_node_evals.resize(num_polys, KzgEval::<E>::zero());
+        }
+
+        if poly_num_storage_nodes.len() > num_polys {
+            return Err(VidError::Argument(format!(
+                "poly_num_storage_nodes len {} exceeds number of polynomials {}",
+                poly_num_storage_nodes.len(),
+                num_polys
+            )));
+        }
+        let mut poly_num_storage_nodes = poly_num_storage_nodes.to_vec();
+        poly_num_storage_nodes.resize(num_polys, self.num_storage_nodes);
+        if let Some((poly_index, n)) = poly_num_storage_nodes
+            .iter()
+            .enumerate()
+            .find(|(_, n)| **n < self.payload_chunk_size || **n > self.num_storage_nodes)
+        {
+            return Err(VidError::Argument(format!(
+                "num storage nodes {} for polynomial {} outside [payload_chunk_size {}, num_storage_nodes {}]",
+                n, poly_index, self.payload_chunk_size, self.num_storage_nodes
+            )));
+        }
+
+        let commit =
+            self.poly_commits_hash(partial.poly_commits.iter(), None, partial.bytes_len)?;
+        let mut polys = partial.polys;
+        polys.resize(num_polys, KzgPolynomial::<E>::zero());
+        let zero_poly = KzgPolynomial::<E>::zero();
+        let shares = partial
+            .all_storage_node_evals
+            .into_iter()
+            .enumerate()
+            .map(|(index, evals)| {
+                let evals: Vec<_> = evals
+                    .into_iter()
+                    .zip(poly_num_storage_nodes.iter())
+                    .map(|(eval, n)| (index < *n).then_some(eval))
+                    .collect();
+
+                // polynomials not dispersed to this storage node contribute zero
+                let pseudorandom_scalar =
+                    Self::heterogeneous_pseudorandom_scalar(&commit, index, &evals)?;
+                let aggregate_poly = polynomial_eval(
+                    polys.iter().zip(evals.iter()).map(|(poly, eval)| {
+                        PolynomialMultiplier(if eval.is_some() { poly } else { &zero_poly })
+                    }),
+                    pseudorandom_scalar,
+                );
+                let (aggregate_proof, _) = UnivariateKzgPCS::open(
+                    &self.ck,
+                    &aggregate_poly,
+                    &self.multi_open_domain.element(index),
+                )
+                .map_err(VidError::Pcs)?;
+                Ok(HeterogeneousShare {
+                    index,
+                    evals,
+                    aggregate_proof,
+                })
+            })
+            .collect::<VidResult<_>>()?;
+        Ok(HeterogeneousDisperse {
+            shares,
+            poly_num_storage_nodes,
+            poly_commits: partial.poly_commits,
+            commit,
+            bytes_len: partial.bytes_len,
+        })
+    }
+
+    /// Verify `share` of a [`HeterogeneousDisperse`] against `commit`.
+    ///
+    /// `poly_num_storage_nodes`, `poly_commits` and `bytes_len` are those of
+    /// the [`HeterogeneousDisperse`]. The share must hold an eval for exactly
+    /// the polynomials dispersed to its storage node.
+    ///
+    /// Return value has the same semantics as [`VidScheme::verify_share`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `poly_commits` and `bytes_len` are
+    /// inconsistent with `commit`, or if `share`, `poly_num_storage_nodes`
+    /// and `poly_commits` do not have equal lengths.
+    pub fn verify_share_heterogeneous(
+        &self,
+        share: &HeterogeneousShare<E>,
+        poly_num_storage_nodes: &[usize],
+        poly_commits: &[KzgCommit<E>],
+        bytes_len: usize,
+        commit: &<Self as VidScheme>::Commit,
+    ) -> VidResult<Result<(), ()>> {
+        if self.poly_commits_hash(poly_commits.iter(), None, bytes_len)? != *commit {
+            return Err(VidError::Argument(
+                "commit inconsistent with poly commits".to_string(),
+            ));
+        }
+        if share.evals.len() != poly_commits.len()
+            || poly_num_storage_nodes.len() != poly_commits.len()
+        {
+            return Err(VidError::Argument(format!(
+                "(share eval, poly num storage nodes, poly commit) lengths differ ({},{},{})",
+                share.evals.len(),
+                poly_num_storage_nodes.len(),
+                poly_commits.len()
+            )));
+        }
+        if share.index >= self.num_storage_nodes
+            || share
+                .evals
+                .iter()
+                .zip(poly_num_storage_nodes.iter())
+                .any(|(eval, n)| eval.is_some() != (share.index < *n))
+        {
+            return Ok(Err(())); // not an arg error
+        }
+
+        // aggregate [commitment|evaluation] as in `disperse_heterogeneous`
+        let pseudorandom_scalar =
+            Self::heterogeneous_pseudorandom_scalar(commit, share.index, &share.evals)?;
+        let zero_commit = KzgCommit::<E>::from(E::G1Affine::zero());
+        let aggregate_poly_commit = KzgCommit::<E>::from(
+            polynomial_eval(
+                poly_commits
+                    .iter()
+                    .zip(share.evals.iter())
+                    .map(|(poly_commit, eval)| match eval {
+                        Some(_) => CurveMultiplier(poly_commit.as_ref()),
+                        None => CurveMultiplier(zero_commit.as_ref()),
+                    }),
+                pseudorandom_scalar,
+            )
+            .into(),
+        );
+        let evals: Vec<_> = share
+            .evals
+            .iter()
+            .map(|eval| eval.unwrap_or_else(KzgEval::<E>::zero))
+            .collect();
+        let aggregate_eval =
+            polynomial_eval(evals.iter().map(FieldMultiplier), pseudorandom_scalar);
+
+        Ok(UnivariateKzgPCS::verify(
+            &self.vk,
+            &aggregate_poly_commit,
+            &self.multi_open_domain.element(share.index),
+            &aggregate_eval,
+            &share.aggregate_proof,
+        )
+        .map_err(VidError::Pcs)?
+        .then_some(())
+        .ok_or(()))
+    }
+
+    /// Recover a payload of byte length `bytes_len` from `shares` of a
+    /// [`HeterogeneousDisperse`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `shares` is empty, if `shares` do not
+    /// have equal evals lengths, or if for any polynomial `shares` hold fewer
+    /// than `payload_chunk_size` distinct evals.
+    pub fn recover_payload_heterogeneous(
+        &self,
+        shares: &[HeterogeneousShare<E>],
+        bytes_len: usize,
+    ) -> VidResult<Vec<u8>> {
+        let num_polys = shares
+            .first()
+            .ok_or_else(|| VidError::Argument("shares is empty".into()))?
+            .evals
+            .len();
+        if shares.iter().any(|s| s.evals.len() != num_polys) {
+            return Err(VidError::Argument(
+                "shares do not have equal evals lengths".into(),
+            ));
+        }
+
+        let mut elems = Vec::with_capacity(num_polys * self.payload_chunk_size);
+        for poly_index in 0..num_polys {
+            let evals: Vec<_> = shares
+                .iter()
+                .filter_map(|s| s.evals[poly_index].map(|eval| (s.index, eval)))
+                .collect();
+            self.check_enough_shares(evals.iter().map(|(index, _)| *index))
+                .map_err(|e| VidError::Argument(format!("polynomial {}: {}", poly_index, e)))?;
+            elems.extend(self.to_logical_order(Self::decode_poly_elems(
+                self.payload_chunk_size,
+                &self.multi_open_domain,
+                &self.eval_domain,
+                evals,
+            )?));
+        }
+
+        let mut payload: Vec<_> = self.elems_to_bytes(elems).collect();
+        payload.truncate(bytes_len);
+        Ok(payload)
+    }
+
+    /// Same as [`VidScheme::disperse`] except that the payload is hidden, for
+    /// temporary payload privacy with selective reveal.
+    ///
+    /// A polynomial with uniformly random coefficients, derived from a random
+    /// [`Blind`], is added to each payload polynomial before dispersal, so
+    /// that the dispersed polynomials are uniformly random. Commitments,
+    /// shares and evaluation proofs are those of the blinded polynomials.
+    /// Reveal the blind of one polynomial via
+    /// [`HidingDisperse::reveal_blind`] so that anyone can de-hide its
+    /// commitment via [`Advz::dehide`] and verify payload proofs for ranges in
+    /// it.
+    pub fn disperse_hiding<B, R>(&self, payload: B, rng: &mut R) -> VidResult<HidingDisperse<E, H>>
+    where
+        B: AsRef<[u8]>,
+        R: RngCore + CryptoRng,
+    {
+        let payload = payload.as_ref();
+        let polys = self.payload_polys(payload);
+        let blinds: Vec<_> = polys
+            .iter()
+            .map(|_| {
+                let mut seed = [0; 32];
+                rng.fill_bytes(&mut seed);
+                Blind(seed)
+            })
+            .collect();
+        let blinded_polys = polys
+            .iter()
+            .zip(blinds.iter())
+            .map(|(poly, blind)| poly + &self.blind_polynomial(blind))
+            .collect();
+        let partial = self.partial_from_polys(blinded_polys, 0, payload.len())?;
+        let disperse = self.disperse_from_parts(
+            partial.polys,
+            partial.poly_commits,
+            partial.all_storage_node_evals,
+            partial.bytes_len,
+            None,
+        )?;
+        Ok(HidingDisperse { disperse, blinds })
+    }
+
+    /// De-hide polynomial commitment `poly_index` of the payload commitment
+    /// `commit` and its common data `common` from [`Advz::disperse_hiding`]
+    /// given its revealed `blind`.
+    ///
+    /// Return a payload commitment and common data against which payload
+    /// proofs for ranges in polynomial `poly_index` of the original payload
+    /// verify as usual via [`PayloadProver::payload_verify`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `common` is inconsistent with `commit`
+    /// or if `poly_index` is out of bounds.
+    ///
+    /// [`PayloadProver::payload_verify`]: super::payload_prover::PayloadProver::payload_verify
+    pub fn dehide(
+        &self,
+        commit: &<Self as VidScheme>::Commit,
+        common: &<Self as VidScheme>::Common,
+        poly_index: usize,
+        blind: &Blind,
+    ) -> VidResult<(<Self as VidScheme>::Commit, <Self as VidScheme>::Common)> {
+        if *commit != self.common_commit(common)? {
+            return Err(VidError::Argument(
+                "commit inconsistent with common".to_string(),
+            ));
+        }
+        let mut dehidden = common.clone();
+        let poly_commit = dehidden.poly_commits.get_mut(poly_index).ok_or_else(|| {
+            VidError::Argument(format!(
+                "poly index {} out of bounds for {} poly commits",
+                poly_index,
+                common.poly_commits.len()
+            ))
+        })?;
+        let blind_commit = UnivariateKzgPCS::commit(&self.ck, &self.blind_polynomial(blind))
+            .map_err(VidError::Pcs)?;
+        *poly_commit =
+            KzgCommit::<E>::from((poly_commit.0.into_group() - blind_commit.0).into_affine());
+        Ok((self.common_commit(&dehidden)?, dehidden))
+    }
+
+    /// Update the payload commitment of `old_disperse` after payload element
+    /// `elem_index` changes from `old_elem` to `new_elem`.
+    ///
+    /// Polynomial commitments are linear in the payload elements, so only the
+    /// commitment to the polynomial containing `elem_index` changes, by
+    /// `new_elem - old_elem` times the commitment to a Lagrange basis
+    /// polynomial. Costs one MSM of size `payload_chunk_size` plus one hash
+    /// per polynomial instead of a full [`VidScheme::disperse`].
+    ///
+    /// Return the updated poly commits and payload commitment. Shares and the
+    /// rest of `old_disperse.common` are not updated; disperse the edited
+    /// payload to obtain them. See [`Advz::enumerate_elems`] for the elements
+    /// of a payload.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `elem_index` is out of bounds for the
+    /// payload of `old_disperse`.
+    pub fn update_commitment(
+        &self,
+        old_disperse: &VidDisperse<Self>,
+        elem_index: usize,
+        old_elem: KzgEval<E>,
+        new_elem: KzgEval<E>,
+    ) -> VidResult<(Vec<KzgCommit<E>>, <Self as VidScheme>::Commit)> {
+        let common = &old_disperse.common;
+        self.check_common_params(common)?;
+        let num_elems = self.num_payload_elems(common.bytes_len);
+        if elem_index >= num_elems {
+            return Err(VidError::Argument(format!(
+                "elem index {} out of bounds for payload with {} elems",
+                elem_index, num_elems
+            )));
+        }
+        let poly_index = elem_index / self.payload_chunk_size;
+        let mut poly_commits = common.poly_commits.clone();
+        let poly_commit = poly_commits.get_mut(poly_index).ok_or_else(|| {
+            VidError::Argument(format!(
+                "poly index {} out of bounds for common with {} poly commits",
+                poly_index,
+                common.poly_commits.len()
+            ))
+        })?;
+        let delta = self.lagrange_basis_commit(elem_index % self.payload_chunk_size)
+            * (new_elem - old_elem);
+        *poly_commit = KzgCommit::<E>::from((poly_commit.0.into_group() + delta).into_affine());
+        let commit = self.poly_commits_hash(
+            poly_commits.iter(),
+            common.header_digest.as_ref(),
+            common.bytes_len,
+        )?;
+        Ok((poly_commits, commit))
+    }
+
+    /// Return `(poly_index, commit_a - commit_b)` for each polynomial whose
+    /// commitment differs between `payload_a` and `payload_b`, where
+    /// `commit_a`, `commit_b` are the poly commits at `poly_index` in
+    /// [`VidScheme::disperse`] of each payload.
+    ///
+    /// By linearity of KZG each difference is a commitment to the difference
+    /// of the two polynomials, so a dispute can be narrowed to individual
+    /// polynomials without revealing payload data. A polynomial missing from
+    /// the shorter payload is treated as zero. Returns an empty list if the
+    /// payloads encode to the same polynomials.
+    pub fn commitment_delta<B>(
+        &self,
+        payload_a: B,
+        payload_b: B,
+    ) -> VidResult<Vec<(usize, KzgCommit<E>)>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let elems_a: Vec<_> = self.bytes_to_elems(payload_a.as_ref()).collect();
+        let elems_b: Vec<_> = self.bytes_to_elems(payload_b.as_ref()).collect();
+        let chunks_a: Vec<_> = elems_a.chunks(self.payload_chunk_size).collect();
+        let chunks_b: Vec<_> = elems_b.chunks(self.payload_chunk_size).collect();
+
+        let mut delta = Vec::new();
+        for poly_index in 0..usize::max(chunks_a.len(), chunks_b.len()) {
+            let chunk_a = chunks_a.get(poly_index).copied().unwrap_or_default();
+            let chunk_b = chunks_b.get(poly_index).copied().unwrap_or_default();
+            if chunk_a == chunk_b {
+                continue;
+            }
+            let diff = (0..usize::max(chunk_a.len(), chunk_b.len())).map(|i| {
+                chunk_a.get(i).copied().unwrap_or_else(KzgEval::<E>::zero)
+                    - chunk_b.get(i).copied().unwrap_or_else(KzgEval::<E>::zero)
+            });
+            let poly_commit = UnivariateKzgPCS::commit(&self.ck, &self.polynomial(diff))
+                .map_err(VidError::Pcs)?;
+            delta.push((poly_index, poly_commit));
+        }
+        Ok(delta)
+    }
+
+    /// Merkle-tree-based alternative to the payload commitment
+    /// [`VidScheme::Commit`].
+    ///
+    /// Return the root of a merkle tree whose leaves are `common.poly_commits`.
+    /// Unlike the digest returned by [`VidScheme::commit_only`], inclusion of
+    /// a single poly commit can be proved against this root with a proof of
+    /// size logarithmic in the number of poly commits. See
+    /// [`Advz::poly_commit_inclusion_proof`].
+    ///
+    /// Under [`CommitVersion::V3`] this root is the payload commitment, from
+    /// which a verifier recovers it via [`Advz::commit_poly_commits_root`].
+    pub fn poly_commits_merkle_root(
+        common: &<Self as VidScheme>::Common,
+    ) -> VidResult<HasherNode<H>> {
+        Ok(Self::poly_commits_merkle_tree(&common.poly_commits)?
+            .commitment()
+            .digest())
+    }
+
+    /// Prove that `common.poly_commits[poly_index]` is a leaf of the merkle
+    /// tree with root [`Advz::poly_commits_merkle_root`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `poly_index` is out of bounds.
+    pub fn poly_commit_inclusion_proof(
+        common: &<Self as VidScheme>::Common,
+        poly_index: usize,
+    ) -> VidResult<PolyCommitsMerkleTreeProof<E, H>> {
+        Self::poly_commits_inclusion_proof(&common.poly_commits, poly_index)
+    }
+
+    /// The root of the merkle tree over the poly commits committed to by
+    /// `commit`, against which [`Advz::verify_poly_commit_inclusion`] checks
+    /// a single poly commit without `common`.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if the commit version is not
+    /// [`CommitVersion::V3`], under which the payload commitment is not the
+    /// root.
+    pub fn commit_poly_commits_root(
+        &self,
+        commit: &<Self as VidScheme>::Commit,
+    ) -> VidResult<HasherNode<H>> {
+        self.check_commit_version(&[CommitVersion::V3], "commit_poly_commits_root")?;
+        Ok(HasherNode::from(*commit))
+    }
+
+    /// Verify a proof made by [`Advz::poly_commit_inclusion_proof`] that
+    /// `poly_commit` is at `poly_index` in the merkle tree with root `root`.
+    ///
+    /// Return value has the same semantics as [`VidScheme::verify_share`].
+    pub fn verify_poly_commit_inclusion(
+        root: &HasherNode<H>,
+        poly_index: usize,
+        poly_commit: &KzgCommit<E>,
+        proof: &PolyCommitsMerkleTreeProof<E, H>,
+    ) -> VidResult<Result<(), ()>> {
+        // the authenticated leaf must be the claimed poly commit
+        if proof.elem() != Some(poly_commit) {
+            return Ok(Err(()));
+        }
+        PolyCommitsMerkleTree::<E, H>::verify(
+            root,
+            PolyCommitsMerkleTreeIndex::<E, H>::from(poly_index as u64),
+            proof,
+        )
+        .map_err(vid)
+    }
+
+    /// Merkle root of the shares output by dispersal, for data availability
+    /// sampling via [`Advz::verify_sampled_share`].
+    ///
+    /// Each leaf is the hash of the index, evaluations and aggregate proof of
+    /// a share, so the root does not depend on [`Advz::set_share_encoding`].
+    ///
+    /// `shares` are all the shares output by dispersal. The root is not bound
+    /// into the payload commitment, so the disperser publishes it alongside
+    /// the commitment and sampling clients must get it from a source they
+    /// trust. Each sampled share is still checked against the payload
+    /// commitment via [`VidScheme::verify_share`].
+    pub fn shares_root(shares: &[<Self as VidScheme>::Share]) -> VidResult<HasherNode<H>> {
+        Ok(Self::shares_merkle_tree(shares)?.commitment().digest())
+    }
+
+    /// Prove that `shares[index]` is the share at `index` in the merkle tree
+    /// with root [`Advz::shares_root`]. `shares` are all the shares output by
+    /// dispersal.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `index` is out of bounds.
+    pub fn share_root_proof(
+        shares: &[<Self as VidScheme>::Share],
+        index: usize,
+    ) -> VidResult<SharesMerkleTreeProof<H>> {
+        if index >= shares.len() {
+            return Err(VidError::Argument(format!(
+                "share index {} out of bounds for {} shares",
+                index,
+                shares.len()
+            )));
+        }
+        Ok(Self::shares_merkle_tree(shares)?
+            .lookup(SharesMerkleTreeIndex::<H>::from(index as u64))
+            .expect_ok()
+            .map_err(vid)?
+            .1)
+    }
+
+    /// Verify a proof made by [`Advz::share_root_proof`] that `share` is the
+    /// share at `index` in the merkle tree with root `shares_root`, as
+    /// returned by [`Advz::shares_root`].
+    ///
+    /// A sampling client can thus check a share without holding the others.
+    /// Check the share against the payload commitment via
+    /// [`VidScheme::verify_share`].
+    ///
+    /// Return value has the same semantics as [`VidScheme::verify_share`].
+    pub fn verify_sampled_share(
+        share: &<Self as VidScheme>::Share,
+        index: usize,
+        share_root_proof: &SharesMerkleTreeProof<H>,
+        shares_root: &HasherNode<H>,
+    ) -> VidResult<Result<(), ()>> {
+        // the authenticated leaf must be the digest of the claimed share
+        if share.index != index || share_root_proof.elem() != Some(&Self::share_digest(share)?) {
+            return Ok(Err(()));
+        }
+        SharesMerkleTree::<H>::verify(
+            shares_root,
+            SharesMerkleTreeIndex::<H>::from(index as u64),
+            share_root_proof,
+        )
+        .map_err(vid)
+    }
+
+    /// Compute a KZG commitment to the subslice `payload[range]` alone.
+    ///
+    /// The subslice is encoded into field elements from its own start, so
+    /// equal subslices produce equal commitments regardless of their position
+    /// in the payload. Parties can compare these commitments to check that
+    /// they hold the same subslice. The commitment is deterministic, so it
+    /// does not hide a subslice that is easy to guess.
+    ///
+    /// Unrelated to [`VidScheme::Commit`] and to payload proofs.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `range` is out of bounds for
+    /// `payload` or if the subslice encodes into more field elements than the
+    /// SRS supports.
+    pub fn subslice_commitment<B>(&self, payload: B, range: Range<usize>) -> VidResult<KzgCommit<E>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let subslice = payload.get(range.clone()).ok_or_else(|| {
+            VidError::Argument(format!(
+                "range ({}..{}) out of bounds for payload len {}",
+                range.start,
+                range.end,
+                payload.len()
+            ))
+        })?;
+        let elems: Vec<_> = bytes_to_field::<_, KzgEval<E>>(subslice).collect();
+        if elems.len() > self.ck.powers_of_g.len() {
+            return Err(VidError::Argument(format!(
+                "subslice len {} encodes into {} elems, exceeding the SRS max {}",
+                subslice.len(),
+                elems.len(),
+                self.ck.powers_of_g.len()
+            )));
+        }
+        UnivariateKzgPCS::commit(&self.ck, &DenseUVPolynomial::from_coefficients_vec(elems))
+            .map_err(VidError::Pcs)
+    }
+
+    /// Compress `common` into a [`CompactCommon`] by replacing each
+    /// polynomial commitment with its hash.
+    ///
+    /// The payload commitment for `common` is also the payload commitment for
+    /// the returned [`CompactCommon`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if the commit version is
+    /// [`CommitVersion::V0`] or [`CommitVersion::V3`], which do not hash
+    /// each polynomial commitment separately.
+    pub fn compact_common(
+        &self,
+        common: &<Self as VidScheme>::Common,
+    ) -> VidResult<CompactCommon<E, H>> {
+        self.check_commit_version(&[CommitVersion::V1, CommitVersion::V2], "compact_common")?;
+        Ok(CompactCommon {
+            poly_commit_hashes: common
+                .poly_commits
+                .iter()
+                .map(|c| Self::poly_commit_hash(c).map(HasherNode::from))
+                .collect::<VidResult<_>>()?,
+            all_evals_digest: common.all_evals_digest,
+            bytes_len: common.bytes_len,
+            header_digest: common.header_digest,
+            payload_chunk_size: common.payload_chunk_size,
+            num_storage_nodes: common.num_storage_nodes,
+        })
+    }
+
+    /// A standalone proof that the payload commitment for `common` binds the
+    /// payload byte length of `common`, for protocols that need only agree on
+    /// payload size. Verify it via [`Advz::verify_length`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if the commit version is not
+    /// [`CommitVersion::V2`].
+    pub fn length_proof(&self, common: &<Self as VidScheme>::Common) -> VidResult<LengthProof<H>> {
+        self.check_commit_version(&[CommitVersion::V2], "length_proof")?;
+        Ok(LengthProof {
+            bytes_len: common.bytes_len,
+            prefix_digest: Self::poly_commits_prefix_digest(
+                common.poly_commits.iter(),
+                common.header_digest.as_ref(),
+            )?,
+        })
+    }
+
+    /// Check a proof made by [`Advz::length_proof`] that `commit` binds a
+    /// payload of byte length `claimed_len`.
+    ///
+    /// Return value has the same semantics as [`VidScheme::verify_share`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if the commit version is not
+    /// [`CommitVersion::V2`].
+    pub fn verify_length(
+        &self,
+        commit: &<Self as VidScheme>::Commit,
+        claimed_len: usize,
+        proof: &LengthProof<H>,
+    ) -> VidResult<Result<(), ()>> {
+        self.check_commit_version(&[CommitVersion::V2], "verify_length")?;
+        Ok((claimed_len == proof.bytes_len
+            && *commit == Self::length_bound_commit(&proof.prefix_digest, claimed_len))
+        .then_some(())
+        .ok_or(()))
+    }
+
+    /// Random linear combination `sum_i challenge^i * poly_commits[p + i]`
+    /// of the poly commits of `common` for the polynomials `poly_range =
+    /// p..q`, eg. those spanned by a namespace.
+    ///
+    /// By linearity of KZG the result is a commitment to the same linear
+    /// combination of the polynomials, against which aggregated multi-poly
+    /// proofs can be checked.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `poly_range` is empty or out of
+    /// bounds for `common`.
+    pub fn aggregate_commitment(
+        common: &<Self as VidScheme>::Common,
+        poly_range: Range<usize>,
+        challenge: KzgEval<E>,
+    ) -> VidResult<KzgCommit<E>> {
+        let poly_commits = common
+            .poly_commits
+            .get(poly_range.clone())
+            .filter(|poly_commits| !poly_commits.is_empty())
+            .ok_or_else(|| {
+                VidError::Argument(format!(
+                    "poly range ({}..{}) empty or out of bounds for {} poly commits",
+                    poly_range.start,
+                    poly_range.end,
+                    common.poly_commits.len()
+                ))
+            })?;
+        let mut aggregate = E::G1::zero();
+        let mut power = KzgEval::<E>::one();
+        for poly_commit in poly_commits {
+            aggregate += poly_commit.0 * power;
+            power *= challenge;
+        }
+        Ok(KzgCommit::<E>::from(aggregate.into_affine()))
+    }
+
+    /// Field-friendly alternative to the payload commitment
+    /// [`VidScheme::Commit`].
+    ///
+    /// Hash `common.poly_commits` to a single element of the base field `F`
+    /// of the KZG commitment group via [`VariableLengthRescueCRHF`]. Unlike
+    /// the digest returned by [`VidScheme::commit_only`], this commitment can
+    /// be carried through a recursive proof and the consistency check
+    /// [`Advz::check_common_commit_field_consistency`] can be mirrored
+    /// in-circuit via the Rescue sponge gadget.
+    ///
+    /// Each poly commit is absorbed as its affine coordinates `(x, y)`. The
+    /// point at infinity is absorbed as `(0, 0)`.
+    ///
+    /// Select it as the payload commitment via [`Advz::new_with_field_commit`].
+    pub fn poly_commits_hash_field<F>(common: &<Self as VidScheme>::Common) -> VidResult<F>
+    where
+        F: RescueParameter,
+        E::G1Affine: AffineRepr<BaseField = F>,
+        H: HasherDigest,
+    {
+        Self::field_hash(&common.poly_commits)
+    }
+
+    /// The payload commitment under [`CommitVersion::V4`] as a field element,
+    /// ie. the output of [`Advz::poly_commits_hash_field`] for the committed
+    /// `common`, to be carried through a recursive proof.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `commit` does not encode an element
+    /// of `F`.
+    pub fn commit_field<F>(commit: &<Self as VidScheme>::Commit) -> VidResult<F>
+    where
+        F: RescueParameter,
+        E::G1Affine: AffineRepr<BaseField = F>,
+        H: HasherDigest,
+    {
+        F::deserialize_compressed(commit.as_slice()).map_err(|e| {
+            VidError::Argument(format!("commit does not encode a field element: {}", e))
+        })
+    }
+
+    /// An empty [`CommitAccumulator`] for a payload of `num_polys`
+    /// polynomials under the payload commitment format of `self`.
+    pub fn commit_accumulator(&self, num_polys: usize) -> CommitAccumulator<E, H> {
+        let mut accumulator = CommitAccumulator::new(num_polys, self.commit_version);
+        accumulator.field_commit = self.field_commit.clone();
+        accumulator
+    }
+
+    /// Same as [`Advz::poly_commits_hash_field`] given the poly commits.
+    fn field_hash<F>(poly_commits: &[KzgCommit<E>]) -> VidResult<F>
+    where
+        F: RescueParameter,
+        E::G1Affine: AffineRepr<BaseField = F>,
+    {
+        let mut input = Vec::with_capacity(2 * poly_commits.len());
+        for poly_commit in poly_commits.iter() {
+            let (x, y) = poly_commit
+                .0
+                .xy()
+                .map_or((F::zero(), F::zero()), |(x, y)| (*x, *y));
+            input.push(x);
+            input.push(y);
+        }
+        Ok(VariableLengthRescueCRHF::<F, 1>::evaluate(input).map_err(vid)?[0])
+    }
+
+    /// The bytes of the [`CommitVersion::V4`] payload commitment to
+    /// `poly_commits`.
+    fn field_commit_bytes<F>(poly_commits: &[KzgCommit<E>]) -> VidResult<Vec<u8>>
+    where
+        F: RescueParameter,
+        E::G1Affine: AffineRepr<BaseField = F>,
+    {
+        let mut bytes = Vec::new();
+        Self::field_hash::<F>(poly_commits)?
+            .serialize_compressed(&mut bytes)
+            .map_err(vid)?;
+        Ok(bytes)
+    }
+
+    /// Same as the consistency check between `common` and `commit` done by
+    /// [`VidScheme::verify_share`] and [`PayloadProver::payload_verify`]
+    /// except that `commit` is computed by [`Advz::poly_commits_hash_field`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `common` is inconsistent with
+    /// `commit`.
+    ///
+    /// [`PayloadProver::payload_verify`]: super::payload_prover::PayloadProver::payload_verify
+    pub fn check_common_commit_field_consistency<F>(
+        common: &<Self as VidScheme>::Common,
+        commit: &F,
+    ) -> VidResult<()>
+    where
+        F: RescueParameter,
+        E::G1Affine: AffineRepr<BaseField = F>,
+        H: HasherDigest,
+    {
+        if *commit != Self::poly_commits_hash_field(common)? {
+            return Err(VidError::Argument(
+                "common inconsistent with field commit".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Compressed [`CanonicalSerialize`] byte length of a [`Share`], as
+    /// framed by [`VidDisperse::to_bytes`], for a payload of
+    /// `payload_byte_len` bytes, for storage and bandwidth planning.
+    ///
+    /// This is the size of the share for storage node 0. No other share is
+    /// larger. Shares are equal in size if `num_storage_nodes` is a power of
+    /// the merkle tree arity; otherwise the merkle proofs of some shares are
+    /// shorter.
+    ///
+    /// Computed without dispersing a payload, but the cost grows with
+    /// `num_storage_nodes` times the number of polynomials.
+    pub fn share_byte_size(&self, payload_byte_len: usize) -> VidResult<usize> {
+        let poly_byte_len = self.poly_byte_len();
+        let num_polys = usize::max(
+            (payload_byte_len + poly_byte_len - 1) / poly_byte_len,
+            self.min_polynomials,
+        );
+
+        // a share of the same shape as that produced by `disperse`
+        let evals = vec![KzgEval::<E>::zero(); num_polys];
+        let all_evals_commit =
+            Self::all_evals_merkle_tree(&vec![evals.clone(); self.num_storage_nodes])?;
+        let share = Share::<E, H> {
+            index: 0,
+            evals,
+            aggregate_proof: UnivariateKzgProof {
+                proof: E::G1Affine::zero(),
+            },
+            evals_proof: self.share_evals_proof(&all_evals_commit, 0)?,
+        };
+        Ok(share.compressed_size())
+    }
+
+    /// Fingerprint of the parameters of `self`.
+    ///
+    /// Two instances agree on commitments, shares and proofs if and only if
+    /// their fingerprints are equal, so peers can exchange fingerprints to
+    /// detect a parameter mismatch before exchanging proofs.
+    ///
+    /// Hashes `payload_chunk_size`, `num_storage_nodes`, the curve `E`, the
+    /// size and coset offset of each evaluation domain, the KZG verifier key,
+    /// an identifier for `H`, the padding set by
+    /// [`Advz::set_min_polynomials`] and the commit version.
+    pub fn fingerprint(&self) -> VidResult<[u8; 32]> {
+        let mut hasher = Sha256::new();
+        hasher.update(b"advz fingerprint");
+        hasher.update(Self::magic());
+        hasher.update(H::digest(b"advz fingerprint"));
+        for n in [
+            self.payload_chunk_size,
+            self.num_storage_nodes,
+            self.eval_domain.size(),
+            self.multi_open_domain.size(),
+            self.min_polynomials,
+        ] {
+            hasher.update((n as u64).to_le_bytes());
+        }
+        hasher.update([self.commit_version as u8]);
+        let mut bytes = Vec::new();
+        self.eval_domain
+            .coset_offset()
+            .serialize_compressed(&mut bytes)
+            .map_err(vid)?;
+        self.multi_open_domain
+            .coset_offset()
+            .serialize_compressed(&mut bytes)
+            .map_err(vid)?;
+        self.vk.serialize_compressed(&mut bytes).map_err(vid)?;
+        hasher.update(bytes);
+        if let Some(permutation) = &self.permutation {
+            for p in permutation.physical.iter() {
+                hasher.update((*p as u64).to_le_bytes());
+            }
+        }
+        Ok(hasher.finalize().into())
+    }
+
+    /// Encode `commit` as the fixed-length bytes posted on chain.
+    ///
+    /// The bytes are the hash digest in order, so a Solidity contract decodes
+    /// them as a `bytes32` whose most significant byte is `bytes[0]`. Unlike
+    /// the serialized [`VidScheme::Common`], this encoding has fixed length.
+    /// Invert via [`Advz::commit_from_onchain_bytes`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if the digest length of `H` is not 32
+    /// bytes.
+    pub fn commit_to_onchain_bytes(commit: &<Self as VidScheme>::Commit) -> VidResult<[u8; 32]>
+    where
+        H: HasherDigest,
+    {
+        <[u8; 32]>::try_from(commit.as_slice()).map_err(|_| {
+            VidError::Argument(format!(
+                "commit len {} differs from on-chain len 32",
+                commit.len()
+            ))
+        })
+    }
+
+    /// Inverse of [`Advz::commit_to_onchain_bytes`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if the digest length of `H` is not 32
+    /// bytes.
+    pub fn commit_from_onchain_bytes(bytes: &[u8; 32]) -> VidResult<<Self as VidScheme>::Commit>
+    where
+        H: HasherDigest,
+    {
+        Output::<H>::from_exact_iter(bytes.iter().copied()).ok_or_else(|| {
+            VidError::Argument(format!(
+                "on-chain len 32 differs from commit len {}",
+                Output::<H>::default().len()
+            ))
+        })
+    }
+
+    /// Compress `poly_commit` to the affine x-coordinate of its curve point
+    /// with flag bits for the sign of the y-coordinate and for the point at
+    /// infinity.
+    ///
+    /// Half the size of the uncompressed point, for compact on-chain storage.
+    /// Invert via [`Advz::decompress_poly_commit`].
+    pub fn compress_poly_commit(poly_commit: &KzgCommit<E>) -> VidResult<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(poly_commit.compressed_size());
+        poly_commit.serialize_compressed(&mut bytes).map_err(vid)?;
+        Ok(bytes)
+    }
+
+    /// Recover a poly commit from its compressed form produced by
+    /// [`Advz::compress_poly_commit`].
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `bytes` has the wrong length or if its
+    /// x-coordinate is not that of a point in the prime-order subgroup.
+    pub fn decompress_poly_commit(mut bytes: &[u8]) -> VidResult<KzgCommit<E>> {
+        let poly_commit = KzgCommit::<E>::deserialize_compressed(&mut bytes)
+            .map_err(|e| VidError::Argument(format!("invalid compressed poly commit: {}", e)))?;
+        if !bytes.is_empty() {
+            return Err(VidError::Argument(format!(
+                "{} trailing bytes in compressed poly commit",
+                bytes.len()
+            )));
+        }
+        Ok(poly_commit)
+    }
+
+    /// Check that the commit version of `self` is among `supported`, as
+    /// required by the method `what`.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] otherwise.
+    fn check_commit_version(&self, supported: &[CommitVersion], what: &str) -> VidResult<()> {
+        if !supported.contains(&self.commit_version) {
+            return Err(VidError::Argument(format!(
+                "{} unsupported under commit version {:?}",
+                what, self.commit_version
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check that `common` was produced by an [`Advz`] instance with the same
+    /// parameters as `self`.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `payload_chunk_size` or
+    /// `num_storage_nodes` differ.
+    fn check_common_params(&self, common: &<Self as VidScheme>::Common) -> VidResult<()>
+    where
+        H: HasherDigest,
+    {
+        self.check_params(common.payload_chunk_size, common.num_storage_nodes)
+    }
+
+    /// Parameters of `0` were not recorded, as in common data deserialized
+    /// from before [`COMMON_FORMAT_V1`], and pass.
+    fn check_params(&self, payload_chunk_size: usize, num_storage_nodes: usize) -> VidResult<()> {
+        if (payload_chunk_size, num_storage_nodes) == (0, 0) {
+            return Ok(());
+        }
+        if payload_chunk_size != self.payload_chunk_size {
+            return Err(VidError::Argument(format!(
+                "common payload_chunk_size {} differs from verifier payload_chunk_size {}",
+                payload_chunk_size, self.payload_chunk_size
+            )));
+        }
+        if num_storage_nodes != self.num_storage_nodes {
+            return Err(VidError::Argument(format!(
+                "common num_storage_nodes {} differs from verifier num_storage_nodes {}",
+                num_storage_nodes, self.num_storage_nodes
+            )));
+        }
+        Ok(())
+    }
+
+    /// Number of powers of `X` by which a [`DegreeBoundProof`] shifts its
+    /// polynomial: the largest degree supported by `self.ck` minus the
+    /// largest degree of a payload polynomial.
+    fn degree_bound_shift(&self) -> usize {
+        self.ck.powers_of_g.len() - self.payload_chunk_size
+    }
+
+    fn degree_bound_proof_for_poly(
+        &self,
+        poly: &KzgPolynomial<E>,
+    ) -> VidResult<DegreeBoundProof<E>> {
+        let shifted_commits = (1..=self.degree_bound_shift())
+            .map(|shift| {
+                let mut coeffs = vec![KzgEval::<E>::zero(); shift];
+                coeffs.extend_from_slice(&poly.coeffs);
+                UnivariateKzgPCS::commit(
+                    &self.ck,
+                    &DenseUVPolynomial::from_coefficients_vec(coeffs),
+                )
+            })
+            .collect::<Result<_, _>>()
+            .map_err(VidError::Pcs)?;
+        Ok(DegreeBoundProof { shifted_commits })
+    }
+
+    fn verify_degree_bound_for_commit(
+        &self,
+        poly_commit: &KzgCommit<E>,
+        proof: &DegreeBoundProof<E>,
+    ) -> VidResult<Result<(), ()>> {
+        if proof.shifted_commits.len() != self.degree_bound_shift() {
+            return Err(VidError::Argument(format!(
+                "degree bound proof len {} differs from expected {}",
+                proof.shifted_commits.len(),
+                self.degree_bound_shift()
+            )));
+        }
+
+        // e(C_k, h) == e(C_{k-1}, beta h) where C_0 is `poly_commit`
+        let mut prev = poly_commit;
+        for curr in proof.shifted_commits.iter() {
+            if E::pairing(curr.0, self.vk.h) != E::pairing(prev.0, self.vk.beta_h) {
+                return Ok(Err(()));
+            }
+            prev = curr;
+        }
+        Ok(Ok(()))
+    }
+
+    /// Return the number of polynomials in `shares`.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `shares` is empty or if `shares` do
+    /// not have equal evals lengths.
+    fn shares_num_polys(shares: &[<Self as VidScheme>::Share]) -> VidResult<usize> {
+        // all shares must have equal evals len
+        let num_polys = shares
+            .first()
+            .ok_or_else(|| VidError::Argument("shares is empty".into()))?
+            .evals
+            .len();
+        if let Some((index, share)) = shares
+            .iter()
+            .enumerate()
+            .find(|(_, s)| s.evals.len() != num_polys)
+        {
+            return Err(VidError::Argument(format!(
+                "shares do not have equal evals lengths: share {} len {}, share {} len {}",
+                0,
+                num_polys,
+                index,
+                share.evals.len()
+            )));
+        }
+        Ok(num_polys)
+    }
+
+    /// Check that `indices` holds at least `payload_chunk_size` distinct
+    /// storage node indices, the minimum needed to recover a payload.
+    ///
+    /// Fewer shares, or duplicate shares, would otherwise decode to a
+    /// plausible but wrong payload.
+    fn check_enough_shares<I>(&self, indices: I) -> VidResult<()>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut indices: Vec<_> = indices.into_iter().collect();
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.len() < self.payload_chunk_size {
+            return Err(VidError::Argument(format!(
+                "not enough distinct shares {}, need {} more for at least {}",
+                indices.len(),
+                self.payload_chunk_size - indices.len(),
+                self.payload_chunk_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Recover the payload elems of a single polynomial from its
+    /// `(storage node index, eval)` pairs.
+    ///
+    /// Takes fields of `self` rather than `&self` so that it can run in
+    /// parallel even if `H` is not `Sync`.
+    fn decode_poly_elems<D>(
+        payload_chunk_size: usize,
+        multi_open_domain: &Radix2EvaluationDomain<KzgPoint<E>>,
+        eval_domain: &Radix2EvaluationDomain<KzgPoint<E>>,
+        evals: D,
+    ) -> VidResult<Vec<KzgEval<E>>>
+    where
+        D: IntoIterator<Item = (usize, KzgEval<E>)>,
+        D::IntoIter: ExactSizeIterator + Clone,
+    {
+        let mut coeffs =
+            reed_solomon_erasure_decode_rou(evals, payload_chunk_size, multi_open_domain)
+                .map_err(vid)?;
+
+        // TODO TEMPORARY: use FFT to encode polynomials in eval form
+        // Remove these FFTs after we get KZG in eval form
+        // https://github.com/EspressoSystems/jellyfish/issues/339
+        eval_domain.fft_in_place(&mut coeffs);
+
+        Ok(coeffs)
+    }
+
+    /// Same as [`Advz::poly_commit_inclusion_proof`] except that the poly
+    /// commits are given directly.
+    fn poly_commits_inclusion_proof(
+        poly_commits: &[KzgCommit<E>],
+        poly_index: usize,
+    ) -> VidResult<PolyCommitsMerkleTreeProof<E, H>> {
+        if poly_index >= poly_commits.len() {
+            return Err(VidError::Argument(format!(
+                "poly index {} out of bounds for {} poly commits",
+                poly_index,
+                poly_commits.len()
+            )));
+        }
+        Ok(Self::poly_commits_merkle_tree(poly_commits)?
+            .lookup(PolyCommitsMerkleTreeIndex::<E, H>::from(poly_index as u64))
+            .expect_ok()
+            .map_err(vid)?
+            .1)
+    }
+
+    fn poly_commits_merkle_tree(
+        poly_commits: &[KzgCommit<E>],
+    ) -> VidResult<PolyCommitsMerkleTree<E, H>> {
+        let height = merkle_tree_height(
+            poly_commits.len(),
+            PolyCommitsMerkleTree::<E, H>::ARITY,
+            "poly commits",
+        )?;
+        PolyCommitsMerkleTree::<E, H>::from_elems(height, poly_commits).map_err(vid)
+    }
+
+    /// Merkle tree whose leaves are the digests of `shares`.
+    fn shares_merkle_tree(shares: &[<Self as VidScheme>::Share]) -> VidResult<SharesMerkleTree<H>> {
+        let share_digests = shares
+            .iter()
+            .map(Self::share_digest)
+            .collect::<VidResult<Vec<_>>>()?;
+        let height =
+            merkle_tree_height(share_digests.len(), SharesMerkleTree::<H>::ARITY, "shares")?;
+        SharesMerkleTree::<H>::from_elems(height, share_digests).map_err(vid)
+    }
+
+    fn share_digest(share: &<Self as VidScheme>::Share) -> VidResult<HasherNode<H>> {
+        let mut hasher = H::new();
+        hasher.update((share.index as u64).to_le_bytes());
+        share
+            .evals
+            .serialize_uncompressed(&mut hasher)
+            .map_err(vid)?;
+        share
+            .aggregate_proof
+            .serialize_uncompressed(&mut hasher)
+            .map_err(vid)?;
+        Ok(hasher.finalize().into())
+    }
+
+    /// Apply `f` to each item, in parallel if the `parallel` feature is
+    /// enabled and there are at least `self.parallel_threshold` items.
+    /// Results are in the order of `items` either way.
+    fn maybe_par_map<T, U, F>(&self, items: &[T], f: F) -> Vec<U>
+    where
+        T: Sync,
+        U: Send,
+        F: Fn(&T) -> U + Sync + Send,
+    {
+        #[cfg(feature = "parallel")]
+        if items.len() >= self.parallel_threshold {
+            return items.par_iter().map(f).collect();
+        }
+        items.iter().map(f).collect()
+    }
+
+    /// Commitment to the Lagrange basis polynomial of `eval_domain` that is
+    /// one at the point of logical element offset `index` and zero at all
+    /// other elements.
+    ///
+    /// Over the coset `h * <w>` of size `n` its coefficients are
+    /// `x^-k / n` where `x = h * w^j` for physical offset `j`.
+    fn lagrange_basis_commit(&self, index: usize) -> E::G1 {
+        let point_inv = self
+            .elem_point(index)
+            .inverse()
+            .expect("domain element should be nonzero");
+        let mut scalar = self.eval_domain.size_inv();
+        let scalars: Vec<_> = (0..self.payload_chunk_size)
+            .map(|_| {
+                let s = scalar;
+                scalar *= point_inv;
+                s
+            })
+            .collect();
+        E::G1::msm_unchecked(&self.ck.powers_of_g[..self.payload_chunk_size], &scalars)
+    }
+
+    /// Byte length of the payload segment encoded into a single polynomial.
+    ///
+    /// A whole number under either [`FieldPacking`], as ensured by
+    /// [`Advz::set_field_packing`].
+    fn poly_byte_len(&self) -> usize {
+        self.payload_chunk_size * self.elem_bit_capacity() / 8
+    }
+
+    /// Number of payload bits encoded into each field element.
+    fn elem_bit_capacity(&self) -> usize {
+        match self.field_packing {
+            FieldPacking::Bytes => 8 * elem_byte_capacity::<KzgEval<E>>(),
+            FieldPacking::Bits => packed_elem_bit_capacity::<KzgEval<E>>(),
+        }
+    }
+
+    /// Number of field elements encoding a payload of `bytes_len` bytes.
+    fn num_payload_elems(&self, bytes_len: usize) -> usize {
+        let elem_bit_capacity = self.elem_bit_capacity();
+        (8 * bytes_len + elem_bit_capacity - 1) / elem_bit_capacity
+    }
+
+    /// Encode `bytes` into field elements as per [`Advz::set_field_packing`].
+    fn bytes_to_elems<I>(&self, bytes: I) -> impl Iterator<Item = KzgEval<E>>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<u8>,
+    {
+        match self.field_packing {
+            FieldPacking::Bytes => Either::Left(bytes_to_field(bytes)),
+            FieldPacking::Bits => Either::Right(bytes_to_field_packed(bytes)),
+        }
+    }
+
+    /// Inverse of [`Advz::bytes_to_elems`], which might append zero bytes.
+    fn elems_to_bytes<I>(&self, elems: I) -> impl Iterator<Item = u8>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<KzgEval<E>>,
+    {
+        match self.field_packing {
+            FieldPacking::Bytes => Either::Left(field_to_bytes(elems)),
+            FieldPacking::Bits => Either::Right(field_to_bytes_packed(elems)),
+        }
+    }
+
+    /// Elements of polynomial `poly_index` of `payload`, as encoded by
+    /// [`Advz::payload_polys`]. Fewer than `payload_chunk_size` for the final
+    /// polynomial, none beyond it.
+    fn poly_elems(&self, payload: &[u8], poly_index: usize) -> Vec<KzgEval<E>> {
+        let start = ark_std::cmp::min(poly_index * self.poly_byte_len(), payload.len());
+        self.bytes_to_elems(&payload[start..])
+            .take(self.payload_chunk_size)
+            .collect()
+    }
+
+    /// Extension degree of `F` over the scalar field of `E`, checked as per
+    /// [`Advz::disperse_ext`].
+    fn check_ext_degree<F: Field>(&self) -> VidResult<usize> {
+        self.check_byte_packing("extension field packing")?;
+        let degree = usize::try_from(F::extension_degree()).map_err(vid)?;
+        if self.payload_chunk_size % degree != 0 {
+            return Err(VidError::Argument(format!(
+                "payload_chunk_size {} not a multiple of extension degree {}",
+                self.payload_chunk_size, degree
+            )));
+        }
+        Ok(degree)
+    }
+
+    /// Return [`VidError::Argument`] naming `method` unless payload bytes are
+    /// packed via [`FieldPacking::Bytes`].
+    fn check_byte_packing(&self, method: &str) -> VidResult<()> {
+        match self.field_packing {
+            FieldPacking::Bytes => Ok(()),
+            FieldPacking::Bits => Err(VidError::Argument(format!(
+                "{} requires FieldPacking::Bytes",
+                method
+            ))),
+        }
+    }
+
+    /// Compute the merkle tree, payload commitment, aggregate proofs and
+    /// shares from the polynomials of the entire payload.
+    ///
+    /// If `header_digest` is given then it is bound into the payload
+    /// commitment. Polynomials are padded as per
+    /// [`Advz::set_min_polynomials`].
+    fn disperse_from_parts(
+        &self,
+        mut polys: Vec<KzgPolynomial<E>>,
+        mut poly_commits: Vec<KzgCommit<E>>,
+        mut all_storage_node_evals: Vec<Vec<KzgEval<E>>>,
+        bytes_len: usize,
+        header_digest: Option<HasherNode<H>>,
+    ) -> VidResult<VidDisperse<Self>>
+    where
+        H: HasherDigest,
+    {
+        // the zero polynomial has zero commitment and zero evals
+        if polys.len() < self.min_polynomials {
+            polys.resize(self.min_polynomials, KzgPolynomial::<E>::zero());
+            poly_commits.resize(
+                self.min_polynomials,
+                KzgCommit::<E>::from(E::G1Affine::zero()),
+            );
+            for storage_node_evals in all_storage_node_evals.iter_mut() {
+                storage_node_evals.resize(self.min_polynomials, KzgEval::<E>::zero());
+            }
+        }
+
+        // vector commitment to polynomial evaluations
+        let all_evals_commit_timer =
+            start_timer!(|| "compute merkle root of all storage node evals");
+        let all_evals_commit = Self::all_evals_merkle_tree(&all_storage_node_evals)?;
+        end_timer!(all_evals_commit_timer);
+
+        let common = Common {
+            poly_commits,
+            all_evals_digest: all_evals_commit.commitment().digest(),
+            bytes_len,
+            header_digest,
+            evals_leaf_digests: self.evals_leaf_digests(&all_storage_node_evals)?,
+            payload_chunk_size: self.payload_chunk_size,
+            num_storage_nodes: self.num_storage_nodes,
+        };
+
+        let commit = self.common_commit(&common)?;
+        let pseudorandom_scalar = Self::pseudorandom_scalar(&common, &commit)?;
+
+        // Compute aggregate polynomial as a pseudorandom linear combo of polynomial via
+        // evaluation of the polynomial whose coefficients are polynomials and whose
+        // input point is the pseudorandom scalar.
+        let aggregate_poly =
+            polynomial_eval(polys.iter().map(PolynomialMultiplier), pseudorandom_scalar);
+
+        let agg_proofs_timer = start_timer!(|| format!(
+            "compute aggregate proofs for {} storage nodes",
+            self.num_storage_nodes
+        ));
+        let aggregate_proofs = UnivariateKzgPCS::multi_open_rou_proofs(
+            &self.ck,
+            &aggregate_poly,
+            self.num_storage_nodes,
+            &self.multi_open_domain,
+        )
+        .map_err(VidError::Pcs)?;
+        end_timer!(agg_proofs_timer);
+
+        let assemblage_timer = start_timer!(|| "assemble shares for dispersal");
+        let shares = all_storage_node_evals
+            .into_iter()
+            .zip(aggregate_proofs)
+            .enumerate()
+            .map(|(index, (evals, aggregate_proof))| {
+                Ok(Share {
+                    index,
+                    evals,
+                    aggregate_proof,
+                    evals_proof: self.share_evals_proof(&all_evals_commit, index)?,
+                })
+            })
+            .collect::<Result<Vec<_>, VidError>>()?;
+        end_timer!(assemblage_timer);
+
+        Ok(VidDisperse {
+            shares,
+            common,
+            commit,
+        })
+    }
+
+    /// Merkle tree whose leaves are the evals of each storage node.
+    fn all_evals_merkle_tree(
+        all_storage_node_evals: &[Vec<KzgEval<E>>],
+    ) -> VidResult<KzgEvalsMerkleTree<E, H>> {
+        let height = Self::all_evals_tree_height(all_storage_node_evals.len())?;
+        KzgEvalsMerkleTree::<E, H>::from_elems(height, all_storage_node_evals).map_err(vid)
+    }
+
+    /// Height of the merkle tree of [`Advz::all_evals_merkle_tree`] for
+    /// `num_storage_nodes` leaves.
+    fn all_evals_tree_height(num_storage_nodes: usize) -> VidResult<usize> {
+        // TODO why do I need to compute the height of the merkle tree?
+        let height: usize = num_storage_nodes
+            .checked_ilog(KzgEvalsMerkleTree::<E, H>::ARITY)
+            .ok_or_else(|| {
+                VidError::Argument(format!(
+                    "num_storage_nodes {} log base {} invalid",
+                    num_storage_nodes,
+                    KzgEvalsMerkleTree::<E, H>::ARITY
+                ))
+            })?
+            .try_into()
+            .expect("num_storage_nodes log base arity should fit into usize");
+        Ok(height + 1) // avoid fully qualified syntax for try_into()
+    }
+
+    /// The random polynomial added to a payload polynomial in
+    /// [`Advz::disperse_hiding`], derived from `blind`. Its degree is less
+    /// than `payload_chunk_size`, so the sum is uniformly random.
+    fn blind_polynomial(&self, blind: &Blind) -> KzgPolynomial<E> {
+        let mut rng = ChaCha20Rng::from_seed(blind.0);
+        KzgPolynomial::<E>::from_coefficients_vec(
+            (0..self.payload_chunk_size)
+                .map(|_| KzgEval::<E>::rand(&mut rng))
+                .collect(),
+        )
+    }
+
+    /// Like [`Advz::pseudorandom_scalar`] for a [`HeterogeneousShare`], whose
+    /// evals are hashed in directly.
+    fn heterogeneous_pseudorandom_scalar(
+        commit: &<Self as VidScheme>::Commit,
+        index: usize,
+        evals: &[Option<KzgEval<E>>],
+    ) -> VidResult<KzgEval<E>> {
+        let mut hasher = H::new();
+        commit.serialize_uncompressed(&mut hasher).map_err(vid)?;
+        (index as u64)
+            .serialize_uncompressed(&mut hasher)
+            .map_err(vid)?;
+        evals.serialize_uncompressed(&mut hasher).map_err(vid)?;
+        Ok(PrimeField::from_le_bytes_mod_order(&hasher.finalize()))
+    }
+
+    fn pseudorandom_scalar(
+        common: &<Self as VidScheme>::Common,
+        commit: &<Self as VidScheme>::Commit,
+    ) -> VidResult<KzgEval<E>> {
+        let mut hasher = H::new();
+        commit.serialize_uncompressed(&mut hasher).map_err(vid)?;
+        common
+            .all_evals_digest
+            .serialize_uncompressed(&mut hasher)
+            .map_err(vid)?;
+
+        // Notes on hash-to-field:
+        // - Can't use `Field::from_random_bytes` because it's fallible. (In what sense
+        //   is it from "random" bytes?!). This despite the docs explicitly say: "This
+        //   function is primarily intended for sampling random field elements from a
+        //   hash-function or RNG output."
+        // - We could use `ark_ff::fields::field_hashers::HashToField` but that forces
+        //   us to add additional trait bounds `Clone + Default + DynDigest` everywhere.
+        //   Also, `HashToField` does not expose an incremental API (ie. `update`) so we
+        //   would need to use an ordinary hasher and pipe `hasher.finalize()` through
+        //   `hash_to_field`. (Ugh!)
+        // - We don't need the resulting field element to be cryptographically
+        //   indistinguishable from uniformly random. We only need it to be
+        //   unpredictable. So it suffices to use
+        Ok(PrimeField::from_le_bytes_mod_order(&hasher.finalize()))
+    }
+
+    fn polynomial<I>(&self, coeffs: I) -> KzgPolynomial<E>
+    where
+        I: Iterator,
+        I::Item: Borrow<KzgEval<E>>,
+    {
+        // TODO TEMPORARY: use FFT to encode polynomials in eval form
+        // Remove these FFTs after we get KZG in eval form
+        // https://github.com/EspressoSystems/jellyfish/issues/339
+        let mut coeffs_vec: Vec<_> = coeffs.map(|c| *c.borrow()).collect();
+        let pre_fft_len = coeffs_vec.len();
+        coeffs_vec = self.to_physical_order(coeffs_vec);
+        self.eval_domain.ifft_in_place(&mut coeffs_vec);
+
+        // sanity check: the fft did not resize coeffs.
+        // If pre_fft_len != self.payload_chunk_size then we were not given the correct
+        // number of coeffs. In that case coeffs.len() could be anything, so
+        // there's nothing to sanity check.
+        if pre_fft_len == self.payload_chunk_size {
+            assert_eq!(coeffs_vec.len(), pre_fft_len);
+        }
+
+        DenseUVPolynomial::from_coefficients_vec(coeffs_vec)
+    }
+
+    /// Physical byte offset of logical payload byte `offset` under the
+    /// permutation set via [`Advz::set_permutation`].
+    pub fn physical_byte_offset(&self, offset: usize) -> usize {
+        let elem_byte_len = elem_byte_capacity::<KzgEval<E>>();
+        let poly_byte_len = self.poly_byte_len();
+        let poly_offset = offset % poly_byte_len;
+        offset - poly_offset
+            + self.physical_elem_offset(poly_offset / elem_byte_len) * elem_byte_len
+            + poly_offset % elem_byte_len
+    }
+
+    /// Physical offset of logical element offset `offset` within a
+    /// polynomial as per [`Advz::set_permutation`].
+    fn physical_elem_offset(&self, offset: usize) -> usize {
+        match &self.permutation {
+            Some(permutation) => permutation.physical[offset],
+            None => offset,
+        }
+    }
+
+    /// Input point of logical element offset `offset` within a polynomial.
+    fn elem_point(&self, offset: usize) -> KzgPoint<E> {
+        self.eval_domain.element(self.physical_elem_offset(offset))
+    }
+
+    /// Reorder the logical elements of one polynomial, zero-padded, into
+    /// physical order. Identity if there is no permutation.
+    fn to_physical_order(&self, mut elems: Vec<KzgEval<E>>) -> Vec<KzgEval<E>> {
+        match &self.permutation {
+            Some(permutation) if elems.len() <= self.payload_chunk_size => {
+                elems.resize(self.payload_chunk_size, KzgEval::<E>::zero());
+                permutation.logical.iter().map(|i| elems[*i]).collect()
+            },
+            _ => elems,
+        }
+    }
+
+    /// Reorder items indexed by the physical offsets of one polynomial into
+    /// logical order. Identity if there is no permutation.
+    fn to_logical_order<T>(&self, items: Vec<T>) -> Vec<T>
+    where
+        T: Clone,
+    {
+        match &self.permutation {
+            Some(permutation) => {
+                assert_eq!(items.len(), permutation.len()); // sanity
+                permutation
+                    .physical
+                    .iter()
+                    .map(|i| items[*i].clone())
+                    .collect()
+            },
+            None => items,
+        }
+    }
+
+    /// The payload commitment to `poly_commits` under the commit version of
+    /// `self`, see [`CommitVersion`].
+    fn poly_commits_hash<I>(
+        &self,
+        poly_commits: I,
+        header_digest: Option<&HasherNode<H>>,
+        bytes_len: usize,
+    ) -> VidResult<<Self as VidScheme>::Commit>
+    where
+        I: Iterator,
+        I::Item: Borrow<KzgCommit<E>>,
+    {
+        Self::versioned_poly_commits_hash(
+            self.commit_version,
+            self.field_commit.as_ref(),
+            poly_commits,
+            header_digest,
+            bytes_len,
+        )
+    }
+
+    /// Same as [`Advz::poly_commits_hash`] under `commit_version`, with
+    /// `field_commit` as set by [`Advz::new_with_field_commit`].
+    fn versioned_poly_commits_hash<I>(
+        commit_version: CommitVersion,
+        field_commit: Option<&FieldCommit<E>>,
+        poly_commits: I,
+        header_digest: Option<&HasherNode<H>>,
+        bytes_len: usize,
+    ) -> VidResult<<Self as VidScheme>::Commit>
+    where
+        I: Iterator,
+        I::Item: Borrow<KzgCommit<E>>,
+    {
+        match commit_version {
+            CommitVersion::V0 => {
+                let mut hasher = H::new();
+                for poly_commit in poly_commits {
+                    poly_commit
+                        .borrow()
+                        .serialize_uncompressed(&mut hasher)
+                        .map_err(vid)?;
+                }
+                if let Some(header_digest) = header_digest {
+                    hasher.update(header_digest.as_ref());
+                }
+                Ok(hasher.finalize())
+            },
+            CommitVersion::V1 | CommitVersion::V2 => Self::versioned_commit(
+                commit_version,
+                Self::poly_commits_prefix_digest(poly_commits, header_digest)?,
+                bytes_len,
+            ),
+            CommitVersion::V3 | CommitVersion::V4 => {
+                if header_digest.is_some() {
+                    return Err(VidError::Argument(format!(
+                        "header unsupported under commit version {:?}",
+                        commit_version
+                    )));
+                }
+                let poly_commits: Vec<_> = poly_commits.map(|c| *c.borrow()).collect();
+                if commit_version == CommitVersion::V3 {
+                    return Ok(*Self::poly_commits_merkle_tree(&poly_commits)?
+                        .commitment()
+                        .digest()
+                        .as_ref());
+                }
+                let field_commit = field_commit.ok_or_else(|| {
+                    VidError::Argument(
+                        "commit version V4 requires Advz::new_with_field_commit".to_string(),
+                    )
+                })?;
+                // byte length checked by `new_with_field_commit`
+                Output::<H>::from_exact_iter((field_commit.0)(&poly_commits)?).ok_or_else(|| {
+                    VidError::Internal(anyhow::anyhow!("field commit byte len mismatch"))
+                })
+            },
+        }
+    }
+
+    /// The payload commitment under `commit_version` given the digest
+    /// `prefix_digest` of the poly commit hashes and header digest.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `commit_version` is
+    /// [`CommitVersion::V0`] or [`CommitVersion::V3`], which do not hash the
+    /// poly commits separately.
+    fn versioned_commit(
+        commit_version: CommitVersion,
+        prefix_digest: HasherNode<H>,
+        bytes_len: usize,
+    ) -> VidResult<<Self as VidScheme>::Commit> {
+        match commit_version {
+            CommitVersion::V0 | CommitVersion::V3 | CommitVersion::V4 => {
+                Err(VidError::Argument(format!(
+                    "poly commit hashes unsupported under commit version {:?}",
+                    commit_version
+                )))
+            },
+            CommitVersion::V1 => Ok(*prefix_digest.as_ref()),
+            CommitVersion::V2 => Ok(Self::length_bound_commit(&prefix_digest, bytes_len)),
+        }
+    }
+
+    /// Digest of the poly commit hashes and header digest, which is the
+    /// [`CommitVersion::V1`] commitment and the prefix of the
+    /// [`CommitVersion::V2`] preimage.
+    fn poly_commits_prefix_digest<I>(
+        poly_commits: I,
+        header_digest: Option<&HasherNode<H>>,
+    ) -> VidResult<HasherNode<H>>
+    where
+        I: Iterator,
+        I::Item: Borrow<KzgCommit<E>>,
+    {
+        let mut hasher = H::new();
+        for poly_commit in poly_commits {
+            hasher.update(Self::poly_commit_hash(poly_commit.borrow())?);
+        }
+        if let Some(header_digest) = header_digest {
+            hasher.update(header_digest.as_ref());
+        }
+        Ok(hasher.finalize().into())
+    }
+
+    /// The [`CommitVersion::V2`] commitment for `prefix_digest` and
+    /// `bytes_len`.
+    fn length_bound_commit(
+        prefix_digest: &HasherNode<H>,
+        bytes_len: usize,
+    ) -> <Self as VidScheme>::Commit {
+        let mut hasher = H::new();
+        hasher.update(prefix_digest.as_ref());
+        hasher.update((bytes_len as u64).to_le_bytes());
+        hasher.finalize()
+    }
+
+    /// The payload commitment for `common`.
+    fn common_commit(
+        &self,
+        common: &<Self as VidScheme>::Common,
+    ) -> VidResult<<Self as VidScheme>::Commit> {
+        self.poly_commits_hash(
+            common.poly_commits.iter(),
+            common.header_digest.as_ref(),
+            common.bytes_len,
+        )
+    }
+
+    fn poly_commit_hash(poly_commit: &KzgCommit<E>) -> VidResult<Output<H>> {
+        let mut hasher = H::new();
+        poly_commit
+            .serialize_uncompressed(&mut hasher)
+            .map_err(vid)?;
+        Ok(hasher.finalize())
+    }
+
+    /// Same as [`Advz::poly_commits_hash`] given the poly commit hashes.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if the commit version is
+    /// [`CommitVersion::V0`] or [`CommitVersion::V3`].
+    fn poly_commit_hashes_hash<I>(
+        &self,
+        poly_commit_hashes: I,
+        header_digest: Option<&HasherNode<H>>,
+        bytes_len: usize,
+    ) -> VidResult<<Self as VidScheme>::Commit>
+    where
+        I: Iterator,
+        I::Item: Borrow<HasherNode<H>>,
+    {
+        Self::versioned_commit(
+            self.commit_version,
+            Self::poly_commit_hashes_prefix_digest(poly_commit_hashes, header_digest),
+            bytes_len,
+        )
+    }
+
+    fn poly_commit_hashes_prefix_digest<I>(
+        poly_commit_hashes: I,
+        header_digest: Option<&HasherNode<H>>,
+    ) -> HasherNode<H>
+    where
+        I: Iterator,
+        I::Item: Borrow<HasherNode<H>>,
+    {
+        let mut hasher = H::new();
+        for poly_commit_hash in poly_commit_hashes {
+            hasher.update(poly_commit_hash.borrow().as_ref());
+        }
+        if let Some(header_digest) = header_digest {
+            hasher.update(header_digest.as_ref());
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Smallest height of a merkle tree of arity `arity` whose capacity holds
+/// `num_leaves` leaves of kind `what`.
+fn merkle_tree_height(num_leaves: usize, arity: usize, what: &str) -> VidResult<usize> {
+    let mut height = 1;
+    let mut capacity = arity;
+    while capacity < num_leaves {
+        capacity = capacity.checked_mul(arity).ok_or_else(|| {
+            VidError::Argument(format!(
+                "too many {} {} for a merkle tree",
+                what, num_leaves
+            ))
+        })?;
+        height += 1;
+    }
+    Ok(height)
+}
+
+/// Evaluate a generalized polynomial at a given point using Horner's method.
+///
+/// Coefficients can be anything that can be multiplied by a point
+/// and such that the result of such multiplications can be added.
+fn polynomial_eval<U, F, I>(coeffs: I, point: impl Borrow<F>) -> U
+where
+    I: IntoIterator,
+    I::Item: for<'a> Mul<&'a F, Output = U>,
+    U: Add<Output = U> + Zero,
+{
+    coeffs
+        .into_iter()
+        .fold(U::zero(), |res, coeff| coeff * point.borrow() + res)
+}
+
+struct FieldMultiplier<'a, F>(&'a F);
+
+/// Arkworks does not provide (&F,&F) multiplication
+impl<F> Mul<&F> for FieldMultiplier<'_, F>
+where
+    F: Field,
+{
+    type Output = F;
+
+    fn mul(self, rhs: &F) -> Self::Output {
+        *self.0 * rhs
+    }
+}
+
+/// Arkworks does not provide (&C,&F) multiplication
+struct CurveMultiplier<'a, C>(&'a C);
+
+impl<C, F> Mul<&F> for CurveMultiplier<'_, C>
+where
+    C: AffineRepr<ScalarField = F>,
+{
+    type Output = C::Group;
+
+    fn mul(self, rhs: &F) -> Self::Output {
+        *self.0 * rhs
+    }
+}
+
+/// Arkworks does not provide (&P,&F) multiplication
+struct PolynomialMultiplier<'a, P>(&'a P);
+
+impl<P, F> Mul<&F> for PolynomialMultiplier<'_, P>
+where
+    P: DenseUVPolynomial<F>,
+    F: Field,
+{
+    type Output = P;
+
+    fn mul(self, rhs: &F) -> Self::Output {
+        // `Polynomial` does not impl `Mul` by scalar
+        // so we need to multiply each coeff by `rhs`
+        P::from_coefficients_vec(self.0.coeffs().iter().map(|coeff| *coeff * rhs).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VidError::Argument, *};
+
+    use crate::pcs::prelude::{PCSError, UnivariateUniversalParams};
+    use ark_bls12_381::Bls12_381;
+    use ark_ff::{Fp2, Fp2Config, MontFp};
+    use ark_std::vec;
+    use sha2::Sha256;
+
+    // #[test]
+    #[allow(dead_code)]
+    fn disperse_timer() {
+        // run with 'print-trace' feature to see timer output
+        let (payload_chunk_size, num_storage_nodes) = (256, 512);
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(payload_chunk_size, &mut rng);
+        let advz =
+            Advz::<Bls12_381, Sha256>::new(payload_chunk_size, num_storage_nodes, srs).unwrap();
+        let payload_random = init_random_payload(1 << 20, &mut rng);
+
+        let _ = advz.disperse(&payload_random);
+    }
+
+    // #[test]
+    #[allow(dead_code)]
+    fn commit_only_timer() {
+        // run with 'print-trace' feature to see timer output
+        let (payload_chunk_size, num_storage_nodes) = (256, 512);
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(payload_chunk_size, &mut rng);
+        let advz =
+            Advz::<Bls12_381, Sha256>::new(payload_chunk_size, num_storage_nodes, srs).unwrap();
+        let payload_random = init_random_payload(1 << 20, &mut rng);
+
+        let _ = advz.commit_only(&payload_random);
+    }
+
+    #[test]
+    fn commit_accumulator() {
+        let (advz, payload) = avdz_init();
+        let header = b"header";
+        for (d, header) in [
+            (advz.disperse(&payload).unwrap(), None),
+            (
+                advz.disperse_with_header(header, &payload).unwrap(),
+                Some(&header[..]),
+            ),
+        ] {
+            let poly_commits = &d.common.poly_commits;
+            let num_polys = poly_commits.len();
+            assert!(num_polys > 2);
+
+            // reverse order, with the middle poly commit last
+            let mut acc =
+                CommitAccumulator::<Bls12_381, Sha256>::new(num_polys, advz.commit_version);
+            let mid = num_polys / 2;
+            for index in (0..num_polys).rev().filter(|i| *i != mid) {
+                acc.insert(index, &poly_commits[index]).unwrap();
+            }
+            assert_eq!(acc.len(), num_polys - 1);
+            assert_arg_err(
+                acc.clone().finalize(header, payload.len()),
+                "missing poly index should be arg error",
+            );
+            acc.insert(mid, &poly_commits[mid]).unwrap();
+            assert_arg_err(
+                acc.insert(mid, &poly_commits[mid]),
+                "duplicate poly index should be arg error",
+            );
+            assert_arg_err(
+                acc.insert(num_polys, &poly_commits[0]),
+                "poly index out of bounds should be arg error",
+            );
+            assert_eq!(acc.finalize(header, payload.len()).unwrap(), d.commit);
+        }
+        assert!(CommitAccumulator::<Bls12_381, Sha256>::new(3, CommitVersion::V2).is_empty());
+    }
+
+    #[test]
+    fn disperse_with_deadline() {
+        use std::time::{Duration, Instant};
+        let (advz, payload) = avdz_init();
+        let disperse = advz.disperse(&payload).unwrap();
+        let poly_byte_len = advz.poly_byte_len();
+
+        // generous deadline
+        let generous = Instant::now() + Duration::from_secs(3600);
+        assert_eq!(
+            advz.disperse_with_deadline(&payload, generous).unwrap(),
+            DeadlineDisperse::Complete(disperse.clone())
+        );
+        let empty = advz.disperse_with_deadline([], generous).unwrap();
+        assert_eq!(
+            empty,
+            DeadlineDisperse::Complete(advz.disperse([]).unwrap())
+        );
+
+        // expired deadline: the deadline is checked only after each polynomial,
+        // so exactly one polynomial is dispersed regardless of timing
+        let expired = Instant::now()
+            .checked_sub(Duration::from_secs(1))
+            .unwrap_or_else(Instant::now);
+        let partial = match advz.disperse_with_deadline(&payload, expired).unwrap() {
+            DeadlineDisperse::Partial(partial) => partial,
+            DeadlineDisperse::Complete(_) => panic!("expired deadline should be partial"),
+        };
+        assert_eq!(partial.num_polys(), 1);
+        assert_eq!(partial.byte_range(), 0..poly_byte_len);
+        let rest_offset = partial.byte_range().end;
+        let rest = advz
+            .disperse_partial(&payload[rest_offset..], rest_offset)
+            .unwrap();
+        assert_eq!(advz.merge_commitments(&[partial, rest]).unwrap(), disperse);
+
+        // a single polynomial completes even with an expired deadline
+        let short_payload = &payload[..poly_byte_len];
+        assert_eq!(
+            advz.disperse_with_deadline(short_payload, expired).unwrap(),
+            DeadlineDisperse::Complete(advz.disperse(short_payload).unwrap())
+        );
+    }
+
+    #[test]
+    fn permutation() {
+        use crate::vid::payload_prover::{PayloadProver, Statement};
+        use payload_prover::{LargeRangeProof, SmallRangeProof};
+        let (plain, payload) = avdz_init();
+        let mut advz = plain.clone();
+        advz.set_permutation(Some(Permutation::transpose(2, 2).unwrap()))
+            .unwrap();
+
+        let d = advz.disperse(&payload).unwrap();
+        assert_ne!(d.commit, plain.commit_only(&payload).unwrap());
+        for share in d.shares.iter() {
+            advz.verify_share(share, &d.common, &d.commit)
+                .unwrap()
+                .unwrap();
+        }
+        assert_eq!(advz.recover_payload(&d.shares, &d.common).unwrap(), payload);
+
+        // logical range spanning the swapped elements 1 and 2
+        let elem_byte_len = elem_byte_capacity::<KzgEval<Bls12_381>>();
+        assert_eq!(
+            advz.physical_byte_offset(elem_byte_len + 3),
+            2 * elem_byte_len + 3
+        );
+        assert_eq!(
+            advz.physical_byte_offset(3 * elem_byte_len),
+            3 * elem_byte_len
+        );
+        assert_eq!(
+            plain.physical_byte_offset(elem_byte_len + 3),
+            elem_byte_len + 3
+        );
+        let range = elem_byte_len - 5..2 * elem_byte_len + 5;
+        let stmt = Statement {
+            payload_subslice: &payload[range.clone()],
+            range: range.clone(),
+            commit: &d.commit,
+            common: &d.common,
+        };
+        let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        advz.payload_verify(stmt.clone(), &small).unwrap().unwrap();
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        advz.payload_verify(stmt.clone(), &large).unwrap().unwrap();
+
+        // verifier without the permutation
+        plain.payload_verify(stmt, &small).unwrap().unwrap_err();
+
+        let mut advz = plain;
+        assert_arg_err(
+            advz.set_permutation(Some(Permutation::transpose(2, 3).unwrap())),
+            "wrong permutation len should be arg error",
+        );
+        assert_arg_err(
+            Permutation::new(vec![0, 1, 1]),
+            "repeated offset should be arg error",
+        );
+    }
+
+    #[test]
+    fn merge_commitments() {
+        let (advz, payload) = avdz_init();
+        let poly_byte_len = advz.poly_byte_len();
+        let disperse = advz.disperse(&payload).unwrap();
+
+        // split at a polynomial boundary
+        let split = 3 * poly_byte_len;
+        assert!(split < payload.len());
+        let partials = [
+            advz.disperse_partial(&payload[..split], 0).unwrap(),
+            advz.disperse_partial(&payload[split..], split).unwrap(),
+        ];
+        let merged = advz.merge_commitments(&partials).unwrap();
+        assert_eq!(merged.commit, disperse.commit);
+        assert_eq!(merged, disperse);
+
+        // single segment
+        let partial = advz.disperse_partial(&payload, 0).unwrap();
+        let merged = advz.merge_commitments(&[partial]).unwrap();
+        assert_eq!(merged, disperse);
+
+        // misaligned byte offset
+        assert_arg_err(
+            advz.disperse_partial(&payload[split + 1..], split + 1),
+            "misaligned byte offset should be arg error",
+        );
+
+        // out of order
+        let [first, second] = partials.clone();
+        assert_arg_err(
+            advz.merge_commitments(&[second, first]),
+            "out of order segments should be arg error",
+        );
+
+        // gap between segments
+        assert_arg_err(
+            advz.merge_commitments(&partials[1..]),
+            "gap between segments should be arg error",
+        );
+
+        // segment that does not end on a polynomial boundary
+        let mut second = partials[1].clone();
+        second.byte_offset = split - 1;
+        let misaligned = [
+            advz.disperse_partial(&payload[..split - 1], 0).unwrap(),
+            second,
+        ];
+        assert_arg_err(
+            advz.merge_commitments(&misaligned),
+            "misaligned segment should be arg error",
+        );
+    }
+
+    #[test]
+    fn verify_sampled_share() {
+        type A = Advz<Bls12_381, Sha256>;
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let root = A::shares_root(&d.shares).unwrap();
+        assert_ne!(root, HasherNode::default());
+
+        // independent of the share encoding
+        let mut advz_minimal = advz.clone();
+        advz_minimal.set_share_encoding(ShareEncoding::Minimal);
+        let shares_minimal = advz_minimal.disperse(&payload).unwrap().shares;
+        assert_eq!(A::shares_root(&shares_minimal).unwrap(), root);
+
+        for index in [0, 1, d.shares.len() - 1] {
+            let proof = A::share_root_proof(&d.shares, index).unwrap();
+            A::verify_sampled_share(&d.shares[index], index, &proof, &root)
+                .unwrap()
+                .unwrap();
+        }
+
+        // forged share
+        let index = 2;
+        let proof = A::share_root_proof(&d.shares, index).unwrap();
+        let mut forged = d.shares[index].clone();
+        forged.evals[0] += KzgEval::<Bls12_381>::one();
+        A::verify_sampled_share(&forged, index, &proof, &root)
+            .unwrap()
+            .unwrap_err();
+
+        // share or proof for another index
+        A::verify_sampled_share(&d.shares[index + 1], index, &proof, &root)
+            .unwrap()
+            .unwrap_err();
+        A::verify_sampled_share(&d.shares[index], index + 1, &proof, &root)
+            .unwrap()
+            .unwrap_err();
+
+        // root of another payload
+        let mut other_payload = payload.clone();
+        other_payload[0] ^= 1;
+        let other_root = A::shares_root(&advz.disperse(&other_payload).unwrap().shares).unwrap();
+        A::verify_sampled_share(&d.shares[index], index, &proof, &other_root)
+            .unwrap()
+            .unwrap_err();
+
+        assert_arg_err(
+            A::share_root_proof(&d.shares, d.shares.len()),
+            "share index out of bounds should be arg error",
+        );
+    }
+
+    #[test]
+    fn segment_boundaries() {
+        let (advz, payload) = avdz_init();
+        let poly_byte_len = advz.poly_byte_len();
+        let num_polys = (payload.len() + poly_byte_len - 1) / poly_byte_len;
+        let disperse = advz.disperse(&payload).unwrap();
+
+        for num_segments in [1, 2, 5, num_polys, num_polys + 3] {
+            let segments = advz
+                .segment_boundaries(payload.len(), num_segments)
+                .unwrap();
+            assert_eq!(segments.len(), num_segments.min(num_polys));
+
+            // segments tile the payload at polynomial boundaries
+            assert_eq!(segments.first().unwrap().start, 0);
+            assert_eq!(segments.last().unwrap().end, payload.len());
+            for pair in segments.windows(2) {
+                assert_eq!(pair[0].end, pair[1].start);
+                assert_eq!(pair[0].end % poly_byte_len, 0);
+            }
+            let (min_len, max_len) = segments[..segments.len() - 1]
+                .iter()
+                .fold((usize::MAX, 0), |(min, max), s| {
+                    (min.min(s.len()), max.max(s.len()))
+                });
+            assert!(segments.len() == 1 || max_len - min_len <= poly_byte_len);
+
+            let partials: Vec<_> = segments
+                .iter()
+                .map(|s| advz.disperse_partial(&payload[s.clone()], s.start).unwrap())
+                .collect();
+            assert_eq!(advz.merge_commitments(&partials).unwrap(), disperse);
+        }
+
+        // empty payload
+        let segments = advz.segment_boundaries(0, 3).unwrap();
+        assert_eq!(segments, vec![0..0]);
+        let partial = advz.disperse_partial([], 0).unwrap();
+        assert_eq!(
+            advz.merge_commitments(&[partial]).unwrap(),
+            advz.disperse([]).unwrap()
+        );
+
+        assert_arg_err(
+            advz.segment_boundaries(payload.len(), 0),
+            "zero segments should be arg error",
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn disperse_stream() {
+        use ark_std::rand::Rng;
+        use std::{sync::mpsc::sync_channel, thread};
+
+        let (advz, payload) = avdz_init();
+        let poly_byte_len = advz.poly_byte_len();
+        let mut rng = jf_utils::test_rng();
+
+        for len in [
+            0,
+            1,
+            poly_byte_len,
+            2 * poly_byte_len,
+            2 * poly_byte_len + 1,
+            payload.len(),
+        ] {
+            let payload = payload[..len].to_vec();
+            let expect = advz.disperse(&payload).unwrap();
+
+            // feed arbitrarily sized chunks, including empty ones
+            let mut chunks = Vec::new();
+            let mut rest = payload.as_slice();
+            while !rest.is_empty() {
+                let chunk_len = usize::min(rng.gen_range(0..2 * poly_byte_len), rest.len());
+                chunks.push(rest[..chunk_len].to_vec());
+                rest = &rest[chunk_len..];
+            }
+            let (tx, rx) = sync_channel(1);
+            let sender = thread::spawn(move || {
+                for chunk in chunks {
+                    tx.send(chunk).unwrap();
+                }
+            });
+            assert_eq!(advz.disperse_stream(rx).unwrap(), expect);
+            sender.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn parallel_threshold() {
+        let (mut advz, payload) = avdz_init();
+        let num_polys = (payload.len() + advz.poly_byte_len() - 1) / advz.poly_byte_len();
+
+        // output does not depend on the threshold
+        advz.set_parallel_threshold(usize::MAX);
+        let serial = advz.disperse(&payload).unwrap();
+        for threshold in [0, 1, num_polys, num_polys + 1] {
+            advz.set_parallel_threshold(threshold);
+            let d = advz.disperse(&payload).unwrap();
+            assert_eq!(d.commit, serial.commit);
+            assert_eq!(d.common, serial.common);
+            assert_eq!(d.shares, serial.shares);
+        }
+
+        // below-threshold inputs run on the calling thread, others in the
+        // rayon pool
+        #[cfg(feature = "parallel")]
+        {
+            let items = vec![0u8; num_polys];
+            advz.set_parallel_threshold(num_polys + 1);
+            assert!(advz
+                .maybe_par_map(&items, |_| rayon::current_thread_index())
+                .iter()
+                .all(Option::is_none));
+            advz.set_parallel_threshold(num_polys);
+            assert!(advz
+                .maybe_par_map(&items, |_| rayon::current_thread_index())
+                .iter()
+                .all(Option::is_some));
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        use crate::vid::payload_prover::{PayloadProver, Statement};
+        use payload_prover::{LargeRangeProof, SmallRangeProof};
+        let (mut parallel, payload) = avdz_init();
+        parallel.set_parallel_threshold(0);
+        let mut deterministic = parallel.clone();
+        deterministic.set_deterministic(true);
+        let d = parallel.disperse(&payload).unwrap();
+        assert_eq!(deterministic.disperse(&payload).unwrap(), d);
+
+        // identical outcomes, including for a bad subslice and for a subslice
+        // of the wrong length
+        fn outcome(result: VidResult<Result<(), ()>>) -> &'static str {
+            match result {
+                Ok(Ok(())) => "ok",
+                Ok(Err(())) => "rejected",
+                Err(VidError::Argument(_)) => "argument error",
+                Err(_) => "other error",
+            }
+        }
+        let range = 10..40;
+        let small: SmallRangeProof<_> = parallel.payload_proof(&payload, range.clone()).unwrap();
+        let large: LargeRangeProof<_> = parallel.payload_proof(&payload, range.clone()).unwrap();
+        let mut bad_payload = payload.clone();
+        bad_payload[range.start] ^= 1;
+        let subslices = [
+            &payload[range.clone()],
+            &bad_payload[range.clone()],
+            &payload[range.start..range.end - 1],
+        ];
+        let outcomes = |advz: &Advz<Bls12_381, Sha256>| {
+            let mut outcomes = Vec::new();
+            for subslice in subslices.iter() {
+                let stmt = Statement {
+                    payload_subslice: subslice,
+                    range: range.clone(),
+                    commit: &d.commit,
+                    common: &d.common,
+                };
+                outcomes.push(outcome(advz.payload_verify(stmt.clone(), &small)));
+                outcomes.push(outcome(advz.payload_verify(stmt, &large)));
+            }
+            outcomes.push(outcome(
+                advz.recover_payload(&d.shares[..3], &d.common)
+                    .map(|_| Ok(())),
+            ));
+            outcomes
+        };
+
+        // every build and target must produce exactly these outcomes, with or
+        // without the `parallel` feature
+        let expected = [
+            "ok",
+            "ok",
+            "rejected",
+            "rejected",
+            "argument error",
+            "argument error",
+            "argument error",
+        ];
+        assert_eq!(outcomes(&parallel), expected);
+        assert_eq!(outcomes(&deterministic), expected);
+        assert_eq!(
+            deterministic.recover_payload(&d.shares, &d.common).unwrap(),
+            payload
+        );
+
+        // deterministic channel results arrive in request order
+        #[cfg(feature = "std")]
+        {
+            use payload_prover::{OwnedStatement, VerifyRequest};
+            use std::sync::mpsc::channel;
+
+            let (request_tx, request_rx) = channel();
+            let (result_tx, result_rx) = channel();
+            for (id, subslice) in subslices.iter().enumerate() {
+                request_tx
+                    .send(VerifyRequest {
+                        id: id as u64,
+                        stmt: OwnedStatement {
+                            payload_subslice: subslice.to_vec(),
+                            range: range.clone(),
+                            commit: d.commit,
+                            common: d.common.clone(),
+                        },
+                        proof: small.clone(),
+                    })
+                    .unwrap();
+            }
+            drop(request_tx);
+            deterministic.payload_verify_channel(request_rx, result_tx);
+            let results: Vec<_> = result_rx
+                .iter()
+                .map(|result| (result.id, outcome(result.outcome)))
+                .collect();
+            assert_eq!(
+                results,
+                [(0, expected[0]), (1, expected[2]), (2, expected[4])]
+            );
+        }
+    }
+
+    #[test]
+    fn poly_commits_hash_field() {
+        use crate::circuit::rescue::RescueNativeGadget;
+        use ark_bn254::{Bn254, Fq};
+        use jf_relation::{Circuit, PlonkCircuit};
+
+        // Rescue parameters for the BLS12-381 base field are mocks, so use BN254
+        let (payload_chunk_size, num_storage_nodes) = (4, 6);
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(payload_chunk_size, &mut rng);
+        let advz = Advz::<Bn254, Sha256>::new(payload_chunk_size, num_storage_nodes, srs).unwrap();
+        let payload = init_random_payload(1000, &mut rng);
+        let common = advz.disperse(&payload).unwrap().common;
+
+        // deterministic
+        let commit = Advz::<Bn254, Sha256>::poly_commits_hash_field::<Fq>(&common).unwrap();
+        assert_eq!(
+            commit,
+            Advz::<Bn254, Sha256>::poly_commits_hash_field(&common).unwrap()
+        );
+        Advz::<Bn254, Sha256>::check_common_commit_field_consistency(&common, &commit).unwrap();
+
+        // sensitive to `common`
+        let common_other = advz.disperse(&payload[1..]).unwrap().common;
+        assert_ne!(
+            commit,
+            Advz::<Bn254, Sha256>::poly_commits_hash_field(&common_other).unwrap()
+        );
+        assert_arg_err(
+            Advz::<Bn254, Sha256>::check_common_commit_field_consistency(&common_other, &commit),
+            "inconsistent field commit should be arg error",
+        );
+
+        // native and in-circuit computations agree
+        let mut circuit = PlonkCircuit::<Fq>::new_turbo_plonk();
+        let mut input_vars = Vec::with_capacity(2 * common.poly_commits.len());
+        for poly_commit in common.poly_commits.iter() {
+            let (x, y) = poly_commit.0.xy().unwrap();
+            input_vars.push(circuit.create_variable(*x).unwrap());
+            input_vars.push(circuit.create_variable(*y).unwrap());
+        }
+        let commit_var =
+            RescueNativeGadget::<Fq>::rescue_sponge_with_padding(&mut circuit, &input_vars, 1)
+                .unwrap()[0];
+        assert_eq!(circuit.witness(commit_var).unwrap(), commit);
+        circuit.check_circuit_satisfiability(&[]).unwrap();
+    }
+
+    #[test]
+    fn field_commit_version() {
+        use ark_bn254::{Bn254, Fq};
+        type A = Advz<Bn254, Sha256>;
+
+        let (payload_chunk_size, num_storage_nodes) = (4, 6);
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(payload_chunk_size, &mut rng);
+        let payload = init_random_payload(1000, &mut rng);
+
+        // V4 without the field commit fails
+        assert_arg_err(
+            A::new_with_commit_version(
+                payload_chunk_size,
+                num_storage_nodes,
+                &srs,
+                CommitVersion::V4,
+            ),
+            "V4 without field commit should be arg error",
+        );
+
+        let advz =
+            A::new_with_field_commit::<Fq>(payload_chunk_size, num_storage_nodes, &srs).unwrap();
+        let d = advz.disperse(&payload).unwrap();
+        assert_eq!(d.commit, advz.commit_only(&payload).unwrap());
+        let commit = A::commit_field::<Fq>(&d.commit).unwrap();
+        assert_eq!(commit, A::poly_commits_hash_field(&d.common).unwrap());
+        A::check_common_commit_field_consistency(&d.common, &commit).unwrap();
+        for share in d.shares.iter() {
+            advz.verify_share(share, &d.common, &d.commit)
+                .unwrap()
+                .unwrap();
+        }
+
+        // accumulator under the field commit
+        let num_polys = d.common.poly_commits.len();
+        let mut accumulator = advz.commit_accumulator(num_polys);
+        for (index, poly_commit) in d.common.poly_commits.iter().enumerate().rev() {
+            accumulator.insert(index, poly_commit).unwrap();
+        }
+        assert_eq!(accumulator.finalize(None, payload.len()).unwrap(), d.commit);
+        assert_arg_err(
+            CommitAccumulator::<Bn254, Sha256>::new(num_polys, CommitVersion::V4)
+                .finalize(None, payload.len()),
+            "accumulator without field commit should be arg error",
+        );
+
+        // commitment to another payload differs
+        let commit_other = advz.commit_only(&payload[1..]).unwrap();
+        assert_ne!(A::commit_field::<Fq>(&commit_other).unwrap(), commit);
+
+        assert_arg_err(
+            advz.disperse_with_header(b"header", &payload),
+            "header should be arg error under V4",
+        );
+        assert_arg_err(
+            advz.compact_common(&d.common),
+            "compact common should be arg error under V4",
+        );
+
+        // a field element does not fit a 64-byte digest
+        assert_arg_err(
+            Advz::<Bn254, sha2::Sha512>::new_with_field_commit::<Fq>(
+                payload_chunk_size,
+                num_storage_nodes,
+                srs,
+            ),
+            "field commit with 64-byte digest should be arg error",
+        );
+    }
+
+    #[test]
+    fn share_encoding() {
+        let (mut advz, payload) = avdz_init();
+        let full = advz.disperse(&payload).unwrap();
+        assert!(full.shares.iter().all(|share| share.evals_proof.is_some()));
+
+        advz.set_share_encoding(ShareEncoding::Minimal);
+        let minimal = advz.disperse(&payload).unwrap();
+        assert_eq!(minimal.commit, full.commit);
+        assert_eq!(
+            minimal.common.all_evals_digest,
+            full.common.all_evals_digest
+        );
+        assert_eq!(
+            Advz::<Bls12_381, Sha256>::all_evals_root(
+                minimal.common.evals_leaf_digests.as_ref().unwrap()
+            )
+            .unwrap(),
+            full.common.all_evals_digest
+        );
+        for (share, full_share) in minimal.shares.iter().zip(full.shares.iter()) {
+            assert!(share.evals_proof.is_none());
+            assert!(
+                bincode::serialized_size(share).unwrap()
+                    < bincode::serialized_size(full_share).unwrap()
+            );
+            advz.verify_share(share, &minimal.common, &minimal.commit)
+                .unwrap()
+                .unwrap();
+
+            // a tampered eval does not match its leaf digest
+            let mut bad_share = share.clone();
+            bad_share.evals[0] += KzgEval::<Bls12_381>::one();
+            advz.verify_share(&bad_share, &minimal.common, &minimal.commit)
+                .unwrap()
+                .unwrap_err();
+
+            // full shares still verify, including their merkle paths
+            advz.verify_share(full_share, &full.common, &full.commit)
+                .unwrap()
+                .unwrap();
+            let mut bad_full_share = full_share.clone();
+            bad_full_share.evals[0] += KzgEval::<Bls12_381>::one();
+            advz.verify_share(&bad_full_share, &full.common, &full.commit)
+                .unwrap()
+                .unwrap_err();
+        }
+        assert_eq!(
+            advz.recover_payload(&minimal.shares, &minimal.common)
+                .unwrap(),
+            payload
+        );
+
+        // a tampered leaf digest does not match the merkle root
+        let mut bad_common = minimal.common.clone();
+        let share = &minimal.shares[1];
+        let mut bad_share = share.clone();
+        bad_share.evals[0] += KzgEval::<Bls12_381>::one();
+        bad_common.evals_leaf_digests.as_mut().unwrap()[1] =
+            Advz::<Bls12_381, Sha256>::evals_leaf_digest(1, &bad_share.evals).unwrap();
+        advz.verify_share(&bad_share, &bad_common, &minimal.commit)
+            .unwrap()
+            .unwrap_err();
+
+        // verification does not depend on the encoding of the verifier, but a
+        // share without a merkle path needs leaf digests in `common`
+        advz.set_share_encoding(ShareEncoding::Full);
+        advz.verify_share(share, &minimal.common, &minimal.commit)
+            .unwrap()
+            .unwrap();
+        advz.verify_share(share, &full.common, &full.commit)
+            .unwrap()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn length_proof() {
+        let (mut advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        assert_arg_err(
+            advz.length_proof(&d.common),
+            "length proof under V0 should be arg error",
+        );
+
+        advz = avdz_init_with_commit_version(CommitVersion::V2).0;
+        let d = advz.disperse(&payload).unwrap();
+        let proof = advz.length_proof(&d.common).unwrap();
+        advz.verify_length(&d.commit, payload.len(), &proof)
+            .unwrap()
+            .unwrap();
+        for bad_len in [payload.len() - 1, payload.len() + 1, 0] {
+            advz.verify_length(&d.commit, bad_len, &proof)
+                .unwrap()
+                .unwrap_err();
+        }
+
+        // zero padding is indistinguishable from payload, but the length is
+        // still bound
+        let mut padded_payload = payload.clone();
+        padded_payload.push(0);
+        let padded_commit = advz.commit_only(&padded_payload).unwrap();
+        assert_ne!(padded_commit, d.commit);
+        advz.verify_length(&padded_commit, payload.len(), &proof)
+            .unwrap()
+            .unwrap_err();
+
+        // proof for another commitment
+        let other = advz
+            .length_proof(&advz.disperse(&padded_payload).unwrap().common)
+            .unwrap();
+        advz.verify_length(&d.commit, payload.len(), &other)
+            .unwrap()
+            .unwrap_err();
+        advz.verify_length(&padded_commit, padded_payload.len(), &other)
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn disperse_heterogeneous() {
+        let (advz, payload) = avdz_init();
+        let k = advz.payload_chunk_size;
+        let poly_num_storage_nodes = [6, 4, 5];
+        let d = advz
+            .disperse_heterogeneous(&payload, &poly_num_storage_nodes)
+            .unwrap();
+        assert_eq!(d.commit, advz.commit_only(&payload).unwrap());
+        assert_eq!(d.commit, advz.disperse(&payload).unwrap().commit);
+        assert_eq!(d.shares.len(), advz.num_storage_nodes);
+        assert_eq!(&d.poly_num_storage_nodes[..3], &poly_num_storage_nodes);
+        assert!(d.poly_num_storage_nodes[3..]
+            .iter()
+            .all(|n| *n == advz.num_storage_nodes));
+
+        // recovery succeeds iff each polynomial has enough of its storage
+        // nodes
+        for node_indices in [
+            vec![0, 1, 2, 3],
+            vec![2, 3, 4, 5],
+            vec![0, 1, 3, 4],
+            vec![0, 1, 2, 3, 4, 5],
+            vec![1, 2, 3, 4, 5],
+        ] {
+            let shares: Vec<_> = node_indices.iter().map(|i| d.shares[*i].clone()).collect();
+            let recoverable = d
+                .poly_num_storage_nodes
+                .iter()
+                .all(|n| node_indices.iter().filter(|i| **i < *n).count() >= k);
+            let res = advz.recover_payload_heterogeneous(&shares, d.bytes_len);
+            if recoverable {
+                assert_eq!(res.unwrap(), payload, "nodes {:?}", node_indices);
+            } else {
+                assert_arg_err(res, "too few storage nodes should be arg error");
+            }
+        }
+
+        for bad in [vec![k - 1], vec![advz.num_storage_nodes + 1], vec![k; 1000]] {
+            assert_arg_err(
+                advz.disperse_heterogeneous(&payload, &bad),
+                "bad poly num storage nodes should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn verify_share_heterogeneous() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse_heterogeneous(&payload, &[6, 4, 5]).unwrap();
+        assert_eq!(
+            d.poly_commits,
+            advz.disperse(&payload).unwrap().common.poly_commits
+        );
+        let verify = |share: &HeterogeneousShare<Bls12_381>| {
+            advz.verify_share_heterogeneous(
+                share,
+                &d.poly_num_storage_nodes,
+                &d.poly_commits,
+                d.bytes_len,
+                &d.commit,
+            )
+        };
+        for share in d.shares.iter() {
+            verify(share).unwrap().unwrap();
+        }
+
+        let share = &d.shares[4];
+        assert!(share.evals[0].is_some() && share.evals[1].is_none());
+
+        // tampered eval
+        let mut bad_share = share.clone();
+        *bad_share.evals[0].as_mut().unwrap() += KzgEval::<Bls12_381>::one();
+        verify(&bad_share).unwrap().unwrap_err();
+
+        // tampered evals that preserve a linear combination fixed in advance
+        let mut bad_share = share.clone();
+        *bad_share.evals[0].as_mut().unwrap() += KzgEval::<Bls12_381>::one();
+        *bad_share.evals[2].as_mut().unwrap() -= KzgEval::<Bls12_381>::one();
+        verify(&bad_share).unwrap().unwrap_err();
+
+        // dropped or added eval
+        let mut bad_share = share.clone();
+        bad_share.evals[0] = None;
+        verify(&bad_share).unwrap().unwrap_err();
+        let mut bad_share = share.clone();
+        bad_share.evals[1] = Some(KzgEval::<Bls12_381>::zero());
+        verify(&bad_share).unwrap().unwrap_err();
+
+        // another share's proof, or relabeled as another storage node
+        let mut bad_share = share.clone();
+        bad_share.aggregate_proof = d.shares[3].aggregate_proof.clone();
+        verify(&bad_share).unwrap().unwrap_err();
+        let mut bad_share = share.clone();
+        bad_share.index = 3;
+        verify(&bad_share).unwrap().unwrap_err();
+        let mut bad_share = share.clone();
+        bad_share.index = advz.num_storage_nodes;
+        verify(&bad_share).unwrap().unwrap_err();
+
+        // inconsistent arguments
+        let mut bad_poly_commits = d.poly_commits.clone();
+        bad_poly_commits.swap(0, 1);
+        assert_arg_err(
+            advz.verify_share_heterogeneous(
+                share,
+                &d.poly_num_storage_nodes,
+                &bad_poly_commits,
+                d.bytes_len,
+                &d.commit,
+            ),
+            "poly commits inconsistent with commit should be arg error",
+        );
+        assert_arg_err(
+            advz.verify_share_heterogeneous(
+                share,
+                &d.poly_num_storage_nodes[1..],
+                &d.poly_commits,
+                d.bytes_len,
+                &d.commit,
+            ),
+            "wrong poly num storage nodes len should be arg error",
+        );
+    }
+
+    #[test]
+    fn disperse_hiding() {
+        use crate::vid::payload_prover::{PayloadProver, Statement};
+        use payload_prover::SmallRangeProof;
+        let (advz, payload) = avdz_init();
+        let mut rng = jf_utils::test_rng();
+        let plain = advz.disperse(&payload).unwrap();
+        let hd = advz.disperse_hiding(&payload, &mut rng).unwrap();
+        let d = &hd.disperse;
+        assert_eq!(d.common.bytes_len, payload.len());
+        assert_ne!(d.commit, plain.commit);
+        for (hidden, plain) in d
+            .common
+            .poly_commits
+            .iter()
+            .zip(plain.common.poly_commits.iter())
+        {
+            assert_ne!(hidden, plain);
+        }
+        for (share, plain_share) in d.shares.iter().zip(plain.shares.iter()) {
+            advz.verify_share(share, &d.common, &d.commit)
+                .unwrap()
+                .unwrap();
+            for (hidden, plain) in share.evals.iter().zip(plain_share.evals.iter()) {
+                assert_ne!(hidden, plain);
+            }
+        }
+
+        // a fresh dispersal of the same payload is unlinkable
+        let other = advz.disperse_hiding(&payload, &mut rng).unwrap();
+        assert_ne!(other.disperse.commit, d.commit);
+
+        let poly_index = 1;
+        let poly_start = poly_index * advz.poly_byte_len();
+        let range = poly_start + 3..poly_start + 50;
+        let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let verify = |commit, common| {
+            advz.payload_verify(
+                Statement {
+                    payload_subslice: &payload[range.clone()],
+                    range: range.clone(),
+                    commit,
+                    common,
+                },
+                &proof,
+            )
+        };
+
+        // without the blind
+        verify(&d.commit, &d.common).unwrap().unwrap_err();
+
+        // with the blind
+        let blind = hd.reveal_blind(poly_index).unwrap();
+        let (commit, common) = advz
+            .dehide(&d.commit, &d.common, poly_index, &blind)
+            .unwrap();
+        assert_eq!(
+            common.poly_commits[poly_index],
+            plain.common.poly_commits[poly_index]
+        );
+        verify(&commit, &common).unwrap().unwrap();
+
+        // blind of another polynomial
+        let (commit, common) = advz
+            .dehide(
+                &d.commit,
+                &d.common,
+                poly_index,
+                &hd.reveal_blind(0).unwrap(),
+            )
+            .unwrap();
+        verify(&commit, &common).unwrap().unwrap_err();
+
+        assert_arg_err(
+            hd.reveal_blind(d.common.poly_commits.len()),
+            "poly index out of bounds should be arg error",
+        );
+        assert_arg_err(
+            advz.dehide(&plain.commit, &d.common, poly_index, &blind),
+            "inconsistent commit should be arg error",
+        );
+    }
+
+    #[test]
+    fn reencode_shares() {
+        let (advz, payload) = avdz_init();
+        let header = b"header";
+        let threshold = advz.payload_chunk_size;
+        for d in [
+            advz.disperse(&payload).unwrap(),
+            advz.disperse_with_header(header, &payload).unwrap(),
+        ] {
+            for new_num_storage_nodes in [threshold, 9, 16] {
+                let new_advz = advz.with_num_storage_nodes(new_num_storage_nodes).unwrap();
+                let r = advz
+                    .reencode_shares(
+                        &d.shares[1..threshold + 1],
+                        &d.common,
+                        new_num_storage_nodes,
+                    )
+                    .unwrap();
+                assert_eq!(r.commit, d.commit);
+                assert_eq!(r.shares.len(), new_num_storage_nodes);
+                for share in r.shares.iter() {
+                    new_advz
+                        .verify_share(share, &r.common, &d.commit)
+                        .unwrap()
+                        .unwrap();
+                }
+                assert_eq!(
+                    new_advz
+                        .recover_payload(&r.shares[new_num_storage_nodes - threshold..], &r.common)
+                        .unwrap(),
+                    payload
+                );
+            }
+        }
+        let d = advz.disperse(&payload).unwrap();
+        let r = advz.reencode_shares(&d.shares, &d.common, 9).unwrap();
+        assert_eq!(
+            r,
+            advz.with_num_storage_nodes(9)
+                .unwrap()
+                .disperse(&payload)
+                .unwrap()
+        );
+
+        // shares of another payload
+        let mut other_payload = payload.clone();
+        other_payload[0] ^= 1;
+        let other = advz.disperse(&other_payload).unwrap();
+        assert_arg_err(
+            advz.reencode_shares(&other.shares, &d.common, 9),
+            "shares inconsistent with common should be arg error",
+        );
+
+        assert_arg_err(
+            advz.reencode_shares(&d.shares[..threshold - 1], &d.common, 9),
+            "too few shares should be arg error",
+        );
+        assert_arg_err(
+            advz.reencode_shares(&d.shares, &d.common, threshold - 1),
+            "too few storage nodes should be arg error",
+        );
+    }
+
+    #[test]
+    fn reconstruct_common() {
+        let (mut advz, mut payload) = avdz_init();
+        let header = b"header";
+        let d = advz.disperse(&payload).unwrap();
+        assert_arg_err(
+            advz.reconstruct_common(&d.shares, &d.commit, None),
+            "reconstruct_common under V0 should be arg error",
+        );
+        advz = avdz_init_with_commit_version(CommitVersion::V2).0;
+
+        // trailing zero bytes and zero padding polynomials are indistinguishable
+        // from the payload in shares
+        payload.extend_from_slice(&[0; 100]);
+        for min_polynomials in [0, 200] {
+            advz.set_min_polynomials(min_polynomials);
+            let d = advz.disperse(&payload).unwrap();
+            let d_header = advz.disperse_with_header(header, &payload).unwrap();
+            let threshold = advz.payload_chunk_size;
+            for d_shares in [
+                &d.shares[..threshold],
+                &d.shares[d.shares.len() - threshold..],
+            ] {
+                assert_eq!(
+                    advz.reconstruct_common(d_shares, &d.commit, None).unwrap(),
+                    d.common
+                );
+            }
+            assert_eq!(
+                advz.reconstruct_common(&d_header.shares, &d_header.commit, Some(header))
+                    .unwrap(),
+                d_header.common
+            );
+
+            assert_arg_err(
+                advz.reconstruct_common(&d_header.shares, &d_header.commit, None),
+                "missing header should be arg error",
+            );
+            assert_arg_err(
+                advz.reconstruct_common(&d.shares, &d_header.commit, None),
+                "wrong commit should be arg error",
+            );
+            assert_arg_err(
+                advz.reconstruct_common(&d.shares[..threshold - 1], &d.commit, None),
+                "not enough shares should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn common_format() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+
+        let mut bytes = Vec::new();
+        d.common.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes[..8], COMMON_FORMAT_V1.to_le_bytes());
+        assert_eq!(bytes.len(), d.common.compressed_size());
+        assert_eq!(
+            Common::<Bls12_381, Sha256>::deserialize_compressed(&bytes[..]).unwrap(),
+            d.common
+        );
+
+        // common data serialized before the format was versioned
+        let mut legacy_bytes = Vec::new();
+        d.common
+            .poly_commits
+            .serialize_compressed(&mut legacy_bytes)
+            .unwrap();
+        d.common
+            .all_evals_digest
+            .serialize_compressed(&mut legacy_bytes)
+            .unwrap();
+        d.common
+            .bytes_len
+            .serialize_compressed(&mut legacy_bytes)
+            .unwrap();
+        let legacy =
+            Common::<Bls12_381, Sha256>::deserialize_compressed(&legacy_bytes[..]).unwrap();
+        assert_eq!(legacy.poly_commits, d.common.poly_commits);
+        assert_eq!(legacy.all_evals_digest, d.common.all_evals_digest);
+        assert_eq!(legacy.bytes_len, d.common.bytes_len);
+        assert_eq!(legacy.header_digest, None);
+        assert_eq!(
+            (legacy.payload_chunk_size, legacy.num_storage_nodes),
+            (0, 0)
+        );
+
+        for share in d.shares.iter() {
+            advz.verify_share(share, &legacy, &d.commit)
+                .unwrap()
+                .unwrap();
+        }
+        assert_eq!(advz.recover_payload(&d.shares, &legacy).unwrap(), payload);
+    }
+
+    #[test]
+    fn disperse_with_header() {
+        use crate::vid::payload_prover::{PayloadProver, Statement};
+        use payload_prover::{CompactStatement, PayloadVerifyCompact, SmallRangeProof};
+        let header = b"height 7, parent 0xabcd";
+        for commit_version in [CommitVersion::V0, CommitVersion::V1] {
+            let (advz, payload) = avdz_init_with_commit_version(commit_version);
+            let d = advz.disperse_with_header(header, &payload).unwrap();
+            let d_plain = advz.disperse(&payload).unwrap();
+            assert_eq!(d.common.poly_commits, d_plain.common.poly_commits);
+            assert_ne!(d.commit, d_plain.commit);
+            for share in d.shares.iter() {
+                advz.verify_share(share, &d.common, &d.commit)
+                    .unwrap()
+                    .unwrap();
+            }
+            assert_eq!(advz.recover_payload(&d.shares, &d.common).unwrap(), payload);
+
+            advz.verify_header(header, &d.commit, &d.common)
+                .unwrap()
+                .unwrap();
+            advz.verify_header(b"height 8", &d.commit, &d.common)
+                .unwrap()
+                .unwrap_err();
+            advz.verify_header(header, &d_plain.commit, &d_plain.common)
+                .unwrap()
+                .unwrap_err();
+
+            // tampered header changes the commitment
+            let d_tampered = advz
+                .disperse_with_header(b"height 8, parent 0xabcd", &payload)
+                .unwrap();
+            assert_ne!(d_tampered.commit, d.commit);
+            assert_arg_err(
+                advz.verify_header(header, &d.commit, &d_tampered.common),
+                "common for other header should be arg error",
+            );
+
+            // body proofs verify against the commitment with header
+            let range = 3..50;
+            let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            let stmt = Statement {
+                payload_subslice: &payload[range.clone()],
+                range,
+                commit: &d.commit,
+                common: &d.common,
+            };
+            advz.payload_verify(stmt.clone(), &proof).unwrap().unwrap();
+            if commit_version != CommitVersion::V0 {
+                let compact = advz.compact_common(&d.common).unwrap();
+                advz.payload_verify_compact(
+                    CompactStatement {
+                        payload_subslice: stmt.payload_subslice,
+                        range: stmt.range.clone(),
+                        commit: stmt.commit,
+                        common: &compact,
+                        poly_commit: &d.common.poly_commits[0],
+                    },
+                    &proof,
+                )
+                .unwrap()
+                .unwrap();
+            }
+            assert_arg_err(
+                advz.payload_verify(
+                    Statement {
+                        commit: &d_tampered.commit,
+                        ..stmt
+                    },
+                    &proof,
+                ),
+                "commit for other header should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn update_commitment() {
+        type A = Advz<Bls12_381, Sha256>;
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let capacity = elem_byte_capacity::<KzgEval<Bls12_381>>();
+        let elems: Vec<_> = A::enumerate_elems(&payload).map(|(_, e)| e).collect();
+
+        for elem_index in [0, 1, advz.payload_chunk_size + 3, elems.len() - 1] {
+            let mut edited = payload.clone();
+            edited[elem_index * capacity] ^= 0xFF;
+            let new_elem = A::enumerate_elems(&edited).nth(elem_index).unwrap().1;
+            let (poly_commits, commit) = advz
+                .update_commitment(&d, elem_index, elems[elem_index], new_elem)
+                .unwrap();
+            let fresh = advz.disperse(&edited).unwrap();
+            assert_eq!(poly_commits, fresh.common.poly_commits);
+            assert_eq!(commit, fresh.commit);
+            assert_ne!(commit, d.commit);
+        }
+
+        // unchanged elem
+        let (poly_commits, commit) = advz.update_commitment(&d, 2, elems[2], elems[2]).unwrap();
+        assert_eq!(poly_commits, d.common.poly_commits);
+        assert_eq!(commit, d.commit);
+
+        assert_arg_err(
+            advz.update_commitment(&d, elems.len(), elems[0], elems[0]),
+            "out of bounds elem index should be arg error",
+        );
+    }
+
+    #[test]
+    fn aggregate_commitment() {
+        type A = Advz<Bls12_381, Sha256>;
+        let (advz, payload) = avdz_init();
+        let common = advz.disperse(&payload).unwrap().common;
+        let challenge = KzgEval::<Bls12_381>::from(2u64);
+        let c = |i: usize| common.poly_commits[i].0.into_group();
+
+        assert_eq!(
+            A::aggregate_commitment(&common, 2..5, challenge).unwrap().0,
+            (c(2)
+                + c(3) * KzgEval::<Bls12_381>::from(2u64)
+                + c(4) * KzgEval::<Bls12_381>::from(4u64))
+            .into_affine()
+        );
+        assert_eq!(
+            A::aggregate_commitment(&common, 3..4, challenge).unwrap(),
+            common.poly_commits[3]
+        );
+
+        // linearity: aggregate commitment commits to the aggregate polynomial
+        let polys: Vec<_> = bytes_to_field::<_, KzgEval<Bls12_381>>(&payload)
+            .chunks(advz.payload_chunk_size)
+            .into_iter()
+            .map(|evals_iter| advz.polynomial(evals_iter))
+            .collect();
+        let aggregate_poly =
+            &(&polys[2] + &(&polys[3] * challenge)) + &(&polys[4] * (challenge * challenge));
+        assert_eq!(
+            A::aggregate_commitment(&common, 2..5, challenge).unwrap(),
+            UnivariateKzgPCS::commit(&advz.ck, &aggregate_poly).unwrap()
+        );
+
+        let num_polys = common.poly_commits.len();
+        for poly_range in [3..3, num_polys - 1..num_polys + 1] {
+            assert_arg_err(
+                A::aggregate_commitment(&common, poly_range, challenge),
+                "empty or out of bounds poly range should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn commitment_delta() {
+        let (advz, payload) = avdz_init();
+        let poly_byte_len = advz.poly_byte_len();
+        assert!(advz
+            .commitment_delta(&payload, &payload.clone())
+            .unwrap()
+            .is_empty());
+
+        let d = advz.disperse(&payload).unwrap();
+        for byte_index in [0, poly_byte_len + 5, payload.len() - 1] {
+            let mut edited = payload.clone();
+            edited[byte_index] ^= 0x01;
+            let delta = advz.commitment_delta(&edited, &payload).unwrap();
+            assert_eq!(delta.len(), 1);
+            let (poly_index, poly_commit_delta) = delta[0];
+            assert_eq!(poly_index, byte_index / poly_byte_len);
+
+            // delta is the difference of the poly commits
+            let fresh = advz.disperse(&edited).unwrap();
+            assert_eq!(
+                poly_commit_delta.0,
+                (fresh.common.poly_commits[poly_index].0.into_group()
+                    - d.common.poly_commits[poly_index].0)
+                    .into_affine()
+            );
+        }
+
+        // extra polynomial in the longer payload
+        let shorter = payload[..2 * poly_byte_len].to_vec();
+        let mut longer = shorter.clone();
+        longer.resize(3 * poly_byte_len, 0xAB);
+        let delta = advz.commitment_delta(&longer, &shorter).unwrap();
+        assert_eq!(delta.len(), 1);
+        let fresh = advz.disperse(&longer).unwrap();
+        assert_eq!(
+            delta[0],
+            (
+                fresh.common.poly_commits.len() - 1,
+                *fresh.common.poly_commits.last().unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn enumerate_elems() {
+        type A = Advz<Bls12_381, Sha256>;
+        let (_, payload) = avdz_init();
+        let capacity = elem_byte_capacity::<KzgEval<Bls12_381>>();
+
+        for len in [0, 1, capacity - 1, capacity, capacity + 1, payload.len()] {
+            let payload = &payload[..len];
+            let elems: Vec<_> = A::enumerate_elems(payload).collect();
+            assert_eq!(elems.len(), (len + capacity - 1) / capacity);
+            for (i, (index, elem)) in elems.iter().enumerate() {
+                assert_eq!(*index, i);
+                let start = i * capacity;
+                let end = usize::min(start + capacity, len);
+                assert_eq!(
+                    *elem,
+                    KzgEval::<Bls12_381>::from_le_bytes_mod_order(&payload[start..end])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn share_byte_size() {
+        let (advz, payload) = avdz_init();
+        let poly_byte_len = advz.poly_byte_len();
+        for len in [0, 1, poly_byte_len, poly_byte_len + 1, payload.len()] {
+            let d = advz.disperse(&payload[..len]).unwrap();
+            let share_byte_size = advz.share_byte_size(len).unwrap();
+            let mut bytes = Vec::new();
+            d.shares[0].serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(share_byte_size, bytes.len());
+            for share in d.shares.iter() {
+                assert!(share.compressed_size() <= share_byte_size);
+            }
+        }
+    }
+
+    #[test]
+    fn min_polynomials() {
+        use crate::vid::payload_prover::{PayloadProver, Statement};
+        use payload_prover::SmallRangeProof;
+        let (mut advz, payload) = avdz_init();
+        let min_polynomials = 5;
+        advz.set_min_polynomials(min_polynomials);
+
+        for len in [0, 1, 3 * advz.poly_byte_len() - 1, payload.len()] {
+            let payload = &payload[..len];
+            let d = advz.disperse(payload).unwrap();
+            let num_polys = (len + advz.poly_byte_len() - 1) / advz.poly_byte_len();
+            assert_eq!(
+                d.common.poly_commits.len(),
+                usize::max(num_polys, min_polynomials)
+            );
+            assert_eq!(d.common.bytes_len, len);
+            assert_eq!(advz.commit_only(payload).unwrap(), d.commit);
+            for share in d.shares.iter() {
+                advz.verify_share(share, &d.common, &d.commit)
+                    .unwrap()
+                    .unwrap();
+            }
+            assert_eq!(
+                d.shares[0].compressed_size(),
+                advz.share_byte_size(len).unwrap()
+            );
+            let shares = &d.shares[..advz.payload_chunk_size];
+            assert_eq!(advz.recover_payload(shares, &d.common).unwrap(), payload);
+
+            if len > 0 {
+                let range = len - 1..len;
+                let proof: SmallRangeProof<_> = advz.payload_proof(payload, range.clone()).unwrap();
+                let stmt = Statement {
+                    payload_subslice: &payload[range.clone()],
+                    range,
+                    commit: &d.commit,
+                    common: &d.common,
+                };
+                advz.payload_verify(stmt.clone(), &proof).unwrap().unwrap();
+                assert_arg_err(
+                    advz.payload_verify(
+                        Statement {
+                            range: len..len + 1,
+                            ..stmt
+                        },
+                        &proof,
+                    ),
+                    "range in padding should be arg error",
+                );
+            }
+        }
+
+        // tiny payload has exactly `min_polynomials` polynomials and a
+        // commitment distinct from that without padding
+        let d = advz.disperse([1u8]).unwrap();
+        assert_eq!(d.common.poly_commits.len(), min_polynomials);
+        advz.set_min_polynomials(0);
+        let d_unpadded = advz.disperse([1u8]).unwrap();
+        assert_eq!(d_unpadded.common.poly_commits.len(), 1);
+        assert_ne!(d_unpadded.commit, d.commit);
+    }
+
+    #[test]
+    fn disperse_ext() {
+        let (mut advz, payload) = avdz_init();
+
+        // same layout as packing into the scalar field
+        for len in [0, 1, 62, 63, payload.len() - 20, payload.len()] {
+            let payload = &payload[..len];
+            let d = advz.disperse_ext::<Fr2, _>(payload).unwrap();
+            assert_eq!(d, advz.disperse(payload).unwrap());
+            for share in d.shares.iter() {
+                advz.verify_share(share, &d.common, &d.commit)
+                    .unwrap()
+                    .unwrap();
+            }
+            let shares = &d.shares[..advz.payload_chunk_size];
+            assert_eq!(advz.recover_payload(shares, &d.common).unwrap(), payload);
+        }
+
+        // an element of `Fr2` must not span polynomials
+        let mut rng = jf_utils::test_rng();
+        let advz_tiny = Advz::<Bls12_381, Sha256>::new(1, 6, init_srs(1, &mut rng)).unwrap();
+        assert_arg_err(
+            advz_tiny.disperse_ext::<Fr2, _>(&payload),
+            "chunk size not a multiple of extension degree should be arg error",
+        );
+        advz.set_field_packing(FieldPacking::Bits).unwrap();
+        assert_arg_err(
+            advz.disperse_ext::<Fr2, _>(&payload),
+            "extension packing under bit packing should be arg error",
+        );
+    }
+
+    #[test]
+    fn field_packing() {
+        let (mut advz, payload) = avdz_init();
+        let d_bytes = advz.disperse(&payload).unwrap();
+
+        // polynomials must hold a whole number of bytes
+        let mut rng = jf_utils::test_rng();
+        let mut advz_odd = Advz::<Bls12_381, Sha256>::new(2, 6, init_srs(2, &mut rng)).unwrap();
+        assert_arg_err(
+            advz_odd.set_field_packing(FieldPacking::Bits),
+            "polynomial of a fractional number of bytes should be arg error",
+        );
+        advz.set_field_packing(FieldPacking::Bits).unwrap();
+        assert!(
+            advz.poly_byte_len()
+                > elem_byte_capacity::<KzgEval<Bls12_381>>() * advz.payload_chunk_size
+        );
+
+        for len in [0, 1, 31, 32, advz.poly_byte_len() + 1, payload.len()] {
+            let payload = &payload[..len];
+            let d = advz.disperse(payload).unwrap();
+            let num_polys = (len + advz.poly_byte_len() - 1) / advz.poly_byte_len();
+            assert_eq!(d.common.poly_commits.len(), num_polys);
+            assert_eq!(advz.commit_only(payload).unwrap(), d.commit);
+            for share in d.shares.iter() {
+                advz.verify_share(share, &d.common, &d.commit)
+                    .unwrap()
+                    .unwrap();
+            }
+            let shares = &d.shares[..advz.payload_chunk_size];
+            assert_eq!(advz.recover_payload(shares, &d.common).unwrap(), payload);
+        }
+
+        // fewer polynomials than byte packing, and a distinct commitment
+        let d_bits = advz.disperse(&payload).unwrap();
+        assert!(d_bits.common.poly_commits.len() < d_bytes.common.poly_commits.len());
+        assert_ne!(d_bits.commit, d_bytes.commit);
 
-        // partition payload into polynomial coefficients
-        // and count `elems_len` for later
-        let bytes_to_polys_time = start_timer!(|| "encode payload bytes into polynomials");
-        let elems_iter = bytes_to_field::<_, KzgEval<E>>(payload);
-        let polys: Vec<_> = elems_iter
-            .chunks(self.payload_chunk_size)
-            .into_iter()
-            .map(|evals_iter| self.polynomial(evals_iter))
-            .collect();
-        end_timer!(bytes_to_polys_time);
+        // byte-only APIs
+        assert_arg_err(
+            advz.prove_common_prefix(&payload, &payload, 1),
+            "common prefix proof under bit packing should be arg error",
+        );
+    }
 
-        // evaluate polynomials
-        let all_storage_node_evals_timer = start_timer!(|| format!(
-            "compute all storage node evals for {} polynomials of degree {}",
-            polys.len(),
-            self.payload_chunk_size
-        ));
-        let all_storage_node_evals = {
-            let mut all_storage_node_evals =
-                vec![Vec::with_capacity(polys.len()); self.num_storage_nodes];
-
-            for poly in polys.iter() {
-                let poly_evals = UnivariateKzgPCS::<E>::multi_open_rou_evals(
-                    poly,
-                    self.num_storage_nodes,
-                    &self.multi_open_domain,
-                )
-                .map_err(vid)?;
+    #[test]
+    fn fingerprint() {
+        let (payload_chunk_size, num_storage_nodes) = (4, 6);
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(2 * payload_chunk_size, &mut rng);
+        let fingerprint = |advz: Advz<Bls12_381, Sha256>| advz.fingerprint().unwrap();
 
-                for (storage_node_evals, poly_eval) in
-                    all_storage_node_evals.iter_mut().zip(poly_evals)
-                {
-                    storage_node_evals.push(poly_eval);
-                }
-            }
+        let base = fingerprint(Advz::new(payload_chunk_size, num_storage_nodes, &srs).unwrap());
+        assert_eq!(
+            fingerprint(Advz::new(payload_chunk_size, num_storage_nodes, &srs).unwrap()),
+            base
+        );
 
-            // sanity checks
-            assert_eq!(all_storage_node_evals.len(), self.num_storage_nodes);
-            for storage_node_evals in all_storage_node_evals.iter() {
-                assert_eq!(storage_node_evals.len(), polys.len());
+        // each parameter change yields a distinct fingerprint
+        let other_srs = init_srs(2 * payload_chunk_size, &mut rng);
+        let others = [
+            fingerprint(Advz::new(2 * payload_chunk_size, 2 * num_storage_nodes, &srs).unwrap()),
+            fingerprint(Advz::new(payload_chunk_size, num_storage_nodes + 1, &srs).unwrap()),
+            fingerprint(Advz::new(payload_chunk_size, 2 * num_storage_nodes, &srs).unwrap()),
+            fingerprint(
+                Advz::new_coset(
+                    payload_chunk_size,
+                    num_storage_nodes,
+                    <Bls12_381 as Pairing>::ScalarField::GENERATOR,
+                    &srs,
+                )
+                .unwrap(),
+            ),
+            fingerprint(Advz::new(payload_chunk_size, num_storage_nodes, &other_srs).unwrap()),
+            {
+                let mut advz = Advz::new(payload_chunk_size, num_storage_nodes, &srs).unwrap();
+                advz.set_min_polynomials(3);
+                fingerprint(advz)
+            },
+            Advz::<Bls12_381, sha3::Keccak256>::new(payload_chunk_size, num_storage_nodes, &srs)
+                .unwrap()
+                .fingerprint()
+                .unwrap(),
+            {
+                use ark_bn254::Bn254;
+                let srs = init_srs::<Bn254, _>(payload_chunk_size, &mut rng);
+                Advz::<Bn254, Sha256>::new(payload_chunk_size, num_storage_nodes, &srs)
+                    .unwrap()
+                    .fingerprint()
+                    .unwrap()
+            },
+        ];
+        for (i, other) in others.iter().enumerate() {
+            assert_ne!(*other, base, "parameter change {}", i);
+            for other2 in others[i + 1..].iter() {
+                assert_ne!(other, other2);
             }
+        }
+    }
 
-            all_storage_node_evals
-        };
-        end_timer!(all_storage_node_evals_timer);
+    #[test]
+    fn commit_onchain_bytes() {
+        type A = Advz<Bls12_381, Sha256>;
+        let (advz, payload) = avdz_init();
 
-        // vector commitment to polynomial evaluations
-        // TODO why do I need to compute the height of the merkle tree?
-        let all_evals_commit_timer =
-            start_timer!(|| "compute merkle root of all storage node evals");
-        let height: usize = all_storage_node_evals
-            .len()
-            .checked_ilog(KzgEvalsMerkleTree::<E, H>::ARITY)
-            .ok_or_else(|| {
-                VidError::Argument(format!(
-                    "num_storage_nodes {} log base {} invalid",
-                    all_storage_node_evals.len(),
-                    KzgEvalsMerkleTree::<E, H>::ARITY
-                ))
-            })?
-            .try_into()
-            .expect("num_storage_nodes log base arity should fit into usize");
-        let height = height + 1; // avoid fully qualified syntax for try_into()
-        let all_evals_commit =
-            KzgEvalsMerkleTree::<E, H>::from_elems(height, &all_storage_node_evals).map_err(vid)?;
-        end_timer!(all_evals_commit_timer);
+        for len in [0, 1, payload.len()] {
+            let commit = advz.commit_only(&payload[..len]).unwrap();
+            let bytes = A::commit_to_onchain_bytes(&commit).unwrap();
+            assert_eq!(bytes.len(), 32);
+            assert_eq!(bytes.as_slice(), commit.as_slice());
+            assert_eq!(A::commit_from_onchain_bytes(&bytes).unwrap(), commit);
+        }
 
-        let common_timer = start_timer!(|| format!("compute {} KZG commitments", polys.len()));
-        let common = Common {
-            poly_commits: polys
-                .iter()
-                .map(|poly| UnivariateKzgPCS::commit(&self.ck, poly))
-                .collect::<Result<_, _>>()
-                .map_err(vid)?,
-            all_evals_digest: all_evals_commit.commitment().digest(),
-            bytes_len: payload_len,
-        };
-        end_timer!(common_timer);
+        // golden vectors, one per commit version: changes here break on-chain
+        // decoding
+        for (commit_version, golden) in [
+            (
+                CommitVersion::V0,
+                [
+                    0x48, 0x1f, 0xef, 0x3e, 0xd3, 0x72, 0x5a, 0xc6, 0xfd, 0xdb, 0xce, 0x0d, 0xd2,
+                    0x3e, 0xd2, 0x2f, 0xf2, 0x6b, 0x1f, 0xf9, 0x70, 0xcf, 0xdc, 0xc7, 0x84, 0x60,
+                    0xdb, 0x12, 0x5a, 0x4c, 0xb9, 0x30,
+                ],
+            ),
+            (
+                CommitVersion::V1,
+                [
+                    0x1c, 0x10, 0xbb, 0xd8, 0x13, 0x1b, 0x90, 0x73, 0x50, 0x0b, 0x07, 0x52, 0x8e,
+                    0xfa, 0xed, 0xaf, 0xb5, 0xb3, 0xf2, 0xee, 0x30, 0x7f, 0xa1, 0x09, 0xde, 0x17,
+                    0xbe, 0xd8, 0xba, 0x04, 0xa8, 0x85,
+                ],
+            ),
+            (
+                CommitVersion::V2,
+                [
+                    0x74, 0xc8, 0xef, 0xa2, 0x39, 0x85, 0x32, 0x64, 0x57, 0x85, 0x5f, 0xc0, 0x6d,
+                    0xb4, 0x46, 0x86, 0xed, 0xfa, 0x84, 0x20, 0xdb, 0x2d, 0x33, 0x42, 0xf6, 0x42,
+                    0xa0, 0x82, 0xd9, 0xfa, 0x81, 0xa4,
+                ],
+            ),
+            (
+                CommitVersion::V3,
+                [
+                    0xe4, 0x75, 0x39, 0xd5, 0xda, 0xf3, 0xe2, 0xdf, 0xfa, 0x70, 0x76, 0x52, 0x02,
+                    0x8e, 0xcf, 0xf2, 0x21, 0x63, 0xc8, 0x70, 0x92, 0xc0, 0x99, 0x05, 0xa4, 0xa8,
+                    0x08, 0xaa, 0xcb, 0x5a, 0x5b, 0x0f,
+                ],
+            ),
+        ] {
+            let (advz, _) = avdz_init_with_commit_version(commit_version);
+            let commit = advz.commit_only(b"jellyfish").unwrap();
+            assert_eq!(A::commit_to_onchain_bytes(&commit).unwrap(), golden);
+        }
 
-        let commit = Self::poly_commits_hash(common.poly_commits.iter())?;
-        let pseudorandom_scalar = Self::pseudorandom_scalar(&common, &commit)?;
+        // digest of the wrong length
+        type A512 = Advz<Bls12_381, sha2::Sha512>;
+        assert_arg_err(
+            A512::commit_to_onchain_bytes(&Output::<sha2::Sha512>::default()),
+            "64-byte digest should be arg error",
+        );
+        assert_arg_err(
+            A512::commit_from_onchain_bytes(&[0; 32]),
+            "64-byte digest should be arg error",
+        );
+    }
 
-        // Compute aggregate polynomial as a pseudorandom linear combo of polynomial via
-        // evaluation of the polynomial whose coefficients are polynomials and whose
-        // input point is the pseudorandom scalar.
-        let aggregate_poly =
-            polynomial_eval(polys.iter().map(PolynomialMultiplier), pseudorandom_scalar);
+    #[test]
+    fn required_srs_degree() {
+        let mut rng = jf_utils::test_rng();
+        for payload_chunk_size in [1, 2, 4, 8, 16] {
+            let num_storage_nodes = 2 * payload_chunk_size;
+            let degree =
+                Advz::<Bls12_381, Sha256>::required_srs_degree(payload_chunk_size).unwrap();
 
-        let agg_proofs_timer = start_timer!(|| format!(
-            "compute aggregate proofs for {} storage nodes",
-            self.num_storage_nodes
-        ));
-        let aggregate_proofs = UnivariateKzgPCS::multi_open_rou_proofs(
-            &self.ck,
-            &aggregate_poly,
-            self.num_storage_nodes,
-            &self.multi_open_domain,
-        )
-        .map_err(vid)?;
-        end_timer!(agg_proofs_timer);
+            let srs = UnivariateKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, degree).unwrap();
+            Advz::<Bls12_381, Sha256>::new(payload_chunk_size, num_storage_nodes, srs).unwrap();
 
-        let assemblage_timer = start_timer!(|| "assemble shares for dispersal");
-        let shares = all_storage_node_evals
-            .into_iter()
-            .zip(aggregate_proofs)
-            .enumerate()
-            .map(|(index, (evals, aggregate_proof))| {
-                Ok(Share {
-                    index,
-                    evals,
-                    aggregate_proof,
-                    evals_proof: all_evals_commit
-                        .lookup(KzgEvalsMerkleTreeIndex::<E, H>::from(index as u64))
-                        .expect_ok()
-                        .map_err(vid)?
-                        .1,
-                })
-            })
-            .collect::<Result<_, VidError>>()?;
-        end_timer!(assemblage_timer);
+            if degree > 0 {
+                let srs = UnivariateKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, degree - 1)
+                    .unwrap();
+                assert!(
+                    Advz::<Bls12_381, Sha256>::new(payload_chunk_size, num_storage_nodes, srs)
+                        .is_err(),
+                    "srs of degree {} should be too small for payload_chunk_size {}",
+                    degree - 1,
+                    payload_chunk_size
+                );
+            }
+        }
+        assert_arg_err(
+            Advz::<Bls12_381, Sha256>::required_srs_degree(0),
+            "zero payload_chunk_size should be arg error",
+        );
+    }
 
-        end_timer!(disperse_time);
-        Ok(VidDisperse {
-            shares,
-            common,
-            commit,
-        })
+    #[test]
+    fn num_storage_nodes_exceeds_domain() {
+        let payload_chunk_size = 4;
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(payload_chunk_size, &mut rng);
+        let max_domain_size = 1usize << <Bls12_381 as Pairing>::ScalarField::TWO_ADICITY;
+        assert_arg_err(
+            Advz::<Bls12_381, Sha256>::new(payload_chunk_size, max_domain_size + 1, &srs),
+            "num_storage_nodes exceeding domain size should be arg error",
+        );
     }
 
-    fn verify_share(
-        &self,
-        share: &Self::Share,
-        common: &Self::Common,
-        commit: &Self::Commit,
-    ) -> VidResult<Result<(), ()>> {
-        // check arguments
-        if share.evals.len() != common.poly_commits.len() {
-            return Err(VidError::Argument(format!(
-                "(share eval, common poly commit) lengths differ ({},{})",
-                share.evals.len(),
-                common.poly_commits.len()
-            )));
-        }
-        if share.index >= self.num_storage_nodes {
-            return Ok(Err(())); // not an arg error
-        }
+    #[test]
+    fn pcs_error_source() {
+        use ark_std::error::Error;
 
-        // check `common` against `commit`
-        let commit_rebuilt = Self::poly_commits_hash(common.poly_commits.iter())?;
-        if commit_rebuilt != *commit {
-            return Err(VidError::Argument(
-                "commit inconsistent with common".to_string(),
-            ));
-        }
+        // srs too small for `payload_chunk_size`
+        let mut rng = jf_utils::test_rng();
+        let srs = UnivariateKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, 2).unwrap();
+        let err = Advz::<Bls12_381, Sha256>::new(8, 16, srs).unwrap_err();
+        let pcs_err = match &err {
+            VidError::Pcs(e) => e,
+            _ => panic!("srs too small should be pcs error, got {:?}", err),
+        };
+        assert!(matches!(pcs_err, PCSError::InvalidParameters(_)));
+        let source = err.source().expect("pcs error should have a source");
+        assert_eq!(source.to_string(), pcs_err.to_string());
+        assert!(err.to_string().contains(&pcs_err.to_string()));
 
-        // verify eval proof
-        if KzgEvalsMerkleTree::<E, H>::verify(
-            common.all_evals_digest,
-            &KzgEvalsMerkleTreeIndex::<E, H>::from(share.index as u64),
-            &share.evals_proof,
-        )
-        .map_err(vid)?
-        .is_err()
-        {
-            return Ok(Err(()));
+        // other variants have no source
+        assert!(VidError::Argument("oops".into()).source().is_none());
+    }
+
+    #[test]
+    fn disperse_bytes_round_trip() {
+        use ark_bn254::Bn254;
+
+        let (advz, payload) = avdz_init();
+        let disperse = advz.disperse(&payload).unwrap();
+
+        let bytes = disperse.to_bytes().unwrap();
+        assert_eq!(
+            bytes[0],
+            VidDisperse::<Advz<Bls12_381, Sha256>>::BYTES_VERSION
+        );
+        let disperse_rebuilt = VidDisperse::<Advz<Bls12_381, Sha256>>::from_bytes(&bytes).unwrap();
+        assert_eq!(disperse_rebuilt, disperse);
+
+        // truncated blobs
+        for len in [0, 1, 5, bytes.len() / 2, bytes.len() - 1] {
+            assert_arg_err(
+                VidDisperse::<Advz<Bls12_381, Sha256>>::from_bytes(&bytes[..len]),
+                "truncated blob should be arg error",
+            );
         }
 
-        let pseudorandom_scalar = Self::pseudorandom_scalar(common, commit)?;
+        // trailing bytes
+        let mut bytes_trailing = bytes.clone();
+        bytes_trailing.push(0);
+        assert_arg_err(
+            VidDisperse::<Advz<Bls12_381, Sha256>>::from_bytes(&bytes_trailing),
+            "trailing bytes should be arg error",
+        );
 
-        // Compute aggregate polynomial [commitment|evaluation]
-        // as a pseudorandom linear combo of [commitments|evaluations]
-        // via evaluation of the polynomial whose coefficients are
-        // [commitments|evaluations] and whose input point is the pseudorandom
-        // scalar.
-        let aggregate_poly_commit = KzgCommit::<E>::from(
-            polynomial_eval(
-                common
-                    .poly_commits
-                    .iter()
-                    .map(|x| CurveMultiplier(x.as_ref())),
-                pseudorandom_scalar,
-            )
-            .into(),
+        // unsupported version
+        let mut bytes_version = bytes.clone();
+        bytes_version[0] += 1;
+        assert_arg_err(
+            VidDisperse::<Advz<Bls12_381, Sha256>>::from_bytes(&bytes_version),
+            "unsupported version should be arg error",
         );
-        let aggregate_eval =
-            polynomial_eval(share.evals.iter().map(FieldMultiplier), pseudorandom_scalar);
 
-        // verify aggregate proof
-        Ok(UnivariateKzgPCS::verify(
-            &self.vk,
-            &aggregate_poly_commit,
-            &self.multi_open_domain.element(share.index),
-            &aggregate_eval,
-            &share.aggregate_proof,
-        )
-        .map_err(vid)?
-        .then_some(())
-        .ok_or(()))
+        // different curve
+        assert_ne!(
+            Advz::<Bls12_381, Sha256>::magic(),
+            Advz::<Bn254, Sha256>::magic()
+        );
+        match VidDisperse::<Advz<Bn254, Sha256>>::from_bytes(&bytes) {
+            Err(Argument(msg)) => assert!(msg.contains("magic"), "{}", msg),
+            _ => panic!("cross-curve blob should be arg error"),
+        }
     }
 
-    fn recover_payload(&self, shares: &[Self::Share], common: &Self::Common) -> VidResult<Vec<u8>> {
-        if shares.len() < self.payload_chunk_size {
-            return Err(VidError::Argument(format!(
-                "not enough shares {}, expected at least {}",
-                shares.len(),
-                self.payload_chunk_size
-            )));
+    #[test]
+    fn disperse_bytes_deterministic() {
+        let (advz, payload) = avdz_init();
+        let disperse = advz.disperse(&payload).unwrap();
+        for (i, share) in disperse.shares.iter().enumerate() {
+            assert_eq!(share.index, i);
         }
 
-        // all shares must have equal evals len
-        let num_polys = shares
-            .first()
-            .ok_or_else(|| VidError::Argument("shares is empty".into()))?
-            .evals
-            .len();
-        if let Some((index, share)) = shares
-            .iter()
-            .enumerate()
-            .find(|(_, s)| s.evals.len() != num_polys)
-        {
-            return Err(VidError::Argument(format!(
-                "shares do not have equal evals lengths: share {} len {}, share {} len {}",
-                0,
-                num_polys,
-                index,
-                share.evals.len()
-            )));
+        // independent instances and dispersal paths produce identical bytes
+        let (advz2, _) = avdz_init();
+        let split = 2 * advz.poly_byte_len();
+        let partials = [
+            advz2.disperse_partial(&payload[..split], 0).unwrap(),
+            advz2.disperse_partial(&payload[split..], split).unwrap(),
+        ];
+        let bytes = disperse.to_bytes().unwrap();
+        let serde_bytes = bincode::serialize(&disperse).unwrap();
+        for other in [
+            advz.disperse(&payload).unwrap(),
+            advz2.disperse(&payload).unwrap(),
+            advz2.merge_commitments(&partials).unwrap(),
+        ] {
+            assert_eq!(other.to_bytes().unwrap(), bytes);
+            assert_eq!(bincode::serialize(&other).unwrap(), serde_bytes);
         }
+    }
 
-        let elems_capacity = num_polys * self.payload_chunk_size;
-        let mut elems = Vec::with_capacity(elems_capacity);
-        for i in 0..num_polys {
-            let mut coeffs = reed_solomon_erasure_decode_rou(
-                shares.iter().map(|s| (s.index, s.evals[i])),
-                self.payload_chunk_size,
-                &self.multi_open_domain,
-            )
-            .map_err(vid)?;
+    #[test]
+    fn degree_bound_proof() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let num_polys = d.common.poly_commits.len();
 
-            // TODO TEMPORARY: use FFT to encode polynomials in eval form
-            // Remove these FFTs after we get KZG in eval form
-            // https://github.com/EspressoSystems/jellyfish/issues/339
-            self.eval_domain.fft_in_place(&mut coeffs);
+        for poly_index in [0, num_polys - 1] {
+            let proof = advz.degree_bound_proof(&payload, poly_index).unwrap();
+            advz.verify_degree_bound(&d.common, &d.commit, poly_index, &proof)
+                .unwrap()
+                .unwrap();
 
-            elems.append(&mut coeffs);
+            // wrong poly commit
+            advz.verify_degree_bound(&d.common, &d.commit, (poly_index + 1) % num_polys, &proof)
+                .unwrap()
+                .unwrap_err();
         }
-        assert_eq!(elems.len(), elems_capacity);
 
-        let mut payload: Vec<_> = field_to_bytes(elems).collect();
-        payload.truncate(common.bytes_len);
-        Ok(payload)
+        // polynomial of degree `payload_chunk_size`, one too many. Its commitment
+        // is within reach of the SRS but the top coefficient of each shifted
+        // polynomial is not, so a cheating prover must drop it.
+        let mut rng = jf_utils::test_rng();
+        let poly = KzgPolynomial::<Bls12_381>::rand(advz.payload_chunk_size, &mut rng);
+        let poly_commit = UnivariateKzgPCS::commit(&advz.ck, &poly).unwrap();
+        let max_len = advz.ck.powers_of_g.len();
+        let shifted_commits = (1..=advz.degree_bound_shift())
+            .map(|shift| {
+                let mut coeffs = vec![KzgEval::<Bls12_381>::zero(); shift];
+                coeffs.extend_from_slice(&poly.coeffs);
+                coeffs.truncate(max_len);
+                UnivariateKzgPCS::commit(
+                    &advz.ck,
+                    &DenseUVPolynomial::from_coefficients_vec(coeffs),
+                )
+                .unwrap()
+            })
+            .collect();
+        let forged = DegreeBoundProof { shifted_commits };
+        advz.verify_degree_bound_for_commit(&poly_commit, &forged)
+            .unwrap()
+            .unwrap_err();
+
+        // bad args
+        let proof = advz.degree_bound_proof(&payload, 0).unwrap();
+        assert_arg_err(
+            advz.degree_bound_proof(&payload, num_polys),
+            "poly index out of bounds should be arg error",
+        );
+        assert_arg_err(
+            advz.verify_degree_bound(&d.common, &d.commit, num_polys, &proof),
+            "poly index out of bounds should be arg error",
+        );
+        let mut short = proof.clone();
+        short.shifted_commits.pop();
+        assert_arg_err(
+            advz.verify_degree_bound(&d.common, &d.commit, 0, &short),
+            "short proof should be arg error",
+        );
     }
-}
 
-impl<E, H> Advz<E, H>
-where
-    E: Pairing,
-    H: HasherDigest,
-{
-    fn pseudorandom_scalar(
-        common: &<Self as VidScheme>::Common,
-        commit: &<Self as VidScheme>::Commit,
-    ) -> VidResult<KzgEval<E>> {
-        let mut hasher = H::new();
-        commit.serialize_uncompressed(&mut hasher).map_err(vid)?;
-        common
-            .all_evals_digest
-            .serialize_uncompressed(&mut hasher)
-            .map_err(vid)?;
+    #[test]
+    fn recover_payload_parallel() {
+        let (mut advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        assert!(d.common.poly_commits.len() > 1);
+        let shares = &d.shares[..advz.payload_chunk_size];
+        let share_columns = Advz::share_columns(shares).unwrap();
 
-        // Notes on hash-to-field:
-        // - Can't use `Field::from_random_bytes` because it's fallible. (In what sense
-        //   is it from "random" bytes?!). This despite the docs explicitly say: "This
-        //   function is primarily intended for sampling random field elements from a
-        //   hash-function or RNG output."
-        // - We could use `ark_ff::fields::field_hashers::HashToField` but that forces
-        //   us to add additional trait bounds `Clone + Default + DynDigest` everywhere.
-        //   Also, `HashToField` does not expose an incremental API (ie. `update`) so we
-        //   would need to use an ordinary hasher and pipe `hasher.finalize()` through
-        //   `hash_to_field`. (Ugh!)
-        // - We don't need the resulting field element to be cryptographically
-        //   indistinguishable from uniformly random. We only need it to be
-        //   unpredictable. So it suffices to use
-        Ok(PrimeField::from_le_bytes_mod_order(&hasher.finalize()))
+        advz.set_parallel_threshold(usize::MAX);
+        let serial = advz.recover_payload(shares, &d.common).unwrap();
+        let serial_columns = advz
+            .recover_payload_columns(&share_columns, &d.common)
+            .unwrap();
+        advz.set_parallel_threshold(1);
+        let parallel = advz.recover_payload(shares, &d.common).unwrap();
+        let parallel_columns = advz
+            .recover_payload_columns(&share_columns, &d.common)
+            .unwrap();
+
+        assert_eq!(serial, payload);
+        assert_eq!(parallel, serial);
+        assert_eq!(serial_columns, serial);
+        assert_eq!(parallel_columns, serial);
     }
 
-    fn polynomial<I>(&self, coeffs: I) -> KzgPolynomial<E>
-    where
-        I: Iterator,
-        I::Item: Borrow<KzgEval<E>>,
-    {
-        // TODO TEMPORARY: use FFT to encode polynomials in eval form
-        // Remove these FFTs after we get KZG in eval form
-        // https://github.com/EspressoSystems/jellyfish/issues/339
-        let mut coeffs_vec: Vec<_> = coeffs.map(|c| *c.borrow()).collect();
-        let pre_fft_len = coeffs_vec.len();
-        self.eval_domain.ifft_in_place(&mut coeffs_vec);
+    #[test]
+    fn disperse_columns() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let (d_columns, mut share_columns) = advz.disperse_columns(&payload).unwrap();
+        assert_eq!(d_columns, d);
+        assert_eq!(share_columns, Advz::share_columns(&d.shares).unwrap());
+        assert_eq!(share_columns.num_shares(), advz.num_storage_nodes);
 
-        // sanity check: the fft did not resize coeffs.
-        // If pre_fft_len != self.payload_chunk_size then we were not given the correct
-        // number of coeffs. In that case coeffs.len() could be anything, so
-        // there's nothing to sanity check.
-        if pre_fft_len == self.payload_chunk_size {
-            assert_eq!(coeffs_vec.len(), pre_fft_len);
+        // both layouts recover identical payloads
+        let recovered = advz
+            .recover_payload_columns(&share_columns, &d.common)
+            .unwrap();
+        assert_eq!(recovered, payload);
+        share_columns.truncate(advz.payload_chunk_size);
+        assert_eq!(
+            share_columns,
+            Advz::share_columns(&d.shares[..advz.payload_chunk_size]).unwrap()
+        );
+        assert_eq!(
+            advz.recover_payload_columns(&share_columns, &d.common)
+                .unwrap(),
+            advz.recover_payload(&d.shares[..advz.payload_chunk_size], &d.common)
+                .unwrap()
+        );
+        share_columns.truncate(advz.payload_chunk_size - 1);
+        assert_arg_err(
+            advz.recover_payload_columns(&share_columns, &d.common),
+            "not enough shares should be arg error",
+        );
+    }
+
+    #[test]
+    fn recover_payload_columns() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+
+        for shares in [
+            &d.shares[..],
+            &d.shares[..advz.payload_chunk_size],
+            &d.shares[d.shares.len() - advz.payload_chunk_size..],
+        ] {
+            let share_columns = Advz::share_columns(shares).unwrap();
+            assert_eq!(share_columns.columns.len(), d.common.poly_commits.len());
+            let recovered = advz
+                .recover_payload_columns(&share_columns, &d.common)
+                .unwrap();
+            assert_eq!(recovered, advz.recover_payload(shares, &d.common).unwrap());
+            assert_eq!(recovered, payload);
         }
 
-        DenseUVPolynomial::from_coefficients_vec(coeffs_vec)
+        // bad args
+        let share_columns = Advz::share_columns(&d.shares[..advz.payload_chunk_size - 1]).unwrap();
+        assert_arg_err(
+            advz.recover_payload_columns(&share_columns, &d.common),
+            "not enough shares should be arg error",
+        );
+        let mut share_columns = Advz::share_columns(&d.shares).unwrap();
+        share_columns.columns[1].pop();
+        assert_arg_err(
+            advz.recover_payload_columns(&share_columns, &d.common),
+            "short column should be arg error",
+        );
+        assert_arg_err(
+            Advz::<Bls12_381, Sha256>::share_columns(&[]),
+            "empty shares should be arg error",
+        );
     }
 
-    fn poly_commits_hash<I>(poly_commits: I) -> VidResult<<Self as VidScheme>::Commit>
-    where
-        I: Iterator,
-        I::Item: Borrow<KzgCommit<E>>,
-    {
-        let mut hasher = H::new();
-        for poly_commit in poly_commits {
-            poly_commit
-                .borrow()
-                .serialize_uncompressed(&mut hasher)
-                .map_err(vid)?;
+    #[test]
+    fn poly_commit_inclusion_proof() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let num_polys = d.common.poly_commits.len();
+        assert!(num_polys > 1);
+        let root = Advz::poly_commits_merkle_root(&d.common).unwrap();
+
+        for (i, poly_commit) in d.common.poly_commits.iter().enumerate() {
+            let proof = Advz::poly_commit_inclusion_proof(&d.common, i).unwrap();
+            Advz::verify_poly_commit_inclusion(&root, i, poly_commit, &proof)
+                .unwrap()
+                .unwrap();
+
+            // wrong poly commit
+            let other = &d.common.poly_commits[(i + 1) % num_polys];
+            Advz::verify_poly_commit_inclusion(&root, i, other, &proof)
+                .unwrap()
+                .unwrap_err();
         }
-        Ok(hasher.finalize())
+
+        // tampered proof: claim the leaf of one index at another
+        let proof = Advz::poly_commit_inclusion_proof(&d.common, 0).unwrap();
+        assert!(!matches!(
+            Advz::verify_poly_commit_inclusion(&root, 1, &d.common.poly_commits[0], &proof),
+            Ok(Ok(()))
+        ));
+
+        // wrong root
+        let mut payload_other = payload.clone();
+        payload_other[0] = payload_other[0].wrapping_add(1);
+        let d_other = advz.disperse(&payload_other).unwrap();
+        let root_other = Advz::poly_commits_merkle_root(&d_other.common).unwrap();
+        assert_ne!(root, root_other);
+        Advz::verify_poly_commit_inclusion(&root_other, 0, &d.common.poly_commits[0], &proof)
+            .unwrap()
+            .unwrap_err();
+
+        assert_arg_err(
+            Advz::poly_commit_inclusion_proof(&d.common, num_polys),
+            "poly index out of bounds should be arg error",
+        );
     }
-}
 
-/// Evaluate a generalized polynomial at a given point using Horner's method.
-///
-/// Coefficients can be anything that can be multiplied by a point
-/// and such that the result of such multiplications can be added.
-fn polynomial_eval<U, F, I>(coeffs: I, point: impl Borrow<F>) -> U
-where
-    I: IntoIterator,
-    I::Item: for<'a> Mul<&'a F, Output = U>,
-    U: Add<Output = U> + Zero,
-{
-    coeffs
-        .into_iter()
-        .fold(U::zero(), |res, coeff| coeff * point.borrow() + res)
-}
+    #[test]
+    fn poly_commits_root_commit() {
+        let (advz, payload) = avdz_init_with_commit_version(CommitVersion::V3);
+        let d = advz.disperse(&payload).unwrap();
+        assert!(d.common.poly_commits.len() > 1);
+        let root = advz.commit_poly_commits_root(&d.commit).unwrap();
+        assert_eq!(root, Advz::poly_commits_merkle_root(&d.common).unwrap());
+        assert_eq!(d.commit, advz.commit_only(&payload).unwrap());
 
-struct FieldMultiplier<'a, F>(&'a F);
+        // a single poly commit verifies against the payload commitment
+        for (i, poly_commit) in d.common.poly_commits.iter().enumerate() {
+            let proof = Advz::poly_commit_inclusion_proof(&d.common, i).unwrap();
+            Advz::verify_poly_commit_inclusion(&root, i, poly_commit, &proof)
+                .unwrap()
+                .unwrap();
+        }
 
-/// Arkworks does not provide (&F,&F) multiplication
-impl<F> Mul<&F> for FieldMultiplier<'_, F>
-where
-    F: Field,
-{
-    type Output = F;
+        // but not against the commitment to another payload
+        let mut payload_other = payload.clone();
+        payload_other[0] = payload_other[0].wrapping_add(1);
+        let commit_other = advz.commit_only(&payload_other).unwrap();
+        let root_other = advz.commit_poly_commits_root(&commit_other).unwrap();
+        let proof = Advz::poly_commit_inclusion_proof(&d.common, 0).unwrap();
+        Advz::verify_poly_commit_inclusion(&root_other, 0, &d.common.poly_commits[0], &proof)
+            .unwrap()
+            .unwrap_err();
 
-    fn mul(self, rhs: &F) -> Self::Output {
-        *self.0 * rhs
+        // shares and the empty payload
+        for share in d.shares.iter() {
+            advz.verify_share(share, &d.common, &d.commit)
+                .unwrap()
+                .unwrap();
+        }
+        let d_empty = advz.disperse([]).unwrap();
+        assert_eq!(d_empty.commit, advz.commit_only([]).unwrap());
+
+        assert_arg_err(
+            advz.disperse_with_header(b"header", &payload),
+            "header should be arg error under V3",
+        );
+        assert_arg_err(
+            advz.compact_common(&d.common),
+            "compact common should be arg error under V3",
+        );
+        let advz = avdz_init_with_commit_version(CommitVersion::V1).0;
+        assert_arg_err(
+            advz.commit_poly_commits_root(&d.commit),
+            "commit root should be arg error under V1",
+        );
     }
-}
 
-/// Arkworks does not provide (&C,&F) multiplication
-struct CurveMultiplier<'a, C>(&'a C);
+    #[test]
+    fn subslice_commitment() {
+        let (advz, payload) = avdz_init();
+        let len = 50;
 
-impl<C, F> Mul<&F> for CurveMultiplier<'_, C>
-where
-    C: AffineRepr<ScalarField = F>,
-{
-    type Output = C::Group;
+        // equal subslices at different positions
+        let mut other = vec![0; 200];
+        other[100..100 + len].copy_from_slice(&payload[7..7 + len]);
+        let commit = advz.subslice_commitment(&payload, 7..7 + len).unwrap();
+        assert_eq!(
+            commit,
+            advz.subslice_commitment(&other, 100..100 + len).unwrap()
+        );
 
-    fn mul(self, rhs: &F) -> Self::Output {
-        *self.0 * rhs
+        // different subslices
+        other[100] = other[100].wrapping_add(1);
+        assert_ne!(
+            commit,
+            advz.subslice_commitment(&other, 100..100 + len).unwrap()
+        );
+        assert_ne!(
+            commit,
+            advz.subslice_commitment(&payload, 8..8 + len).unwrap()
+        );
+        assert_ne!(
+            commit,
+            advz.subslice_commitment(&payload, 7..7 + len - 1).unwrap()
+        );
+
+        // bad args
+        assert_arg_err(
+            advz.subslice_commitment(&payload, payload.len() - 1..payload.len() + 1),
+            "out of bounds range should be arg error",
+        );
+        assert_arg_err(
+            advz.subslice_commitment(&payload, 0..payload.len()),
+            "subslice too long should be arg error",
+        );
     }
-}
 
-/// Arkworks does not provide (&P,&F) multiplication
-struct PolynomialMultiplier<'a, P>(&'a P);
+    #[test]
+    fn validate_share_set() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
 
-impl<P, F> Mul<&F> for PolynomialMultiplier<'_, P>
-where
-    P: DenseUVPolynomial<F>,
-    F: Field,
-{
-    type Output = P;
+        advz.validate_share_set(&d.shares).unwrap();
+        advz.validate_share_set(&d.shares[3..]).unwrap();
+        advz.validate_share_set(&[]).unwrap();
 
-    fn mul(self, rhs: &F) -> Self::Output {
-        // `Polynomial` does not impl `Mul` by scalar
-        // so we need to multiply each coeff by `rhs`
-        P::from_coefficients_vec(self.0.coeffs().iter().map(|coeff| *coeff * rhs).collect())
+        // duplicate
+        let mut shares = d.shares[..5].to_vec();
+        shares.push(d.shares[3].clone());
+        match advz.validate_share_set(&shares) {
+            Err(Argument(msg)) => {
+                assert_eq!(msg, "duplicate storage node index 3 in shares 3 and 5")
+            },
+            res => panic!("duplicate index should be arg error, got {:?}", res),
+        }
+
+        // out of bounds
+        let mut shares = d.shares[..5].to_vec();
+        shares[2].index = advz.num_storage_nodes;
+        match advz.validate_share_set(&shares) {
+            Err(Argument(msg)) => assert!(
+                msg.contains(&format!("index {}", advz.num_storage_nodes)),
+                "{}",
+                msg
+            ),
+            res => panic!("out of bounds index should be arg error, got {:?}", res),
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{VidError::Argument, *};
+    #[test]
+    fn self_verify_disperse() {
+        let (mut advz, payload) = avdz_init();
+        let disperse = advz.disperse(&payload).unwrap();
 
-    use crate::{
-        merkle_tree::hasher::HasherNode,
-        pcs::{checked_fft_size, prelude::UnivariateUniversalParams},
-    };
-    use ark_bls12_381::Bls12_381;
-    use ark_std::{
-        rand::{CryptoRng, RngCore},
-        vec,
-    };
-    use sha2::Sha256;
+        for sample_size in [None, Some(0), Some(2), Some(usize::MAX)] {
+            advz.set_self_verify_sample_size(sample_size);
+            advz.self_verify_disperse(&disperse).unwrap();
+        }
 
-    // #[test]
-    #[allow(dead_code)]
-    fn disperse_timer() {
-        // run with 'print-trace' feature to see timer output
-        let (payload_chunk_size, num_storage_nodes) = (256, 512);
-        let mut rng = jf_utils::test_rng();
-        let srs = init_srs(payload_chunk_size, &mut rng);
-        let advz =
-            Advz::<Bls12_381, Sha256>::new(payload_chunk_size, num_storage_nodes, srs).unwrap();
-        let payload_random = init_random_payload(1 << 20, &mut rng);
+        // corrupt share is caught when sampled
+        advz.set_self_verify_sample_size(None);
+        let mut corrupt = disperse.clone();
+        corrupt.shares[3].evals[0].double_in_place();
+        assert_arg_err(
+            advz.self_verify_disperse(&corrupt),
+            "corrupt share should be arg error",
+        );
+        advz.set_self_verify_sample_size(Some(2));
+        assert_arg_err(
+            advz.self_verify_disperse(&corrupt),
+            "sampled corrupt share should be arg error",
+        );
 
-        let _ = advz.disperse(&payload_random);
+        // missing or misordered shares
+        let mut missing = disperse.clone();
+        missing.shares.pop();
+        assert_arg_err(
+            advz.self_verify_disperse(&missing),
+            "missing share should be arg error",
+        );
+        let mut misordered = disperse;
+        misordered.shares.swap(0, 1);
+        assert_arg_err(
+            advz.self_verify_disperse(&misordered),
+            "misordered shares should be arg error",
+        );
     }
 
-    // #[test]
-    #[allow(dead_code)]
-    fn commit_only_timer() {
-        // run with 'print-trace' feature to see timer output
-        let (payload_chunk_size, num_storage_nodes) = (256, 512);
+    #[test]
+    fn verify_all_shares() {
+        let (advz, payload) = avdz_init();
         let mut rng = jf_utils::test_rng();
-        let srs = init_srs(payload_chunk_size, &mut rng);
-        let advz =
-            Advz::<Bls12_381, Sha256>::new(payload_chunk_size, num_storage_nodes, srs).unwrap();
-        let payload_random = init_random_payload(1 << 20, &mut rng);
+        let disperse = advz.disperse(&payload).unwrap();
+        let (mut shares, common, commit) = (disperse.shares, disperse.common, disperse.commit);
 
-        let _ = advz.commit_only(&payload_random);
+        assert_eq!(
+            advz.verify_all_shares(&shares, &common, &commit, &mut rng)
+                .unwrap(),
+            vec![true; shares.len()]
+        );
+
+        // corrupt evals, aggregate proof, index
+        shares[1].evals[0].double_in_place();
+        shares[3].aggregate_proof = shares[0].aggregate_proof.clone();
+        shares[4].index = shares[5].index;
+        let expected: Vec<_> = (0..shares.len()).map(|i| ![1, 3, 4].contains(&i)).collect();
+        assert_eq!(
+            advz.verify_all_shares(&shares, &common, &commit, &mut rng)
+                .unwrap(),
+            expected
+        );
+        for (share, valid) in shares.iter().zip(expected) {
+            assert_eq!(
+                advz.verify_share(share, &common, &commit).unwrap().is_ok(),
+                valid
+            );
+        }
+
+        // corrupt commit
+        assert_arg_err(
+            advz.verify_all_shares(&shares, &common, &Output::<Sha256>::default(), &mut rng),
+            "corrupt commit should be arg error",
+        );
+    }
+
+    #[test]
+    fn verify_share_index() {
+        let (advz, payload) = avdz_init();
+        let disperse = advz.disperse(&payload).unwrap();
+        let (shares, common) = (disperse.shares, disperse.common);
+
+        for (i, share) in shares.iter().enumerate() {
+            assert!(advz.verify_share_index(share, i, &common).unwrap());
+            let wrong_index = (i + 1) % shares.len();
+            assert!(!advz
+                .verify_share_index(share, wrong_index, &common)
+                .unwrap());
+
+            // another node's share relabeled as its own
+            let mut relabeled = share.clone();
+            relabeled.index = wrong_index;
+            assert!(!advz
+                .verify_share_index(&relabeled, wrong_index, &common)
+                .unwrap());
+        }
+        assert!(!advz
+            .verify_share_index(&shares[0], advz.num_storage_nodes, &common)
+            .unwrap());
+
+        let mut short_share = shares[0].clone();
+        short_share.evals.pop();
+        assert_arg_err(
+            advz.verify_share_index(&short_share, 0, &common),
+            "share evals len mismatch should be arg error",
+        );
     }
 
     #[test]
@@ -733,6 +6656,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sad_path_verify_share_evals_proof_vs_aggregate_proof() {
+        let (advz, bytes_random) = avdz_init();
+        let disperse = advz.disperse(&bytes_random).unwrap();
+        let (shares, common, commit) = (disperse.shares, disperse.common, disperse.commit);
+
+        for (i, share) in shares.iter().enumerate() {
+            // happy path: both checks pass
+            advz.verify_share_evals_proof(share, &common)
+                .unwrap()
+                .unwrap();
+            advz.verify_share_aggregate_proof(share, &common, &commit)
+                .unwrap()
+                .unwrap();
+
+            // corrupted share eval: the merkle path no longer authenticates the evals
+            // and the KZG check fails
+            {
+                let mut share_bad_eval = share.clone();
+                share_bad_eval.evals[0].double_in_place();
+                advz.verify_share_evals_proof(&share_bad_eval, &common)
+                    .unwrap()
+                    .expect_err("bad share value should fail merkle path verification");
+                advz.verify_share_aggregate_proof(&share_bad_eval, &common, &commit)
+                    .unwrap()
+                    .expect_err("bad share value should fail KZG verification");
+            }
+
+            // corrupted merkle path: only the merkle check fails
+            {
+                let share_bad_evals_proof = Share {
+                    evals_proof: shares[(i + 1) % shares.len()].evals_proof.clone(),
+                    ..share.clone()
+                };
+                advz.verify_share_evals_proof(&share_bad_evals_proof, &common)
+                    .unwrap()
+                    .expect_err("bad merkle path should fail merkle path verification");
+                advz.verify_share_aggregate_proof(&share_bad_evals_proof, &common, &commit)
+                    .unwrap()
+                    .expect("bad merkle path should not affect KZG verification");
+                advz.verify_share(&share_bad_evals_proof, &common, &commit)
+                    .unwrap()
+                    .expect_err("bad merkle path should fail verification");
+            }
+        }
+    }
+
     #[test]
     fn sad_path_verify_share_corrupt_commit() {
         let (advz, bytes_random) = avdz_init();
@@ -804,6 +6774,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn recover_payload_not_enough_shares() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let threshold = advz.payload_chunk_size;
+
+        assert_eq!(
+            advz.recover_payload(&d.shares[..threshold], &d.common)
+                .unwrap(),
+            payload
+        );
+
+        // threshold minus one shares
+        match advz.recover_payload(&d.shares[..threshold - 1], &d.common) {
+            Err(Argument(msg)) => assert!(msg.contains("need 1 more"), "{}", msg),
+            res => panic!("expect not enough shares error, got {:?}", res),
+        }
+        assert_arg_err(
+            advz.recover_payload_columns(
+                &Advz::<Bls12_381, Sha256>::share_columns(&d.shares[..threshold - 1]).unwrap(),
+                &d.common,
+            ),
+            "threshold minus one share columns should be arg error",
+        );
+
+        // enough shares but not enough distinct shares
+        let mut shares = d.shares[..threshold].to_vec();
+        shares[0] = shares[1].clone();
+        match advz.recover_payload(&shares, &d.common) {
+            Err(Argument(msg)) => assert!(msg.contains("need 1 more"), "{}", msg),
+            res => panic!("expect not enough shares error, got {:?}", res),
+        }
+    }
+
     #[test]
     fn sad_path_recover_payload_corrupt_shares() {
         let (advz, bytes_random) = avdz_init();
@@ -855,6 +6859,78 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_disperse_span() {
+        use std::{
+            string::String,
+            sync::{Arc, Mutex},
+        };
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id, Record},
+            subscriber::with_default,
+            Event, Metadata, Subscriber,
+        };
+
+        type SpanFields = Vec<(&'static str, String)>;
+
+        /// Records the name and fields of every new span.
+        #[derive(Clone, Default)]
+        struct SpanRecorder(Arc<Mutex<Vec<(&'static str, SpanFields)>>>);
+
+        struct FieldRecorder<'a>(&'a mut SpanFields);
+
+        impl Visit for FieldRecorder<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn ark_std::fmt::Debug) {
+                self.0.push((field.name(), format!("{:?}", value)));
+            }
+        }
+
+        impl Subscriber for SpanRecorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut fields = Vec::new();
+                span.record(&mut FieldRecorder(&mut fields));
+                let mut spans = self.0.lock().unwrap();
+                spans.push((span.metadata().name(), fields));
+                Id::from_u64(spans.len() as u64)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let (advz, bytes_random) = avdz_init();
+        let recorder = SpanRecorder::default();
+        with_default(recorder.clone(), || advz.disperse(&bytes_random).unwrap());
+
+        let spans = recorder.0.lock().unwrap();
+        let (_, fields) = spans
+            .iter()
+            .find(|(name, _)| *name == "disperse")
+            .expect("disperse span should be emitted");
+        assert!(fields.contains(&("payload_byte_len", format!("{}", bytes_random.len()))));
+        assert!(fields.contains(&("num_storage_nodes", format!("{}", advz.num_storage_nodes))));
+    }
+
+    /// Quadratic extension of the BLS12-381 scalar field.
+    pub(super) struct Fr2Config;
+
+    impl Fp2Config for Fr2Config {
+        type Fp = KzgEval<Bls12_381>;
+
+        // 7 generates the multiplicative group, so it is not a square
+        const NONRESIDUE: Self::Fp = MontFp!("7");
+        const FROBENIUS_COEFF_FP2_C1: &'static [Self::Fp] = &[MontFp!("1"), MontFp!("-1")];
+    }
+
+    pub(super) type Fr2 = Fp2<Fr2Config>;
+
     /// Routine initialization tasks.
     ///
     /// Returns the following tuple:
@@ -869,6 +6945,25 @@ mod tests {
         (advz, bytes_random)
     }
 
+    /// Same as [`avdz_init`] except that payload commitments are under
+    /// `commit_version`.
+    pub(super) fn avdz_init_with_commit_version(
+        commit_version: CommitVersion,
+    ) -> (Advz<Bls12_381, Sha256>, Vec<u8>) {
+        let (payload_chunk_size, num_storage_nodes) = (4, 6);
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(payload_chunk_size, &mut rng);
+        let advz = Advz::new_with_commit_version(
+            payload_chunk_size,
+            num_storage_nodes,
+            srs,
+            commit_version,
+        )
+        .unwrap();
+        let bytes_random = init_random_payload(4000, &mut rng);
+        (advz, bytes_random)
+    }
+
     /// Convenience wrapper to assert [`VidError::Argument`] return value.
     pub(super) fn assert_arg_err<T>(res: VidResult<T>, msg: &str) {
         assert!(matches!(res, Err(Argument(_))), "{}", msg);
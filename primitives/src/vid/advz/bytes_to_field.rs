@@ -1,9 +1,9 @@
-use ark_ff::{BigInteger, PrimeField};
+use ark_ff::{BigInteger, Field, PrimeField};
 use ark_std::{
     borrow::Borrow,
-    iter::Take,
     marker::PhantomData,
     vec::{IntoIter, Vec},
+    Zero,
 };
 
 /// Deterministic, infallible, invertible iterator adaptor to convert from
@@ -11,6 +11,10 @@ use ark_std::{
 ///
 /// The final field element is padded with zero bytes as needed.
 ///
+/// `F` may be an extension field, in which case each element holds
+/// [`elem_byte_capacity`] bytes packed into its coordinates over
+/// [`Field::BasePrimeField`], lowest coordinate first.
+///
 /// # Example
 ///
 /// [doctest ignored because it's a private module.]
@@ -27,13 +31,15 @@ use ark_std::{
 ///
 /// Panics only under conditions that should be checkable at compile time:
 ///
-/// - The [`PrimeField`] modulus bit length is too small to hold a `u64`.
-/// - The [`PrimeField`] byte length is too large to fit inside a `usize`.
+/// - The [`Field::BasePrimeField`] modulus bit length is too small to hold a
+///   `u64`.
+/// - The [`Field::BasePrimeField`] byte length or the extension degree of `F`
+///   is too large to fit inside a `usize`.
 ///
 /// If any of the above conditions holds then this function *always* panics.
 pub fn bytes_to_field<I, F>(bytes: I) -> impl Iterator<Item = F>
 where
-    F: PrimeField,
+    F: Field,
     I: IntoIterator,
     I::Item: Borrow<u8>,
 {
@@ -67,13 +73,133 @@ where
 /// Panics under the conditions listed at [`bytes_to_field`].
 pub fn field_to_bytes<I, F>(elems: I) -> impl Iterator<Item = u8>
 where
-    F: PrimeField,
+    F: Field,
     I: IntoIterator,
     I::Item: Borrow<F>,
 {
     FieldToBytes::new(elems.into_iter())
 }
 
+/// Like [`bytes_to_field`] except that each field element reserves its first
+/// byte for `tag`.
+///
+/// Each element holds `tag` followed by up to [`tagged_elem_byte_capacity`]
+/// bytes of data. The final field element is padded with zero bytes as
+/// needed. Invert via [`field_to_bytes_tagged`].
+///
+/// ## Panics
+///
+/// Panics under the conditions listed at [`bytes_to_field`], or if the
+/// [`PrimeField`] byte capacity is too small to hold a tag byte and a data
+/// byte.
+pub fn bytes_to_field_tagged<I, F>(tag: u8, bytes: I) -> impl Iterator<Item = F>
+where
+    F: PrimeField,
+    I: IntoIterator,
+    I::Item: Borrow<u8>,
+{
+    let capacity = tagged_elem_byte_capacity::<F>();
+    let mut bytes_iter = bytes.into_iter();
+    ark_std::iter::from_fn(move || {
+        let mut elem_bytes = Vec::with_capacity(capacity + 1);
+        elem_bytes.push(tag);
+        elem_bytes.extend(bytes_iter.by_ref().take(capacity).map(|b| *b.borrow()));
+        if elem_bytes.len() == 1 {
+            None
+        } else {
+            Some(F::from_le_bytes_mod_order(&elem_bytes))
+        }
+    })
+}
+
+/// Deterministic, infallible inverse of [`bytes_to_field_tagged`].
+///
+/// Yields the tag and data bytes of each field element. Data bytes always
+/// have length [`tagged_elem_byte_capacity`], so the data of the final
+/// element might contain extra zero bytes.
+///
+/// ## Panics
+///
+/// Panics under the conditions listed at [`bytes_to_field_tagged`].
+pub fn field_to_bytes_tagged<I, F>(elems: I) -> impl Iterator<Item = (u8, Vec<u8>)>
+where
+    F: PrimeField,
+    I: IntoIterator,
+    I::Item: Borrow<F>,
+{
+    let capacity = tagged_elem_byte_capacity::<F>();
+    elems.into_iter().map(move |elem| {
+        let mut elem_bytes = elem.borrow().into_bigint().to_bytes_le().into_iter();
+        let tag = elem_bytes.next().unwrap_or(0);
+        let mut data: Vec<_> = elem_bytes.take(capacity).collect();
+        data.resize(capacity, 0);
+        (tag, data)
+    })
+}
+
+/// Like [`bytes_to_field`] except that bits are packed densely across
+/// element boundaries.
+///
+/// Each field element holds [`packed_elem_bit_capacity`] bits of data instead
+/// of `8 * `[`elem_byte_capacity`], so large payloads encode into fewer field
+/// elements. Bits are read from each byte least significant first. The final
+/// field element is padded with zero bits as needed. Invert via
+/// [`field_to_bytes_packed`].
+///
+/// ## Panics
+///
+/// Panics under the conditions listed at [`bytes_to_field`].
+pub fn bytes_to_field_packed<I, F>(bytes: I) -> impl Iterator<Item = F>
+where
+    F: PrimeField,
+    I: IntoIterator,
+    I::Item: Borrow<u8>,
+{
+    BytesToFieldPacked::new(bytes.into_iter(), 0)
+}
+
+/// Same as [`bytes_to_field_packed`] except that the `skip_bits` least
+/// significant bits of the first byte are ignored, as when `bytes` begins
+/// inside the previous field element.
+///
+/// ## Panics
+///
+/// Panics if `skip_bits` is not less than 8, or under the conditions listed
+/// at [`bytes_to_field`].
+pub(crate) fn bytes_to_field_packed_skip<I, F>(
+    bytes: I,
+    skip_bits: usize,
+) -> impl Iterator<Item = F>
+where
+    F: PrimeField,
+    I: IntoIterator,
+    I::Item: Borrow<u8>,
+{
+    assert!(
+        skip_bits < 8,
+        "skip_bits {} should be less than 8",
+        skip_bits
+    );
+    BytesToFieldPacked::new(bytes.into_iter(), skip_bits)
+}
+
+/// Deterministic, infallible inverse of [`bytes_to_field_packed`].
+///
+/// The composition of [`field_to_bytes_packed`] with [`bytes_to_field_packed`]
+/// might contain extra zero bytes.
+///
+/// ## Panics
+///
+/// Panics under the conditions listed at [`bytes_to_field`].
+pub fn field_to_bytes_packed<I, F>(elems: I) -> impl Iterator<Item = u8>
+where
+    F: PrimeField,
+    I: IntoIterator,
+    I::Item: Borrow<F>,
+{
+    FieldToBytesPacked::new(elems.into_iter())
+}
+
 struct BytesToField<I, F> {
     bytes_iter: I,
     elem_byte_capacity: usize,
@@ -82,7 +208,7 @@ struct BytesToField<I, F> {
 
 impl<I, F> BytesToField<I, F>
 where
-    F: PrimeField,
+    F: Field,
 {
     fn new(bytes_iter: I) -> Self {
         Self {
@@ -97,7 +223,7 @@ impl<I, F> Iterator for BytesToField<I, F>
 where
     I: Iterator,
     I::Item: Borrow<u8>,
-    F: PrimeField,
+    F: Field,
 {
     type Item = F;
 
@@ -113,27 +239,179 @@ where
         if elem_bytes.is_empty() {
             None
         } else {
-            Some(F::from_le_bytes_mod_order(&elem_bytes))
+            Some(field_from_le_bytes(&elem_bytes))
+        }
+    }
+}
+
+/// Bits are written directly into the limbs of each element, so no
+/// per-element buffer is allocated.
+struct BytesToFieldPacked<I, F: PrimeField> {
+    bytes_iter: I,
+    capacity: usize,
+    // bits of the first byte yet to be dropped
+    skip_bits: usize,
+    // high bits of the last byte read that did not fit into the previous
+    // element
+    carry: u8,
+    carry_len: usize,
+    _phantom: PhantomData<F>,
+}
+
+impl<I, F> BytesToFieldPacked<I, F>
+where
+    F: PrimeField,
+{
+    fn new(bytes_iter: I, skip_bits: usize) -> Self {
+        Self {
+            bytes_iter,
+            capacity: packed_elem_bit_capacity::<F>(),
+            skip_bits,
+            carry: 0,
+            carry_len: 0,
+            _phantom: PhantomData,
         }
     }
 }
 
+impl<I, F> Iterator for BytesToFieldPacked<I, F>
+where
+    I: Iterator,
+    I::Item: Borrow<u8>,
+    F: PrimeField,
+{
+    type Item = F;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bigint = F::BigInt::default();
+        let mut len = self.carry_len;
+        if len > 0 {
+            write_bits(bigint.as_mut(), 0, self.carry);
+            self.carry_len = 0;
+        }
+        while len < self.capacity {
+            let (mut byte, mut byte_len) = match self.bytes_iter.next() {
+                Some(byte) => (*byte.borrow(), 8),
+                None => break,
+            };
+            if self.skip_bits > 0 {
+                byte >>= self.skip_bits;
+                byte_len -= self.skip_bits;
+                self.skip_bits = 0;
+            }
+            let take = ark_std::cmp::min(byte_len, self.capacity - len);
+            write_bits(bigint.as_mut(), len, byte & low_bits_mask(take));
+            len += take;
+            if take < byte_len {
+                self.carry = byte >> take;
+                self.carry_len = byte_len - take;
+            }
+        }
+        if len == 0 {
+            None
+        } else {
+            Some(
+                F::from_bigint(bigint)
+                    .expect("packed bits should be less than the prime field modulus"),
+            )
+        }
+    }
+}
+
+struct FieldToBytesPacked<I, F: PrimeField> {
+    elems_iter: I,
+    capacity: usize,
+    // current element and the number of its bits already read
+    elem: F::BigInt,
+    elem_pos: usize,
+}
+
+impl<I, F> FieldToBytesPacked<I, F>
+where
+    F: PrimeField,
+{
+    fn new(elems_iter: I) -> Self {
+        let capacity = packed_elem_bit_capacity::<F>();
+        Self {
+            elems_iter,
+            capacity,
+            elem: F::BigInt::default(),
+            elem_pos: capacity,
+        }
+    }
+}
+
+impl<I, F> Iterator for FieldToBytesPacked<I, F>
+where
+    I: Iterator,
+    I::Item: Borrow<F>,
+    F: PrimeField,
+{
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut byte = 0u8;
+        let mut len = 0;
+        while len < 8 {
+            if self.elem_pos == self.capacity {
+                match self.elems_iter.next() {
+                    Some(elem) => self.elem = elem.borrow().into_bigint(),
+                    None => break,
+                }
+                self.elem_pos = 0;
+            }
+            let take = ark_std::cmp::min(8 - len, self.capacity - self.elem_pos);
+            byte |= read_bits(self.elem.as_ref(), self.elem_pos, take) << len;
+            len += take;
+            self.elem_pos += take;
+        }
+        if len == 0 {
+            None
+        } else {
+            Some(byte)
+        }
+    }
+}
+
+/// OR `bits` into `limbs` starting at bit `pos`.
+fn write_bits(limbs: &mut [u64], pos: usize, bits: u8) {
+    let (index, offset) = (pos / 64, pos % 64);
+    let bits = u64::from(bits);
+    limbs[index] |= bits << offset;
+    if offset > 56 && bits >> (64 - offset) != 0 {
+        limbs[index + 1] |= bits >> (64 - offset);
+    }
+}
+
+/// Read `len` bits, at most 8, from `limbs` starting at bit `pos`.
+fn read_bits(limbs: &[u64], pos: usize, len: usize) -> u8 {
+    let (index, offset) = (pos / 64, pos % 64);
+    let mut bits = limbs[index] >> offset;
+    if offset + len > 64 {
+        bits |= limbs[index + 1] << (64 - offset);
+    }
+    // truncation intended: at most 8 bits remain after masking
+    (bits as u8) & low_bits_mask(len)
+}
+
+fn low_bits_mask(len: usize) -> u8 {
+    u8::try_from((1u16 << len) - 1).expect("mask len should be at most 8")
+}
+
 struct FieldToBytes<I, F> {
     elems_iter: I,
-    bytes_iter: Take<IntoIter<u8>>,
-    elem_byte_capacity: usize,
+    bytes_iter: IntoIter<u8>,
     _phantom: PhantomData<F>,
 }
 
 impl<I, F> FieldToBytes<I, F>
 where
-    F: PrimeField,
+    F: Field,
 {
     fn new(elems_iter: I) -> Self {
         Self {
             elems_iter,
-            bytes_iter: Vec::new().into_iter().take(0),
-            elem_byte_capacity: elem_byte_capacity::<F>(),
+            bytes_iter: Vec::new().into_iter(),
             _phantom: PhantomData,
         }
     }
@@ -143,7 +421,7 @@ impl<I, F> Iterator for FieldToBytes<I, F>
 where
     I: Iterator,
     I::Item: Borrow<F>,
-    F: PrimeField,
+    F: Field,
 {
     type Item = u8;
 
@@ -152,39 +430,97 @@ where
             return Some(byte);
         }
         if let Some(elem) = self.elems_iter.next() {
-            self.bytes_iter = elem
-                .borrow()
-                .into_bigint()
-                .to_bytes_le()
-                .into_iter()
-                .take(self.elem_byte_capacity);
+            self.bytes_iter = field_to_le_bytes(elem.borrow()).into_iter();
             return self.bytes_iter.next();
         }
         None
     }
 }
 
-/// Return the number of bytes that can be encoded into a generic [`PrimeField`]
+/// Pack up to [`elem_byte_capacity`] bytes into a field element, filling its
+/// coordinates over [`Field::BasePrimeField`] in order.
+fn field_from_le_bytes<F: Field>(bytes: &[u8]) -> F {
+    let extension_degree = extension_degree::<F>();
+    if extension_degree == 1 {
+        return F::from_base_prime_field(F::BasePrimeField::from_le_bytes_mod_order(bytes));
+    }
+    let mut coords: Vec<F::BasePrimeField> = bytes
+        .chunks(elem_byte_capacity::<F::BasePrimeField>())
+        .map(F::BasePrimeField::from_le_bytes_mod_order)
+        .collect();
+    coords.resize(extension_degree, F::BasePrimeField::zero());
+    F::from_base_prime_field_elems(&coords)
+        .expect("number of coordinates should equal the extension degree")
+}
+
+/// Inverse of [`field_from_le_bytes`]. Always returns
+/// [`elem_byte_capacity`] bytes.
+fn field_to_le_bytes<F: Field>(elem: &F) -> Vec<u8> {
+    let base_capacity = elem_byte_capacity::<F::BasePrimeField>();
+    elem.to_base_prime_field_elements()
+        .flat_map(|coord| {
+            coord
+                .into_bigint()
+                .to_bytes_le()
+                .into_iter()
+                .take(base_capacity)
+        })
+        .collect()
+}
+
+fn extension_degree<F: Field>() -> usize {
+    usize::try_from(F::extension_degree()).expect("extension degree should fit into usize")
+}
+
+/// Return the number of bytes that can be encoded into a generic [`Field`]
 /// parameter.
 ///
-/// Returns the byte length of the [`PrimeField`] modulus minus 1.
+/// Returns the byte length of the [`Field::BasePrimeField`] modulus minus 1,
+/// times the extension degree of `F`.
 ///
 /// It should be possible to do all this at compile time but I don't know how.
 /// Want to panic on overflow, so use checked arithetic and type conversion.
-pub fn elem_byte_capacity<F: PrimeField>() -> usize {
-    usize::try_from((F::MODULUS_BIT_SIZE - 1) / 8)
-        .expect("prime field modulus byte len should fit into usize")
+pub fn elem_byte_capacity<F: Field>() -> usize {
+    usize::try_from((F::BasePrimeField::MODULUS_BIT_SIZE - 1) / 8)
+        .ok()
+        .and_then(|c| c.checked_mul(extension_degree::<F>()))
+        .expect("field byte capacity should fit into usize")
+}
+
+/// Return the number of data bits that can be encoded into a generic
+/// [`PrimeField`] parameter by [`bytes_to_field_packed`].
+///
+/// Returns the bit length of the [`PrimeField`] modulus minus 1.
+pub fn packed_elem_bit_capacity<F: PrimeField>() -> usize {
+    usize::try_from(F::MODULUS_BIT_SIZE - 1)
+        .expect("prime field modulus bit len should fit into usize")
+}
+
+/// Return the number of data bytes that can be encoded into a generic
+/// [`PrimeField`] parameter by [`bytes_to_field_tagged`].
+///
+/// One less than [`elem_byte_capacity`] so as to reserve a byte for the tag.
+pub fn tagged_elem_byte_capacity<F: PrimeField>() -> usize {
+    elem_byte_capacity::<F>()
+        .checked_sub(1)
+        .filter(|c| *c > 0)
+        .expect("prime field modulus byte len should fit a tag byte and a data byte")
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{bytes_to_field, field_to_bytes, PrimeField, Vec};
+    use super::{
+        bytes_to_field, bytes_to_field_packed, bytes_to_field_packed_skip, bytes_to_field_tagged,
+        elem_byte_capacity, field_to_bytes, field_to_bytes_packed, field_to_bytes_tagged,
+        packed_elem_bit_capacity, tagged_elem_byte_capacity, Field, PrimeField, Vec,
+    };
+    use ark_bls12_381::{Fq as Fq381, Fq12 as Fq12_381, Fq2 as Fq2_381};
     use ark_ed_on_bls12_377::Fr as Fr377;
     use ark_ed_on_bls12_381::Fr as Fr381;
     use ark_ed_on_bn254::Fr as Fr254;
-    use ark_std::rand::RngCore;
+    use ark_std::{rand::RngCore, vec};
 
-    fn bytes_to_field_iter<F: PrimeField>() {
+    fn bytes_to_field_iter<F: Field>() {
         let byte_lens = [0, 1, 2, 16, 31, 32, 33, 48, 65, 100, 200, 5000];
 
         let max_len = *byte_lens.iter().max().unwrap();
@@ -231,5 +567,179 @@ mod tests {
         bytes_to_field_iter::<Fr254>();
         bytes_to_field_iter::<Fr377>();
         bytes_to_field_iter::<Fr381>();
+        bytes_to_field_iter::<Fq2_381>();
+        bytes_to_field_iter::<Fq12_381>();
+    }
+
+    #[test]
+    fn test_bytes_field_elems_extension() {
+        // an extension element holds one base element's worth per coordinate
+        let base_capacity = elem_byte_capacity::<Fq381>();
+        assert_eq!(elem_byte_capacity::<Fq2_381>(), 2 * base_capacity);
+        assert_eq!(elem_byte_capacity::<Fq12_381>(), 12 * base_capacity);
+
+        // coordinates are filled lowest first
+        let bytes: Vec<u8> = (1..=u8::try_from(base_capacity + 1).unwrap()).collect();
+        let elems: Vec<Fq2_381> = bytes_to_field(bytes.iter()).collect();
+        assert_eq!(elems.len(), 1);
+        let coords: Vec<Fq381> = elems[0].to_base_prime_field_elements().collect();
+        assert_eq!(
+            coords,
+            [
+                bytes_to_field::<_, Fq381>(&bytes[..base_capacity])
+                    .next()
+                    .unwrap(),
+                Fq381::from(u64::from(bytes[base_capacity])),
+            ]
+        );
+    }
+
+    fn bytes_to_field_tagged_iter<F: PrimeField>() {
+        let capacity = tagged_elem_byte_capacity::<F>();
+        let byte_lens = [0, 1, capacity - 1, capacity, capacity + 1, 100, 5000];
+        let mut rng = jf_utils::test_rng();
+
+        for (tag, len) in byte_lens.into_iter().enumerate() {
+            let tag = 0xF0 | tag as u8;
+            let mut bytes = vec![0; len];
+            rng.fill_bytes(&mut bytes);
+
+            let elems: Vec<F> = bytes_to_field_tagged(tag, bytes.iter()).collect();
+            assert_eq!(elems.len(), (len + capacity - 1) / capacity);
+
+            let mut round_trip = Vec::with_capacity(len);
+            for (elem_tag, data) in field_to_bytes_tagged::<_, F>(elems.iter()) {
+                assert_eq!(elem_tag, tag);
+                assert_eq!(data.len(), capacity);
+                round_trip.extend(data);
+            }
+            round_trip.truncate(len);
+            assert_eq!(round_trip, bytes);
+
+            // tags survive a round trip through untagged bytes, as in a VID
+            // payload
+            let payload: Vec<u8> = field_to_bytes::<_, F>(elems.iter()).collect();
+            let recovered: Vec<F> = bytes_to_field(payload).collect();
+            assert_eq!(recovered, elems);
+            assert!(field_to_bytes_tagged(recovered).all(|(t, _)| t == tag));
+        }
+    }
+
+    #[test]
+    fn test_bytes_field_elems_tagged_iter() {
+        bytes_to_field_tagged_iter::<Fr254>();
+        bytes_to_field_tagged_iter::<Fr377>();
+        bytes_to_field_tagged_iter::<Fr381>();
+    }
+
+    fn bytes_to_field_packed_iter<F: PrimeField>() {
+        let bit_capacity = packed_elem_bit_capacity::<F>();
+        let byte_capacity = elem_byte_capacity::<F>();
+        // lengths whose bit length falls on, just before and just after element
+        // boundaries
+        let boundary = bit_capacity / 8;
+        let byte_lens = [
+            0,
+            1,
+            boundary,
+            boundary + 1,
+            bit_capacity,
+            bit_capacity + 1,
+            2 * bit_capacity - 1,
+            100,
+            5000,
+        ];
+        let mut rng = jf_utils::test_rng();
+
+        for len in byte_lens {
+            let mut bytes = vec![0; len];
+            rng.fill_bytes(&mut bytes);
+
+            let elems: Vec<F> = bytes_to_field_packed(bytes.iter()).collect();
+            assert_eq!(elems.len(), (8 * len + bit_capacity - 1) / bit_capacity);
+            assert!(elems.len() <= (len + byte_capacity - 1) / byte_capacity);
+
+            let round_trip: Vec<_> = field_to_bytes_packed::<_, F>(elems.iter()).collect();
+            assert!(round_trip.len() >= len);
+            assert!(round_trip[len..].iter().all(|b| *b == 0));
+            assert_eq!(&round_trip[..len], &bytes[..]);
+        }
+
+        // bit-packing beats byte-aligned encoding for large payloads
+        let bytes = vec![0xFF; 5000];
+        assert!(
+            bytes_to_field_packed::<_, F>(bytes.iter()).count()
+                < bytes_to_field::<_, F>(bytes.iter()).count()
+        );
+    }
+
+    #[test]
+    fn test_bytes_field_elems_packed_iter() {
+        bytes_to_field_packed_iter::<Fr254>();
+        bytes_to_field_packed_iter::<Fr377>();
+        bytes_to_field_packed_iter::<Fr381>();
+        bytes_to_field_packed_iter::<Fq381>();
+    }
+
+    fn bytes_to_field_packed_unaligned<F: PrimeField>() {
+        // elements straddle byte boundaries
+        let bit_capacity = packed_elem_bit_capacity::<F>();
+        assert_ne!(bit_capacity % 8, 0);
+
+        // all-ones bytes: each full element holds `bit_capacity` ones and the
+        // final element holds the remaining bits
+        let len = 3 * bit_capacity / 8 + 1;
+        let elems: Vec<F> = bytes_to_field_packed(vec![0xFFu8; len]).collect();
+        let ones =
+            |num_bits: usize| (0..num_bits).fold(F::zero(), |acc, _| acc.double() + F::one());
+        assert_eq!(elems.len(), 4);
+        for elem in &elems[..3] {
+            assert_eq!(*elem, ones(bit_capacity));
+        }
+        assert_eq!(elems[3], ones(8 * len - 3 * bit_capacity));
+
+        // a single set bit lands in the element and position it belongs to
+        for bit in [
+            bit_capacity - 1,
+            bit_capacity,
+            bit_capacity + 1,
+            2 * bit_capacity + 3,
+        ] {
+            let mut bytes = vec![0u8; len];
+            bytes[bit / 8] = 1 << (bit % 8);
+            let elems: Vec<F> = bytes_to_field_packed(bytes.iter()).collect();
+            for (i, elem) in elems.iter().enumerate() {
+                if i == bit / bit_capacity {
+                    assert_eq!(*elem, F::from(2u64).pow([(bit % bit_capacity) as u64]));
+                } else {
+                    assert!(elem.is_zero());
+                }
+            }
+            let round_trip: Vec<_> = field_to_bytes_packed::<_, F>(elems.iter())
+                .take(len)
+                .collect();
+            assert_eq!(round_trip, bytes);
+        }
+
+        // decoding from the byte holding the first bit of any element
+        let mut bytes = vec![0; 5 * bit_capacity / 8];
+        jf_utils::test_rng().fill_bytes(&mut bytes);
+        let elems: Vec<F> = bytes_to_field_packed(bytes.iter()).collect();
+        for first_elem in 0..elems.len() {
+            let first_bit = first_elem * bit_capacity;
+            let skipped: Vec<F> =
+                bytes_to_field_packed_skip(&bytes[first_bit / 8..], first_bit % 8)
+                    .take(elems.len() - first_elem)
+                    .collect();
+            assert_eq!(skipped, elems[first_elem..]);
+        }
+    }
+
+    #[test]
+    fn test_bytes_field_elems_packed_unaligned() {
+        bytes_to_field_packed_unaligned::<Fr254>();
+        bytes_to_field_packed_unaligned::<Fr377>();
+        bytes_to_field_packed_unaligned::<Fr381>();
+        bytes_to_field_packed_unaligned::<Fq381>();
     }
 }
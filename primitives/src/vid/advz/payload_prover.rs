@@ -6,7 +6,7 @@
 
 //! Implementations of [`PayloadProver`] for `Advz`.
 //!
-//! Two implementations:
+//! Three implementations:
 //! 1. `PROOF = `[`SmallRangeProof`]: Useful for small sub-slices of `payload`
 //!    such as an individual transaction within a block. Not snark-friendly
 //!    because it requires a pairing. Consists of metadata required to verify a
@@ -14,31 +14,82 @@
 //! 2. `PROOF = `[`LargeRangeProof`]: Useful for large sub-slices of `payload`
 //!    such as a complete namespace. Snark-friendly because it does not require
 //!    a pairing. Consists of metadata required to rebuild a KZG commitment.
+//! 3. `PROOF = `[`SingleOpeningProof`]: Same use case as [`SmallRangeProof`]
+//!    but of constant size: a single KZG opening at a Fiat-Shamir point
+//!    regardless of the length of the range.
+//!
+//! # Memory-mapped payloads
+//!
+//! Provers accept any `B: AsRef<[u8]>` as the payload, including a
+//! memory-mapped file such as `memmap2::Mmap`. Proving a range reads only the
+//! bytes of the polynomial containing that range, so a proof for a small range
+//! of a large file faults in only a few pages rather than the entire file.
 
 use super::{
-    bytes_to_field, bytes_to_field::elem_byte_capacity, Advz, KzgEval, KzgProof,
-    PolynomialCommitmentScheme, Vec, VidResult,
+    bytes_to_field,
+    bytes_to_field::{bytes_to_field_packed_skip, elem_byte_capacity, field_to_bytes},
+    Advz, Common, CompactCommon, FieldPacking, KzgCommit, KzgEval, KzgPoint, KzgProof,
+    PolyCommitsMerkleTreeProof, PolynomialCommitmentScheme, Vec, VidResult,
 };
 use crate::{
     alloc::string::ToString,
-    merkle_tree::hasher::HasherDigest,
-    pcs::prelude::UnivariateKzgPCS,
+    merkle_tree::{
+        hasher::{HasherDigest, HasherDigestAlgorithm, HasherNode},
+        universal_merkle_tree::UniversalMerkleTree,
+        Index, MerkleTreeScheme, ToTraversalPath,
+    },
+    pcs::{prelude::UnivariateKzgPCS, UnivariatePCS},
     vid::{
-        payload_prover::{PayloadProver, Statement},
-        vid, VidError, VidScheme,
+        payload_prover::{CommitStore, PayloadProver, Statement},
+        vid, VidError, VidScheme, VidSchemeMagic,
     },
 };
-use ark_ec::pairing::Pairing;
-use ark_poly::EvaluationDomain;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{format, ops::Range};
+use ark_ec::{
+    pairing::Pairing, scalar_mul::variable_base::VariableBaseMSM, AffineRepr, CurveGroup,
+};
+use ark_ff::{batch_inversion, Field, PrimeField};
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain, Polynomial};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use ark_std::{
+    borrow::Borrow,
+    collections::BTreeMap,
+    fmt::Debug,
+    format,
+    ops::Range,
+    rand::{CryptoRng, RngCore},
+    vec, One, Zero,
+};
+use derivative::Derivative;
+use digest::crypto_common::Output;
+use displaydoc::Display;
+use hashbrown::HashMap;
 use jf_utils::canonical;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use typenum::U3;
+
+/// Sparse merkle tree of payload commitments keyed by `I`, such as a tree of
+/// all historical block commitments keyed by block hash.
+///
+/// Verify a payload proof together with membership of its commitment via
+/// [`Advz::payload_verify_in_smt`].
+pub type CommitSmt<H, I> =
+    UniversalMerkleTree<HasherNode<H>, HasherDigestAlgorithm, I, U3, HasherNode<H>>;
+
+/// Membership proof for a payload commitment in a [`CommitSmt`].
+pub type CommitSmtProof<H, I> = <CommitSmt<H, I> as MerkleTreeScheme>::MembershipProof;
 
 /// A proof intended for use on small payload subslices.
 ///
 /// KZG batch proofs and accompanying metadata.
 ///
+/// The [`CanonicalSerialize`] encoding is, in order: the number of KZG
+/// proofs followed by each proof, the length of the prefix bytes followed by
+/// those bytes, the same for the suffix bytes, and the start and end of the
+/// chunk range. Numbers are little-endian `u64`.
+///
 /// TODO use batch proof instead of `Vec<P>` <https://github.com/EspressoSystems/jellyfish/issues/387>
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(bound = "P: CanonicalSerialize + CanonicalDeserialize")]
@@ -50,9 +101,127 @@ pub struct SmallRangeProof<P> {
     chunk_range: Range<usize>,
 }
 
+/// Same as [`SmallRangeProof`] except that the KZG proofs are stored in a
+/// fixed-size array, for ranges whose element count `N` is known at compile
+/// time.
+///
+/// Convert to and from a [`SmallRangeProof`] via [`From`] and [`TryFrom`].
+/// The [`CanonicalSerialize`] encoding is that of the converted
+/// [`SmallRangeProof`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "P: CanonicalSerialize + CanonicalDeserialize")]
+pub struct SmallRangeProofN<P, const N: usize> {
+    #[serde(with = "canonical")]
+    proofs: [P; N],
+    prefix_bytes: Vec<u8>,
+    suffix_bytes: Vec<u8>,
+    chunk_range: Range<usize>,
+}
+
+impl<P, const N: usize> From<SmallRangeProofN<P, N>> for SmallRangeProof<P> {
+    fn from(proof: SmallRangeProofN<P, N>) -> Self {
+        Self {
+            proofs: proof.proofs.into(),
+            prefix_bytes: proof.prefix_bytes,
+            suffix_bytes: proof.suffix_bytes,
+            chunk_range: proof.chunk_range,
+        }
+    }
+}
+
+impl<P, const N: usize> TryFrom<SmallRangeProof<P>> for SmallRangeProofN<P, N> {
+    type Error = VidError;
+
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `proof` does not hold exactly `N`
+    /// KZG proofs.
+    fn try_from(proof: SmallRangeProof<P>) -> VidResult<Self> {
+        Ok(Self {
+            proofs: proof.proofs.try_into().map_err(|proofs: Vec<P>| {
+                VidError::Argument(format!("proof len {} differs from {}", proofs.len(), N))
+            })?,
+            prefix_bytes: proof.prefix_bytes,
+            suffix_bytes: proof.suffix_bytes,
+            chunk_range: proof.chunk_range,
+        })
+    }
+}
+
+/// Accessors shared by [`SmallRangeProof`] and [`SmallRangeProofN`] so that
+/// both use the same verification logic.
+trait SmallRangeProofParts<P> {
+    fn proofs(&self) -> &[P];
+    fn prefix_bytes(&self) -> &[u8];
+    fn suffix_bytes(&self) -> &[u8];
+    fn chunk_range(&self) -> &Range<usize>;
+}
+
+impl<P> SmallRangeProofParts<P> for SmallRangeProof<P> {
+    fn proofs(&self) -> &[P] {
+        &self.proofs
+    }
+    fn prefix_bytes(&self) -> &[u8] {
+        &self.prefix_bytes
+    }
+    fn suffix_bytes(&self) -> &[u8] {
+        &self.suffix_bytes
+    }
+    fn chunk_range(&self) -> &Range<usize> {
+        &self.chunk_range
+    }
+}
+
+impl<P, const N: usize> SmallRangeProofParts<P> for SmallRangeProofN<P, N> {
+    fn proofs(&self) -> &[P] {
+        &self.proofs
+    }
+    fn prefix_bytes(&self) -> &[u8] {
+        &self.prefix_bytes
+    }
+    fn suffix_bytes(&self) -> &[u8] {
+        &self.suffix_bytes
+    }
+    fn chunk_range(&self) -> &Range<usize> {
+        &self.chunk_range
+    }
+}
+
+/// KZG opening proofs at every input point of every polynomial of a payload,
+/// computed by [`Advz::offline_precompute`].
+///
+/// [`Advz::online_proof`] assembles a [`SmallRangeProof`] for any range of
+/// the payload from this data without further group operations, amortizing
+/// the MSM-heavy work across many proofs.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct OfflineProofData<E>
+where
+    E: Pairing,
+{
+    payload: Vec<u8>,
+    // indexed by polynomial, then by input point
+    proofs: Vec<Vec<KzgProof<E>>>,
+
+    // parameters of the `Advz` instance that produced `self`
+    payload_chunk_size: usize,
+    num_storage_nodes: usize,
+}
+
 /// A proof intended for use on large payload subslices.
 ///
 /// Metadata needed to recover a KZG commitment.
+///
+/// The [`CanonicalSerialize`] encoding is, in order: the number of prefix
+/// elements followed by each element, the same for the suffix elements, the
+/// length of the prefix bytes followed by those bytes, the same for the
+/// suffix bytes, and the start and end of the chunk range. Numbers are
+/// little-endian `u64`.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(bound = "F: CanonicalSerialize + CanonicalDeserialize")]
 pub struct LargeRangeProof<F> {
@@ -65,472 +234,8971 @@ pub struct LargeRangeProof<F> {
     chunk_range: Range<usize>,
 }
 
-impl<E, H> PayloadProver<SmallRangeProof<KzgProof<E>>> for Advz<E, H>
+/// A constant-size proof for small payload subslices.
+///
+/// Let `p` be the polynomial containing the range and `S` the set of input
+/// points of the elements covering the range. The prover commits to the
+/// quotient `q` of `p` by the vanishing polynomial `Z_S` of `S` and opens `p`
+/// and `q` at a Fiat-Shamir point `z` with a single KZG opening of `p + c * q`
+/// for another Fiat-Shamir scalar `c`. The verifier interpolates the data
+/// elements over `S` to get `I(z)` and checks `p(z) - I(z) = Z_S(z) * q(z)`.
+///
+/// The [`CanonicalSerialize`] encoding is, in order: the commitment to `q`,
+/// `p(z)`, `q(z)`, the KZG opening proof, the length of the prefix bytes
+/// followed by those bytes, the same for the suffix bytes, and the start and
+/// end of the chunk range. Numbers are little-endian `u64`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SingleOpeningProof<E>
 where
     E: Pairing,
-    H: HasherDigest,
 {
-    fn payload_proof<B>(
-        &self,
-        payload: B,
-        range: Range<usize>,
-    ) -> VidResult<SmallRangeProof<KzgProof<E>>>
-    where
-        B: AsRef<[u8]>,
-    {
-        let payload = payload.as_ref();
-        check_range_nonempty_and_inside_payload(payload, &range)?;
+    #[serde(with = "canonical")]
+    quotient_commit: KzgCommit<E>,
+    #[serde(with = "canonical")]
+    poly_eval: KzgEval<E>,
+    #[serde(with = "canonical")]
+    quotient_eval: KzgEval<E>,
+    #[serde(with = "canonical")]
+    proof: KzgProof<E>,
+    prefix_bytes: Vec<u8>,
+    suffix_bytes: Vec<u8>,
+    chunk_range: Range<usize>,
+}
 
-        // index conversion
-        let range_elem = self.range_byte_to_elem(&range);
-        let range_poly = self.range_elem_to_poly(&range_elem);
-        let start_namespace_byte = self.index_poly_to_byte(range_poly.start);
-        let offset_elem = range_elem.start - self.index_byte_to_elem(start_namespace_byte);
-        let range_elem_byte = self.range_elem_to_byte_clamped(&range_elem, payload.len());
+/// A proof for an arbitrary set of elements within a single polynomial.
+///
+/// Useful for columnar payload encodings in which a logical range of data maps
+/// to a strided selection of elements. See [`Advz::payload_proof_strided`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "P: CanonicalSerialize + CanonicalDeserialize")]
+pub struct StridedProof<P> {
+    #[serde(with = "canonical")]
+    proofs: Vec<P>,
+}
 
-        check_range_poly(&range_poly)?;
+/// A proof for a single payload element: one KZG opening proof.
+///
+/// Made by [`Advz::element_proof`], verified by [`Advz::verify_element`].
+/// Elements are aligned by construction, so unlike a [`SmallRangeProof`] no
+/// prefix or suffix bytes are needed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "P: CanonicalSerialize + CanonicalDeserialize")]
+pub struct ElementProof<P> {
+    #[serde(with = "canonical")]
+    proof: P,
+}
 
-        // grab the polynomial that contains `range`
-        // TODO allow precomputation: https://github.com/EspressoSystems/jellyfish/issues/397
-        let polynomial = self.polynomial(
-            bytes_to_field::<_, KzgEval<E>>(payload[start_namespace_byte..].iter())
-                .take(self.payload_chunk_size),
-        );
+/// A proof for a range of the concatenation of several payloads dispersed
+/// via [`Advz::disperse_multi`].
+///
+/// One sub-proof per payload overlapped by the range, in order. Made by
+/// [`Advz::payload_proof_cross`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CrossProof<PROOF> {
+    proofs: Vec<PROOF>,
+}
 
-        // prepare list of input points
-        // perf: can't avoid use of `skip`
-        let points: Vec<_> = {
-            self.eval_domain
-                .elements()
-                .skip(offset_elem)
-                .take(range_elem.len())
-                .collect()
-        };
+/// A proof that two ranges of a payload hold equal data, without revealing
+/// the data. See [`Advz::prove_ranges_equal`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "P: CanonicalSerialize + CanonicalDeserialize")]
+pub struct RangesEqualProof<P> {
+    #[serde(with = "canonical")]
+    proofs: Vec<P>,
+}
 
-        let (proofs, _evals) =
-            UnivariateKzgPCS::multi_open(&self.ck, &polynomial, &points).map_err(vid)?;
+/// A proof that two payloads share a common prefix. See
+/// [`Advz::prove_common_prefix`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct CommonPrefixProof<E>
+where
+    E: Pairing,
+{
+    // openings to zero of the difference of the polynomials containing the
+    // end of the prefix, at each element wholly in the prefix
+    #[serde(with = "canonical")]
+    diff_proofs: Vec<KzgProof<E>>,
+    // openings of each payload's polynomial at the element containing the end
+    // of the prefix, if the prefix ends inside an element
+    #[serde(with = "canonical")]
+    boundary: Option<[(KzgEval<E>, KzgProof<E>); 2]>,
+}
 
-        Ok(SmallRangeProof {
-            proofs,
-            prefix_bytes: payload[range_elem_byte.start..range.start].to_vec(),
-            suffix_bytes: payload[range.end..range_elem_byte.end].to_vec(),
-            chunk_range: range,
-        })
+/// A [`LargeRangeProof`] that also carries a commitment to the complement of
+/// its range: the polynomial containing the range with the elements covering
+/// the range zeroed.
+///
+/// By linearity the commitment to the polynomial is the sum of the
+/// commitments to the range and to the complement, so the complement
+/// commitment binds everything else in the polynomial for use in
+/// compositional proofs. See [`Advz::payload_proof_with_complement`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ComplementProof<E>
+where
+    E: Pairing,
+{
+    proof: LargeRangeProof<KzgEval<E>>,
+    #[serde(with = "canonical")]
+    complement_commit: KzgCommit<E>,
+}
+
+impl<E> ComplementProof<E>
+where
+    E: Pairing,
+{
+    /// The commitment to the complement of the proved range.
+    pub fn complement_commit(&self) -> &KzgCommit<E> {
+        &self.complement_commit
     }
+}
 
-    fn payload_verify(
-        &self,
-        stmt: Statement<Self>,
-        proof: &SmallRangeProof<KzgProof<E>>,
-    ) -> VidResult<Result<(), ()>> {
-        Self::check_stmt_proof_consistency(&stmt, &proof.chunk_range)?;
+/// A proof that a payload range does *not* hold some claimed bytes.
+///
+/// Consists of the actual bytes in the range and a [`LargeRangeProof`] for
+/// them. See [`Advz::prove_differs`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "F: CanonicalSerialize + CanonicalDeserialize")]
+pub struct AbsenceProof<F> {
+    actual_bytes: Vec<u8>,
+    proof: LargeRangeProof<F>,
+}
 
-        // index conversion
-        let range_elem = self.range_byte_to_elem(&proof.chunk_range);
-        let range_poly = self.range_elem_to_poly(&range_elem);
-        let start_namespace_byte = self.index_poly_to_byte(range_poly.start);
-        let offset_elem = range_elem.start - self.index_byte_to_elem(start_namespace_byte);
+/// Handle to a payload proof computed in the background.
+///
+/// Created by [`Advz::spawn_proof`]. Await the proof via
+/// [`ProofHandle::wait`] or poll via [`ProofHandle::try_wait`].
+#[cfg(all(feature = "std", feature = "parallel"))]
+#[derive(Debug)]
+pub struct ProofHandle<PROOF> {
+    rx: std::sync::mpsc::Receiver<std::thread::Result<VidResult<PROOF>>>,
+}
 
-        check_range_poly(&range_poly)?;
-        Self::check_common_commit_consistency(stmt.common, stmt.commit)?;
+/// A request to verify a payload proof, sent to
+/// [`Advz::payload_verify_channel`].
+#[cfg(feature = "std")]
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "PROOF: Clone"),
+    Debug(bound = "PROOF: Debug"),
+    Eq(bound = "PROOF: Eq"),
+    PartialEq(bound = "PROOF: PartialEq")
+)]
+pub struct VerifyRequest<PROOF, E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Caller-chosen identifier, echoed in the [`VerifyResult`].
+    pub id: u64,
+    /// The statement to verify.
+    pub stmt: OwnedStatement<E, H>,
+    /// The proof of `stmt`.
+    pub proof: PROOF,
+}
 
-        // prepare list of data elems
-        let data_elems: Vec<_> = bytes_to_field::<_, KzgEval<E>>(
-            proof
-                .prefix_bytes
-                .iter()
-                .chain(stmt.payload_subslice)
-                .chain(proof.suffix_bytes.iter()),
-        )
-        .collect();
+/// The outcome of a [`VerifyRequest`], sent by
+/// [`Advz::payload_verify_channel`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct VerifyResult {
+    /// Same as [`VerifyRequest::id`].
+    pub id: u64,
+    /// Same as the return value of [`PayloadProver::payload_verify`].
+    pub outcome: VidResult<Result<(), ()>>,
+}
 
-        // prepare list of input points
-        // perf: can't avoid use of `skip`
-        let points: Vec<_> = {
-            self.eval_domain
-                .elements()
-                .skip(offset_elem)
-                .take(range_elem.len())
-                .collect()
-        };
+/// Incremental verifier for a [`LargeRangeProof`] that consumes the payload
+/// subslice in chunks.
+///
+/// Created by [`Advz::payload_verify_stream`]. Feed subslice bytes via
+/// [`PayloadVerifyStream::update`] then call
+/// [`PayloadVerifyStream::finalize`]. Memory use is bounded by
+/// `payload_chunk_size` regardless of the length of the subslice.
+pub struct PayloadVerifyStream<'a, E>
+where
+    E: Pairing,
+{
+    // commitments to the Lagrange basis polynomials of `eval_domain`
+    lagrange_commits: Vec<E::G1Affine>,
+    poly_commit: &'a KzgCommit<E>,
+    proof: &'a LargeRangeProof<KzgEval<E>>,
+    acc: E::G1,
+    next_elem: usize,
+    pending_bytes: Vec<u8>,
+    bytes_remaining: usize,
+}
 
-        // verify proof
-        // TODO naive verify for multi_open https://github.com/EspressoSystems/jellyfish/issues/387
-        if data_elems.len() != proof.proofs.len() {
-            return Err(VidError::Argument(format!(
-                "data len {} differs from proof len {}",
-                data_elems.len(),
-                proof.proofs.len()
-            )));
-        }
-        assert_eq!(data_elems.len(), points.len()); // sanity
-        let poly_commit = &stmt.common.poly_commits[range_poly.start];
-        for (point, (elem, pf)) in points
-            .iter()
-            .zip(data_elems.iter().zip(proof.proofs.iter()))
-        {
-            if !UnivariateKzgPCS::verify(&self.vk, poly_commit, point, elem, pf).map_err(vid)? {
-                return Ok(Err(()));
-            }
-        }
-        Ok(Ok(()))
-    }
+/// Verifier for a mixed queue of [`SmallRangeProof`]s and
+/// [`LargeRangeProof`]s.
+///
+/// Created by [`Advz::mixed_verifier`]. The KZG opening proofs of every
+/// [`SmallRangeProof`] added via [`MixedVerifier::add_small`] are collected
+/// and checked together by a single multi-pairing in
+/// [`MixedVerifier::finalize`]. Each [`LargeRangeProof`] added via
+/// [`MixedVerifier::add_large`] needs no pairing, so it is verified inline.
+pub struct MixedVerifier<'a, E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    advz: &'a Advz<E, H>,
+    poly_commits: Vec<KzgCommit<E>>,
+    points: Vec<KzgPoint<E>>,
+    evals: Vec<KzgEval<E>>,
+    proofs: Vec<KzgProof<E>>,
+    large_failed: bool,
 }
 
-impl<E, H> PayloadProver<LargeRangeProof<KzgEval<E>>> for Advz<E, H>
+/// Verifier that remembers the outcome of recent payload verifications, so
+/// that a proof re-presented in an identical context is not re-verified.
+///
+/// Created by [`Advz::verify_cache`]. Entries are keyed by a hash of
+/// everything [`PayloadProver::payload_verify`] depends on besides the
+/// [`Advz`] instance itself: the proof type, `commit`, `range`,
+/// `payload_subslice` and the proof. `common` is identified by `commit`
+/// together with its payload byte length and header digest rather than
+/// hashed in full, so a `common` whose poly commits are inconsistent with
+/// `commit` may be answered from the cache instead of rejected. The least
+/// recently used entry is evicted beyond the capacity. Errors are not
+/// cached.
+pub struct VerifyCache<'a, E, H>
 where
     E: Pairing,
     H: HasherDigest,
 {
-    fn payload_proof<B>(
-        &self,
-        payload: B,
-        range: Range<usize>,
-    ) -> VidResult<LargeRangeProof<KzgEval<E>>>
-    where
-        B: AsRef<[u8]>,
-    {
-        let payload = payload.as_ref();
-        check_range_nonempty_and_inside_payload(payload, &range)?;
+    advz: &'a Advz<E, H>,
+    capacity: usize,
+    // outcome and last use of each entry
+    entries: HashMap<Output<H>, (bool, u64)>,
+    // key of each entry by last use, least recent first
+    lru: BTreeMap<u64, Output<H>>,
+    tick: u64,
+    metrics: VerifyCacheMetrics,
+}
 
-        // index conversion
-        let range_elem = self.range_byte_to_elem(&range);
-        let range_poly = self.range_elem_to_poly(&range_elem);
-        let start_namespace_byte = self.index_poly_to_byte(range_poly.start);
-        let offset_elem = range_elem.start - self.index_byte_to_elem(start_namespace_byte);
-        let range_elem_byte = self.range_elem_to_byte_clamped(&range_elem, payload.len());
+/// Counters reported by [`VerifyCache::metrics`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct VerifyCacheMetrics {
+    /// Verifications answered from the cache.
+    pub hits: u64,
+    /// Verifications run because the cache had no entry.
+    pub misses: u64,
+    /// Entries evicted to stay within capacity.
+    pub evictions: u64,
+}
 
-        check_range_poly(&range_poly)?;
+/// Reason a payload proof failed verification, as reported by
+/// [`PayloadVerifyVerbose::payload_verify_verbose`].
+#[derive(Clone, Debug, Display, Eq, PartialEq)]
+pub enum VerifyFailure {
+    /// KZG opening proof failed at element {elem_index} of the proof range
+    KzgPairing {
+        /// Index of the failing element, relative to the start of the proof
+        /// range.
+        elem_index: usize,
+    },
+    /// reconstructed commitment differs from poly commit {poly_index}
+    CommitmentMismatch {
+        /// Index of the polynomial whose commitment differs.
+        poly_index: usize,
+    },
+    /// data matches poly commit {poly_index} over candidate domain {candidate_index}, not the verifier's domain
+    DomainMismatch {
+        /// Index of the polynomial whose commitment differs.
+        poly_index: usize,
+        /// Index of the matching candidate coset offset passed to
+        /// [`Advz::payload_verify_domain_check`].
+        candidate_index: usize,
+    },
+    /// length mismatch: expected {expected}, got {actual}
+    LengthMismatch {
+        /// Expected length.
+        expected: usize,
+        /// Actual length.
+        actual: usize,
+    },
+}
 
-        // compute the prefix and suffix elems
-        let mut elems_iter =
-            bytes_to_field::<_, KzgEval<E>>(payload[start_namespace_byte..].iter())
-                .take(self.payload_chunk_size);
-        let prefix_elems: Vec<_> = elems_iter.by_ref().take(offset_elem).collect();
-        let suffix_elems: Vec<_> = elems_iter.skip(range_elem.len()).collect();
+/// Discrepancy between a [`SmallRangeProof`] and the polynomial it should
+/// open, as reported by [`Advz::debug_verify_with_poly`].
+#[cfg(feature = "debug-tools")]
+#[derive(Clone, Debug, Display, Eq, PartialEq)]
+pub enum Discrepancy<F> {
+    /// proof range {actual:?} differs from expected range {expected:?}
+    RangeMismatch {
+        /// The range passed to [`Advz::debug_verify_with_poly`].
+        expected: Range<usize>,
+        /// The range recorded in the proof.
+        actual: Range<usize>,
+    },
+    /// proof has {actual} KZG proofs, expected {expected}
+    ProofCount {
+        /// Number of elements covering the range.
+        expected: usize,
+        /// Number of KZG proofs in the proof.
+        actual: usize,
+    },
+    /// KZG opening proof fails at element {elem_index} of the proof range
+    Opening {
+        /// Index of the failing element, relative to the start of the proof
+        /// range.
+        elem_index: usize,
+        /// Input point of the failing element.
+        point: F,
+        /// Evaluation of the supplied polynomial at `point`.
+        eval: F,
+    },
+}
 
-        Ok(LargeRangeProof {
-            prefix_elems,
-            suffix_elems,
-            prefix_bytes: payload[range_elem_byte.start..range.start].to_vec(),
-            suffix_bytes: payload[range.end..range_elem_byte.end].to_vec(),
-            chunk_range: range,
-        })
-    }
+/// Reason a requested byte range cannot be proved, as reported by
+/// [`Advz::prepare_request`].
+#[derive(Clone, Debug, Display, Eq, PartialEq)]
+pub enum InvalidRequest {
+    /// requested range is empty
+    Empty,
+    /// requested start {start} out of bounds for payload len {payload_len}
+    OutOfBounds {
+        /// Requested start byte.
+        start: usize,
+        /// Byte length of the payload.
+        payload_len: usize,
+    },
+    /// requested range spans polynomials {first_poly} through {last_poly}
+    SpansMultiplePolys {
+        /// Index of the polynomial containing the start of the range.
+        first_poly: usize,
+        /// Index of the polynomial containing the end of the range.
+        last_poly: usize,
+    },
+}
 
-    fn payload_verify(
-        &self,
-        stmt: Statement<Self>,
-        proof: &LargeRangeProof<KzgEval<E>>,
-    ) -> VidResult<Result<(), ()>> {
-        Self::check_stmt_proof_consistency(&stmt, &proof.chunk_range)?;
+impl From<InvalidRequest> for VidError {
+    fn from(e: InvalidRequest) -> Self {
+        Self::Argument(e.to_string())
+    }
+}
 
-        // index conversion
-        let range_poly = self.range_byte_to_poly(&proof.chunk_range);
+/// Rough cost of one KZG opening verification, two pairings, in multiples
+/// of the cost per element of an MSM. Used by [`Advz::recommend_proof_type`].
+pub const KZG_VERIFY_MSM_LEN: usize = 64;
 
-        check_range_poly(&range_poly)?;
-        Self::check_common_commit_consistency(stmt.common, stmt.commit)?;
+/// Kind of payload proof, for APIs such as [`Advz::cost_profile`] and
+/// [`Advz::payload_verify_bytes`] that do not know the proof type at compile
+/// time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProofKind {
+    /// [`SmallRangeProof`]
+    Small,
+    /// [`LargeRangeProof`]
+    Large,
+    /// [`SingleOpeningProof`]
+    SingleOpening,
+}
 
-        // rebuild the poly commit, check against `common`
-        let poly_commit = {
-            let poly = self.polynomial(
-                proof
-                    .prefix_elems
-                    .iter()
-                    .cloned()
-                    .chain(bytes_to_field::<_, KzgEval<E>>(
-                        proof
-                            .prefix_bytes
-                            .iter()
-                            .chain(stmt.payload_subslice)
-                            .chain(proof.suffix_bytes.iter()),
-                    ))
-                    .chain(proof.suffix_elems.iter().cloned()),
-            );
-            UnivariateKzgPCS::commit(&self.ck, &poly).map_err(vid)?
-        };
-        if poly_commit != stmt.common.poly_commits[range_poly.start] {
-            return Ok(Err(()));
+impl ProofKind {
+    /// Byte identifying `self` in the header written by
+    /// [`Advz::proof_to_bytes`].
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Self::Small => 0,
+            Self::Large => 1,
+            Self::SingleOpening => 2,
         }
+    }
 
-        Ok(Ok(()))
+    /// Inverse of [`ProofKind::to_byte`].
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Small),
+            1 => Some(Self::Large),
+            2 => Some(Self::SingleOpening),
+            _ => None,
+        }
     }
 }
 
-impl<E, H> Advz<E, H>
+/// A payload proof type that can be serialized with a header via
+/// [`Advz::proof_to_bytes`].
+pub trait VersionedProof: CanonicalSerialize + CanonicalDeserialize {
+    /// The [`ProofKind`] recorded in the header.
+    const KIND: ProofKind;
+}
+
+impl<P> VersionedProof for SmallRangeProof<P>
 where
-    E: Pairing,
-    H: HasherDigest,
+    P: CanonicalSerialize + CanonicalDeserialize,
 {
-    // lots of index manipulation
-    fn index_byte_to_elem(&self, index: usize) -> usize {
-        index_coarsen(index, elem_byte_capacity::<KzgEval<E>>())
-    }
-    fn index_poly_to_byte(&self, index: usize) -> usize {
-        index_refine(
-            index,
-            self.payload_chunk_size * elem_byte_capacity::<KzgEval<E>>(),
-        )
+    const KIND: ProofKind = ProofKind::Small;
+}
+
+impl<F> VersionedProof for LargeRangeProof<F>
+where
+    F: CanonicalSerialize + CanonicalDeserialize,
+{
+    const KIND: ProofKind = ProofKind::Large;
+}
+
+/// A component of the cost of computing a payload proof.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CostComponent {
+    /// Multi-scalar multiplications.
+    Msm,
+    /// Per-point openings.
+    Openings,
+    /// Conversions from payload bytes to field elements.
+    FieldConversions,
+}
+
+/// Analytic estimate of the cost of computing a payload proof, made by
+/// [`Advz::cost_profile`].
+///
+/// Each term counts the basic operations of its component: scalar
+/// multiplications for [`CostProfile::msm`], opened points for
+/// [`CostProfile::openings`] and payload bytes for
+/// [`CostProfile::field_conversions`]. The model ignores lower-order terms
+/// such as FFTs and hashing.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CostProfile {
+    /// Total size of all multi-scalar multiplications.
+    pub msm: usize,
+    /// Number of points at which a polynomial is opened.
+    pub openings: usize,
+    /// Number of payload bytes converted to field elements.
+    pub field_conversions: usize,
+}
+
+impl CostProfile {
+    /// The component with the largest term. Ties go to the component listed
+    /// first in [`CostComponent`].
+    pub fn dominant(&self) -> CostComponent {
+        let mut dominant = (CostComponent::Msm, self.msm);
+        for (component, term) in [
+            (CostComponent::Openings, self.openings),
+            (CostComponent::FieldConversions, self.field_conversions),
+        ] {
+            if term > dominant.1 {
+                dominant = (component, term);
+            }
+        }
+        dominant.0
     }
-    fn range_byte_to_elem(&self, range: &Range<usize>) -> Range<usize> {
-        range_coarsen(range, elem_byte_capacity::<KzgEval<E>>())
+}
+
+/// Costs of basic verifier operations on this host, measured by
+/// [`Advz::calibrate_verify_cost`] for use by
+/// [`Advz::estimate_verify_latency`].
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VerifyCostModel {
+    // parameters of the `Advz` instance that produced `self`
+    curve: core::any::TypeId,
+    payload_chunk_size: usize,
+
+    // one KZG opening check, dominated by its pairings
+    kzg_verify: std::time::Duration,
+    // one MSM of size `payload_chunk_size`, as in a `LargeRangeProof`
+    poly_msm: std::time::Duration,
+}
+
+/// Diagnostic variant of [`PayloadProver::payload_verify`].
+pub trait PayloadVerifyVerbose<PROOF>: PayloadProver<PROOF>
+where
+    PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+{
+    /// Same as [`PayloadProver::payload_verify`] except that verification
+    /// failure is reported as a [`VerifyFailure`] explaining why the proof was
+    /// rejected.
+    ///
+    /// Length inconsistencies among `stmt.payload_subslice`, `stmt.range` and
+    /// `proof`, which [`PayloadProver::payload_verify`] reports as
+    /// [`VidError::Argument`], are instead reported as
+    /// [`VerifyFailure::LengthMismatch`].
+    fn payload_verify_verbose(
+        &self,
+        stmt: Statement<Self>,
+        proof: &PROOF,
+    ) -> VidResult<Result<(), VerifyFailure>>;
+}
+
+/// Like [`Statement`] except that `common` is a [`CompactCommon`].
+///
+/// A [`CompactCommon`] holds only a hash of each polynomial commitment, so the
+/// verifier must also be given the full commitment to the polynomial
+/// containing `range`.
+pub struct CompactStatement<'a, E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// The subslice `payload[range.start..range.end]` from a call to
+    /// [`PayloadProver::payload_proof`].
+    pub payload_subslice: &'a [u8],
+    /// The range used to make [`Self::payload_subslice`].
+    pub range: Range<usize>,
+    /// VID commitment against which the proof will be checked.
+    pub commit: &'a <Advz<E, H> as VidScheme>::Commit,
+    /// Compressed VID data against which the proof will be checked.
+    pub common: &'a CompactCommon<E, H>,
+    /// Commitment to the polynomial containing [`Self::range`].
+    pub poly_commit: &'a KzgCommit<E>,
+}
+
+impl<'a, E, H> Clone for CompactStatement<'a, E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn clone(&self) -> Self {
+        Self {
+            payload_subslice: self.payload_subslice,
+            range: self.range.clone(),
+            commit: self.commit,
+            common: self.common,
+            poly_commit: self.poly_commit,
+        }
     }
-    fn range_elem_to_byte(&self, range: &Range<usize>) -> Range<usize> {
-        range_refine(range, elem_byte_capacity::<KzgEval<E>>())
+}
+
+/// Same as [`Statement`] except that the payload subslice is given as field
+/// elements.
+///
+/// Used by [`Advz::payload_verify_elems`].
+pub struct ElemStatement<'a, E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// The field elements of the payload that contain the bytes of
+    /// [`Self::range`].
+    pub payload_subslice_elems: &'a [KzgEval<E>],
+    /// The range used to make the proof.
+    pub range: Range<usize>,
+    /// VID commitment against which the proof will be checked.
+    pub commit: &'a <Advz<E, H> as VidScheme>::Commit,
+    /// VID data against which the proof will be checked.
+    pub common: &'a Common<E, H>,
+}
+
+impl<'a, E, H> Clone for ElemStatement<'a, E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn clone(&self) -> Self {
+        Self {
+            payload_subslice_elems: self.payload_subslice_elems,
+            range: self.range.clone(),
+            commit: self.commit,
+            common: self.common,
+        }
     }
-    fn range_elem_to_byte_clamped(&self, range: &Range<usize>, len: usize) -> Range<usize> {
-        let result = self.range_elem_to_byte(range);
-        Range {
-            end: ark_std::cmp::min(result.end, len),
-            ..result
+}
+
+/// Owned form of a [`Statement`], serialized to make the statement bytes for
+/// [`Advz::payload_verify_bytes`].
+///
+/// The [`CanonicalSerialize`] encoding is, in order: the start and end
+/// of the range, the length of the payload subslice followed by its bytes,
+/// the raw bytes of the payload commitment and the canonical encoding of
+/// the common data. Numbers are little-endian `u64`.
+#[derive(Derivative, Deserialize, Serialize)]
+#[serde(bound = "Output<H>: Serialize + for<'a> Deserialize<'a>")]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct OwnedStatement<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Same as [`Statement::payload_subslice`].
+    pub payload_subslice: Vec<u8>,
+    /// Same as [`Statement::range`].
+    pub range: Range<usize>,
+    /// Same as [`Statement::commit`].
+    pub commit: Output<H>,
+    /// Same as [`Statement::common`].
+    pub common: Common<E, H>,
+}
+
+impl<E, H> OwnedStatement<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Borrow `self` as a [`Statement`].
+    pub fn as_statement(&self) -> Statement<'_, Advz<E, H>> {
+        Statement {
+            payload_subslice: &self.payload_subslice,
+            range: self.range.clone(),
+            commit: &self.commit,
+            common: &self.common,
         }
     }
-    fn range_elem_to_poly(&self, range: &Range<usize>) -> Range<usize> {
-        range_coarsen(range, self.payload_chunk_size)
+}
+
+impl<E, H> CanonicalSerialize for OwnedStatement<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_range(&self.range, &mut writer, compress)?;
+        self.payload_subslice
+            .serialize_with_mode(&mut writer, compress)?;
+        HasherNode::<H>::from(self.commit).serialize_with_mode(&mut writer, compress)?;
+        self.common.serialize_with_mode(&mut writer, compress)
     }
-    fn range_byte_to_poly(&self, range: &Range<usize>) -> Range<usize> {
-        range_coarsen(
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        range_serialized_size(compress)
+            + self.payload_subslice.serialized_size(compress)
+            + HasherNode::<H>::from(self.commit).serialized_size(compress)
+            + self.common.serialized_size(compress)
+    }
+}
+
+impl<E, H> Valid for OwnedStatement<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.common.check()
+    }
+}
+
+impl<E, H> CanonicalDeserialize for OwnedStatement<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let range = deserialize_range(&mut reader, compress, validate)?;
+        let payload_subslice = Vec::deserialize_with_mode(&mut reader, compress, validate)?;
+        let commit = HasherNode::<H>::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(Self {
+            payload_subslice,
             range,
-            self.payload_chunk_size * elem_byte_capacity::<KzgEval<E>>(),
-        )
+            commit: *commit.as_ref(),
+            common: Common::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
     }
+}
 
-    fn check_common_commit_consistency(
-        common: &<Self as VidScheme>::Common,
-        commit: &<Self as VidScheme>::Commit,
-    ) -> VidResult<()> {
-        if *commit != Self::poly_commits_hash(common.poly_commits.iter())? {
-            return Err(VidError::Argument(
-                "common inconsistent with commit".to_string(),
-            ));
+/// Reproducible record of what [`Advz::payload_verify_with_audit`] checked,
+/// serializable for archival.
+#[derive(Derivative, Deserialize, Serialize)]
+#[serde(bound = "Output<H>: Serialize + for<'a> Deserialize<'a>")]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct AuditLog<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Payload commitment against which the proof was checked.
+    pub commit: Output<H>,
+    /// Index of the polynomial containing the proved range.
+    pub poly_index: usize,
+    /// The proved byte range.
+    pub range: Range<usize>,
+    /// One entry per element covering `range`, in order.
+    pub entries: Vec<AuditEntry<KzgEval<E>>>,
+}
+
+/// Outcome of the check of one element in an [`AuditLog`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "F: CanonicalSerialize + CanonicalDeserialize")]
+pub struct AuditEntry<F> {
+    /// Input point at which the polynomial was opened.
+    #[serde(with = "canonical")]
+    pub point: F,
+    /// Claimed evaluation at `point`, decoded from the payload bytes.
+    #[serde(with = "canonical")]
+    pub eval: F,
+    /// Whether the KZG opening proof for `point` verified.
+    pub verified: bool,
+}
+
+/// A payload proof bundled with a proof that the commitment to the relevant
+/// polynomial is included in the merkle tree with root
+/// [`Advz::poly_commits_merkle_root`].
+///
+/// Self-contained: verifiable against the merkle root alone. Made by
+/// [`Advz::payload_proof_bundled`].
+#[derive(Derivative, Deserialize, Serialize)]
+#[serde(bound = "PROOF: Serialize + DeserializeOwned, \
+                 Output<H>: Serialize + for<'a> Deserialize<'a>")]
+#[derivative(
+    Clone(bound = "PROOF: Clone"),
+    Debug(bound = "PROOF: Debug"),
+    Eq(bound = "PROOF: Eq"),
+    PartialEq(bound = "PROOF: PartialEq")
+)]
+pub struct BundledProof<PROOF, E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    proof: PROOF,
+    #[serde(with = "canonical")]
+    poly_commit: KzgCommit<E>,
+    inclusion_proof: PolyCommitsMerkleTreeProof<E, H>,
+}
+
+/// A payload proof bundled with the minimal common data needed to verify it
+/// against the payload commitment [`VidScheme::Commit`] alone.
+///
+/// Holds the commitment to the polynomial containing the proved range and
+/// the hashes of every other polynomial commitment, from which the verifier
+/// recomputes the payload commitment. Made by
+/// [`Advz::payload_proof_commit_bound`] from the payload and its commitment,
+/// without the full [`Common`].
+#[derive(Derivative, Deserialize, Serialize)]
+#[serde(bound = "PROOF: Serialize + DeserializeOwned")]
+#[derivative(
+    Clone(bound = "PROOF: Clone"),
+    Debug(bound = "PROOF: Debug"),
+    Eq(bound = "PROOF: Eq"),
+    PartialEq(bound = "PROOF: PartialEq")
+)]
+pub struct CommitBoundProof<PROOF, E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    proof: PROOF,
+    #[serde(with = "canonical")]
+    poly_commit: KzgCommit<E>,
+    // hashes of all poly commits except `poly_commit`, in order
+    #[serde(with = "canonical")]
+    other_poly_commit_hashes: Vec<HasherNode<H>>,
+    #[serde(with = "canonical")]
+    header_digest: Option<HasherNode<H>>,
+    bytes_len: usize,
+}
+
+/// Same as [`Statement`] except that the payload is committed by the root of
+/// the merkle tree [`Advz::poly_commits_merkle_root`].
+pub struct BundledStatement<'a, H>
+where
+    H: HasherDigest,
+{
+    /// The subslice `payload[range.start..range.end]` from a call to
+    /// [`PayloadProver::payload_proof`].
+    pub payload_subslice: &'a [u8],
+    /// The range used to make [`Self::payload_subslice`].
+    pub range: Range<usize>,
+    /// Merkle root against which the proof will be checked. Under
+    /// [`CommitVersion::V3`] it is recovered from the payload commitment via
+    /// [`Advz::commit_poly_commits_root`].
+    ///
+    /// [`CommitVersion::V3`]: super::CommitVersion::V3
+    pub root: &'a HasherNode<H>,
+}
+
+impl<'a, H> Clone for BundledStatement<'a, H>
+where
+    H: HasherDigest,
+{
+    fn clone(&self) -> Self {
+        Self {
+            payload_subslice: self.payload_subslice,
+            range: self.range.clone(),
+            root: self.root,
         }
-        Ok(())
     }
+}
 
-    fn check_stmt_proof_consistency(
-        stmt: &Statement<Self>,
-        proof_range: &Range<usize>,
-    ) -> VidResult<()> {
-        if stmt.range.is_empty() {
+/// Verification of payload proofs bundled with a poly commit inclusion proof.
+pub trait PayloadVerifyBundled<E, H, PROOF>: PayloadProver<PROOF>
+where
+    E: Pairing,
+    H: HasherDigest,
+    PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+{
+    /// Verify a proof made by [`Advz::payload_proof_bundled`] against
+    /// `stmt.root` alone.
+    ///
+    /// The root does not bind the payload byte length, so a range in the
+    /// zero padding of the final polynomial may verify.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] under the conditions of
+    /// [`PayloadProver::payload_verify`] that do not depend on `common`.
+    fn payload_verify_bundled(
+        &self,
+        stmt: BundledStatement<H>,
+        proof: &BundledProof<PROOF, E, H>,
+    ) -> VidResult<Result<(), ()>>;
+}
+
+/// Verification of payload proofs bundled with the minimal common data for
+/// the payload commitment.
+pub trait PayloadVerifyCommitBound<E, H, PROOF>: PayloadProver<PROOF>
+where
+    E: Pairing,
+    H: HasherDigest,
+    PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+{
+    /// Verify a proof made by [`Advz::payload_proof_commit_bound`] that
+    /// `payload_subslice` is at `range` of the payload committed to by
+    /// `commit`, without the full common data.
+    ///
+    /// The commitment does not bind the payload byte length, so a range in
+    /// the zero padding of the final polynomial may verify.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] under the conditions of
+    /// [`PayloadProver::payload_verify`] that do not depend on `common`.
+    fn payload_verify_commit_bound(
+        &self,
+        payload_subslice: &[u8],
+        range: Range<usize>,
+        commit: &<Self as VidScheme>::Commit,
+        proof: &CommitBoundProof<PROOF, E, H>,
+    ) -> VidResult<Result<(), ()>>;
+}
+
+/// Verification of payload proofs against a [`CompactCommon`].
+pub trait PayloadVerifyCompact<E, H, PROOF>: PayloadProver<PROOF>
+where
+    E: Pairing,
+    H: HasherDigest,
+    PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+{
+    /// Same as [`PayloadProver::payload_verify`] except that `stmt` holds a
+    /// [`CompactCommon`].
+    ///
+    /// # Errors
+    /// In addition to the errors of [`PayloadProver::payload_verify`], return
+    /// [`VidError::Argument`] if `stmt.poly_commit` is inconsistent with
+    /// `stmt.common`.
+    fn payload_verify_compact(
+        &self,
+        stmt: CompactStatement<E, H>,
+        proof: &PROOF,
+    ) -> VidResult<Result<(), ()>>;
+}
+
+impl<P> SmallRangeProof<P> {
+    /// Cheap structural checks that do not require `commit` or `common`.
+    ///
+    /// Useful to reject a malformed proof before fetching `common` or doing
+    /// any expensive cryptographic verification. Passing these checks does not
+    /// imply that the proof is valid.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if any of the following hold:
+    /// - `chunk_range` is empty or spans multiple polynomials.
+    /// - the number of KZG proofs differs from the number of elements covered
+    ///   by `chunk_range`.
+    /// - `prefix_bytes` or `suffix_bytes` are inconsistent with the element
+    ///   boundaries around `chunk_range`.
+    pub fn validate_structure<E, H>(&self, params: &Advz<E, H>) -> VidResult<()>
+    where
+        E: Pairing,
+        H: HasherDigest,
+    {
+        if self.chunk_range.is_empty() {
             return Err(VidError::Argument(format!(
-                "empty range ({},{})",
-                stmt.range.start, stmt.range.end
+                "empty range ({}..{})",
+                self.chunk_range.start, self.chunk_range.end
             )));
         }
-        if stmt.payload_subslice.len() != stmt.range.len() {
+        let range_elem = params.range_byte_to_elem(&self.chunk_range);
+        check_range_poly(&params.range_elem_to_poly(&range_elem))?;
+        if self.proofs.len() != range_elem.len() {
             return Err(VidError::Argument(format!(
-                "payload_subslice length {} inconsistent with range length {}",
-                stmt.payload_subslice.len(),
-                stmt.range.len()
+                "proof len {} differs from range elem len {}",
+                self.proofs.len(),
+                range_elem.len()
             )));
         }
-        if stmt.range != *proof_range {
+        let range_elem_byte = params.range_elem_to_byte(&range_elem);
+        let expected_prefix_len = self.chunk_range.start - range_elem_byte.start;
+        if self.prefix_bytes.len() != expected_prefix_len {
             return Err(VidError::Argument(format!(
-                "statement range ({},{}) differs from proof range ({},{})",
-                stmt.range.start, stmt.range.end, proof_range.start, proof_range.end,
+                "prefix bytes len {} differs from expected len {}",
+                self.prefix_bytes.len(),
+                expected_prefix_len
+            )));
+        }
+        // suffix might be truncated by the end of the payload
+        let max_suffix_len = range_elem_byte.end - self.chunk_range.end;
+        if self.suffix_bytes.len() > max_suffix_len {
+            return Err(VidError::Argument(format!(
+                "suffix bytes len {} exceeds max len {}",
+                self.suffix_bytes.len(),
+                max_suffix_len
             )));
         }
         Ok(())
     }
+
+    /// Indices into `common.poly_commits` of the polynomial commitments needed
+    /// to verify `self`, so that a verifier can fetch only those.
+    ///
+    /// Empty if `self` proves an empty range.
+    pub fn dependent_poly_indices<E, H>(&self, params: &Advz<E, H>) -> Vec<usize>
+    where
+        E: Pairing,
+        H: HasherDigest,
+    {
+        dependent_poly_indices(params, &self.chunk_range)
+    }
 }
 
-fn range_coarsen(range: &Range<usize>, denominator: usize) -> Range<usize> {
-    assert!(!range.is_empty(), "{:?}", range);
-    Range {
-        start: index_coarsen(range.start, denominator),
-        end: index_coarsen(range.end - 1, denominator) + 1,
+impl<F> LargeRangeProof<F> {
+    /// Indices into `common.poly_commits` of the polynomial commitments needed
+    /// to verify `self`, so that a verifier can fetch only those.
+    ///
+    /// Empty if `self` proves an empty range.
+    pub fn dependent_poly_indices<E, H>(&self, params: &Advz<E, H>) -> Vec<usize>
+    where
+        E: Pairing,
+        H: HasherDigest,
+    {
+        dependent_poly_indices(params, &self.chunk_range)
     }
 }
 
-fn range_refine(range: &Range<usize>, multiplier: usize) -> Range<usize> {
-    assert!(!range.is_empty(), "{:?}", range);
-    Range {
-        start: index_refine(range.start, multiplier),
-        end: index_refine(range.end, multiplier),
+impl<P> CanonicalSerialize for SmallRangeProof<P>
+where
+    P: CanonicalSerialize,
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_small_range_proof(self, &mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        small_range_proof_serialized_size(self, compress)
     }
 }
 
-fn index_coarsen(index: usize, denominator: usize) -> usize {
-    index / denominator
+impl<P> Valid for SmallRangeProof<P>
+where
+    P: Valid,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.proofs.check()
+    }
 }
 
-fn index_refine(index: usize, multiplier: usize) -> usize {
-    index * multiplier
+impl<P> CanonicalDeserialize for SmallRangeProof<P>
+where
+    P: CanonicalDeserialize,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            proofs: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            prefix_bytes: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            suffix_bytes: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            chunk_range: deserialize_range(&mut reader, compress, validate)?,
+        })
+    }
 }
 
-fn check_range_nonempty_and_inside_payload(payload: &[u8], range: &Range<usize>) -> VidResult<()> {
-    if range.is_empty() {
-        return Err(VidError::Argument(format!(
-            "empty range ({}..{})",
-            range.start, range.end
-        )));
+impl<P, const N: usize> CanonicalSerialize for SmallRangeProofN<P, N>
+where
+    P: CanonicalSerialize,
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_small_range_proof(self, &mut writer, compress)
     }
-    if range.end > payload.len() {
-        return Err(VidError::Argument(format!(
-            "range ({}..{}) out of bounds for payload len {}",
-            range.start,
-            range.end,
-            payload.len()
-        )));
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        small_range_proof_serialized_size(self, compress)
     }
-    Ok(())
 }
 
-fn check_range_poly(range_poly: &Range<usize>) -> VidResult<()> {
-    // TODO TEMPORARY: forbid requests that span multiple polynomials
-    if range_poly.len() != 1 {
-        return Err(VidError::Argument(format!(
-            "request spans {} polynomials, expect 1",
-            range_poly.len()
-        )));
+impl<P, const N: usize> Valid for SmallRangeProofN<P, N>
+where
+    P: Valid,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.proofs.iter().try_for_each(Valid::check)
     }
-    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::vid::{
-        advz::{
-            bytes_to_field::elem_byte_capacity,
-            payload_prover::{LargeRangeProof, SmallRangeProof, Statement},
-            tests::*,
-            *,
-        },
-        payload_prover::PayloadProver,
-    };
-    use ark_bls12_381::Bls12_381;
-    use ark_std::{ops::Range, print, println, rand::Rng};
-    use sha2::Sha256;
+impl<P, const N: usize> CanonicalDeserialize for SmallRangeProofN<P, N>
+where
+    P: CanonicalDeserialize,
+{
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        SmallRangeProof::deserialize_with_mode(reader, compress, validate)?
+            .try_into()
+            .map_err(|_| SerializationError::InvalidData)
+    }
+}
 
-    fn correctness_generic<E, H>()
-    where
-        E: Pairing,
-        H: HasherDigest,
+impl<F> CanonicalSerialize for LargeRangeProof<F>
+where
+    F: CanonicalSerialize,
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.prefix_elems
+            .serialize_with_mode(&mut writer, compress)?;
+        self.suffix_elems
+            .serialize_with_mode(&mut writer, compress)?;
+        self.prefix_bytes
+            .serialize_with_mode(&mut writer, compress)?;
+        self.suffix_bytes
+            .serialize_with_mode(&mut writer, compress)?;
+        serialize_range(&self.chunk_range, &mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.prefix_elems.serialized_size(compress)
+            + self.suffix_elems.serialized_size(compress)
+            + self.prefix_bytes.serialized_size(compress)
+            + self.suffix_bytes.serialized_size(compress)
+            + range_serialized_size(compress)
+    }
+}
+
+impl<F> Valid for LargeRangeProof<F>
+where
+    F: Valid,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.prefix_elems.check()?;
+        self.suffix_elems.check()
+    }
+}
+
+impl<F> CanonicalDeserialize for LargeRangeProof<F>
+where
+    F: CanonicalDeserialize,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            prefix_elems: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            suffix_elems: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            prefix_bytes: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            suffix_bytes: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            chunk_range: deserialize_range(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+impl<E> CanonicalSerialize for SingleOpeningProof<E>
+where
+    E: Pairing,
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.quotient_commit
+            .serialize_with_mode(&mut writer, compress)?;
+        self.poly_eval.serialize_with_mode(&mut writer, compress)?;
+        self.quotient_eval
+            .serialize_with_mode(&mut writer, compress)?;
+        self.proof.serialize_with_mode(&mut writer, compress)?;
+        self.prefix_bytes
+            .serialize_with_mode(&mut writer, compress)?;
+        self.suffix_bytes
+            .serialize_with_mode(&mut writer, compress)?;
+        serialize_range(&self.chunk_range, &mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.quotient_commit.serialized_size(compress)
+            + self.poly_eval.serialized_size(compress)
+            + self.quotient_eval.serialized_size(compress)
+            + self.proof.serialized_size(compress)
+            + self.prefix_bytes.serialized_size(compress)
+            + self.suffix_bytes.serialized_size(compress)
+            + range_serialized_size(compress)
+    }
+}
+
+impl<E> Valid for SingleOpeningProof<E>
+where
+    E: Pairing,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.quotient_commit.check()?;
+        self.poly_eval.check()?;
+        self.quotient_eval.check()?;
+        self.proof.check()
+    }
+}
+
+impl<E> CanonicalDeserialize for SingleOpeningProof<E>
+where
+    E: Pairing,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            quotient_commit: CanonicalDeserialize::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            poly_eval: CanonicalDeserialize::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            quotient_eval: CanonicalDeserialize::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            proof: CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+            prefix_bytes: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            suffix_bytes: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            chunk_range: deserialize_range(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+#[cfg(all(feature = "std", feature = "parallel"))]
+impl<PROOF> ProofHandle<PROOF> {
+    /// Block until the proof is ready and return it.
+    ///
+    /// # Panics
+    ///
+    /// Resume the panic if computing the proof panicked.
+    pub fn wait(self) -> VidResult<PROOF> {
+        Self::unwind(self.rx.recv().ok())
+    }
+
+    /// Return the proof if it is ready, otherwise return `self` so the caller
+    /// can poll again later.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`ProofHandle::wait`].
+    pub fn try_wait(self) -> Result<VidResult<PROOF>, Self> {
+        match self.rx.try_recv() {
+            Ok(res) => Ok(Self::unwind(Some(res))),
+            Err(std::sync::mpsc::TryRecvError::Empty) => Err(self),
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => Ok(Self::unwind(None)),
+        }
+    }
+
+    fn unwind(res: Option<std::thread::Result<VidResult<PROOF>>>) -> VidResult<PROOF> {
+        match res {
+            Some(Ok(proof)) => proof,
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            // the job always sends before it is dropped
+            None => Err(VidError::Internal(anyhow::anyhow!(
+                "proof job exited without a result"
+            ))),
+        }
+    }
+}
+
+impl<'a, E, H> VerifyCache<'a, E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Same as [`PayloadProver::payload_verify`] except that the outcome is
+    /// answered from the cache if present and cached otherwise.
+    pub fn payload_verify<PROOF>(
+        &mut self,
+        stmt: Statement<Advz<E, H>>,
+        proof: &PROOF,
+    ) -> VidResult<Result<(), ()>>
+    where
+        Advz<E, H>: PayloadProver<PROOF>,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned + CanonicalSerialize,
     {
-        // play with these items
-        let (payload_chunk_size, num_storage_nodes) = (4, 6);
-        let num_polys = 3;
+        self.advz.check_common_params(stmt.common)?;
+        let key = Self::key(&stmt, proof)?;
+        self.tick += 1;
+        if let Some((outcome, last_used)) = self.entries.get_mut(&key) {
+            self.lru.remove(last_used);
+            self.lru.insert(self.tick, key);
+            *last_used = self.tick;
+            self.metrics.hits += 1;
+            return Ok(if *outcome { Ok(()) } else { Err(()) });
+        }
+        self.metrics.misses += 1;
+        let outcome = self.advz.payload_verify(stmt, proof)?;
+        if self.capacity > 0 {
+            self.evict_to(self.capacity - 1);
+            self.entries.insert(key, (outcome.is_ok(), self.tick));
+            self.lru.insert(self.tick, key);
+        }
+        Ok(outcome)
+    }
+
+    /// Set the maximum number of cached outcomes, evicting the least recently
+    /// used as needed. Zero disables caching.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict_to(capacity);
+    }
+
+    /// Number of cached outcomes.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no outcome is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all cached outcomes. Metrics are kept.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.lru.clear();
+    }
+
+    /// Hit, miss and eviction counts since creation.
+    pub fn metrics(&self) -> VerifyCacheMetrics {
+        self.metrics
+    }
+
+    fn evict_to(&mut self, len: usize) {
+        while self.entries.len() > len {
+            let (&last_used, &lru_key) =
+                self.lru.iter().next().expect("entries should be nonempty");
+            self.lru.remove(&last_used);
+            self.entries.remove(&lru_key);
+            self.metrics.evictions += 1;
+        }
+    }
+
+    /// Hash of everything the outcome of verifying `proof` against `stmt`
+    /// depends on. Variable-length items are length-prefixed.
+    fn key<PROOF>(stmt: &Statement<Advz<E, H>>, proof: &PROOF) -> VidResult<Output<H>>
+    where
+        PROOF: CanonicalSerialize,
+    {
+        let mut hasher = H::new();
+        for item in [
+            core::any::type_name::<PROOF>().as_bytes(),
+            stmt.commit.as_ref(),
+            stmt.payload_subslice,
+        ] {
+            hasher.update((item.len() as u64).to_le_bytes());
+            hasher.update(item);
+        }
+        hasher.update((stmt.range.start as u64).to_le_bytes());
+        hasher.update((stmt.range.end as u64).to_le_bytes());
+        hasher.update((stmt.common.bytes_len as u64).to_le_bytes());
+        stmt.common
+            .header_digest
+            .serialize_uncompressed(&mut hasher)
+            .map_err(vid)?;
+        proof.serialize_uncompressed(&mut hasher).map_err(vid)?;
+        Ok(hasher.finalize())
+    }
+}
+
+impl<'a, E, H> MixedVerifier<'a, E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Queue a [`SmallRangeProof`] whose pairing checks are deferred to
+    /// [`MixedVerifier::finalize`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the same conditions as
+    /// [`PayloadProver::payload_verify`].
+    pub fn add_small(
+        &mut self,
+        stmt: Statement<Advz<E, H>>,
+        proof: &SmallRangeProof<KzgProof<E>>,
+    ) -> VidResult<()> {
+        let (poly_index, points, data_elems) =
+            self.advz
+                .small_range_proof_inputs(stmt.payload_subslice, &stmt.range, proof)?;
+        self.advz.check_common_params(stmt.common)?;
+        self.advz
+            .check_common_commit_consistency(stmt.common, stmt.commit)?;
+        self.poly_commits
+            .extend(ark_std::iter::repeat(stmt.common.poly_commits[poly_index]).take(points.len()));
+        self.points.extend(points);
+        self.evals.extend(data_elems);
+        self.proofs.extend(proof.proofs.iter().cloned());
+        Ok(())
+    }
+
+    /// Verify a [`LargeRangeProof`] immediately and record the result for
+    /// [`MixedVerifier::finalize`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the same conditions as
+    /// [`PayloadProver::payload_verify`].
+    pub fn add_large(
+        &mut self,
+        stmt: Statement<Advz<E, H>>,
+        proof: &LargeRangeProof<KzgEval<E>>,
+    ) -> VidResult<()> {
+        if self.advz.payload_verify(stmt, proof)?.is_err() {
+            self.large_failed = true;
+        }
+        Ok(())
+    }
+
+    /// Return `Ok(())` if every queued proof is valid.
+    ///
+    /// Checks all queued [`SmallRangeProof`]s via a random linear combination
+    /// with randomizers drawn from `rng`, costing one multi-pairing.
+    pub fn finalize<R>(self, rng: &mut R) -> VidResult<Result<(), ()>>
+    where
+        R: RngCore + CryptoRng,
+    {
+        if self.large_failed {
+            return Ok(Err(()));
+        }
+        if self.points.is_empty() {
+            return Ok(Ok(()));
+        }
+        Ok(UnivariateKzgPCS::batch_verify(
+            &self.advz.vk,
+            &self.poly_commits,
+            &self.points,
+            &self.evals,
+            &self.proofs,
+            rng,
+        )
+        .map_err(VidError::Pcs)?
+        .then_some(())
+        .ok_or(()))
+    }
+}
+
+impl<'a, E> PayloadVerifyStream<'a, E>
+where
+    E: Pairing,
+{
+    /// Consume the next `bytes` of the payload subslice.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if the total number of bytes supplied
+    /// exceeds the length of the range.
+    pub fn update(&mut self, bytes: &[u8]) -> VidResult<()> {
+        if bytes.len() > self.bytes_remaining {
+            return Err(VidError::Argument(format!(
+                "{} bytes exceeds remaining range len {}",
+                bytes.len(),
+                self.bytes_remaining
+            )));
+        }
+        self.bytes_remaining -= bytes.len();
+        self.pending_bytes.extend_from_slice(bytes);
+
+        // absorb all complete elems
+        let elem_byte_len = elem_byte_capacity::<KzgEval<E>>();
+        let num_elems = self.pending_bytes.len() / elem_byte_len;
+        let complete_bytes_len = num_elems * elem_byte_len;
+        let elems: Vec<_> =
+            bytes_to_field::<_, KzgEval<E>>(&self.pending_bytes[..complete_bytes_len]).collect();
+        self.pending_bytes.drain(..complete_bytes_len);
+        self.absorb(&elems)
+    }
+
+    /// Complete verification once all bytes of the subslice have been
+    /// supplied.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if fewer bytes than the length of the
+    /// range were supplied.
+    pub fn finalize(mut self) -> VidResult<Result<(), ()>> {
+        if self.bytes_remaining != 0 {
+            return Err(VidError::Argument(format!(
+                "{} bytes of range remain unconsumed",
+                self.bytes_remaining
+            )));
+        }
+        self.pending_bytes
+            .extend_from_slice(&self.proof.suffix_bytes);
+        let elems: Vec<_> = bytes_to_field::<_, KzgEval<E>>(&self.pending_bytes)
+            .chain(self.proof.suffix_elems.iter().cloned())
+            .collect();
+        self.absorb(&elems)?;
+
+        if self.acc.into_affine() != self.poly_commit.0 {
+            return Ok(Err(()));
+        }
+        Ok(Ok(()))
+    }
+
+    fn absorb(&mut self, elems: &[KzgEval<E>]) -> VidResult<()> {
+        let end_elem = self.next_elem + elems.len();
+        if end_elem > self.lagrange_commits.len() {
+            return Err(VidError::Argument(format!(
+                "elems len {} exceeds polynomial len {}",
+                end_elem,
+                self.lagrange_commits.len()
+            )));
+        }
+        self.acc += E::G1::msm_unchecked(&self.lagrange_commits[self.next_elem..end_elem], elems);
+        self.next_elem = end_elem;
+        Ok(())
+    }
+}
+
+impl<E, H> PayloadProver<SmallRangeProof<KzgProof<E>>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "payload_proof",
+            level = "debug",
+            skip_all,
+            fields(
+                proof_type = "small",
+                payload_byte_len = payload.as_ref().len(),
+                range_start = range.start,
+                range_len = range.len()
+            )
+        )
+    )]
+    fn payload_proof<B>(
+        &self,
+        payload: B,
+        range: Range<usize>,
+    ) -> VidResult<SmallRangeProof<KzgProof<E>>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        self.check_payload_proof_range(payload, &range)?;
+
+        // index conversion
+        let range_elem = self.range_byte_to_elem(&range);
+        let range_poly = self.range_elem_to_poly(&range_elem);
+        let range_elem_byte = self.range_elem_to_byte_clamped(&range_elem, payload.len());
+
+        check_range_poly(&range_poly)?;
+
+        // grab the polynomial that contains `range`
+        // for many proofs from one payload see `Advz::offline_precompute`
+        let polynomial = self.polynomial(self.poly_elems(payload, range_poly.start).into_iter());
+
+        let points = self.range_points(&range);
+        let (proofs, _evals) =
+            UnivariateKzgPCS::multi_open(&self.ck, &polynomial, &points).map_err(VidError::Pcs)?;
+
+        Ok(SmallRangeProof {
+            proofs,
+            prefix_bytes: payload[range_elem_byte.start..range.start].to_vec(),
+            suffix_bytes: payload[range.end..range_elem_byte.end].to_vec(),
+            chunk_range: range,
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "payload_verify",
+            level = "debug",
+            skip_all,
+            fields(
+                proof_type = "small",
+                range_start = stmt.range.start,
+                range_len = stmt.range.len()
+            )
+        )
+    )]
+    fn payload_verify(
+        &self,
+        stmt: Statement<Self>,
+        proof: &SmallRangeProof<KzgProof<E>>,
+    ) -> VidResult<Result<(), ()>> {
+        Ok(match self.small_range_proof_first_failure(&stmt, proof)? {
+            Some(_) => Err(()),
+            None => Ok(()),
+        })
+    }
+}
+
+impl<E, H, const N: usize> PayloadProver<SmallRangeProofN<KzgProof<E>, N>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Same as for [`SmallRangeProof`] except that `range` must cover
+    /// exactly `N` elements, else return [`VidError::Argument`].
+    fn payload_proof<B>(
+        &self,
+        payload: B,
+        range: Range<usize>,
+    ) -> VidResult<SmallRangeProofN<KzgProof<E>, N>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let proof: SmallRangeProof<_> = self.payload_proof(payload, range)?;
+        proof.try_into()
+    }
+
+    fn payload_verify(
+        &self,
+        stmt: Statement<Self>,
+        proof: &SmallRangeProofN<KzgProof<E>, N>,
+    ) -> VidResult<Result<(), ()>> {
+        Ok(match self.small_range_proof_first_failure(&stmt, proof)? {
+            Some(_) => Err(()),
+            None => Ok(()),
+        })
+    }
+}
+
+impl<E, H> PayloadVerifyVerbose<SmallRangeProof<KzgProof<E>>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn payload_verify_verbose(
+        &self,
+        stmt: Statement<Self>,
+        proof: &SmallRangeProof<KzgProof<E>>,
+    ) -> VidResult<Result<(), VerifyFailure>> {
+        if let Err(failure) = check_subslice_len(&stmt) {
+            return Ok(Err(failure));
+        }
+        if !proof.chunk_range.is_empty() {
+            let expected = self.range_byte_to_elem(&proof.chunk_range).len();
+            if proof.proofs.len() != expected {
+                return Ok(Err(VerifyFailure::LengthMismatch {
+                    expected,
+                    actual: proof.proofs.len(),
+                }));
+            }
+        }
+        Ok(match self.small_range_proof_first_failure(&stmt, proof)? {
+            Some(elem_index) => Err(VerifyFailure::KzgPairing { elem_index }),
+            None => Ok(()),
+        })
+    }
+}
+
+impl<E, H> PayloadVerifyCompact<E, H, SmallRangeProof<KzgProof<E>>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn payload_verify_compact(
+        &self,
+        stmt: CompactStatement<E, H>,
+        proof: &SmallRangeProof<KzgProof<E>>,
+    ) -> VidResult<Result<(), ()>> {
+        let (poly_index, points, data_elems) =
+            self.small_range_proof_inputs(stmt.payload_subslice, &stmt.range, proof)?;
+        self.check_compact_stmt(&stmt, poly_index)?;
+        Ok(
+            match self.small_range_proof_first_failure_at(
+                stmt.poly_commit,
+                &points,
+                &data_elems,
+                proof,
+            )? {
+                Some(_) => Err(()),
+                None => Ok(()),
+            },
+        )
+    }
+}
+
+impl<E, H> PayloadProver<LargeRangeProof<KzgEval<E>>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "payload_proof",
+            level = "debug",
+            skip_all,
+            fields(
+                proof_type = "large",
+                payload_byte_len = payload.as_ref().len(),
+                range_start = range.start,
+                range_len = range.len()
+            )
+        )
+    )]
+    fn payload_proof<B>(
+        &self,
+        payload: B,
+        range: Range<usize>,
+    ) -> VidResult<LargeRangeProof<KzgEval<E>>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        self.check_payload_proof_range(payload, &range)?;
+
+        // index conversion
+        let range_poly = self.range_byte_to_poly(&range);
+
+        check_range_poly(&range_poly)?;
+
+        let poly_elems = self.poly_elems(payload, range_poly.start);
+        Ok(self.large_range_proof_from_poly_elems(payload, &poly_elems, range))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "payload_verify",
+            level = "debug",
+            skip_all,
+            fields(
+                proof_type = "large",
+                range_start = stmt.range.start,
+                range_len = stmt.range.len()
+            )
+        )
+    )]
+    fn payload_verify(
+        &self,
+        stmt: Statement<Self>,
+        proof: &LargeRangeProof<KzgEval<E>>,
+    ) -> VidResult<Result<(), ()>> {
+        Ok(self
+            .payload_verify_returning_commit(stmt, proof)?
+            .map(|_| ()))
+    }
+}
+
+impl<E, H> PayloadVerifyVerbose<LargeRangeProof<KzgEval<E>>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn payload_verify_verbose(
+        &self,
+        stmt: Statement<Self>,
+        proof: &LargeRangeProof<KzgEval<E>>,
+    ) -> VidResult<Result<(), VerifyFailure>> {
+        if let Err(failure) = check_subslice_len(&stmt) {
+            return Ok(Err(failure));
+        }
+        let range = stmt.range.clone();
+        Ok(self
+            .payload_verify(stmt, proof)?
+            .map_err(|_| VerifyFailure::CommitmentMismatch {
+                poly_index: self.range_byte_to_poly(&range).start,
+            }))
+    }
+}
+
+impl<E, H> PayloadVerifyBundled<E, H, SmallRangeProof<KzgProof<E>>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn payload_verify_bundled(
+        &self,
+        stmt: BundledStatement<H>,
+        proof: &BundledProof<SmallRangeProof<KzgProof<E>>, E, H>,
+    ) -> VidResult<Result<(), ()>> {
+        let (poly_index, points, data_elems) =
+            self.small_range_proof_inputs(stmt.payload_subslice, &stmt.range, &proof.proof)?;
+        if Self::verify_poly_commit_inclusion(
+            stmt.root,
+            poly_index,
+            &proof.poly_commit,
+            &proof.inclusion_proof,
+        )?
+        .is_err()
+        {
+            return Ok(Err(()));
+        }
+        Ok(
+            match self.small_range_proof_first_failure_at(
+                &proof.poly_commit,
+                &points,
+                &data_elems,
+                &proof.proof,
+            )? {
+                Some(_) => Err(()),
+                None => Ok(()),
+            },
+        )
+    }
+}
+
+impl<E, H> PayloadVerifyCommitBound<E, H, SmallRangeProof<KzgProof<E>>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn payload_verify_commit_bound(
+        &self,
+        payload_subslice: &[u8],
+        range: Range<usize>,
+        commit: &<Self as VidScheme>::Commit,
+        proof: &CommitBoundProof<SmallRangeProof<KzgProof<E>>, E, H>,
+    ) -> VidResult<Result<(), ()>> {
+        let (poly_index, points, data_elems) =
+            self.small_range_proof_inputs(payload_subslice, &range, &proof.proof)?;
+        if self
+            .verify_commit_bound_inclusion(commit, poly_index, proof)?
+            .is_err()
+        {
+            return Ok(Err(()));
+        }
+        Ok(
+            match self.small_range_proof_first_failure_at(
+                &proof.poly_commit,
+                &points,
+                &data_elems,
+                &proof.proof,
+            )? {
+                Some(_) => Err(()),
+                None => Ok(()),
+            },
+        )
+    }
+}
+
+impl<E, H> PayloadVerifyCommitBound<E, H, LargeRangeProof<KzgEval<E>>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn payload_verify_commit_bound(
+        &self,
+        payload_subslice: &[u8],
+        range: Range<usize>,
+        commit: &<Self as VidScheme>::Commit,
+        proof: &CommitBoundProof<LargeRangeProof<KzgEval<E>>, E, H>,
+    ) -> VidResult<Result<(), ()>> {
+        self.check_stmt_proof_consistency(payload_subslice, &range, &proof.proof.chunk_range)?;
+        let range_poly = self.range_byte_to_poly(&proof.proof.chunk_range);
+        check_range_poly(&range_poly)?;
+        if self
+            .verify_commit_bound_inclusion(commit, range_poly.start, proof)?
+            .is_err()
+        {
+            return Ok(Err(()));
+        }
+
+        let poly_commit = self.large_range_proof_poly_commit(payload_subslice, &proof.proof)?;
+        Ok((poly_commit == proof.poly_commit).then_some(()).ok_or(()))
+    }
+}
+
+impl<E, H> PayloadVerifyBundled<E, H, LargeRangeProof<KzgEval<E>>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn payload_verify_bundled(
+        &self,
+        stmt: BundledStatement<H>,
+        proof: &BundledProof<LargeRangeProof<KzgEval<E>>, E, H>,
+    ) -> VidResult<Result<(), ()>> {
+        self.check_stmt_proof_consistency(
+            stmt.payload_subslice,
+            &stmt.range,
+            &proof.proof.chunk_range,
+        )?;
+        let range_poly = self.range_byte_to_poly(&proof.proof.chunk_range);
+        check_range_poly(&range_poly)?;
+        if Self::verify_poly_commit_inclusion(
+            stmt.root,
+            range_poly.start,
+            &proof.poly_commit,
+            &proof.inclusion_proof,
+        )?
+        .is_err()
+        {
+            return Ok(Err(()));
+        }
+
+        let poly_commit =
+            self.large_range_proof_poly_commit(stmt.payload_subslice, &proof.proof)?;
+        Ok((poly_commit == proof.poly_commit).then_some(()).ok_or(()))
+    }
+}
+
+impl<E, H> PayloadVerifyCompact<E, H, LargeRangeProof<KzgEval<E>>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn payload_verify_compact(
+        &self,
+        stmt: CompactStatement<E, H>,
+        proof: &LargeRangeProof<KzgEval<E>>,
+    ) -> VidResult<Result<(), ()>> {
+        self.check_stmt_proof_consistency(stmt.payload_subslice, &stmt.range, &proof.chunk_range)?;
+        let range_poly = self.range_byte_to_poly(&proof.chunk_range);
+        check_range_poly(&range_poly)?;
+        self.check_compact_stmt(&stmt, range_poly.start)?;
+
+        let poly_commit = self.large_range_proof_poly_commit(stmt.payload_subslice, proof)?;
+        Ok((poly_commit == *stmt.poly_commit).then_some(()).ok_or(()))
+    }
+}
+
+impl<E, H> PayloadProver<SingleOpeningProof<E>> for Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    fn payload_proof<B>(&self, payload: B, range: Range<usize>) -> VidResult<SingleOpeningProof<E>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        self.check_payload_proof_range(payload, &range)?;
+
+        // index conversion
+        let range_elem = self.range_byte_to_elem(&range);
+        let range_poly = self.range_elem_to_poly(&range_elem);
+        let start_namespace_byte = self.index_poly_to_byte(range_poly.start);
+        let offset_elem = range_elem.start - self.index_byte_to_elem(start_namespace_byte);
+        let range_elem_byte = self.range_elem_to_byte_clamped(&range_elem, payload.len());
+
+        check_range_poly(&range_poly)?;
+
+        let poly_elems = self.poly_elems(payload, range_poly.start);
+        let data_elems = &poly_elems[offset_elem..offset_elem + range_elem.len()];
+        let polynomial = self.polynomial(poly_elems.iter());
+        let poly_commit = UnivariateKzgPCS::commit(&self.ck, &polynomial).map_err(VidError::Pcs)?;
+
+        // the remainder of this division is the interpolant of `data_elems`
+        let quotient = &polynomial / &vanishing_polynomial(&self.range_points(&range));
+        let quotient_commit =
+            UnivariateKzgPCS::commit(&self.ck, &quotient).map_err(VidError::Pcs)?;
+
+        let point = Self::single_opening_point(&poly_commit, &quotient_commit, &range, data_elems)?;
+        let (poly_eval, quotient_eval) = (polynomial.evaluate(&point), quotient.evaluate(&point));
+        let combiner = Self::single_opening_combiner(&point, &poly_eval, &quotient_eval)?;
+        let (proof, _eval) =
+            UnivariateKzgPCS::open(&self.ck, &(&polynomial + &(&quotient * combiner)), &point)
+                .map_err(VidError::Pcs)?;
+
+        Ok(SingleOpeningProof {
+            quotient_commit,
+            poly_eval,
+            quotient_eval,
+            proof,
+            prefix_bytes: payload[range_elem_byte.start..range.start].to_vec(),
+            suffix_bytes: payload[range.end..range_elem_byte.end].to_vec(),
+            chunk_range: range,
+        })
+    }
+
+    fn payload_verify(
+        &self,
+        stmt: Statement<Self>,
+        proof: &SingleOpeningProof<E>,
+    ) -> VidResult<Result<(), ()>> {
+        self.check_stmt_proof_consistency(stmt.payload_subslice, &stmt.range, &proof.chunk_range)?;
+
+        // index conversion
+        let range_elem = self.range_byte_to_elem(&proof.chunk_range);
+        let range_poly = self.range_elem_to_poly(&range_elem);
+        let range_elem_byte = self.range_elem_to_byte(&range_elem);
+
+        check_range_poly(&range_poly)?;
+
+        // a misplaced prefix would shift the subslice within its elements
+        let expected_prefix_len = proof.chunk_range.start - range_elem_byte.start;
+        if proof.prefix_bytes.len() != expected_prefix_len {
+            return Err(VidError::Argument(format!(
+                "prefix bytes len {} differs from expected len {}",
+                proof.prefix_bytes.len(),
+                expected_prefix_len
+            )));
+        }
+        let data_elems = self.covering_bytes_to_elems(
+            proof
+                .prefix_bytes
+                .iter()
+                .chain(stmt.payload_subslice)
+                .chain(proof.suffix_bytes.iter()),
+            &range_elem,
+        );
+        if data_elems.len() != range_elem.len() {
+            return Err(VidError::Argument(format!(
+                "data len {} differs from range elem len {}",
+                data_elems.len(),
+                range_elem.len()
+            )));
+        }
+
+        self.check_common_params(stmt.common)?;
+        self.check_common_commit_consistency(stmt.common, stmt.commit)?;
+        let poly_commit = stmt
+            .common
+            .poly_commits
+            .get(range_poly.start)
+            .ok_or_else(|| {
+                VidError::Argument(format!(
+                    "poly index {} out of bounds for common with {} poly commits",
+                    range_poly.start,
+                    stmt.common.poly_commits.len()
+                ))
+            })?;
+
+        let point = Self::single_opening_point(
+            poly_commit,
+            &proof.quotient_commit,
+            &proof.chunk_range,
+            &data_elems,
+        )?;
+        let (interpolant_eval, vanishing_eval) =
+            barycentric_eval(&self.range_points(&proof.chunk_range), &data_elems, &point);
+        if proof.poly_eval - interpolant_eval != vanishing_eval * proof.quotient_eval {
+            return Ok(Err(()));
+        }
+
+        // by linearity, a commitment to `p + c * q`
+        let combiner =
+            Self::single_opening_combiner(&point, &proof.poly_eval, &proof.quotient_eval)?;
+        let combined_commit = KzgCommit::<E>::from(
+            (proof.quotient_commit.0 * combiner + poly_commit.0).into_affine(),
+        );
+        let combined_eval = proof.poly_eval + combiner * proof.quotient_eval;
+        Ok(
+            if UnivariateKzgPCS::verify(
+                &self.vk,
+                &combined_commit,
+                &point,
+                &combined_eval,
+                &proof.proof,
+            )
+            .map_err(VidError::Pcs)?
+            {
+                Ok(())
+            } else {
+                Err(())
+            },
+        )
+    }
+}
+
+impl<E, H> Advz<E, H>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    /// Derive a [`SmallRangeProof`] for `sub_range` from a [`LargeRangeProof`]
+    /// without access to the full payload.
+    ///
+    /// # Arguments
+    ///
+    /// - `large`: a [`LargeRangeProof`] for some range `large_range`.
+    /// - `payload_subslice`: the payload data `payload[large_range]` against
+    ///   which `large` was made.
+    /// - `sub_range`: the range for the new proof. Must lie inside
+    ///   `large_range`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `sub_range` is empty or not contained
+    /// in `large_range`, or if `payload_subslice` is inconsistent with
+    /// `large_range`.
+    pub fn small_from_large(
+        &self,
+        large: &LargeRangeProof<KzgEval<E>>,
+        payload_subslice: &[u8],
+        sub_range: Range<usize>,
+    ) -> VidResult<SmallRangeProof<KzgProof<E>>> {
+        let large_range = &large.chunk_range;
+        self.check_proof_range_len(&sub_range)?;
+        if sub_range.is_empty() {
+            return Err(VidError::Argument(format!(
+                "empty range ({}..{})",
+                sub_range.start, sub_range.end
+            )));
+        }
+        if sub_range.start < large_range.start || sub_range.end > large_range.end {
+            return Err(VidError::Argument(format!(
+                "sub range ({}..{}) not contained in proof range ({}..{})",
+                sub_range.start, sub_range.end, large_range.start, large_range.end
+            )));
+        }
+        if payload_subslice.len() != large_range.len() {
+            return Err(VidError::Argument(format!(
+                "payload_subslice length {} inconsistent with proof range length {}",
+                payload_subslice.len(),
+                large_range.len()
+            )));
+        }
+
+        // index conversion
+        let range_elem = self.range_byte_to_elem(&sub_range);
+        let range_poly = self.range_elem_to_poly(&range_elem);
+
+        check_range_poly(&range_poly)?;
+
+        // bytes of `large` cover its elements, starting at `large_bytes_start`
+        let large_bytes: Vec<u8> = large
+            .prefix_bytes
+            .iter()
+            .chain(payload_subslice)
+            .chain(large.suffix_bytes.iter())
+            .cloned()
+            .collect();
+        let large_bytes_start = large_range.start - large.prefix_bytes.len();
+        let range_elem_byte =
+            self.range_elem_to_byte_clamped(&range_elem, large_bytes_start + large_bytes.len());
+
+        // rebuild the polynomial that contains `large_range`
+        let polynomial = self.polynomial(
+            large
+                .prefix_elems
+                .iter()
+                .cloned()
+                .chain(self.covering_bytes_to_elems(
+                    large_bytes.iter(),
+                    &self.range_byte_to_elem(large_range),
+                ))
+                .chain(large.suffix_elems.iter().cloned()),
+        );
+
+        let points = self.range_points(&sub_range);
+
+        let (proofs, _evals) =
+            UnivariateKzgPCS::multi_open(&self.ck, &polynomial, &points).map_err(VidError::Pcs)?;
+
+        Ok(SmallRangeProof {
+            proofs,
+            prefix_bytes: large_bytes
+                [range_elem_byte.start - large_bytes_start..sub_range.start - large_bytes_start]
+                .to_vec(),
+            suffix_bytes: large_bytes
+                [sub_range.end - large_bytes_start..range_elem_byte.end - large_bytes_start]
+                .to_vec(),
+            chunk_range: sub_range,
+        })
+    }
+
+    /// Compute a [`LargeRangeProof`] for each entry of a namespace table.
+    ///
+    /// `table` lists `(namespace id, byte range)` pairs. Payload bytes are
+    /// converted to field elements once and shared among all proofs. Entries
+    /// with an empty range are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if any nonempty range is outside
+    /// `payload` or spans multiple polynomials.
+    pub fn payload_proofs_for_table<B, N, I>(
+        &self,
+        payload: B,
+        table: I,
+    ) -> VidResult<Vec<(N, LargeRangeProof<KzgEval<E>>)>>
+    where
+        B: AsRef<[u8]>,
+        I: IntoIterator<Item = (N, Range<usize>)>,
+    {
+        let payload = payload.as_ref();
+        let elems: Vec<_> = self.bytes_to_elems(payload).collect();
+        table
+            .into_iter()
+            .filter(|(_, range)| !range.is_empty())
+            .map(|(namespace_id, range)| {
+                self.check_payload_proof_range(payload, &range)?;
+                let range_poly = self.range_byte_to_poly(&range);
+                check_range_poly(&range_poly)?;
+                let start_poly_elem = range_poly.start * self.payload_chunk_size;
+                let end_poly_elem =
+                    ark_std::cmp::min(start_poly_elem + self.payload_chunk_size, elems.len());
+                Ok((
+                    namespace_id,
+                    self.large_range_proof_from_poly_elems(
+                        payload,
+                        &elems[start_poly_elem..end_poly_elem],
+                        range,
+                    ),
+                ))
+            })
+            .collect()
+    }
+
+    /// Check that the byte ranges of a namespace table tile
+    /// `0..payload_byte_len`.
+    ///
+    /// `table` lists `(namespace id, byte range)` pairs as for
+    /// [`Advz::payload_proofs_for_table`], in any order. Empty ranges are
+    /// allowed. Purely structural: no cryptographic check is made.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if any range is reversed or ends beyond
+    /// `payload_byte_len`, if two ranges overlap, or if some payload bytes are
+    /// not covered by any range.
+    pub fn validate_namespace_table<N, I>(table: I, payload_byte_len: usize) -> VidResult<()>
+    where
+        I: IntoIterator<Item = (N, Range<usize>)>,
+    {
+        let mut ranges: Vec<_> = table.into_iter().map(|(_, range)| range).collect();
+        if let Some(range) = ranges
+            .iter()
+            .find(|r| r.start > r.end || r.end > payload_byte_len)
+        {
+            return Err(VidError::Argument(format!(
+                "namespace range ({}..{}) invalid for payload len {}",
+                range.start, range.end, payload_byte_len
+            )));
+        }
+        ranges.sort_by_key(|r| (r.start, r.end));
+
+        let mut covered = 0;
+        for range in ranges {
+            if range.start < covered {
+                return Err(VidError::Argument(format!(
+                    "namespace range ({}..{}) overlaps bytes before {}",
+                    range.start, range.end, covered
+                )));
+            }
+            if range.start > covered {
+                return Err(VidError::Argument(format!(
+                    "gap ({}..{}) between namespace ranges",
+                    covered, range.start
+                )));
+            }
+            covered = range.end;
+        }
+        if covered < payload_byte_len {
+            return Err(VidError::Argument(format!(
+                "gap ({}..{}) at end of payload",
+                covered, payload_byte_len
+            )));
+        }
+        Ok(())
+    }
+
+    /// Verify a [`SmallRangeProof`] using a single batched pairing check.
+    ///
+    /// Same as [`PayloadProver::payload_verify`] except that the per-element
+    /// KZG opening proofs are checked together via a random linear combination
+    /// with randomizers drawn from `rng`. Costs one multi-pairing regardless
+    /// of the number of elements in the range.
+    pub fn payload_verify_batch<R>(
+        &self,
+        stmt: Statement<Self>,
+        proof: &SmallRangeProof<KzgProof<E>>,
+        rng: &mut R,
+    ) -> VidResult<Result<(), ()>>
+    where
+        R: RngCore + CryptoRng,
+    {
+        let (poly_index, points, data_elems) =
+            self.small_range_proof_inputs(stmt.payload_subslice, &stmt.range, proof)?;
+        self.check_common_params(stmt.common)?;
+        self.check_common_commit_consistency(stmt.common, stmt.commit)?;
+        let poly_commits = vec![stmt.common.poly_commits[poly_index]; points.len()];
+        Ok(UnivariateKzgPCS::batch_verify(
+            &self.vk,
+            &poly_commits,
+            &points,
+            &data_elems,
+            &proof.proofs,
+            rng,
+        )
+        .map_err(VidError::Pcs)?
+        .then_some(())
+        .ok_or(()))
+    }
+
+    /// Return an empty [`VerifyCache`] holding up to `capacity` outcomes.
+    pub fn verify_cache(&self, capacity: usize) -> VerifyCache<'_, E, H> {
+        VerifyCache {
+            advz: self,
+            capacity,
+            entries: HashMap::new(),
+            lru: BTreeMap::new(),
+            tick: 0,
+            metrics: VerifyCacheMetrics::default(),
+        }
+    }
+
+    /// Return an empty [`MixedVerifier`] for a mixed queue of payload proofs.
+    pub fn mixed_verifier(&self) -> MixedVerifier<'_, E, H> {
+        MixedVerifier {
+            advz: self,
+            poly_commits: Vec::new(),
+            points: Vec::new(),
+            evals: Vec::new(),
+            proofs: Vec::new(),
+            large_failed: false,
+        }
+    }
+
+    /// Compute a proof for the payload elements at `elem_indices`.
+    ///
+    /// Unlike [`PayloadProver::payload_proof`], the proved elements need not
+    /// be contiguous nor sorted. Indices are positions in the sequence of field
+    /// elements encoding `payload`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `elem_indices` is empty, out of bounds
+    /// for `payload` or spans multiple polynomials.
+    pub fn payload_proof_strided<B>(
+        &self,
+        payload: B,
+        elem_indices: &[usize],
+    ) -> VidResult<StridedProof<KzgProof<E>>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let poly_index = self.check_elem_indices(elem_indices, payload.len())?;
+
+        // grab the polynomial that contains `elem_indices`
+        let polynomial = self.polynomial(self.poly_elems(payload, poly_index).into_iter());
+
+        let points = self.strided_points(elem_indices);
+        let (proofs, _evals) =
+            UnivariateKzgPCS::multi_open(&self.ck, &polynomial, &points).map_err(VidError::Pcs)?;
+
+        Ok(StridedProof { proofs })
+    }
+
+    /// Verify a proof made by [`Advz::payload_proof_strided`].
+    ///
+    /// # Arguments
+    ///
+    /// - `elem_bytes`: payload bytes for each element of `elem_indices`. Each
+    ///   item is exactly [`elem_byte_capacity`] bytes long, except for the
+    ///   final element of the payload, which holds exactly the remaining
+    ///   payload bytes.
+    /// - `elem_indices`: the same indices given to
+    ///   [`Advz::payload_proof_strided`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if the lengths of `elem_bytes`,
+    /// `elem_indices` and `proof` differ, if any item of `elem_bytes` has the
+    /// wrong length, if `elem_indices` is invalid as per
+    /// [`Advz::payload_proof_strided`], or if `common` is inconsistent with
+    /// `commit`.
+    pub fn payload_verify_strided(
+        &self,
+        elem_bytes: &[&[u8]],
+        elem_indices: &[usize],
+        commit: &<Self as VidScheme>::Commit,
+        common: &<Self as VidScheme>::Common,
+        proof: &StridedProof<KzgProof<E>>,
+    ) -> VidResult<Result<(), ()>> {
+        if elem_bytes.len() != elem_indices.len() || proof.proofs.len() != elem_indices.len() {
+            return Err(VidError::Argument(format!(
+                "(elem bytes, elem indices, proof) lengths differ ({},{},{})",
+                elem_bytes.len(),
+                elem_indices.len(),
+                proof.proofs.len()
+            )));
+        }
+        let poly_index = self.check_elem_indices(elem_indices, common.bytes_len)?;
+
+        // only the final payload element may be short, else a verifier could
+        // be fooled by bytes that omit trailing zeros
+        let elem_byte_len = elem_byte_capacity::<KzgEval<E>>();
+        for (bytes, index) in elem_bytes.iter().zip(elem_indices.iter()) {
+            let expected_len =
+                ark_std::cmp::min(elem_byte_len, common.bytes_len - index * elem_byte_len);
+            if bytes.len() != expected_len {
+                return Err(VidError::Argument(format!(
+                    "elem bytes len {} for elem index {} differs from expected len {}",
+                    bytes.len(),
+                    index,
+                    expected_len
+                )));
+            }
+        }
+        self.check_common_params(common)?;
+        self.check_common_commit_consistency(common, commit)?;
+
+        let poly_commit = &common.poly_commits[poly_index];
+        let points = self.strided_points(elem_indices);
+        for (point, (bytes, pf)) in points
+            .iter()
+            .zip(elem_bytes.iter().zip(proof.proofs.iter()))
+        {
+            let elem = bytes_to_field::<_, KzgEval<E>>(bytes.iter())
+                .next()
+                .expect("elem bytes should be nonempty");
+            if !UnivariateKzgPCS::verify(&self.vk, poly_commit, point, &elem, pf)
+                .map_err(VidError::Pcs)?
+            {
+                return Ok(Err(()));
+            }
+        }
+        Ok(Ok(()))
+    }
+
+    /// Begin verifying a [`LargeRangeProof`] for `range` whose payload
+    /// subslice will be supplied incrementally.
+    ///
+    /// The final result of [`PayloadVerifyStream::finalize`] equals that of
+    /// [`PayloadProver::payload_verify`] on the complete subslice.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the same conditions as
+    /// [`PayloadProver::payload_verify`] except those that depend on the
+    /// subslice.
+    pub fn payload_verify_stream<'a>(
+        &self,
+        range: Range<usize>,
+        commit: &<Self as VidScheme>::Commit,
+        common: &'a <Self as VidScheme>::Common,
+        proof: &'a LargeRangeProof<KzgEval<E>>,
+    ) -> VidResult<PayloadVerifyStream<'a, E>> {
+        self.check_byte_packing("streaming payload verification")?;
+        self.check_proof_range_len(&range)?;
+        if range.is_empty() {
+            return Err(VidError::Argument(format!(
+                "empty range ({},{})",
+                range.start, range.end
+            )));
+        }
+        if range != proof.chunk_range {
+            return Err(VidError::Argument(format!(
+                "statement range ({},{}) differs from proof range ({},{})",
+                range.start, range.end, proof.chunk_range.start, proof.chunk_range.end,
+            )));
+        }
+
+        // index conversion
+        let range_poly = self.range_byte_to_poly(&range);
+
+        check_range_poly(&range_poly)?;
+        self.check_common_params(common)?;
+        self.check_common_commit_consistency(common, commit)?;
+
+        // The commitment to a polynomial in eval form is a linear combination
+        // of commitments to the Lagrange basis with the evals as coefficients.
+        // These commitments are the IFFT of the SRS powers of g. Over a coset
+        // with offset `h` the `j`th power is first scaled by `h^-j`.
+        let lagrange_commits = {
+            let offset_inv = self.eval_domain.coset_offset_inv();
+            let mut scale = KzgEval::<E>::one();
+            let mut powers_of_g: Vec<E::G1> = self.ck.powers_of_g[..self.payload_chunk_size]
+                .iter()
+                .map(|g| {
+                    let g = if scale.is_one() {
+                        g.into_group()
+                    } else {
+                        *g * scale
+                    };
+                    scale *= offset_inv;
+                    g
+                })
+                .collect();
+            self.eval_domain
+                .get_coset(KzgEval::<E>::one())
+                .ok_or_else(|| VidError::Internal(anyhow::anyhow!("fail to construct domain")))?
+                .ifft_in_place(&mut powers_of_g);
+            // indexed by logical offset
+            self.to_logical_order(E::G1::normalize_batch(&powers_of_g))
+        };
+        if proof.prefix_elems.len() + self.range_byte_to_elem(&range).len()
+            > self.payload_chunk_size
+        {
+            return Err(VidError::Argument(format!(
+                "proof prefix elems len {} too large for range ({},{})",
+                proof.prefix_elems.len(),
+                range.start,
+                range.end
+            )));
+        }
+        let acc = E::G1::msm_unchecked(
+            &lagrange_commits[..proof.prefix_elems.len()],
+            &proof.prefix_elems,
+        );
+
+        Ok(PayloadVerifyStream {
+            lagrange_commits,
+            poly_commit: &common.poly_commits[range_poly.start],
+            proof,
+            acc,
+            next_elem: proof.prefix_elems.len(),
+            pending_bytes: proof.prefix_bytes.clone(),
+            bytes_remaining: range.len(),
+        })
+    }
+
+    /// Compute a proof that `payload[range]` differs from
+    /// `claimed_wrong_bytes`.
+    ///
+    /// VID proves what the data in a range *is*. To prove that some claimed
+    /// data is absent from a range, reveal the actual data along with a
+    /// [`LargeRangeProof`] for it. The verifier checks the proof against the
+    /// actual data and that the actual data differs from the claim. Verify
+    /// the result via [`Advz::verify_absence`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `payload[range]` equals
+    /// `claimed_wrong_bytes` or under the same conditions as
+    /// [`PayloadProver::payload_proof`].
+    pub fn prove_differs<B>(
+        &self,
+        payload: B,
+        range: Range<usize>,
+        claimed_wrong_bytes: &[u8],
+    ) -> VidResult<AbsenceProof<KzgEval<E>>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let proof: LargeRangeProof<_> = self.payload_proof(payload, range.clone())?;
+        let actual_bytes = payload[range].to_vec();
+        if actual_bytes == claimed_wrong_bytes {
+            return Err(VidError::Argument(
+                "claimed bytes equal payload bytes in range".to_string(),
+            ));
+        }
+        Ok(AbsenceProof {
+            actual_bytes,
+            proof,
+        })
+    }
+
+    /// Verify a proof made by [`Advz::prove_differs`] that the payload bytes
+    /// in `range` differ from `claimed_wrong_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the same conditions as
+    /// [`PayloadProver::payload_verify`].
+    pub fn verify_absence(
+        &self,
+        range: Range<usize>,
+        claimed_wrong_bytes: &[u8],
+        commit: &<Self as VidScheme>::Commit,
+        common: &<Self as VidScheme>::Common,
+        proof: &AbsenceProof<KzgEval<E>>,
+    ) -> VidResult<Result<(), ()>> {
+        let stmt = Statement {
+            payload_subslice: &proof.actual_bytes,
+            range,
+            commit,
+            common,
+        };
+        if self.payload_verify(stmt, &proof.proof)?.is_err() {
+            return Ok(Err(()));
+        }
+        if proof.actual_bytes == claimed_wrong_bytes {
+            return Ok(Err(()));
+        }
+        Ok(Ok(()))
+    }
+
+    /// Same as [`PayloadProver::payload_proof`] for [`LargeRangeProof`] except
+    /// that a commitment to the complement of `range` is also computed. Verify
+    /// it via [`Advz::payload_verify_with_complement`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the same conditions as
+    /// [`PayloadProver::payload_proof`].
+    pub fn payload_proof_with_complement<B>(
+        &self,
+        payload: B,
+        range: Range<usize>,
+    ) -> VidResult<ComplementProof<E>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let proof: LargeRangeProof<_> = self.payload_proof(payload, range.clone())?;
+        let (_, complement_commit) =
+            self.large_range_proof_decomposition(&payload[range], &proof)?;
+        Ok(ComplementProof {
+            proof,
+            complement_commit,
+        })
+    }
+
+    /// Verify a proof made by [`Advz::payload_proof_with_complement`].
+    ///
+    /// In addition to the checks of [`PayloadProver::payload_verify`] for
+    /// [`LargeRangeProof`], check that the commitment to the polynomial
+    /// containing the range is the sum of the commitment to the elements
+    /// covering the range and the complement commitment in `proof`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the same conditions as
+    /// [`PayloadProver::payload_verify`].
+    pub fn payload_verify_with_complement(
+        &self,
+        stmt: Statement<Self>,
+        proof: &ComplementProof<E>,
+    ) -> VidResult<Result<(), ()>> {
+        let payload_subslice = stmt.payload_subslice;
+        let poly_commit = match self.payload_verify_returning_commit(stmt, &proof.proof)? {
+            Ok(poly_commit) => poly_commit,
+            Err(()) => return Ok(Err(())),
+        };
+        let (range_commit, _) =
+            self.large_range_proof_decomposition(payload_subslice, &proof.proof)?;
+        Ok(
+            if range_commit.0 + proof.complement_commit.0 == poly_commit.0.into_group() {
+                Ok(())
+            } else {
+                Err(())
+            },
+        )
+    }
+
+    /// Compute a proof that a payload commitment is the commitment to all of
+    /// `payload`, for archival alongside `payload` as a self-contained
+    /// receipt. Verify it via [`Advz::full_payload_verify`].
+    ///
+    /// The proof is a [`LargeRangeProof`] whose range is the whole payload,
+    /// which spans every polynomial.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `payload` is empty or longer than the
+    /// limit set via [`Advz::set_max_proof_range_bytes`].
+    pub fn full_payload_proof<B>(&self, payload: B) -> VidResult<LargeRangeProof<KzgEval<E>>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let range = 0..payload.len();
+        self.check_payload_proof_range(payload, &range)?;
+
+        // the whole payload needs no prefix or suffix
+        Ok(LargeRangeProof {
+            prefix_elems: Vec::new(),
+            suffix_elems: Vec::new(),
+            prefix_bytes: Vec::new(),
+            suffix_bytes: Vec::new(),
+            chunk_range: range,
+        })
+    }
+
+    /// Verify a proof from [`Advz::full_payload_proof`] that `commit` is the
+    /// commitment to all of `payload`.
+    ///
+    /// Every polynomial commitment is rebuilt from `payload`, so the cost is
+    /// that of [`VidScheme::commit_only`]. A commitment bound to a header via
+    /// [`Advz::disperse_with_header`] does not verify.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `proof` is not a proof for the whole
+    /// of a payload of the same length as `payload`, or if `payload` is longer
+    /// than the limit set via [`Advz::set_max_proof_range_bytes`].
+    pub fn full_payload_verify<B>(
+        &self,
+        payload: B,
+        commit: &<Self as VidScheme>::Commit,
+        proof: &LargeRangeProof<KzgEval<E>>,
+    ) -> VidResult<Result<(), ()>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        self.check_stmt_proof_consistency(payload, &(0..payload.len()), &proof.chunk_range)?;
+        if !(proof.prefix_elems.is_empty()
+            && proof.suffix_elems.is_empty()
+            && proof.prefix_bytes.is_empty()
+            && proof.suffix_bytes.is_empty())
+        {
+            return Err(VidError::Argument(
+                "full payload proof has nonempty prefix or suffix".to_string(),
+            ));
+        }
+        Ok(if self.commit_only(payload)? == *commit {
+            Ok(())
+        } else {
+            Err(())
+        })
+    }
+
+    /// Compute a proof that `payload[range_a]` equals `payload[range_b]`.
+    ///
+    /// The proof is a KZG opening to zero of the difference of the two
+    /// polynomials containing the ranges, at the points of the elements
+    /// covering the ranges. Verify it via [`Advz::verify_ranges_equal`].
+    ///
+    /// Equality is proved for whole field elements, so bytes outside the
+    /// ranges that share an element with them must also be equal.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if
+    /// - either range is empty, out of bounds for `payload` or spans multiple
+    ///   polynomials,
+    /// - the ranges differ in length or in offset within their polynomials, or
+    /// - the elements covering the ranges differ.
+    pub fn prove_ranges_equal<B>(
+        &self,
+        payload: B,
+        range_a: Range<usize>,
+        range_b: Range<usize>,
+    ) -> VidResult<RangesEqualProof<KzgProof<E>>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let (poly_a, poly_b, offset_elems) =
+            self.ranges_equal_params(&range_a, &range_b, payload.len())?;
+
+        // elements of each polynomial, zero-padded
+        let poly_elems = |poly_index| {
+            let mut elems = self.poly_elems(payload, poly_index);
+            elems.resize(self.payload_chunk_size, KzgEval::<E>::zero());
+            elems
+        };
+        let (elems_a, elems_b) = (poly_elems(poly_a), poly_elems(poly_b));
+        if elems_a[offset_elems.clone()] != elems_b[offset_elems.clone()] {
+            return Err(VidError::Argument(format!(
+                "elements covering ranges ({}..{}) and ({}..{}) differ",
+                range_a.start, range_a.end, range_b.start, range_b.end
+            )));
+        }
+
+        let polynomial = self.polynomial(elems_a.iter().zip(elems_b.iter()).map(|(a, b)| *a - b));
+        let points: Vec<_> = offset_elems.map(|i| self.elem_point(i)).collect();
+        let (proofs, _evals) =
+            UnivariateKzgPCS::multi_open(&self.ck, &polynomial, &points).map_err(VidError::Pcs)?;
+
+        Ok(RangesEqualProof { proofs })
+    }
+
+    /// Verify a proof made by [`Advz::prove_ranges_equal`] that the payload
+    /// committed to by `commit` holds equal data in `range_a` and `range_b`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if the ranges are invalid as per
+    /// [`Advz::prove_ranges_equal`], if the length of `proof` is wrong or if
+    /// `common` is inconsistent with `commit`.
+    pub fn verify_ranges_equal(
+        &self,
+        range_a: Range<usize>,
+        range_b: Range<usize>,
+        commit: &<Self as VidScheme>::Commit,
+        common: &<Self as VidScheme>::Common,
+        proof: &RangesEqualProof<KzgProof<E>>,
+    ) -> VidResult<Result<(), ()>> {
+        self.check_common_params(common)?;
+        self.check_common_commit_consistency(common, commit)?;
+        let (poly_a, poly_b, offset_elems) =
+            self.ranges_equal_params(&range_a, &range_b, common.bytes_len)?;
+        if proof.proofs.len() != offset_elems.len() {
+            return Err(VidError::Argument(format!(
+                "proof len {} differs from number of elements {}",
+                proof.proofs.len(),
+                offset_elems.len()
+            )));
+        }
+
+        // by linearity, a commitment to the difference polynomial
+        let diff_commit = KzgCommit::<E>::from(
+            (common.poly_commits[poly_a].0.into_group() - common.poly_commits[poly_b].0)
+                .into_affine(),
+        );
+        let zero = KzgEval::<E>::zero();
+        for (i, pf) in offset_elems.zip(proof.proofs.iter()) {
+            let point = self.elem_point(i);
+            if !UnivariateKzgPCS::verify(&self.vk, &diff_commit, &point, &zero, pf)
+                .map_err(VidError::Pcs)?
+            {
+                return Ok(Err(()));
+            }
+        }
+        Ok(Ok(()))
+    }
+
+    /// Compute a proof that the first `prefix_len` bytes of `payload_a` and
+    /// `payload_b` are equal. Verify it via [`Advz::verify_common_prefix`].
+    ///
+    /// Polynomials wholly in the prefix hold equal data, so the verifier
+    /// checks that their commitments are equal. For the polynomial containing
+    /// the end of the prefix, if any, the proof is a KZG opening to zero of the
+    /// difference of the two polynomials at each element wholly in the
+    /// prefix, as per [`Advz::prove_ranges_equal`]. If the prefix ends inside
+    /// an element then that element of each payload is opened so that the
+    /// verifier can compare its bytes in the prefix. Those elements are
+    /// revealed.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `prefix_len` is zero, exceeds the
+    /// length of either payload, or if the prefixes differ.
+    pub fn prove_common_prefix<B1, B2>(
+        &self,
+        payload_a: B1,
+        payload_b: B2,
+        prefix_len: usize,
+    ) -> VidResult<CommonPrefixProof<E>>
+    where
+        B1: AsRef<[u8]>,
+        B2: AsRef<[u8]>,
+    {
+        let (payload_a, payload_b) = (payload_a.as_ref(), payload_b.as_ref());
+        let (poly_index, whole_elems, boundary_bytes) =
+            self.common_prefix_params(prefix_len, payload_a.len(), payload_b.len())?;
+        if payload_a[..prefix_len] != payload_b[..prefix_len] {
+            return Err(VidError::Argument(format!(
+                "payload prefixes of len {} differ",
+                prefix_len
+            )));
+        }
+        if whole_elems == 0 && boundary_bytes == 0 {
+            return Ok(CommonPrefixProof {
+                diff_proofs: Vec::new(),
+                boundary: None,
+            });
+        }
+
+        // elements of polynomial `poly_index` of each payload, zero-padded
+        let poly_elems = |payload: &[u8]| {
+            let mut elems = self.poly_elems(payload, poly_index);
+            elems.resize(self.payload_chunk_size, KzgEval::<E>::zero());
+            elems
+        };
+        let (elems_a, elems_b) = (poly_elems(payload_a), poly_elems(payload_b));
+
+        let diff_polynomial =
+            self.polynomial(elems_a.iter().zip(elems_b.iter()).map(|(a, b)| *a - b));
+        let points: Vec<_> = (0..whole_elems).map(|i| self.elem_point(i)).collect();
+        let (diff_proofs, _evals) =
+            UnivariateKzgPCS::multi_open(&self.ck, &diff_polynomial, &points)
+                .map_err(VidError::Pcs)?;
+
+        let boundary = if boundary_bytes == 0 {
+            None
+        } else {
+            let point = self.elem_point(whole_elems);
+            let open = |elems: &[KzgEval<E>]| {
+                let (proof, eval) =
+                    UnivariateKzgPCS::open(&self.ck, &self.polynomial(elems.iter()), &point)
+                        .map_err(VidError::Pcs)?;
+                Ok::<_, VidError>((eval, proof))
+            };
+            Some([open(&elems_a)?, open(&elems_b)?])
+        };
+
+        Ok(CommonPrefixProof {
+            diff_proofs,
+            boundary,
+        })
+    }
+
+    /// Verify a proof made by [`Advz::prove_common_prefix`] that the payloads
+    /// committed to by `commit_a` and `commit_b` share their first
+    /// `prefix_len` bytes.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `prefix_len` is invalid as per
+    /// [`Advz::prove_common_prefix`], if the shape of `proof` is wrong for
+    /// `prefix_len` or if either common data is inconsistent with its commit.
+    pub fn verify_common_prefix(
+        &self,
+        prefix_len: usize,
+        commit_a: &<Self as VidScheme>::Commit,
+        common_a: &<Self as VidScheme>::Common,
+        commit_b: &<Self as VidScheme>::Commit,
+        common_b: &<Self as VidScheme>::Common,
+        proof: &CommonPrefixProof<E>,
+    ) -> VidResult<Result<(), ()>> {
+        for (common, commit) in [(common_a, commit_a), (common_b, commit_b)] {
+            self.check_common_params(common)?;
+            self.check_common_commit_consistency(common, commit)?;
+        }
+        let (poly_index, whole_elems, boundary_bytes) =
+            self.common_prefix_params(prefix_len, common_a.bytes_len, common_b.bytes_len)?;
+        if proof.diff_proofs.len() != whole_elems
+            || proof.boundary.is_some() != (boundary_bytes > 0)
+        {
+            return Err(VidError::Argument(format!(
+                "proof shape inconsistent with prefix len {}",
+                prefix_len
+            )));
+        }
+
+        // polynomials wholly in the prefix
+        if common_a.poly_commits[..poly_index] != common_b.poly_commits[..poly_index] {
+            return Ok(Err(()));
+        }
+        if whole_elems == 0 && boundary_bytes == 0 {
+            return Ok(Ok(()));
+        }
+
+        // by linearity, a commitment to the difference polynomial
+        let (poly_commit_a, poly_commit_b) = (
+            &common_a.poly_commits[poly_index],
+            &common_b.poly_commits[poly_index],
+        );
+        let diff_commit =
+            KzgCommit::<E>::from((poly_commit_a.0.into_group() - poly_commit_b.0).into_affine());
+        let zero = KzgEval::<E>::zero();
+        for (i, pf) in proof.diff_proofs.iter().enumerate() {
+            let point = self.elem_point(i);
+            if !UnivariateKzgPCS::verify(&self.vk, &diff_commit, &point, &zero, pf)
+                .map_err(VidError::Pcs)?
+            {
+                return Ok(Err(()));
+            }
+        }
+
+        if let Some([(eval_a, proof_a), (eval_b, proof_b)]) = &proof.boundary {
+            let point = self.elem_point(whole_elems);
+            for (poly_commit, eval, pf) in [
+                (poly_commit_a, eval_a, proof_a),
+                (poly_commit_b, eval_b, proof_b),
+            ] {
+                if !UnivariateKzgPCS::verify(&self.vk, poly_commit, &point, eval, pf)
+                    .map_err(VidError::Pcs)?
+                {
+                    return Ok(Err(()));
+                }
+            }
+            if !field_to_bytes::<_, KzgEval<E>>([eval_a])
+                .take(boundary_bytes)
+                .eq(field_to_bytes::<_, KzgEval<E>>([eval_b]).take(boundary_bytes))
+            {
+                return Ok(Err(()));
+            }
+        }
+        Ok(Ok(()))
+    }
+
+    /// Same as [`PayloadProver::payload_verify`] for [`LargeRangeProof`] except
+    /// that on success the reconstructed polynomial commitment is returned.
+    ///
+    /// Callers verifying several proofs against the same polynomial may cache
+    /// the returned commitment.
+    pub fn payload_verify_returning_commit(
+        &self,
+        stmt: Statement<Self>,
+        proof: &LargeRangeProof<KzgEval<E>>,
+    ) -> VidResult<Result<KzgCommit<E>, ()>> {
+        self.check_stmt_proof_consistency(stmt.payload_subslice, &stmt.range, &proof.chunk_range)?;
+
+        // index conversion
+        let range_poly = self.range_byte_to_poly(&proof.chunk_range);
+
+        check_range_poly(&range_poly)?;
+        self.check_common_params(stmt.common)?;
+        self.check_common_commit_consistency(stmt.common, stmt.commit)?;
+
+        // rebuild the poly commit, check against `common`
+        let poly_commit = self.large_range_proof_poly_commit(stmt.payload_subslice, proof)?;
+        if poly_commit != stmt.common.poly_commits[range_poly.start] {
+            return Ok(Err(()));
+        }
+
+        Ok(Ok(poly_commit))
+    }
+
+    /// Same as [`PayloadVerifyVerbose::payload_verify_verbose`] for
+    /// [`LargeRangeProof`] except that a commitment mismatch is diagnosed
+    /// further to tell wrong data from a wrong evaluation domain.
+    ///
+    /// If the polynomial rebuilt from the proof does not match its commitment
+    /// over the verifier's domain then it is re-committed over the domain of
+    /// the same size with each of `candidate_coset_offsets` in turn, such as
+    /// the offset used before a configuration upgrade. A match is reported as
+    /// [`VerifyFailure::DomainMismatch`]: the data is correct but the prover
+    /// and verifier disagree on the domain. Otherwise the failure is reported
+    /// as [`VerifyFailure::CommitmentMismatch`] as usual.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if any of `candidate_coset_offsets` is
+    /// zero or under the same conditions as
+    /// [`PayloadVerifyVerbose::payload_verify_verbose`].
+    pub fn payload_verify_domain_check(
+        &self,
+        stmt: Statement<Self>,
+        proof: &LargeRangeProof<KzgEval<E>>,
+        candidate_coset_offsets: &[KzgEval<E>],
+    ) -> VidResult<Result<(), VerifyFailure>> {
+        let candidate_domains = candidate_coset_offsets
+            .iter()
+            .map(|offset| {
+                self.eval_domain
+                    .get_coset(*offset)
+                    .ok_or_else(|| VidError::Argument("candidate coset offset is zero".to_string()))
+            })
+            .collect::<VidResult<Vec<_>>>()?;
+        let (payload_subslice, common) = (stmt.payload_subslice, stmt.common);
+        let poly_index = match self.payload_verify_verbose(stmt, proof)? {
+            Err(VerifyFailure::CommitmentMismatch { poly_index }) => poly_index,
+            result => return Ok(result),
+        };
+
+        let elems = self.large_range_proof_elems(payload_subslice, proof);
+        for (candidate_index, domain) in candidate_domains.iter().enumerate() {
+            let mut coeffs = self.to_physical_order(elems.clone());
+            domain.ifft_in_place(&mut coeffs);
+            let poly_commit = UnivariateKzgPCS::commit(
+                &self.ck,
+                &DenseUVPolynomial::from_coefficients_vec(coeffs),
+            )
+            .map_err(VidError::Pcs)?;
+            if poly_commit == common.poly_commits[poly_index] {
+                return Ok(Err(VerifyFailure::DomainMismatch {
+                    poly_index,
+                    candidate_index,
+                }));
+            }
+        }
+        Ok(Err(VerifyFailure::CommitmentMismatch { poly_index }))
+    }
+
+    /// Return the input points needed to verify a [`SmallRangeProof`] for
+    /// `range`.
+    ///
+    /// These are exactly the points at which [`PayloadProver::payload_proof`]
+    /// opens the polynomial and at which [`PayloadProver::payload_verify`]
+    /// checks the openings, so external code may use them to reproduce the
+    /// point selection for auditing or custom verification.
+    ///
+    /// Proofs for ranges at the same offset within their polynomial share the
+    /// same points, so callers may compute them once and pass them to
+    /// [`Advz::payload_verify_with_points`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `range` is empty or spans multiple
+    /// polynomials.
+    pub fn payload_proof_points(&self, range: &Range<usize>) -> VidResult<Vec<KzgPoint<E>>> {
+        self.check_proof_range(range)?;
+        Ok(self.range_points(range))
+    }
+
+    /// Return whether the start and end of `range` fall on element
+    /// boundaries.
+    ///
+    /// A [`SmallRangeProof`] for a range aligned at both ends carries no
+    /// `prefix_bytes` or `suffix_bytes`, so clients may snap ranges to
+    /// boundaries for minimal proofs. An unaligned end at the end of the
+    /// payload also needs no `suffix_bytes`, but this method does not know
+    /// the payload length.
+    pub fn is_element_aligned(&self, range: Range<usize>) -> (bool, bool) {
+        let elem_byte_range = self.range_elem_to_byte(&self.range_byte_to_elem(&range));
+        (
+            elem_byte_range.start == range.start,
+            elem_byte_range.end == range.end,
+        )
+    }
+
+    /// Convert a `(start, len)` request for `payload`, such as one reported
+    /// by an external indexer, into a range accepted by
+    /// [`PayloadProver::payload_proof`].
+    ///
+    /// A range that extends past the end of `payload` is clamped to it, so
+    /// that a stale request for a since-shortened payload still proves
+    /// whatever remains. Otherwise the request is rejected with the
+    /// [`InvalidRequest`] explaining why, which converts into
+    /// [`VidError::Argument`] via `?`.
+    pub fn prepare_request<B>(
+        &self,
+        payload: B,
+        start: usize,
+        len: usize,
+    ) -> Result<Range<usize>, InvalidRequest>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload_len = payload.as_ref().len();
+        if len == 0 {
+            return Err(InvalidRequest::Empty);
+        }
+        if start >= payload_len {
+            return Err(InvalidRequest::OutOfBounds { start, payload_len });
+        }
+        let range = start..ark_std::cmp::min(start.saturating_add(len), payload_len);
+        let range_poly = self.range_byte_to_poly(&range);
+        if range_poly.len() != 1 {
+            return Err(InvalidRequest::SpansMultiplePolys {
+                first_poly: range_poly.start,
+                last_poly: range_poly.end - 1,
+            });
+        }
+        Ok(range)
+    }
+
+    /// Compressed [`CanonicalSerialize`] byte length of a [`SmallRangeProof`]
+    /// for `range`, computed without a payload.
+    ///
+    /// Exact unless `range` ends inside the final element of the payload, in
+    /// which case the proof omits some suffix bytes and the estimate is an
+    /// upper bound.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `range` is empty or spans multiple
+    /// polynomials.
+    pub fn estimate_proof_size(&self, range: Range<usize>) -> VidResult<usize> {
+        self.check_proof_range(&range)?;
+
+        // a proof of the same shape as that produced by `payload_proof`
+        let range_elem = self.range_byte_to_elem(&range);
+        let range_elem_byte = self.range_elem_to_byte(&range_elem);
+        let proof = SmallRangeProof {
+            proofs: vec![
+                KzgProof::<E> {
+                    proof: E::G1Affine::zero()
+                };
+                range_elem.len()
+            ],
+            prefix_bytes: vec![0; range.start - range_elem_byte.start],
+            suffix_bytes: vec![0; range_elem_byte.end - range.end],
+            chunk_range: range,
+        };
+        Ok(proof.compressed_size())
+    }
+
+    /// Recommend [`ProofKind::Small`] or [`ProofKind::Large`] for `range`.
+    ///
+    /// A [`SmallRangeProof`] grows with the number of elements in `range`
+    /// whereas a [`LargeRangeProof`] grows with the number of elements in the
+    /// rest of the polynomial, so tiny ranges favour the former and
+    /// near-whole-polynomial ranges the latter. The proof kind with the smaller
+    /// serialized size as per [`Advz::estimate_proof_size`] wins. Ties go to
+    /// the cheaper verification, modelling one KZG opening verification as an
+    /// MSM of [`KZG_VERIFY_MSM_LEN`] elements against the single MSM of
+    /// `payload_chunk_size` elements for a [`LargeRangeProof`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `range` is empty or spans multiple
+    /// polynomials.
+    pub fn recommend_proof_type(&self, range: Range<usize>) -> VidResult<ProofKind> {
+        let small_size = self.estimate_proof_size(range.clone())?;
+        let large_size = self.estimate_large_proof_size(range.clone())?;
+        let small_verify_cost = self
+            .range_byte_to_elem(&range)
+            .len()
+            .saturating_mul(KZG_VERIFY_MSM_LEN);
+        let large_verify_cost = self.payload_chunk_size;
+        Ok(
+            if (small_size, small_verify_cost) <= (large_size, large_verify_cost) {
+                ProofKind::Small
+            } else {
+                ProofKind::Large
+            },
+        )
+    }
+
+    /// Same as [`Advz::estimate_proof_size`] for a [`LargeRangeProof`].
+    fn estimate_large_proof_size(&self, range: Range<usize>) -> VidResult<usize> {
+        self.check_proof_range(&range)?;
+
+        // a proof of the same shape as that produced by `payload_proof`
+        let range_elem = self.range_byte_to_elem(&range);
+        let range_elem_byte = self.range_elem_to_byte(&range_elem);
+        let offset_elem = range_elem.start % self.payload_chunk_size;
+        let proof = LargeRangeProof {
+            prefix_elems: vec![KzgEval::<E>::zero(); offset_elem],
+            suffix_elems: vec![
+                KzgEval::<E>::zero();
+                self.payload_chunk_size - offset_elem - range_elem.len()
+            ],
+            prefix_bytes: vec![0; range.start - range_elem_byte.start],
+            suffix_bytes: vec![0; range_elem_byte.end - range.end],
+            chunk_range: range,
+        };
+        Ok(proof.compressed_size())
+    }
+
+    /// Number of [`SmallRangeProof`]s for ranges of the same shape as `range`
+    /// that fit in `budget_bytes`, as per [`Advz::estimate_proof_size`].
+    ///
+    /// Useful for a client pacing proof requests under a bandwidth budget.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `range` is empty or spans multiple
+    /// polynomials.
+    pub fn proofs_per_budget(&self, range: Range<usize>, budget_bytes: usize) -> VidResult<usize> {
+        Ok(budget_bytes / self.estimate_proof_size(range)?)
+    }
+
+    /// Estimate, without computing it, the cost of a proof of kind
+    /// `proof_kind` for `range`.
+    ///
+    /// Useful for a scheduler deciding how to batch proof requests. Every
+    /// prover rebuilds the entire polynomial containing `range`, so field
+    /// conversions are independent of the length of `range`, whereas a
+    /// [`SmallRangeProof`] needs one MSM of the polynomial's size per element
+    /// of `range`. See [`CostProfile`] for the units of the model.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `range` is empty or spans multiple
+    /// polynomials.
+    pub fn cost_profile(
+        &self,
+        range: Range<usize>,
+        proof_kind: ProofKind,
+    ) -> VidResult<CostProfile> {
+        self.check_proof_range(&range)?;
+
+        let num_points = self.range_byte_to_elem(&range).len();
+        let field_conversions = self.index_poly_to_byte(1);
+        Ok(match proof_kind {
+            // one opening proof per point
+            ProofKind::Small => CostProfile {
+                msm: num_points * self.payload_chunk_size,
+                openings: num_points,
+                field_conversions,
+            },
+            // the prover only collects elements
+            ProofKind::Large => CostProfile {
+                field_conversions,
+                ..Default::default()
+            },
+            // commitments to the polynomial and quotient, and one opening
+            ProofKind::SingleOpening => CostProfile {
+                msm: 3 * self.payload_chunk_size,
+                openings: 1,
+                field_conversions,
+            },
+        })
+    }
+
+    /// Estimate the time to verify a proof of kind `proof_kind` for `range`
+    /// via [`PayloadProver::payload_verify`], for capacity planning.
+    ///
+    /// This is an estimate, not a bound. The model counts only the dominant
+    /// verifier operations: one KZG opening check per element for a
+    /// [`SmallRangeProof`], one MSM of the polynomial's size for a
+    /// [`LargeRangeProof`] and a single KZG opening check for a
+    /// [`SingleOpeningProof`]. The cost of each operation is taken from
+    /// `model`, which the caller obtains once via
+    /// [`Advz::calibrate_verify_cost`] and reuses.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `range` is empty or spans multiple
+    /// polynomials, or if `model` was calibrated for a different curve or
+    /// `payload_chunk_size`.
+    #[cfg(feature = "std")]
+    pub fn estimate_verify_latency(
+        &self,
+        model: &VerifyCostModel,
+        range: Range<usize>,
+        proof_kind: ProofKind,
+    ) -> VidResult<std::time::Duration> {
+        self.check_proof_range(&range)?;
+        if model.curve != core::any::TypeId::of::<E>()
+            || model.payload_chunk_size != self.payload_chunk_size
+        {
+            return Err(VidError::Argument(format!(
+                "verify cost model for payload_chunk_size {} used with payload_chunk_size {} or another curve",
+                model.payload_chunk_size, self.payload_chunk_size
+            )));
+        }
+        self.verify_latency(model, range, proof_kind)
+    }
+
+    /// Same as [`PayloadProver::payload_verify`] for [`SmallRangeProof`]
+    /// except that the input points are supplied by the caller instead of
+    /// computed from `stmt.range`.
+    ///
+    /// Obtain `points` via [`Advz::payload_proof_points`].
+    ///
+    /// # Errors
+    ///
+    /// In addition to the errors of [`PayloadProver::payload_verify`], return
+    /// [`VidError::Argument`] if the length of `points` differs from the
+    /// number of elements in the proof.
+    pub fn payload_verify_with_points(
+        &self,
+        stmt: Statement<Self>,
+        proof: &SmallRangeProof<KzgProof<E>>,
+        points: &[KzgPoint<E>],
+    ) -> VidResult<Result<(), ()>> {
+        let (poly_index, data_elems) =
+            self.small_range_proof_data_elems(stmt.payload_subslice, &stmt.range, proof)?;
+        if points.len() != data_elems.len() {
+            return Err(VidError::Argument(format!(
+                "points len {} differs from proof len {}",
+                points.len(),
+                data_elems.len()
+            )));
+        }
+        self.check_common_params(stmt.common)?;
+        self.check_common_commit_consistency(stmt.common, stmt.commit)?;
+        Ok(
+            match self.small_range_proof_first_failure_at(
+                &stmt.common.poly_commits[poly_index],
+                points,
+                &data_elems,
+                proof,
+            )? {
+                Some(_) => Err(()),
+                None => Ok(()),
+            },
+        )
+    }
+
+    /// Return the index of the polynomial containing byte `offset` of a
+    /// payload of length `payload_byte_len`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `offset` is out of bounds.
+    pub fn poly_index_of_byte(&self, offset: usize, payload_byte_len: usize) -> VidResult<usize> {
+        if offset >= payload_byte_len {
+            return Err(VidError::Argument(format!(
+                "offset {} out of bounds for payload len {}",
+                offset, payload_byte_len
+            )));
+        }
+        Ok(index_coarsen(
+            self.index_byte_to_elem(offset),
+            self.payload_chunk_size,
+        ))
+    }
+
+    /// Same as [`PayloadProver::payload_proof`] except that the proof is
+    /// returned in serialized form.
+    ///
+    /// The proof is serialized via [`CanonicalSerialize::serialize_compressed`]
+    /// into a single buffer allocated up front. The layout of each proof type
+    /// is documented on the type. Deserialize via
+    /// [`CanonicalDeserialize::deserialize_compressed`].
+    pub fn payload_proof_bytes<PROOF, B>(
+        &self,
+        payload: B,
+        range: Range<usize>,
+    ) -> VidResult<Vec<u8>>
+    where
+        Self: PayloadProver<PROOF>,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned + CanonicalSerialize,
+        B: AsRef<[u8]>,
+    {
+        let proof: PROOF = self.payload_proof(payload, range)?;
+        let mut bytes = Vec::with_capacity(proof.compressed_size());
+        proof.serialize_compressed(&mut bytes).map_err(vid)?;
+        Ok(bytes)
+    }
+
+    /// Same as [`PayloadProver::payload_proof`] except that `sub_range` is
+    /// relative to the start of the byte range `namespace` of `payload`.
+    ///
+    /// The proof is identical to a proof for the equivalent global range, so
+    /// verify it against that range.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `namespace` is outside `payload`,
+    /// `sub_range` is outside `namespace`, or the global range is rejected by
+    /// [`PayloadProver::payload_proof`].
+    pub fn payload_proof_in_namespace<PROOF, B>(
+        &self,
+        payload: B,
+        namespace: Range<usize>,
+        sub_range: Range<usize>,
+    ) -> VidResult<PROOF>
+    where
+        Self: PayloadProver<PROOF>,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        if namespace.start > namespace.end || namespace.end > payload.len() {
+            return Err(VidError::Argument(format!(
+                "namespace {:?} out of bounds for payload len {}",
+                namespace,
+                payload.len()
+            )));
+        }
+        if sub_range.start > sub_range.end || sub_range.end > namespace.len() {
+            return Err(VidError::Argument(format!(
+                "sub-range {:?} out of bounds for namespace len {}",
+                sub_range,
+                namespace.len()
+            )));
+        }
+        self.payload_proof(
+            payload,
+            namespace.start + sub_range.start..namespace.start + sub_range.end,
+        )
+    }
+
+    /// Same as [`PayloadProver::payload_verify`] for a [`SmallRangeProof`]
+    /// except that the payload subslice is given as field elements.
+    ///
+    /// Skips the byte-to-field conversion of the payload subslice and ignores
+    /// the prefix and suffix bytes of `proof`. Useful when the caller already
+    /// holds the subslice in element form, such as for a range aligned to
+    /// element boundaries.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `stmt.range` is empty, differs from
+    /// the range of `proof`, spans multiple polynomials, or if the number of
+    /// elements in `stmt.payload_subslice_elems` is inconsistent with
+    /// `stmt.range` or `proof`.
+    pub fn payload_verify_elems(
+        &self,
+        stmt: ElemStatement<E, H>,
+        proof: &SmallRangeProof<KzgProof<E>>,
+    ) -> VidResult<Result<(), ()>> {
+        self.check_proof_range_len(&stmt.range)?;
+        if stmt.range.is_empty() {
+            return Err(VidError::Argument(format!(
+                "empty range ({},{})",
+                stmt.range.start, stmt.range.end
+            )));
+        }
+        if stmt.range != proof.chunk_range {
+            return Err(VidError::Argument(format!(
+                "statement range ({},{}) differs from proof range ({},{})",
+                stmt.range.start, stmt.range.end, proof.chunk_range.start, proof.chunk_range.end,
+            )));
+        }
+        let range_elem = self.range_byte_to_elem(&stmt.range);
+        if stmt.payload_subslice_elems.len() != range_elem.len()
+            || proof.proofs.len() != range_elem.len()
+        {
+            return Err(VidError::Argument(format!(
+                "payload subslice elems len {} and proof len {} differ from range elems len {}",
+                stmt.payload_subslice_elems.len(),
+                proof.proofs.len(),
+                range_elem.len()
+            )));
+        }
+        let range_poly = self.range_elem_to_poly(&range_elem);
+        check_range_poly(&range_poly)?;
+        self.check_common_params(stmt.common)?;
+        self.check_common_commit_consistency(stmt.common, stmt.commit)?;
+        let poly_commit = stmt
+            .common
+            .poly_commits
+            .get(range_poly.start)
+            .ok_or_else(|| {
+                VidError::Argument(format!(
+                    "poly index {} out of bounds for common with {} poly commits",
+                    range_poly.start,
+                    stmt.common.poly_commits.len()
+                ))
+            })?;
+
+        let points = self.range_points(&stmt.range);
+        Ok(
+            match self.small_range_proof_first_failure_at(
+                poly_commit,
+                &points,
+                stmt.payload_subslice_elems,
+                proof,
+            )? {
+                Some(_) => Err(()),
+                None => Ok(()),
+            },
+        )
+    }
+
+    /// Compute a proof for element `elem_index` of `payload`, as enumerated
+    /// by [`Advz::enumerate_elems`].
+    ///
+    /// The leanest possible payload proof: one KZG opening at one point,
+    /// verified by one pairing via [`Advz::verify_element`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `elem_index` is out of bounds for
+    /// `payload`.
+    pub fn element_proof<B>(
+        &self,
+        payload: B,
+        elem_index: usize,
+    ) -> VidResult<ElementProof<KzgProof<E>>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        self.check_elem_index(elem_index, payload.len())?;
+        self.element_opening(payload, elem_index)
+    }
+
+    /// Verify a proof made by [`Advz::element_proof`] that element
+    /// `elem_index` of the payload committed to by `commit` is
+    /// `claimed_elem`.
+    ///
+    /// Return value has the same semantics as [`PayloadProver::payload_verify`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `common` is inconsistent with `commit`
+    /// or `self`, or if `elem_index` is out of bounds for the payload.
+    pub fn verify_element(
+        &self,
+        commit: &<Self as VidScheme>::Commit,
+        common: &<Self as VidScheme>::Common,
+        elem_index: usize,
+        claimed_elem: KzgEval<E>,
+        proof: &ElementProof<KzgProof<E>>,
+    ) -> VidResult<Result<(), ()>> {
+        self.check_common_params(common)?;
+        self.check_common_commit_consistency(common, commit)?;
+        self.check_elem_index(elem_index, common.bytes_len)?;
+        Ok(
+            match self.verify_element_opening(common, elem_index, claimed_elem, proof)? {
+                true => Ok(()),
+                false => Err(()),
+            },
+        )
+    }
+
+    /// Compute a proof for element `ext_index` of `payload` packed into the
+    /// extension field `F` as in [`Advz::disperse_ext`].
+    ///
+    /// One [`ElementProof`] per coordinate of the element, lowest first.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `ext_index` is out of bounds for
+    /// `payload` or under the conditions of [`Advz::disperse_ext`].
+    pub fn ext_element_proof<F, B>(
+        &self,
+        payload: B,
+        ext_index: usize,
+    ) -> VidResult<Vec<ElementProof<KzgProof<E>>>>
+    where
+        F: Field<BasePrimeField = KzgEval<E>>,
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let degree = self.check_ext_elem_index::<F>(ext_index, payload.len())?;
+        (ext_index * degree..(ext_index + 1) * degree)
+            .map(|elem_index| self.element_opening(payload, elem_index))
+            .collect()
+    }
+
+    /// Verify a proof made by [`Advz::ext_element_proof`] that element
+    /// `ext_index` of the payload committed to by `commit`, packed into the
+    /// extension field `F`, is `claimed_elem`.
+    ///
+    /// Return value has the same semantics as [`PayloadProver::payload_verify`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `common` is inconsistent with `commit`
+    /// or `self`, if `ext_index` is out of bounds for the payload, if `proofs`
+    /// does not hold one proof per coordinate of `F` or under the conditions
+    /// of [`Advz::disperse_ext`].
+    pub fn verify_ext_element<F>(
+        &self,
+        commit: &<Self as VidScheme>::Commit,
+        common: &<Self as VidScheme>::Common,
+        ext_index: usize,
+        claimed_elem: F,
+        proofs: &[ElementProof<KzgProof<E>>],
+    ) -> VidResult<Result<(), ()>>
+    where
+        F: Field<BasePrimeField = KzgEval<E>>,
+    {
+        self.check_common_params(common)?;
+        self.check_common_commit_consistency(common, commit)?;
+        let degree = self.check_ext_elem_index::<F>(ext_index, common.bytes_len)?;
+        if proofs.len() != degree {
+            return Err(VidError::Argument(format!(
+                "{} proofs differ from extension degree {}",
+                proofs.len(),
+                degree
+            )));
+        }
+        for ((elem_index, coord), proof) in (ext_index * degree..)
+            .zip(claimed_elem.to_base_prime_field_elements())
+            .zip(proofs)
+        {
+            if !self.verify_element_opening(common, elem_index, coord, proof)? {
+                return Ok(Err(()));
+            }
+        }
+        Ok(Ok(()))
+    }
+
+    /// Debugging aid: check `proof` for `range` against the polynomial `poly`
+    /// that the prover should have used, and report every discrepancy.
+    ///
+    /// Recomputes the commitment to `poly` and its evaluation at the input
+    /// point of each element covering `range`, then checks each KZG opening
+    /// proof against them. Unlike [`PayloadProver::payload_verify`], the
+    /// check does not stop at the first failure and does not need the
+    /// payload, so it isolates index-math bugs in the prover. An empty result
+    /// means that `proof` is consistent with `poly`.
+    ///
+    /// Not for production use.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `range` is empty or spans multiple
+    /// polynomials.
+    #[cfg(feature = "debug-tools")]
+    pub fn debug_verify_with_poly(
+        &self,
+        proof: &SmallRangeProof<KzgProof<E>>,
+        poly: &DensePolynomial<KzgEval<E>>,
+        range: Range<usize>,
+    ) -> VidResult<Vec<Discrepancy<KzgEval<E>>>> {
+        self.check_proof_range_len(&range)?;
+        self.check_proof_range(&range)?;
+        let mut discrepancies = Vec::new();
+        if proof.chunk_range != range {
+            discrepancies.push(Discrepancy::RangeMismatch {
+                expected: range.clone(),
+                actual: proof.chunk_range.clone(),
+            });
+        }
+        let points = self.range_points(&range);
+        if proof.proofs.len() != points.len() {
+            discrepancies.push(Discrepancy::ProofCount {
+                expected: points.len(),
+                actual: proof.proofs.len(),
+            });
+        }
+
+        let poly_commit = UnivariateKzgPCS::commit(&self.ck, poly).map_err(VidError::Pcs)?;
+        for (elem_index, (point, pf)) in points.into_iter().zip(proof.proofs.iter()).enumerate() {
+            let eval = poly.evaluate(&point);
+            if !UnivariateKzgPCS::verify(&self.vk, &poly_commit, &point, &eval, pf)
+                .map_err(VidError::Pcs)?
+            {
+                discrepancies.push(Discrepancy::Opening {
+                    elem_index,
+                    point,
+                    eval,
+                });
+            }
+        }
+        Ok(discrepancies)
+    }
+
+    /// Offline phase of [`SmallRangeProof`] generation: compute KZG opening
+    /// proofs at every input point of every polynomial of `payload`.
+    ///
+    /// Uses the FK23 algorithm to open each polynomial at all points at once,
+    /// at a cost comparable to a few proofs via
+    /// [`PayloadProver::payload_proof`]. Then compute any number of proofs
+    /// cheaply via [`Advz::online_proof`]. The result holds a copy of
+    /// `payload` and one group element per payload element.
+    pub fn offline_precompute<B>(&self, payload: B) -> VidResult<OfflineProofData<E>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let polys = self.payload_polys(payload);
+        let (ck, eval_domain, num_points) = (&self.ck, &self.eval_domain, self.payload_chunk_size);
+        let proofs = self
+            .maybe_par_map(&polys, |poly| {
+                UnivariateKzgPCS::multi_open_rou_proofs(ck, poly, num_points, eval_domain)
+            })
+            .into_iter()
+            .map(|proofs| proofs.map(|proofs| self.to_logical_order(proofs)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(VidError::Pcs)?;
+        Ok(OfflineProofData {
+            payload: payload.to_vec(),
+            proofs,
+            payload_chunk_size: self.payload_chunk_size,
+            num_storage_nodes: self.num_storage_nodes,
+        })
+    }
+
+    /// Online phase of [`SmallRangeProof`] generation: select the proofs for
+    /// `range` from `offline`, computed by [`Advz::offline_precompute`].
+    ///
+    /// The result is identical to [`PayloadProver::payload_proof`] for
+    /// `range` of the payload of `offline`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the conditions of
+    /// [`PayloadProver::payload_proof`] or if `offline` was produced by an
+    /// [`Advz`] instance with different parameters.
+    pub fn online_proof(
+        &self,
+        offline: &OfflineProofData<E>,
+        range: Range<usize>,
+    ) -> VidResult<SmallRangeProof<KzgProof<E>>> {
+        self.check_params(offline.payload_chunk_size, offline.num_storage_nodes)?;
+        let payload = &offline.payload;
+        self.check_payload_proof_range(payload, &range)?;
+
+        // index conversion
+        let range_elem = self.range_byte_to_elem(&range);
+        let range_poly = self.range_elem_to_poly(&range_elem);
+        check_range_poly(&range_poly)?;
+        let start_namespace_byte = self.index_poly_to_byte(range_poly.start);
+        let offset_elem = range_elem.start - self.index_byte_to_elem(start_namespace_byte);
+        let range_elem_byte = self.range_elem_to_byte_clamped(&range_elem, payload.len());
+
+        Ok(SmallRangeProof {
+            proofs: offline.proofs[range_poly.start][offset_elem..offset_elem + range_elem.len()]
+                .to_vec(),
+            prefix_bytes: payload[range_elem_byte.start..range.start].to_vec(),
+            suffix_bytes: payload[range.end..range_elem_byte.end].to_vec(),
+            chunk_range: range,
+        })
+    }
+
+    /// Same as [`PayloadProver::payload_proof`] except that the proof is
+    /// bundled with a proof that the commitment to the polynomial containing
+    /// `range` is included in the merkle tree with root
+    /// [`Advz::poly_commits_merkle_root`] of `common`.
+    ///
+    /// The result is verifiable against the merkle root alone via
+    /// [`PayloadVerifyBundled::payload_verify_bundled`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the conditions of
+    /// [`PayloadProver::payload_proof`] or if `common` is inconsistent with
+    /// `payload` or with the parameters of `self`.
+    pub fn payload_proof_bundled<PROOF, B>(
+        &self,
+        payload: B,
+        range: Range<usize>,
+        common: &Common<E, H>,
+    ) -> VidResult<BundledProof<PROOF, E, H>>
+    where
+        Self: PayloadProver<PROOF>,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+        B: AsRef<[u8]>,
+    {
+        // `common` is not `<Self as VidScheme>::Common` here due to the
+        // `Self: PayloadProver` bound, so check its fields directly
+        self.check_params(common.payload_chunk_size, common.num_storage_nodes)?;
+        let proof = self.payload_proof(payload, range.clone())?;
+
+        // `range` is nonempty, else `payload_proof` would have failed
+        let poly_index = self.range_byte_to_poly(&range).start;
+        let poly_commit = common.poly_commits.get(poly_index).ok_or_else(|| {
+            VidError::Argument(format!(
+                "poly index {} out of bounds for common with {} poly commits",
+                poly_index,
+                common.poly_commits.len()
+            ))
+        })?;
+        Ok(BundledProof {
+            proof,
+            poly_commit: *poly_commit,
+            inclusion_proof: Self::poly_commits_inclusion_proof(&common.poly_commits, poly_index)?,
+        })
+    }
+
+    /// Same as [`PayloadProver::payload_proof`] except that the proof is
+    /// bundled with the minimal common data needed to verify it against
+    /// `commit` alone: the commitment to the polynomial containing `range`
+    /// and the hashes of the other polynomial commitments.
+    ///
+    /// Only `payload` and its commitment `commit` are needed, not the full
+    /// common data. Pass the `header` given to
+    /// [`Advz::disperse_with_header`], if any. The result is verifiable via
+    /// [`PayloadVerifyCommitBound::payload_verify_commit_bound`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the conditions of
+    /// [`PayloadProver::payload_proof`] or if `commit` is inconsistent with
+    /// `payload` and `header`.
+    pub fn payload_proof_commit_bound<PROOF, B>(
+        &self,
+        payload: B,
+        range: Range<usize>,
+        commit: &<Self as VidScheme>::Commit,
+        header: Option<&[u8]>,
+    ) -> VidResult<CommitBoundProof<PROOF, E, H>>
+    where
+        Self: PayloadProver<PROOF>,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+        B: AsRef<[u8]>,
+    {
+        let payload = payload.as_ref();
+        let proof = self.payload_proof(payload, range.clone())?;
+
+        // recompute the poly commits, padded as in `disperse`
+        let mut poly_commits = self
+            .payload_polys(payload)
+            .iter()
+            .map(|poly| UnivariateKzgPCS::commit(&self.ck, poly).map_err(VidError::Pcs))
+            .collect::<VidResult<Vec<_>>>()?;
+        if poly_commits.len() < self.min_polynomials {
+            poly_commits.resize(
+                self.min_polynomials,
+                KzgCommit::<E>::from(E::G1Affine::zero()),
+            );
+        }
+        let mut poly_commit_hashes = poly_commits
+            .iter()
+            .map(|c| Self::poly_commit_hash(c).map(HasherNode::from))
+            .collect::<VidResult<Vec<_>>>()?;
+        let header_digest = header.map(|header| HasherNode::from(H::digest(header)));
+        if *commit
+            != self.poly_commit_hashes_hash(
+                poly_commit_hashes.iter(),
+                header_digest.as_ref(),
+                payload.len(),
+            )?
+        {
+            return Err(VidError::Argument(
+                "commit inconsistent with payload and header".to_string(),
+            ));
+        }
+
+        // `range` is nonempty and in bounds, else `payload_proof` would have
+        // failed
+        let poly_index = self.range_byte_to_poly(&range).start;
+        poly_commit_hashes.remove(poly_index);
+        Ok(CommitBoundProof {
+            proof,
+            poly_commit: poly_commits[poly_index],
+            other_poly_commit_hashes: poly_commit_hashes,
+            header_digest,
+            bytes_len: payload.len(),
+        })
+    }
+
+    /// Same as [`PayloadProver::payload_verify`] except that `stmt.commit`
+    /// must also be the leaf at key `block_key` of the [`CommitSmt`] with root
+    /// `smt_root`, as proved by `smt_proof`.
+    ///
+    /// Confirms in one call that the block is canonical and that the range is
+    /// included in its payload. Key the tree by a type wide enough to hold
+    /// the whole block hash, such as a field element, so that distinct blocks
+    /// get distinct keys.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the conditions of
+    /// [`PayloadProver::payload_verify`]. Return [`VidError::Internal`] if
+    /// `smt_proof` is malformed.
+    pub fn payload_verify_in_smt<PROOF, I>(
+        &self,
+        stmt: Statement<Self>,
+        proof: &PROOF,
+        block_key: &I,
+        smt_proof: &CommitSmtProof<H, I>,
+        smt_root: &HasherNode<H>,
+    ) -> VidResult<Result<(), ()>>
+    where
+        Self: PayloadProver<PROOF>,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+        I: Index + From<u64> + ToTraversalPath<U3> + CanonicalSerialize,
+    {
+        if Self::verify_commit_in_smt(stmt.commit, block_key, smt_proof, smt_root)?.is_err() {
+            return Ok(Err(()));
+        }
+        self.payload_verify(stmt, proof)
+    }
+
+    /// Compute a proof for `global_range` of the concatenation of `payloads`
+    /// dispersed via [`Advz::disperse_multi`].
+    ///
+    /// The range is split at payload boundaries into one sub-proof per
+    /// overlapped payload. Verify it via [`Advz::payload_verify_cross`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `global_range` is empty or out of
+    /// bounds for the concatenation of `payloads`, or if any of the sub-proofs
+    /// fails as per [`PayloadProver::payload_proof`].
+    pub fn payload_proof_cross<PROOF, B>(
+        &self,
+        payloads: &[B],
+        global_range: Range<usize>,
+    ) -> VidResult<CrossProof<PROOF>>
+    where
+        Self: PayloadProver<PROOF>,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+        B: AsRef<[u8]>,
+    {
+        let payload_byte_lens: Vec<_> = payloads.iter().map(|p| p.as_ref().len()).collect();
+        let proofs = split_global_range(&payload_byte_lens, &global_range)?
+            .into_iter()
+            .map(|(index, range)| self.payload_proof(payloads[index].as_ref(), range))
+            .collect::<VidResult<_>>()?;
+        Ok(CrossProof { proofs })
+    }
+
+    /// Verify a proof made by [`Advz::payload_proof_cross`] that
+    /// `payload_subslice` is at `global_range` of the concatenation of the
+    /// payloads committed to by `root`.
+    ///
+    /// `commons` is the common data of every payload under `root`, in order.
+    /// Each sub-proof is checked against the commitment of its payload.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `commons` is inconsistent with `root`,
+    /// if `payload_subslice` and `global_range` differ in length, if
+    /// `global_range` is empty or out of bounds, if the number of sub-proofs
+    /// is wrong, or under the conditions of [`PayloadProver::payload_verify`]
+    /// for any sub-proof.
+    pub fn payload_verify_cross<PROOF>(
+        &self,
+        payload_subslice: &[u8],
+        global_range: Range<usize>,
+        root: &HasherNode<H>,
+        commons: &[<Self as VidScheme>::Common],
+        proof: &CrossProof<PROOF>,
+    ) -> VidResult<Result<(), ()>>
+    where
+        Self: PayloadProver<PROOF>,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+    {
+        if self.multi_root(commons)? != *root {
+            return Err(VidError::Argument(
+                "commons inconsistent with root".to_string(),
+            ));
+        }
+        if payload_subslice.len() != global_range.len() {
+            return Err(VidError::Argument(format!(
+                "payload_subslice len {} differs from range ({}..{}) len {}",
+                payload_subslice.len(),
+                global_range.start,
+                global_range.end,
+                global_range.len()
+            )));
+        }
+        let sub_ranges = split_global_range(&Self::payload_byte_lens(commons), &global_range)?;
+        if proof.proofs.len() != sub_ranges.len() {
+            return Err(VidError::Argument(format!(
+                "proof len {} differs from number of overlapped payloads {}",
+                proof.proofs.len(),
+                sub_ranges.len()
+            )));
+        }
+
+        let mut subslice_start = 0;
+        for ((index, range), sub_proof) in sub_ranges.into_iter().zip(proof.proofs.iter()) {
+            let subslice_end = subslice_start + range.len();
+            let commit = self.common_commit(&commons[index])?;
+            let stmt = Statement {
+                payload_subslice: &payload_subslice[subslice_start..subslice_end],
+                range,
+                commit: &commit,
+                common: &commons[index],
+            };
+            if self.payload_verify(stmt, sub_proof)?.is_err() {
+                return Ok(Err(()));
+            }
+            subslice_start = subslice_end;
+        }
+        Ok(Ok(()))
+    }
+
+    /// Same as [`PayloadProver::payload_verify`] except that the statement and
+    /// proof are given as bytes and the proof type is chosen at runtime by
+    /// `proof_kind`.
+    ///
+    /// `stmt_bytes` is an [`OwnedStatement`] and `proof_bytes` a proof of
+    /// kind `proof_kind`, each in its compressed [`CanonicalSerialize`]
+    /// encoding. Useful for a generic
+    /// verification service that does not know the proof type at compile
+    /// time.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if either of `stmt_bytes` or
+    /// `proof_bytes` is malformed, including a proof of a kind other than
+    /// `proof_kind`, or under the conditions of
+    /// [`PayloadProver::payload_verify`].
+    pub fn payload_verify_bytes(
+        &self,
+        stmt_bytes: &[u8],
+        proof_bytes: &[u8],
+        proof_kind: ProofKind,
+    ) -> VidResult<Result<(), ()>> {
+        let stmt: OwnedStatement<E, H> = deserialize_bytes(stmt_bytes, "statement")?;
+        let stmt = stmt.as_statement();
+        match proof_kind {
+            ProofKind::Small => self.payload_verify(
+                stmt,
+                &deserialize_bytes::<SmallRangeProof<KzgProof<E>>>(proof_bytes, "proof")?,
+            ),
+            ProofKind::Large => self.payload_verify(
+                stmt,
+                &deserialize_bytes::<LargeRangeProof<KzgEval<E>>>(proof_bytes, "proof")?,
+            ),
+            ProofKind::SingleOpening => self.payload_verify(
+                stmt,
+                &deserialize_bytes::<SingleOpeningProof<E>>(proof_bytes, "proof")?,
+            ),
+        }
+    }
+
+    /// Version byte at the start of the bytes produced by
+    /// [`Advz::proof_to_bytes`].
+    ///
+    /// Version 1 followed the header with a `bincode` encoding and is no
+    /// longer supported.
+    pub const PROOF_BYTES_VERSION: u8 = 2;
+
+    /// Serialize `proof` with a header that lets [`Advz::proof_from_bytes`]
+    /// catch a proof made for a different configuration.
+    ///
+    /// The header is the version byte [`Advz::PROOF_BYTES_VERSION`], the
+    /// magic bytes [`VidSchemeMagic::magic`] identifying the curve,
+    /// `payload_chunk_size` as a little-endian `u64` and the byte
+    /// [`ProofKind::to_byte`] of `PROOF`. The proof follows in its compressed
+    /// [`CanonicalSerialize`] encoding.
+    pub fn proof_to_bytes<PROOF>(&self, proof: &PROOF) -> VidResult<Vec<u8>>
+    where
+        PROOF: VersionedProof,
+    {
+        let mut bytes = vec![Self::PROOF_BYTES_VERSION];
+        bytes.extend_from_slice(&Self::magic());
+        bytes.extend_from_slice(&(self.payload_chunk_size as u64).to_le_bytes());
+        bytes.push(PROOF::KIND.to_byte());
+        proof.serialize_compressed(&mut bytes).map_err(vid)?;
+        Ok(bytes)
+    }
+
+    /// Deserialize a proof produced by [`Advz::proof_to_bytes`], checking
+    /// its header against the configuration of `self`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `bytes` is truncated or malformed, has
+    /// an unsupported version, or if its header records a different curve,
+    /// `payload_chunk_size` or proof kind.
+    pub fn proof_from_bytes<PROOF>(&self, bytes: &[u8]) -> VidResult<PROOF>
+    where
+        PROOF: VersionedProof,
+    {
+        const HEADER_LEN: usize = 1 + 4 + 8 + 1;
+        if bytes.len() < HEADER_LEN {
+            return Err(VidError::Argument(format!(
+                "proof bytes len {} shorter than header len {}",
+                bytes.len(),
+                HEADER_LEN
+            )));
+        }
+        let (header, proof_bytes) = bytes.split_at(HEADER_LEN);
+
+        if header[0] != Self::PROOF_BYTES_VERSION {
+            return Err(VidError::Argument(format!(
+                "unsupported proof version {}, expected {}",
+                header[0],
+                Self::PROOF_BYTES_VERSION
+            )));
+        }
+        if header[1..5] != Self::magic() {
+            return Err(VidError::Argument(format!(
+                "proof magic {:02x?} differs from expected {:02x?}: proof is for a different curve",
+                &header[1..5],
+                Self::magic()
+            )));
+        }
+        let payload_chunk_size = u64::from_le_bytes(
+            header[5..13]
+                .try_into()
+                .expect("slice len should equal u64 len"),
+        );
+        if payload_chunk_size != self.payload_chunk_size as u64 {
+            return Err(VidError::Argument(format!(
+                "proof payload_chunk_size {} differs from verifier payload_chunk_size {}",
+                payload_chunk_size, self.payload_chunk_size
+            )));
+        }
+        if header[13] != PROOF::KIND.to_byte() {
+            return Err(VidError::Argument(format!(
+                "proof kind {:?} differs from expected {:?}",
+                ProofKind::from_byte(header[13]),
+                PROOF::KIND
+            )));
+        }
+        deserialize_bytes(proof_bytes, "proof")
+    }
+
+    /// Reference verifier for a [`SmallRangeProof`] from any conforming
+    /// prover, given as bytes.
+    ///
+    /// Accepts the same statements and proofs as [`PayloadProver::payload_verify`]
+    /// from a prover that follows this spec. Every step is spelled out below
+    /// so that the verifier can be reimplemented in another language.
+    ///
+    /// # Encoding
+    ///
+    /// Numbers are little-endian `u64`, field elements and curve points are
+    /// arkworks compressed encodings and digests are the raw bytes output by
+    /// `H`.
+    ///
+    /// - `stmt_bytes`: start and end of the range, length of the payload
+    ///   subslice followed by its bytes, the payload commitment, then the
+    ///   common data: the tag [`super::COMMON_FORMAT_V1`], the number of
+    ///   poly commits followed by each commit, the all-evals digest, the
+    ///   payload byte length, a `0` byte or a `1` byte followed by the header
+    ///   digest, `payload_chunk_size`, `num_storage_nodes`, and a `0` byte or
+    ///   a `1` byte followed by the number of evals leaf digests and each
+    ///   digest.
+    /// - `proof_bytes`: the number of KZG proofs followed by each proof, the
+    ///   length of the prefix bytes followed by those bytes, the same for the
+    ///   suffix bytes, and the start and end of the chunk range.
+    ///
+    /// # Steps
+    ///
+    /// 1. Decode both inputs. Reject trailing bytes, any curve point not in
+    ///    the prime-order subgroup and any input that differs from the
+    ///    re-encoding of its decoding.
+    /// 2. Check that `payload_chunk_size` and `num_storage_nodes` equal those
+    ///    of `self`.
+    /// 3. Check that the payload commitment is computed from the common data
+    ///    in the format of the commit version of `self`, see
+    ///    [`CommitVersion`].
+    /// 4. Check that the range is nonempty and ends at most at the payload
+    ///    byte length, that the range length is within the limit set via
+    ///    [`Advz::set_max_proof_range_bytes`], that the subslice length equals
+    ///    the range length and that the chunk range equals the range.
+    /// 5. With `b` the byte capacity of a field element and `n` equal to
+    ///    `payload_chunk_size`, the range covers elements `s = start / b` to
+    ///    `e = ceil(end / b)` exclusive. Check that `s / n == (e - 1) / n`, the
+    ///    index `p` of the polynomial.
+    /// 6. Check that the prefix holds `start - s * b` bytes, the suffix
+    ///    `min(e * b, bytes_len) - end` bytes and that there are `e - s`
+    ///    proofs.
+    /// 7. Pack prefix, subslice and suffix into field elements via
+    ///    [`bytes_to_field`].
+    /// 8. For each `i` in `0..e - s`, verify the `i`th proof as a KZG opening
+    ///    of poly commit `p` to the `i`th element at the point of
+    ///    `eval_domain` for element `s - p * n + i` of the polynomial.
+    ///
+    /// Failure at steps 1 to 7 is an error. Failure at step 8 returns
+    /// `Ok(Err(()))`.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if either input violates steps 1 to 7,
+    /// or if `self` packs payload bits via [`FieldPacking::Bits`], which this
+    /// specification does not cover.
+    ///
+    /// [`bytes_to_field`]: super::bytes_to_field::bytes_to_field
+    /// [`CommitVersion`]: super::CommitVersion
+    pub fn payload_verify_spec_compliant(
+        &self,
+        stmt_bytes: &[u8],
+        proof_bytes: &[u8],
+    ) -> VidResult<Result<(), ()>> {
+        self.check_byte_packing("spec-compliant payload verification")?;
+
+        // step 1
+        let stmt: OwnedStatement<E, H> = deserialize_canonical_bytes(stmt_bytes, "statement")?;
+        let proof: SmallRangeProof<KzgProof<E>> =
+            deserialize_canonical_bytes(proof_bytes, "proof")?;
+        let OwnedStatement {
+            payload_subslice,
+            range,
+            commit,
+            common,
+        } = stmt;
+
+        // steps 2 and 3
+        self.check_common_params(&common)?;
+        self.check_common_commit_consistency(&common, &commit)?;
+
+        // step 4
+        if range.is_empty() || range.end > common.bytes_len {
+            return Err(VidError::Argument(format!(
+                "range ({},{}) empty or beyond payload byte len {}",
+                range.start, range.end, common.bytes_len
+            )));
+        }
+        self.check_stmt_proof_consistency(&payload_subslice, &range, &proof.chunk_range)?;
+
+        // step 5
+        let elem_bytes = elem_byte_capacity::<KzgEval<E>>();
+        let start_elem = range.start / elem_bytes;
+        let end_elem = (range.end - 1) / elem_bytes + 1;
+        let poly_index = start_elem / self.payload_chunk_size;
+        if (end_elem - 1) / self.payload_chunk_size != poly_index {
+            return Err(VidError::Argument(format!(
+                "range ({},{}) spans multiple polynomials",
+                range.start, range.end
+            )));
+        }
+
+        // step 6
+        let prefix_len = range.start - start_elem * elem_bytes;
+        let suffix_len = ark_std::cmp::min(end_elem * elem_bytes, common.bytes_len) - range.end;
+        if proof.prefix_bytes.len() != prefix_len
+            || proof.suffix_bytes.len() != suffix_len
+            || proof.proofs.len() != end_elem - start_elem
+        {
+            return Err(VidError::Argument(format!(
+                "proof prefix len {}, suffix len {} or proof count {} differ from expected {}, {} and {}",
+                proof.prefix_bytes.len(),
+                proof.suffix_bytes.len(),
+                proof.proofs.len(),
+                prefix_len,
+                suffix_len,
+                end_elem - start_elem
+            )));
+        }
+
+        // step 7
+        let data_elems: Vec<KzgEval<E>> = bytes_to_field(
+            proof
+                .prefix_bytes
+                .iter()
+                .chain(&payload_subslice)
+                .chain(&proof.suffix_bytes),
+        )
+        .collect();
+        assert_eq!(data_elems.len(), proof.proofs.len()); // sanity
+
+        // step 8
+        let poly_commit = &common.poly_commits[poly_index];
+        let offset_elem = start_elem - poly_index * self.payload_chunk_size;
+        for (i, (elem, pf)) in data_elems.iter().zip(&proof.proofs).enumerate() {
+            if !UnivariateKzgPCS::verify(
+                &self.vk,
+                poly_commit,
+                &self.elem_point(offset_elem + i),
+                elem,
+                pf,
+            )
+            .map_err(VidError::Pcs)?
+            {
+                return Ok(Err(()));
+            }
+        }
+        Ok(Ok(()))
+    }
+
+    /// Same as [`PayloadProver::payload_verify`] for [`SmallRangeProof`]
+    /// except that an [`AuditLog`] of every check is also returned.
+    ///
+    /// Unlike [`PayloadProver::payload_verify`], verification does not stop
+    /// at the first failing element, so that the log records the outcome for
+    /// every element.
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] under the same conditions as
+    /// [`PayloadProver::payload_verify`]. No log is returned in that case.
+    pub fn payload_verify_with_audit(
+        &self,
+        stmt: Statement<Self>,
+        proof: &SmallRangeProof<KzgProof<E>>,
+    ) -> VidResult<(Result<(), ()>, AuditLog<E, H>)> {
+        let (poly_index, points, data_elems) =
+            self.small_range_proof_inputs(stmt.payload_subslice, &stmt.range, proof)?;
+        self.check_common_params(stmt.common)?;
+        self.check_common_commit_consistency(stmt.common, stmt.commit)?;
+        let poly_commit = &stmt.common.poly_commits[poly_index];
+
+        let entries = points
+            .into_iter()
+            .zip(data_elems)
+            .zip(proof.proofs.iter())
+            .map(|((point, eval), pf)| {
+                let verified = UnivariateKzgPCS::verify(&self.vk, poly_commit, &point, &eval, pf)
+                    .map_err(VidError::Pcs)?;
+                Ok(AuditEntry {
+                    point,
+                    eval,
+                    verified,
+                })
+            })
+            .collect::<VidResult<Vec<_>>>()?;
+        let outcome = if entries.iter().all(|entry| entry.verified) {
+            Ok(())
+        } else {
+            Err(())
+        };
+        Ok((
+            outcome,
+            AuditLog {
+                commit: *stmt.commit,
+                poly_index,
+                range: stmt.range,
+                entries,
+            },
+        ))
+    }
+
+    /// Same as [`PayloadProver::payload_verify`] except that the commitment
+    /// and common data are loaded from `store` under `block_id` instead of
+    /// passed in a [`Statement`].
+    ///
+    /// # Errors
+    ///
+    /// Return [`VidError::Argument`] if `store` has no commitment or no common
+    /// data for `block_id`, or under the conditions of
+    /// [`PayloadProver::payload_verify`]. Errors from `store` are passed
+    /// through.
+    pub fn payload_verify_from_store<S, PROOF>(
+        &self,
+        store: &S,
+        block_id: &S::BlockId,
+        payload_subslice: &[u8],
+        range: Range<usize>,
+        proof: &PROOF,
+    ) -> VidResult<Result<(), ()>>
+    where
+        Self: PayloadProver<PROOF>,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+        S: CommitStore<Self>,
+    {
+        let commit = store
+            .get_commit(block_id)?
+            .ok_or_else(|| VidError::Argument("no commit for block in store".to_string()))?;
+        let common = store
+            .get_common(block_id)?
+            .ok_or_else(|| VidError::Argument("no common for block in store".to_string()))?;
+        self.payload_verify(
+            Statement {
+                payload_subslice,
+                range,
+                commit: &commit,
+                common: &common,
+            },
+            proof,
+        )
+    }
+
+    /// Same as [`PayloadVerifyCompact::payload_verify_compact`] except that
+    /// the commitment to the polynomial containing `range` is given in the
+    /// compressed form of [`Advz::compress_poly_commit`].
+    ///
+    /// # Errors
+    ///
+    /// In addition to the errors of
+    /// [`PayloadVerifyCompact::payload_verify_compact`], return
+    /// [`VidError::Argument`] if `compressed_poly_commit` is invalid as per
+    /// [`Advz::decompress_poly_commit`].
+    pub fn payload_verify_from_compressed_commit<PROOF>(
+        &self,
+        payload_subslice: &[u8],
+        range: Range<usize>,
+        commit: &<Self as VidScheme>::Commit,
+        common: &CompactCommon<E, H>,
+        compressed_poly_commit: &[u8],
+        proof: &PROOF,
+    ) -> VidResult<Result<(), ()>>
+    where
+        Self: PayloadVerifyCompact<E, H, PROOF>,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned,
+    {
+        let poly_commit = Self::decompress_poly_commit(compressed_poly_commit)?;
+        self.payload_verify_compact(
+            CompactStatement {
+                payload_subslice,
+                range,
+                commit,
+                common,
+                poly_commit: &poly_commit,
+            },
+            proof,
+        )
+    }
+
+    /// Compute [`PayloadProver::payload_proof`] for `range` of `payload` in
+    /// the background and return a [`ProofHandle`] for the result.
+    ///
+    /// `self` and `payload` are shared rather than copied, so many proofs
+    /// over the same payload may be in flight at once. Work runs on the rayon
+    /// global thread pool, so excess requests queue up rather than add
+    /// threads. A panic while computing the proof is resumed by
+    /// [`ProofHandle::wait`].
+    #[cfg(all(feature = "std", feature = "parallel"))]
+    pub fn spawn_proof<PROOF>(
+        self: &std::sync::Arc<Self>,
+        payload: std::sync::Arc<Vec<u8>>,
+        range: Range<usize>,
+    ) -> ProofHandle<PROOF>
+    where
+        Self: PayloadProver<PROOF> + Send + Sync + 'static,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned + Send + 'static,
+    {
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+        let advz = std::sync::Arc::clone(self);
+        rayon::spawn(move || {
+            let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                advz.payload_proof(payload.as_slice(), range)
+            }));
+            // the receiver might have been dropped; nothing to do
+            tx.send(res).ok();
+        });
+        ProofHandle { rx }
+    }
+
+    /// Verify each request received from `requests` and send its
+    /// [`VerifyResult`] to `results` as soon as it completes, for pipelined
+    /// consumers such as a verification service.
+    ///
+    /// Return once `requests` is closed, ie. every sender is dropped. Results
+    /// may arrive out of order; match them to requests via
+    /// [`VerifyResult::id`]. Requests are verified on the rayon global thread
+    /// pool if the `parallel` feature is enabled and
+    /// [`Advz::set_deterministic`] is not set, otherwise one at a time on the
+    /// calling thread in request order. Stop early if `results` is closed.
+    ///
+    /// Unlike [`Advz::payload_verify_stream`], which streams the bytes of a
+    /// single payload subslice, this method streams whole requests.
+    #[cfg(feature = "std")]
+    pub fn payload_verify_channel<PROOF>(
+        &self,
+        requests: std::sync::mpsc::Receiver<VerifyRequest<PROOF, E, H>>,
+        results: std::sync::mpsc::Sender<VerifyResult>,
+    ) where
+        Self: PayloadProver<PROOF> + Sync,
+        PROOF: Clone + Debug + Eq + PartialEq + Serialize + DeserializeOwned + Send,
+    {
+        let verify = |request: VerifyRequest<PROOF, E, H>| VerifyResult {
+            id: request.id,
+            outcome: self.payload_verify(request.stmt.as_statement(), &request.proof),
+        };
+
+        #[cfg(feature = "parallel")]
+        if !self.deterministic {
+            use rayon::iter::{ParallelBridge, ParallelIterator};
+            // errors mean the results receiver has been dropped; nothing to
+            // do
+            let _ = requests
+                .into_iter()
+                .par_bridge()
+                .try_for_each_with(results, |results, request| results.send(verify(request)));
+            return;
+        }
+        for request in requests {
+            if results.send(verify(request)).is_err() {
+                break;
+            }
+        }
+    }
+
+    // lots of index manipulation
+    //
+    // Under `FieldPacking::Bits` an element might begin or end inside a byte,
+    // so conversions go via bit indices. Polynomials always begin on a byte.
+    fn index_byte_to_elem(&self, index: usize) -> usize {
+        index_coarsen(index_refine(index, 8), self.elem_bit_capacity())
+    }
+    fn index_poly_to_byte(&self, index: usize) -> usize {
+        index_refine(index, self.poly_byte_len())
+    }
+    fn range_byte_to_elem(&self, range: &Range<usize>) -> Range<usize> {
+        range_coarsen(&range_refine(range, 8), self.elem_bit_capacity())
+    }
+    fn range_elem_to_byte(&self, range: &Range<usize>) -> Range<usize> {
+        let range_bit = range_refine(range, self.elem_bit_capacity());
+        Range {
+            start: index_coarsen(range_bit.start, 8),
+            end: (range_bit.end + 7) / 8,
+        }
+    }
+    fn range_elem_to_byte_clamped(&self, range: &Range<usize>, len: usize) -> Range<usize> {
+        let result = self.range_elem_to_byte(range);
+        Range {
+            end: ark_std::cmp::min(result.end, len),
+            ..result
+        }
+    }
+    fn range_elem_to_poly(&self, range: &Range<usize>) -> Range<usize> {
+        range_coarsen(range, self.payload_chunk_size)
+    }
+    fn range_byte_to_poly(&self, range: &Range<usize>) -> Range<usize> {
+        range_coarsen(range, self.poly_byte_len())
+    }
+
+    /// Elements `range_elem` encoded by `bytes`, which are the bytes
+    /// `range_elem_to_byte(range_elem)`, possibly truncated by the end of the
+    /// payload.
+    ///
+    /// Under `FieldPacking::Bits` the first byte might also hold bits of the
+    /// previous element and the final byte bits of the next, which are
+    /// dropped.
+    fn covering_bytes_to_elems<I>(&self, bytes: I, range_elem: &Range<usize>) -> Vec<KzgEval<E>>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<u8>,
+    {
+        match self.field_packing {
+            FieldPacking::Bytes => bytes_to_field(bytes).collect(),
+            FieldPacking::Bits => {
+                let skip_bits = (range_elem.start * self.elem_bit_capacity()) % 8;
+                bytes_to_field_packed_skip(bytes, skip_bits)
+                    .take(range_elem.len())
+                    .collect()
+            },
+        }
+    }
+
+    /// Assemble a [`LargeRangeProof`] for `range` given the elements
+    /// `poly_elems` of the polynomial containing `range`.
+    ///
+    /// Caller must ensure `range` is nonempty, inside `payload` and inside a
+    /// single polynomial.
+    fn large_range_proof_from_poly_elems(
+        &self,
+        payload: &[u8],
+        poly_elems: &[KzgEval<E>],
+        range: Range<usize>,
+    ) -> LargeRangeProof<KzgEval<E>> {
+        // index conversion
+        let range_elem = self.range_byte_to_elem(&range);
+        let range_poly = self.range_elem_to_poly(&range_elem);
+        let start_namespace_byte = self.index_poly_to_byte(range_poly.start);
+        let offset_elem = range_elem.start - self.index_byte_to_elem(start_namespace_byte);
+        let range_elem_byte = self.range_elem_to_byte_clamped(&range_elem, payload.len());
+
+        // compute the prefix and suffix elems
+        LargeRangeProof {
+            prefix_elems: poly_elems[..offset_elem].to_vec(),
+            suffix_elems: poly_elems[offset_elem + range_elem.len()..].to_vec(),
+            prefix_bytes: payload[range_elem_byte.start..range.start].to_vec(),
+            suffix_bytes: payload[range.end..range_elem_byte.end].to_vec(),
+            chunk_range: range,
+        }
+    }
+
+    /// Check that `elem_indices` is nonempty, inside a payload of
+    /// `payload_byte_len` bytes and inside a single polynomial.
+    ///
+    /// Returns the index of that polynomial.
+    fn check_elem_indices(
+        &self,
+        elem_indices: &[usize],
+        payload_byte_len: usize,
+    ) -> VidResult<usize> {
+        self.check_byte_packing("strided payload proof")?;
+        let first = elem_indices
+            .first()
+            .ok_or_else(|| VidError::Argument("elem indices is empty".to_string()))?;
+        let elem_byte_len = elem_byte_capacity::<KzgEval<E>>();
+        match self.max_proof_range_bytes {
+            Some(max) if elem_indices.len().saturating_mul(elem_byte_len) > max => {
+                return Err(VidError::Argument(format!(
+                    "{} elem indices of {} bytes each exceed max_proof_range_bytes {}",
+                    elem_indices.len(),
+                    elem_byte_len,
+                    max
+                )))
+            },
+            _ => (),
+        }
+        let payload_elem_len = (payload_byte_len + elem_byte_len - 1) / elem_byte_len;
+        let poly_index = index_coarsen(*first, self.payload_chunk_size);
+        for index in elem_indices {
+            if *index >= payload_elem_len {
+                return Err(VidError::Argument(format!(
+                    "elem index {} out of bounds for payload elem len {}",
+                    index, payload_elem_len
+                )));
+            }
+            if index_coarsen(*index, self.payload_chunk_size) != poly_index {
+                return Err(VidError::Argument(format!(
+                    "elem indices {} and {} lie in different polynomials",
+                    first, index
+                )));
+            }
+        }
+        Ok(poly_index)
+    }
+
+    /// Check ranges for [`Advz::prove_ranges_equal`] and return the indices
+    /// of their polynomials and the range of their elements within those
+    /// polynomials.
+    fn ranges_equal_params(
+        &self,
+        range_a: &Range<usize>,
+        range_b: &Range<usize>,
+        payload_byte_len: usize,
+    ) -> VidResult<(usize, usize, Range<usize>)> {
+        if range_a.len() != range_b.len() {
+            return Err(VidError::Argument(format!(
+                "range lens {} and {} differ",
+                range_a.len(),
+                range_b.len()
+            )));
+        }
+        let poly_byte_len = self.index_poly_to_byte(1);
+        if range_a.start % poly_byte_len != range_b.start % poly_byte_len {
+            return Err(VidError::Argument(format!(
+                "ranges ({}..{}) and ({}..{}) have different offsets within their polynomials",
+                range_a.start, range_a.end, range_b.start, range_b.end
+            )));
+        }
+        let mut poly_indices = [0; 2];
+        for (range, poly_index) in [range_a, range_b].into_iter().zip(poly_indices.iter_mut()) {
+            self.check_proof_range_len(range)?;
+            if range.is_empty() || range.end > payload_byte_len {
+                return Err(VidError::Argument(format!(
+                    "range ({}..{}) empty or out of bounds for payload len {}",
+                    range.start, range.end, payload_byte_len
+                )));
+            }
+            let range_poly = self.range_byte_to_poly(range);
+            check_range_poly(&range_poly)?;
+            *poly_index = range_poly.start;
+        }
+        let range_elem = self.range_byte_to_elem(range_a);
+        let offset = range_elem.start - poly_indices[0] * self.payload_chunk_size;
+        Ok((
+            poly_indices[0],
+            poly_indices[1],
+            offset..offset + range_elem.len(),
+        ))
+    }
+
+    /// Argument checks shared by [`Advz::prove_common_prefix`] and
+    /// [`Advz::verify_common_prefix`].
+    ///
+    /// Returns the index of the polynomial containing the end of the prefix,
+    /// the number of its elements wholly in the prefix and the number of
+    /// prefix bytes in the next element.
+    fn common_prefix_params(
+        &self,
+        prefix_len: usize,
+        payload_byte_len_a: usize,
+        payload_byte_len_b: usize,
+    ) -> VidResult<(usize, usize, usize)> {
+        self.check_byte_packing("common prefix proof")?;
+        self.check_proof_range_len(&(0..prefix_len))?;
+        if prefix_len == 0 || prefix_len > payload_byte_len_a.min(payload_byte_len_b) {
+            return Err(VidError::Argument(format!(
+                "prefix len {} zero or out of bounds for payload lens {} and {}",
+                prefix_len, payload_byte_len_a, payload_byte_len_b
+            )));
+        }
+        let poly_byte_len = self.index_poly_to_byte(1);
+        let elem_byte_len = elem_byte_capacity::<KzgEval<E>>();
+        let poly_offset = prefix_len % poly_byte_len;
+        Ok((
+            prefix_len / poly_byte_len,
+            poly_offset / elem_byte_len,
+            poly_offset % elem_byte_len,
+        ))
+    }
+
+    fn strided_points(&self, elem_indices: &[usize]) -> Vec<KzgPoint<E>> {
+        elem_indices
+            .iter()
+            .map(|i| self.elem_point(i % self.payload_chunk_size))
+            .collect()
+    }
+
+    fn check_proof_range_len(&self, range: &Range<usize>) -> VidResult<()> {
+        match self.max_proof_range_bytes {
+            Some(max) if range.len() > max => Err(VidError::Argument(format!(
+                "range ({}..{}) len {} exceeds max_proof_range_bytes {}",
+                range.start,
+                range.end,
+                range.len(),
+                max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Argument checks for a proof of `payload[range]`. The limit set via
+    /// [`Advz::set_max_proof_range_bytes`] is checked first.
+    fn check_payload_proof_range(&self, payload: &[u8], range: &Range<usize>) -> VidResult<()> {
+        self.check_proof_range_len(range)?;
+        if range.is_empty() {
+            return Err(VidError::Argument(format!(
+                "empty range ({}..{})",
+                range.start, range.end
+            )));
+        }
+        if range.end > payload.len() {
+            return Err(VidError::Argument(format!(
+                "range ({}..{}) out of bounds for payload len {}",
+                range.start,
+                range.end,
+                payload.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Rebuild the commitment to the polynomial containing the range proved
+    /// by a [`LargeRangeProof`].
+    fn large_range_proof_poly_commit(
+        &self,
+        payload_subslice: &[u8],
+        proof: &LargeRangeProof<KzgEval<E>>,
+    ) -> VidResult<KzgCommit<E>> {
+        let poly = self.polynomial(
+            self.large_range_proof_elems(payload_subslice, proof)
+                .into_iter(),
+        );
+        UnivariateKzgPCS::commit(&self.ck, &poly).map_err(VidError::Pcs)
+    }
+
+    /// Commitments to the elements covering the range of a
+    /// [`LargeRangeProof`] and to its complement, each with the other's
+    /// elements zeroed.
+    fn large_range_proof_decomposition(
+        &self,
+        payload_subslice: &[u8],
+        proof: &LargeRangeProof<KzgEval<E>>,
+    ) -> VidResult<(KzgCommit<E>, KzgCommit<E>)> {
+        let elems = self.large_range_proof_elems(payload_subslice, proof);
+        let range_elems = proof.prefix_elems.len()..elems.len() - proof.suffix_elems.len();
+        let zero = KzgEval::<E>::zero();
+        let range_poly = self.polynomial(elems.iter().enumerate().map(|(i, elem)| {
+            if range_elems.contains(&i) {
+                *elem
+            } else {
+                zero
+            }
+        }));
+        let complement_poly = self.polynomial(elems.iter().enumerate().map(|(i, elem)| {
+            if range_elems.contains(&i) {
+                zero
+            } else {
+                *elem
+            }
+        }));
+        Ok((
+            UnivariateKzgPCS::commit(&self.ck, &range_poly).map_err(VidError::Pcs)?,
+            UnivariateKzgPCS::commit(&self.ck, &complement_poly).map_err(VidError::Pcs)?,
+        ))
+    }
+
+    /// All elements of the polynomial proved by a [`LargeRangeProof`], in
+    /// eval form.
+    fn large_range_proof_elems(
+        &self,
+        payload_subslice: &[u8],
+        proof: &LargeRangeProof<KzgEval<E>>,
+    ) -> Vec<KzgEval<E>> {
+        proof
+            .prefix_elems
+            .iter()
+            .cloned()
+            .chain(
+                self.covering_bytes_to_elems(
+                    proof
+                        .prefix_bytes
+                        .iter()
+                        .chain(payload_subslice)
+                        .chain(proof.suffix_bytes.iter()),
+                    &self.range_byte_to_elem(&proof.chunk_range),
+                ),
+            )
+            .chain(proof.suffix_elems.iter().cloned())
+            .collect()
+    }
+
+    /// Argument checks and preparation shared by all verifiers of
+    /// [`SmallRangeProof`].
+    ///
+    /// Returns the index of the polynomial containing the range, the list of
+    /// input points and the list of data elems. Does not check `common`.
+    #[allow(clippy::type_complexity)]
+    fn small_range_proof_inputs<T>(
+        &self,
+        payload_subslice: &[u8],
+        range: &Range<usize>,
+        proof: &T,
+    ) -> VidResult<(usize, Vec<KzgPoint<E>>, Vec<KzgEval<E>>)>
+    where
+        T: SmallRangeProofParts<KzgProof<E>>,
+    {
+        let (poly_index, data_elems) =
+            self.small_range_proof_data_elems(payload_subslice, range, proof)?;
+        let points = self.range_points(proof.chunk_range());
+        assert_eq!(data_elems.len(), points.len()); // sanity
+        Ok((poly_index, points, data_elems))
+    }
+
+    /// Same as [`Advz::small_range_proof_inputs`] except that the list of input
+    /// points is not computed.
+    fn small_range_proof_data_elems<T>(
+        &self,
+        payload_subslice: &[u8],
+        range: &Range<usize>,
+        proof: &T,
+    ) -> VidResult<(usize, Vec<KzgEval<E>>)>
+    where
+        T: SmallRangeProofParts<KzgProof<E>>,
+    {
+        self.check_stmt_proof_consistency(payload_subslice, range, proof.chunk_range())?;
+
+        // index conversion
+        let range_poly = self.range_byte_to_poly(proof.chunk_range());
+
+        check_range_poly(&range_poly)?;
+
+        // prepare list of data elems
+        let data_elems = self.covering_bytes_to_elems(
+            proof
+                .prefix_bytes()
+                .iter()
+                .chain(payload_subslice)
+                .chain(proof.suffix_bytes().iter()),
+            &self.range_byte_to_elem(proof.chunk_range()),
+        );
+
+        if data_elems.len() != proof.proofs().len() {
+            return Err(VidError::Argument(format!(
+                "data len {} differs from proof len {}",
+                data_elems.len(),
+                proof.proofs().len()
+            )));
+        }
+        Ok((range_poly.start, data_elems))
+    }
+
+    /// List of input points for the elements of byte range `range`, which
+    /// must be nonempty and lie inside a single polynomial.
+    fn range_points(&self, range: &Range<usize>) -> Vec<KzgPoint<E>> {
+        // index conversion
+        let range_elem = self.range_byte_to_elem(range);
+        let range_poly = self.range_elem_to_poly(&range_elem);
+        let start_namespace_byte = self.index_poly_to_byte(range_poly.start);
+        let offset_elem = range_elem.start - self.index_byte_to_elem(start_namespace_byte);
+
+        (offset_elem..offset_elem + range_elem.len())
+            .map(|i| self.elem_point(i))
+            .collect()
+    }
+
+    /// Verify each KZG opening proof in a [`SmallRangeProof`].
+    ///
+    /// Returns the index of the first element whose proof fails, if any.
+    fn small_range_proof_first_failure<T>(
+        &self,
+        stmt: &Statement<Self>,
+        proof: &T,
+    ) -> VidResult<Option<usize>>
+    where
+        T: SmallRangeProofParts<KzgProof<E>>,
+    {
+        let (poly_index, points, data_elems) =
+            self.small_range_proof_inputs(stmt.payload_subslice, &stmt.range, proof)?;
+        self.check_common_params(stmt.common)?;
+        self.check_common_commit_consistency(stmt.common, stmt.commit)?;
+        self.small_range_proof_first_failure_at(
+            &stmt.common.poly_commits[poly_index],
+            &points,
+            &data_elems,
+            proof,
+        )
+    }
+
+    /// Like [`Advz::small_range_proof_first_failure`] except that the inputs
+    /// are already prepared and checked.
+    fn small_range_proof_first_failure_at<T>(
+        &self,
+        poly_commit: &KzgCommit<E>,
+        points: &[KzgPoint<E>],
+        data_elems: &[KzgEval<E>],
+        proof: &T,
+    ) -> VidResult<Option<usize>>
+    where
+        T: SmallRangeProofParts<KzgProof<E>>,
+    {
+        // verify proof
+        // TODO naive verify for multi_open https://github.com/EspressoSystems/jellyfish/issues/387
+        for (i, (point, (elem, pf))) in points
+            .iter()
+            .zip(data_elems.iter().zip(proof.proofs().iter()))
+            .enumerate()
+        {
+            if !UnivariateKzgPCS::verify(&self.vk, poly_commit, point, elem, pf)
+                .map_err(VidError::Pcs)?
+            {
+                return Ok(Some(i));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Fiat-Shamir evaluation point for a [`SingleOpeningProof`].
+    fn single_opening_point(
+        poly_commit: &KzgCommit<E>,
+        quotient_commit: &KzgCommit<E>,
+        chunk_range: &Range<usize>,
+        data_elems: &[KzgEval<E>],
+    ) -> VidResult<KzgPoint<E>> {
+        let mut hasher = H::new();
+        hasher.update(b"advz single opening point");
+        poly_commit
+            .serialize_uncompressed(&mut hasher)
+            .map_err(vid)?;
+        quotient_commit
+            .serialize_uncompressed(&mut hasher)
+            .map_err(vid)?;
+        hasher.update((chunk_range.start as u64).to_le_bytes());
+        hasher.update((chunk_range.end as u64).to_le_bytes());
+        data_elems
+            .serialize_uncompressed(&mut hasher)
+            .map_err(vid)?;
+        Ok(PrimeField::from_le_bytes_mod_order(&hasher.finalize()))
+    }
+
+    /// Fiat-Shamir scalar with which a [`SingleOpeningProof`] combines its two
+    /// openings into one.
+    fn single_opening_combiner(
+        point: &KzgPoint<E>,
+        poly_eval: &KzgEval<E>,
+        quotient_eval: &KzgEval<E>,
+    ) -> VidResult<KzgEval<E>> {
+        let mut hasher = H::new();
+        hasher.update(b"advz single opening combiner");
+        for elem in [point, poly_eval, quotient_eval] {
+            elem.serialize_uncompressed(&mut hasher).map_err(vid)?;
+        }
+        Ok(PrimeField::from_le_bytes_mod_order(&hasher.finalize()))
+    }
+
+    /// Check that `commit` is the payload commitment whose poly commit at
+    /// `poly_index` is `proof.poly_commit`, given the other poly commit
+    /// hashes in `proof`.
+    ///
+    /// Return value has the same semantics as [`VidScheme::verify_share`].
+    fn verify_commit_bound_inclusion<PROOF>(
+        &self,
+        commit: &<Self as VidScheme>::Commit,
+        poly_index: usize,
+        proof: &CommitBoundProof<PROOF, E, H>,
+    ) -> VidResult<Result<(), ()>> {
+        let others = &proof.other_poly_commit_hashes;
+        if poly_index > others.len() {
+            return Ok(Err(()));
+        }
+        let poly_commit_hash = HasherNode::<H>::from(Self::poly_commit_hash(&proof.poly_commit)?);
+        let poly_commit_hashes = others[..poly_index]
+            .iter()
+            .chain(ark_std::iter::once(&poly_commit_hash))
+            .chain(others[poly_index..].iter());
+        Ok((*commit
+            == self.poly_commit_hashes_hash(
+                poly_commit_hashes,
+                proof.header_digest.as_ref(),
+                proof.bytes_len,
+            )?)
+        .then_some(())
+        .ok_or(()))
+    }
+
+    /// Check that `commit` is the leaf at `block_key` of the [`CommitSmt`]
+    /// with root `smt_root`.
+    ///
+    /// Return value has the same semantics as [`VidScheme::verify_share`].
+    fn verify_commit_in_smt<I>(
+        commit: &<Self as VidScheme>::Commit,
+        block_key: &I,
+        smt_proof: &CommitSmtProof<H, I>,
+        smt_root: &HasherNode<H>,
+    ) -> VidResult<Result<(), ()>>
+    where
+        I: Index + From<u64> + ToTraversalPath<U3> + CanonicalSerialize,
+    {
+        // the authenticated leaf must be the claimed commit
+        if smt_proof.elem() != Some(&HasherNode::from(*commit)) {
+            return Ok(Err(()));
+        }
+        CommitSmt::<H, I>::verify(smt_root, block_key, smt_proof).map_err(vid)
+    }
+
+    fn check_proof_range(&self, range: &Range<usize>) -> VidResult<()> {
+        if range.is_empty() {
+            return Err(VidError::Argument(format!(
+                "empty range ({}..{})",
+                range.start, range.end
+            )));
+        }
+        check_range_poly(&self.range_byte_to_poly(range))
+    }
+
+    /// Estimated latency of [`Advz::estimate_verify_latency`] under `model`.
+    #[cfg(feature = "std")]
+    fn verify_latency(
+        &self,
+        model: &VerifyCostModel,
+        range: Range<usize>,
+        proof_kind: ProofKind,
+    ) -> VidResult<std::time::Duration> {
+        Ok(match proof_kind {
+            ProofKind::Small => {
+                model.kzg_verify
+                    * u32::try_from(self.range_byte_to_elem(&range).len()).map_err(vid)?
+            },
+            ProofKind::Large => model.poly_msm,
+            ProofKind::SingleOpening => model.kzg_verify,
+        })
+    }
+
+    /// Measure the cost of basic verifier operations on this host, for
+    /// [`Advz::estimate_verify_latency`].
+    ///
+    /// Times each operation a few times and keeps the fastest run, which is
+    /// least affected by noise. Takes a few pairings and MSMs of size
+    /// `payload_chunk_size`, so callers should calibrate once, eg. at
+    /// startup, and reuse the result for every `Advz` with the same curve and
+    /// `payload_chunk_size`.
+    #[cfg(feature = "std")]
+    pub fn calibrate_verify_cost(&self) -> VidResult<VerifyCostModel> {
+        use ark_std::UniformRand;
+        use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+        use std::time::{Duration, Instant};
+
+        const REPS: usize = 3;
+        let fastest = |op: &mut dyn FnMut() -> VidResult<()>| -> VidResult<Duration> {
+            let mut fastest = Duration::MAX;
+            for _ in 0..REPS {
+                let start = Instant::now();
+                op()?;
+                fastest = ark_std::cmp::min(fastest, start.elapsed());
+            }
+            Ok(fastest)
+        };
+
+        // the outcome of the check is irrelevant, only its cost
+        let g = self.ck.powers_of_g[0];
+        let commit = KzgCommit::<E>::from(g);
+        let proof = KzgProof::<E> { proof: g };
+        let kzg_verify = fastest(&mut || {
+            UnivariateKzgPCS::verify(
+                &self.vk,
+                &commit,
+                &KzgPoint::<E>::one(),
+                &KzgEval::<E>::one(),
+                &proof,
+            )
+            .map_err(vid)?;
+            Ok(())
+        })?;
+
+        // random scalars so that the MSM is not artificially cheap
+        let bases = &self.ck.powers_of_g
+            [..ark_std::cmp::min(self.payload_chunk_size, self.ck.powers_of_g.len())];
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let scalars: Vec<_> = bases.iter().map(|_| KzgEval::<E>::rand(&mut rng)).collect();
+        let poly_msm = fastest(&mut || {
+            E::G1::msm_unchecked(bases, &scalars);
+            Ok(())
+        })?;
+
+        Ok(VerifyCostModel {
+            curve: core::any::TypeId::of::<E>(),
+            payload_chunk_size: self.payload_chunk_size,
+            kzg_verify,
+            poly_msm,
+        })
+    }
+
+    fn payload_byte_lens(commons: &[<Self as VidScheme>::Common]) -> Vec<usize> {
+        commons.iter().map(|common| common.bytes_len).collect()
+    }
+
+    /// Open element `elem_index` of `payload`, which might be padding of the
+    /// final polynomial.
+    fn element_opening(
+        &self,
+        payload: &[u8],
+        elem_index: usize,
+    ) -> VidResult<ElementProof<KzgProof<E>>> {
+        let polynomial = self.polynomial(
+            self.poly_elems(payload, elem_index / self.payload_chunk_size)
+                .into_iter(),
+        );
+        let point = self.elem_point(elem_index % self.payload_chunk_size);
+        let (proof, _) =
+            UnivariateKzgPCS::open(&self.ck, &polynomial, &point).map_err(VidError::Pcs)?;
+        Ok(ElementProof { proof })
+    }
+
+    /// Check an opening made by [`Advz::element_opening`] against `common`,
+    /// whose polynomial `elem_index / payload_chunk_size` must exist.
+    fn verify_element_opening(
+        &self,
+        common: &<Self as VidScheme>::Common,
+        elem_index: usize,
+        claimed_elem: KzgEval<E>,
+        proof: &ElementProof<KzgProof<E>>,
+    ) -> VidResult<bool> {
+        let poly_commit = &common.poly_commits[elem_index / self.payload_chunk_size];
+        let point = self.elem_point(elem_index % self.payload_chunk_size);
+        UnivariateKzgPCS::verify(&self.vk, poly_commit, &point, &claimed_elem, &proof.proof)
+            .map_err(VidError::Pcs)
+    }
+
+    /// Check that element `ext_index` of a payload of `payload_byte_len` bytes
+    /// packed into `F` exists, and return the extension degree of `F`.
+    fn check_ext_elem_index<F: Field>(
+        &self,
+        ext_index: usize,
+        payload_byte_len: usize,
+    ) -> VidResult<usize> {
+        let degree = self.check_ext_degree::<F>()?;
+        let capacity = elem_byte_capacity::<F>();
+        let num_ext_elems = (payload_byte_len + capacity - 1) / capacity;
+        if ext_index >= num_ext_elems {
+            return Err(VidError::Argument(format!(
+                "ext elem index {} out of bounds for payload with {} ext elems",
+                ext_index, num_ext_elems
+            )));
+        }
+        Ok(degree)
+    }
+
+    fn check_elem_index(&self, elem_index: usize, payload_byte_len: usize) -> VidResult<()> {
+        let num_elems = self.num_payload_elems(payload_byte_len);
+        if elem_index >= num_elems {
+            return Err(VidError::Argument(format!(
+                "elem index {} out of bounds for payload with {} elems",
+                elem_index, num_elems
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_common_commit_consistency(
+        &self,
+        common: &<Self as VidScheme>::Common,
+        commit: &<Self as VidScheme>::Commit,
+    ) -> VidResult<()> {
+        if *commit != self.common_commit(common)? {
+            return Err(VidError::Argument(
+                "common inconsistent with commit".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check a [`CompactStatement`] against `self` and the index of the
+    /// polynomial containing the proved range.
+    ///
+    /// # Errors
+    /// Return [`VidError::Argument`] if `stmt.common` was produced by an
+    /// [`Advz`] instance with different parameters, if `stmt.common` is
+    /// inconsistent with `stmt.commit`, or if `stmt.poly_commit` is not the
+    /// commitment at `poly_index` in `stmt.common`.
+    fn check_compact_stmt(
+        &self,
+        stmt: &CompactStatement<E, H>,
+        poly_index: usize,
+    ) -> VidResult<()> {
+        self.check_params(
+            stmt.common.payload_chunk_size,
+            stmt.common.num_storage_nodes,
+        )?;
+        if *stmt.commit
+            != self.poly_commit_hashes_hash(
+                stmt.common.poly_commit_hashes.iter(),
+                stmt.common.header_digest.as_ref(),
+                stmt.common.bytes_len,
+            )?
+        {
+            return Err(VidError::Argument(
+                "compact common inconsistent with commit".to_string(),
+            ));
+        }
+        let poly_commit_hash = stmt
+            .common
+            .poly_commit_hashes
+            .get(poly_index)
+            .ok_or_else(|| {
+                VidError::Argument(format!(
+                    "poly index {} out of bounds for compact common with {} poly commits",
+                    poly_index,
+                    stmt.common.poly_commit_hashes.len()
+                ))
+            })?;
+        if *poly_commit_hash.as_ref() != Self::poly_commit_hash(stmt.poly_commit)? {
+            return Err(VidError::Argument(format!(
+                "poly commit inconsistent with compact common at poly index {}",
+                poly_index
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_stmt_proof_consistency(
+        &self,
+        payload_subslice: &[u8],
+        range: &Range<usize>,
+        proof_range: &Range<usize>,
+    ) -> VidResult<()> {
+        self.check_proof_range_len(range)?;
+        if range.is_empty() {
+            return Err(VidError::Argument(format!(
+                "empty range ({},{})",
+                range.start, range.end
+            )));
+        }
+        if payload_subslice.len() != range.len() {
+            return Err(VidError::Argument(format!(
+                "payload_subslice length {} inconsistent with range length {}",
+                payload_subslice.len(),
+                range.len()
+            )));
+        }
+        if range != proof_range {
+            return Err(VidError::Argument(format!(
+                "statement range ({},{}) differs from proof range ({},{})",
+                range.start, range.end, proof_range.start, proof_range.end,
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn range_coarsen(range: &Range<usize>, denominator: usize) -> Range<usize> {
+    assert!(!range.is_empty(), "{:?}", range);
+    Range {
+        start: index_coarsen(range.start, denominator),
+        end: index_coarsen(range.end - 1, denominator) + 1,
+    }
+}
+
+fn range_refine(range: &Range<usize>, multiplier: usize) -> Range<usize> {
+    assert!(!range.is_empty(), "{:?}", range);
+    Range {
+        start: index_refine(range.start, multiplier),
+        end: index_refine(range.end, multiplier),
+    }
+}
+
+fn index_coarsen(index: usize, denominator: usize) -> usize {
+    index / denominator
+}
+
+fn index_refine(index: usize, multiplier: usize) -> usize {
+    index * multiplier
+}
+
+fn dependent_poly_indices<E, H>(params: &Advz<E, H>, chunk_range: &Range<usize>) -> Vec<usize>
+where
+    E: Pairing,
+    H: HasherDigest,
+{
+    if chunk_range.is_empty() {
+        return Vec::new();
+    }
+    params.range_byte_to_poly(chunk_range).collect()
+}
+
+/// Split `global_range` of the concatenation of payloads with byte lengths
+/// `payload_byte_lens` into the nonempty ranges it covers in each payload.
+fn split_global_range(
+    payload_byte_lens: &[usize],
+    global_range: &Range<usize>,
+) -> VidResult<Vec<(usize, Range<usize>)>> {
+    let total_byte_len: usize = payload_byte_lens.iter().sum();
+    if global_range.is_empty() || global_range.end > total_byte_len {
+        return Err(VidError::Argument(format!(
+            "range ({}..{}) empty or out of bounds for total payload len {}",
+            global_range.start, global_range.end, total_byte_len
+        )));
+    }
+    let mut sub_ranges = Vec::new();
+    let mut offset = 0;
+    for (index, len) in payload_byte_lens.iter().enumerate() {
+        let start = ark_std::cmp::max(global_range.start, offset);
+        let end = ark_std::cmp::min(global_range.end, offset + len);
+        if start < end {
+            sub_ranges.push((index, start - offset..end - offset));
+        }
+        offset += len;
+    }
+    Ok(sub_ranges)
+}
+
+/// Deserialize the compressed [`CanonicalSerialize`] encoding `bytes` with
+/// validation, rejecting trailing bytes so that a value of another type is
+/// unlikely to pass.
+fn deserialize_bytes<T>(mut bytes: &[u8], what: &str) -> VidResult<T>
+where
+    T: CanonicalDeserialize,
+{
+    let value = T::deserialize_compressed(&mut bytes)
+        .map_err(|e| VidError::Argument(format!("malformed {} bytes: {}", what, e)))?;
+    if !bytes.is_empty() {
+        return Err(VidError::Argument(format!(
+            "{} trailing bytes after {}",
+            bytes.len(),
+            what
+        )));
+    }
+    Ok(value)
+}
+
+/// Same as [`deserialize_bytes`] except that `bytes` must also equal the
+/// re-serialization of the result.
+fn deserialize_canonical_bytes<T>(bytes: &[u8], what: &str) -> VidResult<T>
+where
+    T: CanonicalSerialize + CanonicalDeserialize,
+{
+    let value: T = deserialize_bytes(bytes, what)?;
+    let mut canonical = Vec::with_capacity(bytes.len());
+    value.serialize_compressed(&mut canonical).map_err(vid)?;
+    if canonical != bytes {
+        return Err(VidError::Argument(format!(
+            "non-canonical {} encoding",
+            what
+        )));
+    }
+    Ok(value)
+}
+
+fn serialize_small_range_proof<P, W>(
+    proof: &impl SmallRangeProofParts<P>,
+    mut writer: W,
+    compress: Compress,
+) -> Result<(), SerializationError>
+where
+    P: CanonicalSerialize,
+    W: Write,
+{
+    proof.proofs().serialize_with_mode(&mut writer, compress)?;
+    proof
+        .prefix_bytes()
+        .serialize_with_mode(&mut writer, compress)?;
+    proof
+        .suffix_bytes()
+        .serialize_with_mode(&mut writer, compress)?;
+    serialize_range(proof.chunk_range(), &mut writer, compress)
+}
+
+fn small_range_proof_serialized_size<P>(
+    proof: &impl SmallRangeProofParts<P>,
+    compress: Compress,
+) -> usize
+where
+    P: CanonicalSerialize,
+{
+    proof.proofs().serialized_size(compress)
+        + proof.prefix_bytes().serialized_size(compress)
+        + proof.suffix_bytes().serialized_size(compress)
+        + range_serialized_size(compress)
+}
+
+/// Serialize `range` as its start followed by its end.
+fn serialize_range<W: Write>(
+    range: &Range<usize>,
+    writer: W,
+    compress: Compress,
+) -> Result<(), SerializationError> {
+    (range.start, range.end).serialize_with_mode(writer, compress)
+}
+
+fn range_serialized_size(compress: Compress) -> usize {
+    (0usize, 0usize).serialized_size(compress)
+}
+
+fn deserialize_range<R: Read>(
+    reader: R,
+    compress: Compress,
+    validate: Validate,
+) -> Result<Range<usize>, SerializationError> {
+    let (start, end) = <(usize, usize)>::deserialize_with_mode(reader, compress, validate)?;
+    Ok(start..end)
+}
+
+fn check_range_poly(range_poly: &Range<usize>) -> VidResult<()> {
+    // TODO TEMPORARY: forbid requests that span multiple polynomials
+    if range_poly.len() != 1 {
+        return Err(VidError::Argument(format!(
+            "request spans {} polynomials, expect 1",
+            range_poly.len()
+        )));
+    }
+    Ok(())
+}
+
+/// The monic polynomial whose roots are `points`.
+fn vanishing_polynomial<F: Field>(points: &[F]) -> DensePolynomial<F> {
+    let mut coeffs = vec![F::one()];
+    for point in points {
+        // multiply by `X - point`
+        coeffs.insert(0, F::zero());
+        for i in 0..coeffs.len() - 1 {
+            let term = coeffs[i + 1] * point;
+            coeffs[i] -= term;
+        }
+    }
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
+
+/// Evaluate at `point` the interpolant of `evals` over the distinct `points`
+/// via the barycentric formula. Also return the evaluation at `point` of the
+/// vanishing polynomial of `points`.
+///
+/// Costs `O(n^2)` field multiplications for `n` points, which is fine for the
+/// small ranges this is used for.
+fn barycentric_eval<F: Field>(points: &[F], evals: &[F], point: &F) -> (F, F) {
+    let vanishing_eval: F = points.iter().map(|p| *point - p).product();
+    if let Some(i) = points.iter().position(|p| p == point) {
+        return (evals[i], vanishing_eval);
+    }
+    let mut denominators: Vec<F> = points
+        .iter()
+        .enumerate()
+        .map(|(j, p_j)| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|(m, _)| *m != j)
+                .map(|(_, p_m)| *p_j - p_m)
+                .product::<F>()
+                * (*point - p_j)
+        })
+        .collect();
+    batch_inversion(&mut denominators);
+    let sum: F = denominators.iter().zip(evals).map(|(d, e)| *d * e).sum();
+    (vanishing_eval * sum, vanishing_eval)
+}
+
+fn check_subslice_len<V>(stmt: &Statement<V>) -> Result<(), VerifyFailure>
+where
+    V: VidScheme,
+{
+    if stmt.payload_subslice.len() != stmt.range.len() {
+        return Err(VerifyFailure::LengthMismatch {
+            expected: stmt.range.len(),
+            actual: stmt.payload_subslice.len(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vid::{
+        advz::{
+            bytes_to_field::{bytes_to_field_packed, elem_byte_capacity},
+            payload_prover::{
+                AuditLog, BundledProof, BundledStatement, CommitBoundProof, CommitSmt,
+                CommonPrefixProof, CompactStatement, CostComponent, CrossProof, ElemStatement,
+                InvalidRequest, LargeRangeProof, OwnedStatement, PayloadVerifyBundled,
+                PayloadVerifyCommitBound, PayloadVerifyCompact, PayloadVerifyVerbose, ProofKind,
+                SingleOpeningProof, SmallRangeProof, SmallRangeProofN, Statement,
+                VerifyCacheMetrics, VerifyFailure,
+            },
+            tests::*,
+            *,
+        },
+        payload_prover::{MemCommitStore, PayloadProver},
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_std::{ops::Range, print, println, rand::Rng};
+    use sha2::Sha256;
+
+    fn correctness_generic<E, H>(coset_offset: E::ScalarField)
+    where
+        E: Pairing,
+        H: HasherDigest,
+    {
+        // play with these items
+        let (payload_chunk_size, num_storage_nodes) = (4, 6);
+        let num_polys = 3;
+
+        // more items as a function of the above
+        let payload_elems_len = num_polys * payload_chunk_size;
+        let payload_bytes_base_len = payload_elems_len * elem_byte_capacity::<E::ScalarField>();
+        let poly_bytes_len = payload_chunk_size * elem_byte_capacity::<E::ScalarField>();
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(payload_elems_len, &mut rng);
+        let advz =
+            Advz::<E, H>::new_coset(payload_chunk_size, num_storage_nodes, coset_offset, srs)
+                .unwrap();
+
+        // TEST: different payload byte lengths
+        let payload_byte_len_noise_cases = vec![0, poly_bytes_len / 2, poly_bytes_len - 1];
+        let payload_len_cases = payload_byte_len_noise_cases
+            .into_iter()
+            .map(|l| payload_bytes_base_len - l);
+
+        // TEST: prove data ranges for this paylaod
+        // it takes too long to test all combos of (polynomial, start, len)
+        // so do some edge cases and random cases
+        let edge_cases = vec![
+            Range { start: 0, end: 1 },
+            Range { start: 0, end: 2 },
+            Range {
+                start: 0,
+                end: poly_bytes_len - 1,
+            },
+            Range {
+                start: 0,
+                end: poly_bytes_len,
+            },
+            Range { start: 1, end: 2 },
+            Range { start: 1, end: 3 },
+            Range {
+                start: 1,
+                end: poly_bytes_len - 1,
+            },
+            Range {
+                start: 1,
+                end: poly_bytes_len,
+            },
+            Range {
+                start: poly_bytes_len - 2,
+                end: poly_bytes_len - 1,
+            },
+            Range {
+                start: poly_bytes_len - 2,
+                end: poly_bytes_len,
+            },
+            Range {
+                start: poly_bytes_len - 1,
+                end: poly_bytes_len,
+            },
+        ];
+        let random_cases = {
+            let num_cases = edge_cases.len();
+            let mut random_cases = Vec::with_capacity(num_cases);
+            for _ in 0..num_cases {
+                let start = rng.gen_range(0..poly_bytes_len - 1);
+                let end = rng.gen_range(start + 1..poly_bytes_len);
+                random_cases.push(Range { start, end });
+            }
+            random_cases
+        };
+        let all_cases = [(edge_cases, "edge"), (random_cases, "rand")];
+
+        for payload_len_case in payload_len_cases {
+            let payload = init_random_payload(payload_len_case, &mut rng);
+            let d = advz.disperse(&payload).unwrap();
+            println!("payload byte len case: {}", payload.len());
+
+            for poly in 0..num_polys {
+                let poly_offset = poly * poly_bytes_len;
+
+                for cases in all_cases.iter() {
+                    for range in cases.0.iter() {
+                        let range = Range {
+                            start: range.start + poly_offset,
+                            end: range.end + poly_offset,
+                        };
+                        print!("poly {} {} case: {:?}", poly, cases.1, range);
+
+                        // ensure range fits inside payload
+                        let range = if range.start >= payload.len() {
+                            println!(" outside payload len {}, skipping", payload.len());
+                            continue;
+                        } else if range.end > payload.len() {
+                            println!(" clamped to payload len {}", payload.len());
+                            Range {
+                                end: payload.len(),
+                                ..range
+                            }
+                        } else {
+                            println!();
+                            range
+                        };
+
+                        let stmt = Statement {
+                            payload_subslice: &payload[range.clone()],
+                            range: range.clone(),
+                            commit: &d.commit,
+                            common: &d.common,
+                        };
+
+                        let small_range_proof: SmallRangeProof<_> =
+                            advz.payload_proof(&payload, range.clone()).unwrap();
+                        advz.payload_verify(stmt.clone(), &small_range_proof)
+                            .unwrap()
+                            .unwrap();
+
+                        let large_range_proof: LargeRangeProof<_> =
+                            advz.payload_proof(&payload, range.clone()).unwrap();
+                        advz.payload_verify(stmt, &large_range_proof)
+                            .unwrap()
+                            .unwrap();
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn correctness() {
+        correctness_generic::<Bls12_381, Sha256>(One::one());
+        correctness_generic::<Bls12_381, Sha256>(<Bls12_381 as Pairing>::ScalarField::GENERATOR);
+    }
+
+    #[test]
+    fn payload_verify_batch() {
+        let (payload_chunk_size, num_storage_nodes) = (4, 6);
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(payload_chunk_size, &mut rng);
+        let advz =
+            Advz::<Bls12_381, Sha256>::new(payload_chunk_size, num_storage_nodes, srs).unwrap();
+        let poly_bytes_len =
+            payload_chunk_size * elem_byte_capacity::<<Bls12_381 as Pairing>::ScalarField>();
+        let payload = init_random_payload(2 * poly_bytes_len, &mut rng);
+        let d = advz.disperse(&payload).unwrap();
+
+        for range in [
+            0..1,
+            3..poly_bytes_len - 2,
+            poly_bytes_len + 5..2 * poly_bytes_len,
+        ] {
+            let stmt = Statement {
+                payload_subslice: &payload[range.clone()],
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+            let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            advz.payload_verify(stmt.clone(), &proof).unwrap().unwrap();
+            advz.payload_verify_batch(stmt.clone(), &proof, &mut rng)
+                .unwrap()
+                .unwrap();
+
+            // corrupted subslice
+            let mut bad_subslice = payload[range.clone()].to_vec();
+            bad_subslice[0] = bad_subslice[0].wrapping_add(1);
+            let bad_stmt = Statement {
+                payload_subslice: &bad_subslice,
+                ..stmt.clone()
+            };
+            advz.payload_verify(bad_stmt.clone(), &proof)
+                .unwrap()
+                .unwrap_err();
+            advz.payload_verify_batch(bad_stmt, &proof, &mut rng)
+                .unwrap()
+                .unwrap_err();
+
+            // corrupted proof
+            let mut bad_proof = proof.clone();
+            bad_proof.proofs[0].proof =
+                (bad_proof.proofs[0].proof + bad_proof.proofs[0].proof).into();
+            advz.payload_verify(stmt.clone(), &bad_proof)
+                .unwrap()
+                .unwrap_err();
+            advz.payload_verify_batch(stmt, &bad_proof, &mut rng)
+                .unwrap()
+                .unwrap_err();
+        }
+    }
+
+    #[test]
+    fn payload_verify_verbose() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let elem_len = elem_byte_capacity::<<Bls12_381 as Pairing>::ScalarField>();
+        let range = 1..3 * elem_len;
+        let stmt = Statement {
+            payload_subslice: &payload[range.clone()],
+            range: range.clone(),
+            commit: &d.commit,
+            common: &d.common,
+        };
+        let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        advz.payload_verify_verbose(stmt.clone(), &small)
+            .unwrap()
+            .unwrap();
+        advz.payload_verify_verbose(stmt.clone(), &large)
+            .unwrap()
+            .unwrap();
+
+        // corrupt a byte in element 1 of the range
+        let mut bad_subslice = payload[range.clone()].to_vec();
+        bad_subslice[elem_len] = bad_subslice[elem_len].wrapping_add(1);
+        let bad_stmt = Statement {
+            payload_subslice: &bad_subslice,
+            ..stmt.clone()
+        };
+        assert_eq!(
+            advz.payload_verify_verbose(bad_stmt.clone(), &small)
+                .unwrap(),
+            Err(VerifyFailure::KzgPairing { elem_index: 1 })
+        );
+        assert_eq!(
+            advz.payload_verify_verbose(bad_stmt, &large).unwrap(),
+            Err(VerifyFailure::CommitmentMismatch { poly_index: 0 })
+        );
+
+        // subslice length inconsistent with range
+        let short_stmt = Statement {
+            payload_subslice: &payload[range.start..range.end - 1],
+            ..stmt.clone()
+        };
+        let expected = Err(VerifyFailure::LengthMismatch {
+            expected: range.len(),
+            actual: range.len() - 1,
+        });
+        assert_eq!(
+            advz.payload_verify_verbose(short_stmt.clone(), &small)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            advz.payload_verify_verbose(short_stmt, &large).unwrap(),
+            expected
+        );
+
+        // proof count inconsistent with range
+        let mut bad_small = small.clone();
+        bad_small.proofs.pop();
+        assert_eq!(
+            advz.payload_verify_verbose(stmt, &bad_small).unwrap(),
+            Err(VerifyFailure::LengthMismatch {
+                expected: small.proofs.len(),
+                actual: small.proofs.len() - 1,
+            })
+        );
+    }
+
+    #[test]
+    fn payload_verify_param_mismatch() {
+        let num_storage_nodes = 8;
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(8, &mut rng);
+        let advz4 = Advz::<Bls12_381, Sha256>::new(4, num_storage_nodes, &srs).unwrap();
+        let advz8 = Advz::<Bls12_381, Sha256>::new(8, num_storage_nodes, &srs).unwrap();
+        let advz4_16 = Advz::<Bls12_381, Sha256>::new(4, 2 * num_storage_nodes, &srs).unwrap();
+        let payload = init_random_payload(1000, &mut rng);
+        let d = advz4.disperse(&payload).unwrap();
+
+        let range = 3..50;
+        let stmt = Statement {
+            payload_subslice: &payload[range.clone()],
+            range: range.clone(),
+            commit: &d.commit,
+            common: &d.common,
+        };
+        let small: SmallRangeProof<_> = advz4.payload_proof(&payload, range.clone()).unwrap();
+        let large: LargeRangeProof<_> = advz4.payload_proof(&payload, range).unwrap();
+        advz4.payload_verify(stmt.clone(), &small).unwrap().unwrap();
+        advz4.payload_verify(stmt.clone(), &large).unwrap().unwrap();
+
+        let assert_param_err = |res: VidResult<Result<(), ()>>, param: &str| match res {
+            Err(VidError::Argument(msg)) => assert!(msg.contains(param), "{}", msg),
+            _ => panic!("{} mismatch should be arg error", param),
+        };
+        assert_param_err(
+            advz8.payload_verify(stmt.clone(), &small),
+            "payload_chunk_size",
+        );
+        assert_param_err(
+            advz8.payload_verify(stmt.clone(), &large),
+            "payload_chunk_size",
+        );
+        assert_param_err(
+            advz4_16.payload_verify(stmt.clone(), &small),
+            "num_storage_nodes",
+        );
+        assert_param_err(advz4_16.payload_verify(stmt, &large), "num_storage_nodes");
+
+        // shares, too
+        assert_param_err(
+            advz8.verify_share(&d.shares[0], &d.common, &d.commit),
+            "payload_chunk_size",
+        );
+    }
+
+    #[test]
+    fn payload_proofs_for_table() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+
+        let table = vec![
+            ("a", 0..poly_bytes_len),
+            ("b", poly_bytes_len..poly_bytes_len), // empty
+            ("c", poly_bytes_len + 7..2 * poly_bytes_len - 3),
+        ];
+        let proofs = advz
+            .payload_proofs_for_table(&payload, table.clone())
+            .unwrap();
+        assert_eq!(proofs.len(), 2);
+
+        for ((namespace_id, proof), (expected_id, range)) in proofs
+            .iter()
+            .zip(table.iter().filter(|(_, r)| !r.is_empty()))
+        {
+            assert_eq!(namespace_id, expected_id);
+            let expected: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            assert_eq!(*proof, expected);
+            let stmt = Statement {
+                payload_subslice: &payload[range.clone()],
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+            advz.payload_verify(stmt, proof).unwrap().unwrap();
+        }
+
+        // range outside payload
+        assert_arg_err(
+            advz.payload_proofs_for_table(&payload, [(0, payload.len()..payload.len() + 1)]),
+            "out of bounds range should be arg error",
+        );
+    }
+
+    #[test]
+    fn payload_proof_strided() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let elem_len = elem_byte_capacity::<<Bls12_381 as Pairing>::ScalarField>();
+        let payload_elem_len = (payload.len() + elem_len - 1) / elem_len;
+        let elem_bytes = |index: usize| {
+            &payload[index * elem_len..ark_std::cmp::min((index + 1) * elem_len, payload.len())]
+        };
+
+        for elem_indices in [
+            vec![0, 2],
+            vec![7, 5, 4],
+            vec![payload_elem_len - 1],
+            vec![
+                payload_elem_len - 1,
+                (payload_elem_len - 1) / advz.payload_chunk_size * advz.payload_chunk_size,
+            ],
+        ] {
+            let proof = advz.payload_proof_strided(&payload, &elem_indices).unwrap();
+            let bytes: Vec<_> = elem_indices.iter().map(|i| elem_bytes(*i)).collect();
+            advz.payload_verify_strided(&bytes, &elem_indices, &d.commit, &d.common, &proof)
+                .unwrap()
+                .unwrap();
+
+            // corrupted elem bytes
+            let mut bad_elem = bytes[0].to_vec();
+            bad_elem[0] = bad_elem[0].wrapping_add(1);
+            let mut bad_bytes = bytes.clone();
+            bad_bytes[0] = &bad_elem;
+            advz.payload_verify_strided(&bad_bytes, &elem_indices, &d.commit, &d.common, &proof)
+                .unwrap()
+                .unwrap_err();
+
+            // wrong index set
+            let mut bad_indices = elem_indices.clone();
+            bad_indices[0] ^= 1;
+            let bad_indices_bytes: Vec<_> = bad_indices.iter().map(|i| elem_bytes(*i)).collect();
+            advz.payload_verify_strided(
+                &bad_indices_bytes,
+                &bad_indices,
+                &d.commit,
+                &d.common,
+                &proof,
+            )
+            .unwrap()
+            .unwrap_err();
+        }
+
+        // sad path: short elem bytes, even if the missing bytes are zero
+        let mut zero_payload = payload.clone();
+        zero_payload[elem_len - 1] = 0;
+        let d_zero = advz.disperse(&zero_payload).unwrap();
+        let proof = advz.payload_proof_strided(&zero_payload, &[0, 2]).unwrap();
+        let full_elem_bytes = [
+            &zero_payload[..elem_len],
+            &zero_payload[2 * elem_len..3 * elem_len],
+        ];
+        advz.payload_verify_strided(
+            &full_elem_bytes,
+            &[0, 2],
+            &d_zero.commit,
+            &d_zero.common,
+            &proof,
+        )
+        .unwrap()
+        .unwrap();
+        let short_elem_bytes = [&zero_payload[..elem_len - 1], full_elem_bytes[1]];
+        assert_arg_err(
+            advz.payload_verify_strided(
+                &short_elem_bytes,
+                &[0, 2],
+                &d_zero.commit,
+                &d_zero.common,
+                &proof,
+            ),
+            "short non-final elem bytes should be arg error",
+        );
+        let long_elem_bytes = [
+            full_elem_bytes[0],
+            &zero_payload[2 * elem_len..3 * elem_len + 1],
+        ];
+        assert_arg_err(
+            advz.payload_verify_strided(
+                &long_elem_bytes,
+                &[0, 2],
+                &d_zero.commit,
+                &d_zero.common,
+                &proof,
+            ),
+            "long elem bytes should be arg error",
+        );
+
+        // the final elem must hold exactly the remaining payload bytes
+        let last = payload_elem_len - 1;
+        let proof = advz.payload_proof_strided(&payload, &[last]).unwrap();
+        let mut padded_last = elem_bytes(last).to_vec();
+        assert!(padded_last.len() < elem_len);
+        padded_last.push(0);
+        assert_arg_err(
+            advz.payload_verify_strided(&[&padded_last], &[last], &d.commit, &d.common, &proof),
+            "padded final elem bytes should be arg error",
+        );
+        assert_arg_err(
+            advz.payload_verify_strided(&[&[]], &[last], &d.commit, &d.common, &proof),
+            "empty elem bytes should be arg error",
+        );
+
+        // sad path: bad indices
+        assert_arg_err(
+            advz.payload_proof_strided(&payload, &[]),
+            "empty indices should be arg error",
+        );
+        assert_arg_err(
+            advz.payload_proof_strided(&payload, &[payload_elem_len]),
+            "out of bounds index should be arg error",
+        );
+        assert_arg_err(
+            advz.payload_proof_strided(&payload, &[0, 4]),
+            "indices spanning polynomials should be arg error",
+        );
+    }
+
+    #[test]
+    fn payload_verify_domain_check() {
+        let (advz, payload) = avdz_init();
+        let srs = init_srs(advz.payload_chunk_size, &mut jf_utils::test_rng());
+        let coset_offset = <Bls12_381 as Pairing>::ScalarField::GENERATOR;
+        let prover = Advz::new_coset(
+            advz.payload_chunk_size,
+            advz.num_storage_nodes,
+            coset_offset,
+            srs,
+        )
+        .unwrap();
+        let d = prover.disperse(&payload).unwrap();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+        let range = poly_bytes_len + 3..poly_bytes_len + 50;
+        let proof: LargeRangeProof<_> = prover.payload_proof(&payload, range.clone()).unwrap();
+        let stmt = Statement {
+            payload_subslice: &payload[range.clone()],
+            range: range.clone(),
+            commit: &d.commit,
+            common: &d.common,
+        };
+        let candidates = [KzgEval::<Bls12_381>::from(2u64), coset_offset];
+
+        // same domain
+        prover
+            .payload_verify_domain_check(stmt.clone(), &proof, &candidates)
+            .unwrap()
+            .unwrap();
+
+        // correct data, mismatched domain
+        advz.payload_verify(stmt.clone(), &proof)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            advz.payload_verify_domain_check(stmt.clone(), &proof, &candidates)
+                .unwrap(),
+            Err(VerifyFailure::DomainMismatch {
+                poly_index: 1,
+                candidate_index: 1,
+            })
+        );
+        assert_eq!(
+            advz.payload_verify_domain_check(stmt.clone(), &proof, &candidates[..1])
+                .unwrap(),
+            Err(VerifyFailure::CommitmentMismatch { poly_index: 1 })
+        );
+
+        // wrong data, mismatched domain
+        let mut bad_subslice = stmt.payload_subslice.to_vec();
+        bad_subslice[0] = bad_subslice[0].wrapping_add(1);
+        let bad_stmt = Statement {
+            payload_subslice: &bad_subslice,
+            ..stmt.clone()
+        };
+        assert_eq!(
+            advz.payload_verify_domain_check(bad_stmt, &proof, &candidates)
+                .unwrap(),
+            Err(VerifyFailure::CommitmentMismatch { poly_index: 1 })
+        );
+
+        assert_arg_err(
+            advz.payload_verify_domain_check(stmt, &proof, &[KzgEval::<Bls12_381>::zero()]),
+            "zero coset offset should be arg error",
+        );
+    }
+
+    #[test]
+    fn payload_verify_domain_check_permutation() {
+        let (mut advz, payload) = avdz_init();
+        let srs = init_srs(advz.payload_chunk_size, &mut jf_utils::test_rng());
+        let coset_offset = <Bls12_381 as Pairing>::ScalarField::GENERATOR;
+        let mut prover = Advz::new_coset(
+            advz.payload_chunk_size,
+            advz.num_storage_nodes,
+            coset_offset,
+            srs,
+        )
+        .unwrap();
+        let permutation = Permutation::new(vec![2, 0, 3, 1]).unwrap();
+        prover.set_permutation(Some(permutation.clone())).unwrap();
+        let d = prover.disperse(&payload).unwrap();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+        let range = poly_bytes_len + 3..poly_bytes_len + 50;
+        let proof: LargeRangeProof<_> = prover.payload_proof(&payload, range.clone()).unwrap();
+        let stmt = Statement {
+            payload_subslice: &payload[range.clone()],
+            range,
+            commit: &d.commit,
+            common: &d.common,
+        };
+        let candidates = [KzgEval::<Bls12_381>::from(2u64), coset_offset];
+
+        // the domain is detected only under the permutation used at dispersal
+        assert_eq!(
+            advz.payload_verify_domain_check(stmt.clone(), &proof, &candidates)
+                .unwrap(),
+            Err(VerifyFailure::CommitmentMismatch { poly_index: 1 })
+        );
+        advz.set_permutation(Some(permutation)).unwrap();
+        advz.payload_verify(stmt.clone(), &proof)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            advz.payload_verify_domain_check(stmt.clone(), &proof, &candidates)
+                .unwrap(),
+            Err(VerifyFailure::DomainMismatch {
+                poly_index: 1,
+                candidate_index: 1,
+            })
+        );
+        prover
+            .payload_verify_domain_check(stmt, &proof, &candidates)
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn payload_verify_stream() {
+        let (advz, payload) = avdz_init();
+        let srs = init_srs(advz.payload_chunk_size, &mut jf_utils::test_rng());
+        let coset_advz = Advz::new_coset(
+            advz.payload_chunk_size,
+            advz.num_storage_nodes,
+            <Bls12_381 as Pairing>::ScalarField::GENERATOR,
+            srs,
+        )
+        .unwrap();
+        for advz in [advz, coset_advz] {
+            payload_verify_stream_with(&advz, &payload);
+        }
+    }
+
+    fn payload_verify_stream_with(advz: &Advz<Bls12_381, Sha256>, payload: &[u8]) {
+        let d = advz.disperse(payload).unwrap();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+
+        for range in [
+            0..poly_bytes_len,
+            poly_bytes_len + 5..2 * poly_bytes_len - 7,
+            (payload.len() - 1) / poly_bytes_len * poly_bytes_len + 3..payload.len(),
+        ] {
+            let proof: LargeRangeProof<_> = advz.payload_proof(payload, range.clone()).unwrap();
+            let subslice = &payload[range.clone()];
+            let stmt = Statement {
+                payload_subslice: subslice,
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+            advz.payload_verify(stmt, &proof).unwrap().unwrap();
+
+            // feed the subslice in several chunks of various sizes
+            for chunk_size in [1, 7, 31, 32, subslice.len()] {
+                let mut stream = advz
+                    .payload_verify_stream(range.clone(), &d.commit, &d.common, &proof)
+                    .unwrap();
+                for chunk in subslice.chunks(chunk_size) {
+                    stream.update(chunk).unwrap();
+                }
+                stream.finalize().unwrap().unwrap();
+            }
+
+            // corrupted subslice
+            let mut bad_subslice = subslice.to_vec();
+            bad_subslice[0] = bad_subslice[0].wrapping_add(1);
+            let mut stream = advz
+                .payload_verify_stream(range.clone(), &d.commit, &d.common, &proof)
+                .unwrap();
+            for chunk in bad_subslice.chunks(10) {
+                stream.update(chunk).unwrap();
+            }
+            stream.finalize().unwrap().unwrap_err();
+
+            // too few, too many bytes
+            let mut stream = advz
+                .payload_verify_stream(range.clone(), &d.commit, &d.common, &proof)
+                .unwrap();
+            stream.update(&subslice[1..]).unwrap();
+            assert_arg_err(stream.finalize(), "too few bytes should be arg error");
+            let mut stream = advz
+                .payload_verify_stream(range.clone(), &d.commit, &d.common, &proof)
+                .unwrap();
+            stream.update(subslice).unwrap();
+            assert_arg_err(stream.update(&[0]), "too many bytes should be arg error");
+        }
+    }
+
+    #[test]
+    fn verify_absence() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let range = 10..20;
+        let pattern = [0xAB; 10];
+        assert_ne!(payload[range.clone()], pattern);
+
+        let proof = advz
+            .prove_differs(&payload, range.clone(), &pattern)
+            .unwrap();
+        advz.verify_absence(range.clone(), &pattern, &d.commit, &d.common, &proof)
+            .unwrap()
+            .unwrap();
+
+        // the actual bytes are not absent
+        let actual = payload[range.clone()].to_vec();
+        assert_arg_err(
+            advz.prove_differs(&payload, range.clone(), &actual),
+            "proving absence of actual bytes should be arg error",
+        );
+        advz.verify_absence(range.clone(), &actual, &d.commit, &d.common, &proof)
+            .unwrap()
+            .unwrap_err();
+
+        // a forged proof with fake actual bytes fails
+        let mut bad_proof = proof;
+        bad_proof.actual_bytes[0] = bad_proof.actual_bytes[0].wrapping_add(1);
+        advz.verify_absence(range, &pattern, &d.commit, &d.common, &bad_proof)
+            .unwrap()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn max_proof_range_bytes() {
+        let (mut advz, payload) = avdz_init();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+        let elem_len = elem_byte_capacity::<KzgEval<Bls12_381>>();
+
+        // unlimited by default
+        let _: SmallRangeProof<_> = advz.payload_proof(&payload, 0..poly_bytes_len).unwrap();
+
+        // proofs made before the limit is set, for the verifier checks below
+        let d = advz.disperse(&payload).unwrap();
+        let (long, short) = (0..2 * elem_len, 0..elem_len);
+        let small: SmallRangeProof<_> = advz.payload_proof(&payload, long.clone()).unwrap();
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, long.clone()).unwrap();
+        let single: SingleOpeningProof<_> = advz.payload_proof(&payload, long.clone()).unwrap();
+        let strided = advz.payload_proof_strided(&payload, &[0, 2]).unwrap();
+        let full = advz.full_payload_proof(&payload).unwrap();
+        let prefix = advz
+            .prove_common_prefix(&payload, &payload, long.len())
+            .unwrap();
+        let stmt = |range: Range<usize>| Statement {
+            payload_subslice: &payload[range.clone()],
+            range,
+            commit: &d.commit,
+            common: &d.common,
+        };
+
+        advz.set_max_proof_range_bytes(Some(elem_len));
+        let _: SmallRangeProof<_> = advz.payload_proof(&payload, short.clone()).unwrap();
+        let _: LargeRangeProof<_> = advz.payload_proof(&payload, short.clone()).unwrap();
+        let _ = advz.payload_proof_strided(&payload, &[0]).unwrap();
+
+        // rejected up front: this range is also out of bounds and spans multiple
+        // polynomials but the limit is checked first
+        let range = 0..2 * payload.len();
+        let assert_limit_err = |res: VidResult<()>| match res {
+            Err(VidError::Argument(msg)) => {
+                assert!(msg.contains("max_proof_range_bytes"), "{}", msg)
+            },
+            _ => panic!("range over the limit should be arg error"),
+        };
+        assert_limit_err(
+            PayloadProver::<SmallRangeProof<_>>::payload_proof(&advz, &payload, range.clone())
+                .map(|_| ()),
+        );
+        assert_limit_err(
+            PayloadProver::<LargeRangeProof<_>>::payload_proof(&advz, &payload, range).map(|_| ()),
+        );
+
+        // every proof path
+        assert_limit_err(
+            PayloadProver::<SmallRangeProof<_>>::payload_proof(&advz, &payload, long.clone())
+                .map(|_| ()),
+        );
+        assert_limit_err(
+            PayloadProver::<SingleOpeningProof<_>>::payload_proof(&advz, &payload, long.clone())
+                .map(|_| ()),
+        );
+        assert_limit_err(
+            advz.payload_proofs_for_table(&payload, [(0, short.clone()), (1, long.clone())])
+                .map(|_| ()),
+        );
+        assert_limit_err(advz.payload_proof_strided(&payload, &[0, 2]).map(|_| ()));
+        assert_limit_err(
+            advz.small_from_large(&large, &payload[long.clone()], long.clone())
+                .map(|_| ()),
+        );
+        assert_limit_err(
+            advz.online_proof(&advz.offline_precompute(&payload).unwrap(), long.clone())
+                .map(|_| ()),
+        );
+        assert_limit_err(
+            advz.prove_ranges_equal(&payload, long.clone(), long.clone())
+                .map(|_| ()),
+        );
+        assert_limit_err(
+            advz.prove_common_prefix(&payload, &payload, long.len())
+                .map(|_| ()),
+        );
+        assert_limit_err(advz.full_payload_proof(&payload).map(|_| ()));
+
+        // every verify path
+        assert_limit_err(advz.payload_verify(stmt(long.clone()), &small).map(|_| ()));
+        assert_limit_err(advz.payload_verify(stmt(long.clone()), &large).map(|_| ()));
+        assert_limit_err(advz.payload_verify(stmt(long.clone()), &single).map(|_| ()));
+        assert_limit_err(
+            advz.payload_verify_batch(stmt(long.clone()), &small, &mut jf_utils::test_rng())
+                .map(|_| ()),
+        );
+        assert_limit_err(
+            advz.payload_verify_with_points(
+                stmt(long.clone()),
+                &small,
+                &advz.payload_proof_points(&long).unwrap(),
+            )
+            .map(|_| ()),
+        );
+        assert_limit_err(
+            advz.payload_verify_stream(long.clone(), &d.commit, &d.common, &large)
+                .map(|_| ()),
+        );
+        assert_limit_err(
+            advz.payload_verify_strided(
+                &[&payload[..elem_len], &payload[2 * elem_len..3 * elem_len]],
+                &[0, 2],
+                &d.commit,
+                &d.common,
+                &strided,
+            )
+            .map(|_| ()),
+        );
+        assert_limit_err(
+            advz.verify_common_prefix(
+                long.len(),
+                &d.commit,
+                &d.common,
+                &d.commit,
+                &d.common,
+                &prefix,
+            )
+            .map(|_| ()),
+        );
+        assert_limit_err(
+            advz.full_payload_verify(&payload, &d.commit, &full)
+                .map(|_| ()),
+        );
+        assert_limit_err(advz.mixed_verifier().add_small(stmt(long.clone()), &small));
+
+        advz.set_max_proof_range_bytes(None);
+        let _: SmallRangeProof<_> = advz.payload_proof(&payload, 0..poly_bytes_len).unwrap();
+        assert_eq!(
+            advz.payload_verify(stmt(long.clone()), &small).unwrap(),
+            Ok(())
+        );
+        assert_eq!(
+            advz.full_payload_verify(&payload, &d.commit, &full)
+                .unwrap(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn poly_index_of_byte() {
+        let (advz, payload) = avdz_init();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+        let num_polys = (payload.len() + poly_bytes_len - 1) / poly_bytes_len;
+
+        for poly in 0..num_polys {
+            let start = poly * poly_bytes_len;
+            for offset in [start, start + 1, start + poly_bytes_len - 1] {
+                if offset >= payload.len() {
+                    continue;
+                }
+                let poly_index = advz.poly_index_of_byte(offset, payload.len()).unwrap();
+                assert_eq!(poly_index, poly);
+                assert_eq!(
+                    poly_index,
+                    advz.range_byte_to_poly(&(offset..offset + 1)).start
+                );
+            }
+        }
+        assert_eq!(
+            advz.poly_index_of_byte(payload.len() - 1, payload.len())
+                .unwrap(),
+            num_polys - 1
+        );
+        assert_arg_err(
+            advz.poly_index_of_byte(payload.len(), payload.len()),
+            "out of bounds offset should be arg error",
+        );
+    }
+
+    #[test]
+    fn payload_proof_bytes() {
+        let (advz, payload) = avdz_init();
+        let range = 3..50;
+
+        let bytes = advz
+            .payload_proof_bytes::<SmallRangeProof<_>, _>(&payload, range.clone())
+            .unwrap();
+        let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        assert_eq!(
+            SmallRangeProof::deserialize_compressed(bytes.as_slice()).unwrap(),
+            small
+        );
+
+        // documented layout
+        let num_proofs = small.proofs.len();
+        let proof_len = small.proofs[0].compressed_size();
+        let mut offset = 0;
+        let mut next = |len: usize| {
+            offset += len;
+            &bytes[offset - len..offset]
+        };
+        assert_eq!(next(8), (num_proofs as u64).to_le_bytes());
+        next(num_proofs * proof_len);
+        assert_eq!(next(8), (small.prefix_bytes.len() as u64).to_le_bytes());
+        assert_eq!(
+            next(small.prefix_bytes.len()),
+            small.prefix_bytes.as_slice()
+        );
+        assert_eq!(next(8), (small.suffix_bytes.len() as u64).to_le_bytes());
+        assert_eq!(
+            next(small.suffix_bytes.len()),
+            small.suffix_bytes.as_slice()
+        );
+        assert_eq!(next(8), (range.start as u64).to_le_bytes());
+        assert_eq!(next(8), (range.end as u64).to_le_bytes());
+        assert_eq!(offset, bytes.len());
+
+        // same encoding for a fixed-size proof
+        assert_eq!(num_proofs, 2);
+        let small_n = SmallRangeProofN::<_, 2>::try_from(small.clone()).unwrap();
+        let mut bytes_n = Vec::new();
+        small_n.serialize_compressed(&mut bytes_n).unwrap();
+        assert_eq!(bytes_n, bytes);
+        assert_eq!(
+            SmallRangeProofN::<_, 2>::deserialize_compressed(bytes.as_slice()).unwrap(),
+            small_n
+        );
+        assert!(
+            SmallRangeProofN::<KzgProof<Bls12_381>, 3>::deserialize_compressed(bytes.as_slice())
+                .is_err()
+        );
+
+        let bytes = advz
+            .payload_proof_bytes::<LargeRangeProof<_>, _>(&payload, range.clone())
+            .unwrap();
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        assert_eq!(
+            LargeRangeProof::deserialize_compressed(bytes.as_slice()).unwrap(),
+            large
+        );
+
+        let bytes = advz
+            .payload_proof_bytes::<SingleOpeningProof<_>, _>(&payload, range.clone())
+            .unwrap();
+        let single: SingleOpeningProof<_> = advz.payload_proof(&payload, range).unwrap();
+        assert_eq!(
+            SingleOpeningProof::deserialize_compressed(bytes.as_slice()).unwrap(),
+            single
+        );
+        assert!(
+            SingleOpeningProof::<Bls12_381>::deserialize_compressed(&bytes[..bytes.len() - 1])
+                .is_err()
+        );
+
+        assert_arg_err(
+            advz.payload_proof_bytes::<SmallRangeProof<_>, _>(&payload, 0..0),
+            "empty range should be arg error",
+        );
+    }
+
+    #[test]
+    fn payload_proof_in_namespace() {
+        let (advz, payload) = avdz_init();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+        let namespace = poly_bytes_len + 5..2 * poly_bytes_len;
+
+        for sub_range in [
+            0..1,
+            0..10,
+            7..20,
+            namespace.len() - 1..namespace.len(),
+            0..namespace.len(),
+        ] {
+            let global = namespace.start + sub_range.start..namespace.start + sub_range.end;
+            let small: SmallRangeProof<_> = advz
+                .payload_proof_in_namespace(&payload, namespace.clone(), sub_range.clone())
+                .unwrap();
+            assert_eq!(small, advz.payload_proof(&payload, global.clone()).unwrap());
+            let large: LargeRangeProof<_> = advz
+                .payload_proof_in_namespace(&payload, namespace.clone(), sub_range)
+                .unwrap();
+            assert_eq!(large, advz.payload_proof(&payload, global).unwrap());
+        }
+
+        assert_arg_err(
+            advz.payload_proof_in_namespace::<SmallRangeProof<_>, _>(
+                &payload,
+                namespace.clone(),
+                0..namespace.len() + 1,
+            ),
+            "sub-range past namespace end should be arg error",
+        );
+        assert_arg_err(
+            advz.payload_proof_in_namespace::<SmallRangeProof<_>, _>(
+                &payload,
+                namespace.clone(),
+                3..3,
+            ),
+            "empty sub-range should be arg error",
+        );
+        assert_arg_err(
+            advz.payload_proof_in_namespace::<SmallRangeProof<_>, _>(
+                &payload,
+                payload.len() - 1..payload.len() + 1,
+                0..1,
+            ),
+            "namespace outside payload should be arg error",
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn estimate_verify_latency() {
+        use std::time::{Duration, Instant};
+
+        let mut rng = jf_utils::test_rng();
+        let new_advz = |payload_chunk_size, rng: &mut _| {
+            let srs = init_srs(payload_chunk_size, rng);
+            Advz::<Bls12_381, Sha256>::new(payload_chunk_size, 2 * payload_chunk_size, srs).unwrap()
+        };
+        let advz = new_advz(16, &mut rng);
+        let model = advz.calibrate_verify_cost().unwrap();
+        let payload = init_random_payload(4000, &mut rng);
+        let d = advz.disperse(&payload).unwrap();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+        let elem_byte_len = elem_byte_capacity::<<Bls12_381 as Pairing>::ScalarField>();
+
+        // fastest of a few runs, as in calibration
+        fn fastest(mut op: impl FnMut()) -> Duration {
+            (0..3)
+                .map(|_| {
+                    let start = Instant::now();
+                    op();
+                    start.elapsed()
+                })
+                .min()
+                .unwrap()
+        }
+
+        // the estimate is within a reasonable factor of measured latency
+        const FACTOR: u32 = 5;
+        for range in [
+            3..4,
+            elem_byte_len - 1..elem_byte_len + 1,
+            poly_byte_len + 5..poly_byte_len + 5 + 4 * elem_byte_len,
+        ] {
+            let stmt = Statement {
+                payload_subslice: &payload[range.clone()],
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+            let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            let large: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            let single: SingleOpeningProof<_> =
+                advz.payload_proof(&payload, range.clone()).unwrap();
+            for (kind, measured) in [
+                (
+                    ProofKind::Small,
+                    fastest(|| advz.payload_verify(stmt.clone(), &small).unwrap().unwrap()),
+                ),
+                (
+                    ProofKind::Large,
+                    fastest(|| advz.payload_verify(stmt.clone(), &large).unwrap().unwrap()),
+                ),
+                (
+                    ProofKind::SingleOpening,
+                    fastest(|| advz.payload_verify(stmt.clone(), &single).unwrap().unwrap()),
+                ),
+            ] {
+                let estimate = advz
+                    .estimate_verify_latency(&model, range.clone(), kind)
+                    .unwrap();
+                assert!(
+                    estimate <= measured * FACTOR && measured <= estimate * FACTOR,
+                    "{:?} proof for {:?}: estimate {:?}, measured {:?}",
+                    kind,
+                    range,
+                    estimate,
+                    measured
+                );
+            }
+        }
+
+        // a model is specific to the curve and payload chunk size
+        let other = new_advz(32, &mut rng);
+        assert_arg_err(
+            other.estimate_verify_latency(&model, 3..4, ProofKind::Small),
+            "model for another payload chunk size should be arg error",
+        );
+        assert_arg_err(
+            advz.estimate_verify_latency(&model, 3..3, ProofKind::Small),
+            "empty range should be arg error",
+        );
+        assert_arg_err(
+            advz.estimate_verify_latency(&model, 0..poly_byte_len + 1, ProofKind::Small),
+            "multi-poly range should be arg error",
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn payload_verify_channel() {
+        use crate::vid::advz::payload_prover::VerifyRequest;
+        use std::sync::mpsc::channel;
+
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let num_requests = 10;
+        let (request_tx, request_rx) = channel();
+        let (result_tx, result_rx) = channel();
+        for id in 0..num_requests {
+            let range = 10 * id as usize..10 * id as usize + 20;
+            let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            let mut payload_subslice = payload[range.clone()].to_vec();
+            // odd requests are invalid
+            if id % 2 == 1 {
+                payload_subslice[0] ^= 1;
+            }
+            request_tx
+                .send(VerifyRequest {
+                    id,
+                    stmt: OwnedStatement {
+                        payload_subslice,
+                        range,
+                        commit: d.commit,
+                        common: d.common.clone(),
+                    },
+                    proof,
+                })
+                .unwrap();
+        }
+        // an argument error is reported as such
+        let proof: SmallRangeProof<_> = advz.payload_proof(&payload, 0..10).unwrap();
+        request_tx
+            .send(VerifyRequest {
+                id: num_requests,
+                stmt: OwnedStatement {
+                    payload_subslice: payload[0..9].to_vec(),
+                    range: 0..10,
+                    commit: d.commit,
+                    common: d.common.clone(),
+                },
+                proof,
+            })
+            .unwrap();
+        drop(request_tx);
+
+        advz.payload_verify_channel(request_rx, result_tx);
+        let mut results: Vec<_> = result_rx.iter().collect();
+        results.sort_by_key(|result| result.id);
+        assert_eq!(results.len(), num_requests as usize + 1);
+        let arg_err = results.pop().unwrap();
+        assert_eq!(arg_err.id, num_requests);
+        assert_arg_err(arg_err.outcome, "bad subslice len should be arg error");
+        for (id, result) in results.into_iter().enumerate() {
+            assert_eq!(result.id, id as u64);
+            assert_eq!(result.outcome.unwrap().is_ok(), id % 2 == 0, "id {}", id);
+        }
+    }
+
+    #[cfg(all(feature = "std", feature = "parallel"))]
+    #[test]
+    fn spawn_proof() {
+        use std::sync::Arc;
+
+        let (advz, payload) = avdz_init();
+        let (advz, payload) = (Arc::new(advz), Arc::new(payload));
+
+        // more requests than rayon threads queue up
+        let poly_byte_len = advz.index_poly_to_byte(1);
+        let ranges: Vec<_> = (0..32)
+            .map(|i| i * poly_byte_len + 3..i * poly_byte_len + 40)
+            .collect();
+        let handles: Vec<_> = ranges
+            .iter()
+            .map(|range| {
+                advz.spawn_proof::<SmallRangeProof<_>>(Arc::clone(&payload), range.clone())
+            })
+            .collect();
+        let large_handle =
+            advz.spawn_proof::<LargeRangeProof<_>>(Arc::clone(&payload), ranges[1].clone());
+        let bad_handle = advz.spawn_proof::<SmallRangeProof<_>>(Arc::clone(&payload), 0..0);
+
+        for (handle, range) in handles.into_iter().zip(ranges.iter()) {
+            let proof = handle.wait().unwrap();
+            assert_eq!(
+                proof,
+                advz.payload_proof(payload.as_slice(), range.clone())
+                    .unwrap()
+            );
+        }
+
+        // poll once, then block
+        let large = match large_handle.try_wait() {
+            Ok(res) => res,
+            Err(handle) => handle.wait(),
+        }
+        .unwrap();
+        assert_eq!(
+            large,
+            advz.payload_proof(payload.as_slice(), ranges[1].clone())
+                .unwrap()
+        );
+        assert_arg_err(bad_handle.wait(), "empty range should be arg error");
+
+        // a panic in the job is resumed by the waiter
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+        tx.send(Err(Box::new("proof job panicked") as Box<_>))
+            .unwrap();
+        let panicked = super::ProofHandle::<SmallRangeProof<KzgEval<Bls12_381>>> { rx };
+        let panic =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| panicked.wait())).unwrap_err();
+        assert_eq!(panic.downcast_ref::<&str>(), Some(&"proof job panicked"));
+    }
+
+    #[test]
+    fn payload_verify_elems() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let elem_bytes_len = elem_byte_capacity::<<Bls12_381 as Pairing>::ScalarField>();
+        let elems: Vec<_> = bytes_to_field::<_, KzgEval<Bls12_381>>(&payload).collect();
+
+        for range_elem in [0..1, 1..3, 2..4, 4..8, 9..11] {
+            let range = range_elem.start * elem_bytes_len..range_elem.end * elem_bytes_len;
+            let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            let stmt = ElemStatement {
+                payload_subslice_elems: &elems[range_elem.clone()],
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+            let byte_stmt = Statement {
+                payload_subslice: &payload[range.clone()],
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+            assert_eq!(
+                advz.payload_verify_elems(stmt.clone(), &proof).unwrap(),
+                advz.payload_verify(byte_stmt, &proof).unwrap()
+            );
+            advz.payload_verify_elems(stmt.clone(), &proof)
+                .unwrap()
+                .unwrap();
+
+            // wrong elems
+            let mut bad_elems = elems[range_elem.clone()].to_vec();
+            bad_elems[0].double_in_place();
+            let bad_stmt = ElemStatement {
+                payload_subslice_elems: &bad_elems,
+                ..stmt.clone()
+            };
+            advz.payload_verify_elems(bad_stmt, &proof)
+                .unwrap()
+                .unwrap_err();
+
+            // wrong elems len
+            let short_stmt = ElemStatement {
+                payload_subslice_elems: &elems[range_elem.start..range_elem.end - 1],
+                ..stmt
+            };
+            assert_arg_err(
+                advz.payload_verify_elems(short_stmt, &proof),
+                "wrong elems len should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn payload_verify_with_audit() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let elem_len = elem_byte_capacity::<KzgEval<Bls12_381>>();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+        let range = poly_byte_len + 10..poly_byte_len + 10 + 3 * elem_len;
+        let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let num_elems = advz.range_byte_to_elem(&range).len();
+
+        let stmt = Statement {
+            payload_subslice: &payload[range.clone()],
+            range: range.clone(),
+            commit: &d.commit,
+            common: &d.common,
+        };
+        let (outcome, log) = advz.payload_verify_with_audit(stmt, &proof).unwrap();
+        outcome.unwrap();
+        assert_eq!(log.commit, d.commit);
+        assert_eq!(log.poly_index, 1);
+        assert_eq!(log.range, range);
+        assert_eq!(log.entries.len(), num_elems);
+        assert!(log.entries.iter().all(|entry| entry.verified));
+        assert_eq!(
+            log.entries
+                .iter()
+                .map(|entry| entry.point)
+                .collect::<Vec<_>>(),
+            advz.payload_proof_points(&range).unwrap()
+        );
+        let bytes = bincode::serialize(&log).unwrap();
+        assert_eq!(
+            bincode::deserialize::<AuditLog<Bls12_381, Sha256>>(&bytes).unwrap(),
+            log
+        );
+
+        // corrupt a byte in the second element: only its entry fails
+        let mut bad_subslice = payload[range.clone()].to_vec();
+        let bad_elem = 1;
+        bad_subslice[bad_elem * elem_len] ^= 1;
+        let stmt = Statement {
+            payload_subslice: &bad_subslice,
+            range: range.clone(),
+            commit: &d.commit,
+            common: &d.common,
+        };
+        let (outcome, log) = advz.payload_verify_with_audit(stmt, &proof).unwrap();
+        outcome.unwrap_err();
+        assert_eq!(log.entries.len(), num_elems);
+        for (i, entry) in log.entries.iter().enumerate() {
+            assert_eq!(entry.verified, i != bad_elem, "entry {}", i);
+        }
+    }
+
+    #[test]
+    fn payload_verify_spec_compliant() {
+        // conformance vector for chunk size 4, 6 storage nodes, the default
+        // commit version, `payload = [0, 1, ..., 39]` padded to 2
+        // polynomials and range `10..40`: changes here break other
+        // implementations
+        const VK: &str = concat!(
+            "97ae15a6d3f5898bfa5a96b54e4e7f44d9001a1a43e218868df899e4961a439f",
+            "4b753e9ee919c93e6d17697d8a6f197f96159f6d905433a175f63610c51aad73",
+            "b1b2bc024d20d912cea026fc66bf337b2e405ef86caec6103f35de713d672448",
+            "12f9249c0a49939658b01b1212e7017e4767c498f38e6e989be3b56894ef2114",
+            "155aa5c881c732c79f051ad711ab90feaec75b7848db3eacb0068a38379fc73a",
+            "aee4488f2a12207b70757a5d56567b18939e9a02961001e1ca277c9f84b7ee13",
+            "193de85d4f349d23486b4c2037f4a04f37c5d07499a59f02b84205926ead8b0c",
+            "b07433774f65e72233fc566196cbf918",
+        );
+        const STMT: &str = concat!(
+            "0a0000000000000028000000000000001e000000000000000a0b0c0d0e0f1011",
+            "12131415161718191a1b1c1d1e1f2021222324252627d87b983881b8607bf324",
+            "f59af4e5f59c062fc5c6037b51695e3c2c21d29af387ffffffffffffffff0200",
+            "000000000000a5acaa155ce07ccd5916d17483d363afb4ab88f54ef1d9b24d4f",
+            "6d883910badb92337e10359adba08d8b642b9412a568c0000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000006cb37e423f5099b50ca83157ac25855c802c12759883787cd5e2",
+            "9793b0dc59142800000000000000000400000000000000060000000000000000",
+        );
+        const PROOF: &str = concat!(
+            "0200000000000000a5fe8f344d5f7e3067a7276d08728bc5d05a895195a3bf36",
+            "0022591c78b829121cb1ea2f13d36f8787beef53b26bd4e0b61c90c4dd6a5d84",
+            "caf9e01a7e1d00b882de42604a119aadf311f7ff47c94a919a7bf76f317c8f1c",
+            "5b3fd4bc8ad30fd70a0000000000000000010203040506070809000000000000",
+            "00000a000000000000002800000000000000",
+        );
+
+        let (mut advz, _) = avdz_init();
+        advz.set_min_polynomials(2);
+        let payload: Vec<u8> = (0..40).collect();
+        let d = advz.disperse(&payload).unwrap();
+        let serialize_stmt = |payload_subslice: &[u8], range: Range<usize>| {
+            let mut bytes = Vec::new();
+            OwnedStatement::<Bls12_381, Sha256> {
+                payload_subslice: payload_subslice.to_vec(),
+                range,
+                commit: d.commit,
+                common: d.common.clone(),
+            }
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+            bytes
+        };
+        let serialize_proof = |proof: &SmallRangeProof<KzgProof<Bls12_381>>| {
+            let mut bytes = Vec::new();
+            proof.serialize_compressed(&mut bytes).unwrap();
+            bytes
+        };
+        let to_hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<String>()
+        };
+
+        let range = 10..40;
+        let stmt_bytes = serialize_stmt(&payload[range.clone()], range.clone());
+        let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let proof_bytes = serialize_proof(&proof);
+        let mut vk_bytes = Vec::new();
+        advz.vk.serialize_compressed(&mut vk_bytes).unwrap();
+        assert_eq!(to_hex(&vk_bytes), VK);
+        assert_eq!(to_hex(&stmt_bytes), STMT);
+        assert_eq!(to_hex(&proof_bytes), PROOF);
+        advz.payload_verify_spec_compliant(&stmt_bytes, &proof_bytes)
+            .unwrap()
+            .unwrap();
+
+        let mut bad_payload = payload[range.clone()].to_vec();
+        bad_payload[0] ^= 1;
+        advz.payload_verify_spec_compliant(
+            &serialize_stmt(&bad_payload, range.clone()),
+            &proof_bytes,
+        )
+        .unwrap()
+        .unwrap_err();
+
+        // the padding poly commit is the point at infinity, whose encoding
+        // ignores all but the flag bits
+        let infinity = {
+            let mut bytes = Vec::new();
+            KzgCommit::<Bls12_381>::from(<Bls12_381 as Pairing>::G1Affine::zero())
+                .serialize_compressed(&mut bytes)
+                .unwrap();
+            bytes
+        };
+        let pos = stmt_bytes
+            .windows(infinity.len())
+            .position(|w| w == infinity.as_slice())
+            .unwrap();
+        let mut non_canonical_stmt_bytes = stmt_bytes.clone();
+        non_canonical_stmt_bytes[pos + infinity.len() - 1] = 1;
+        OwnedStatement::<Bls12_381, Sha256>::deserialize_compressed(
+            non_canonical_stmt_bytes.as_slice(),
+        )
+        .unwrap();
+        assert_arg_err(
+            advz.payload_verify_spec_compliant(&non_canonical_stmt_bytes, &proof_bytes),
+            "non-canonical statement should be arg error",
+        );
+
+        let mut trailing_proof_bytes = proof_bytes.clone();
+        trailing_proof_bytes.push(0);
+        assert_arg_err(
+            advz.payload_verify_spec_compliant(&stmt_bytes, &trailing_proof_bytes),
+            "trailing bytes should be arg error",
+        );
+
+        // a suffix longer than the payload allows
+        let mut long_suffix_proof = proof.clone();
+        long_suffix_proof.suffix_bytes.push(0);
+        assert_arg_err(
+            advz.payload_verify_spec_compliant(&stmt_bytes, &serialize_proof(&long_suffix_proof)),
+            "wrong suffix len should be arg error",
+        );
+
+        // a point off the subgroup
+        let mut off_subgroup_proof = proof.clone();
+        off_subgroup_proof.proofs[0].proof = (0u64..)
+            .find_map(|x| {
+                <Bls12_381 as Pairing>::G1Affine::get_point_from_x_unchecked(
+                    <Bls12_381 as Pairing>::BaseField::from(x),
+                    false,
+                )
+                .filter(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+            })
+            .unwrap();
+        assert_arg_err(
+            advz.payload_verify_spec_compliant(&stmt_bytes, &serialize_proof(&off_subgroup_proof)),
+            "point off the subgroup should be arg error",
+        );
+    }
+
+    #[test]
+    fn proof_bytes_header() {
+        let (advz, payload) = avdz_init();
+        let range = 10..40;
+        let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, range).unwrap();
+        let small_bytes = advz.proof_to_bytes(&small).unwrap();
+        let large_bytes = advz.proof_to_bytes(&large).unwrap();
+        assert_eq!(
+            small_bytes[0],
+            Advz::<Bls12_381, Sha256>::PROOF_BYTES_VERSION
+        );
+        assert_eq!(
+            advz.proof_from_bytes::<SmallRangeProof<_>>(&small_bytes)
+                .unwrap(),
+            small
+        );
+        assert_eq!(
+            advz.proof_from_bytes::<LargeRangeProof<_>>(&large_bytes)
+                .unwrap(),
+            large
+        );
 
-        // more items as a function of the above
-        let payload_elems_len = num_polys * payload_chunk_size;
-        let payload_bytes_base_len = payload_elems_len * elem_byte_capacity::<E::ScalarField>();
-        let poly_bytes_len = payload_chunk_size * elem_byte_capacity::<E::ScalarField>();
+        let expect_arg_err = |res: VidResult<SmallRangeProof<KzgProof<Bls12_381>>>,
+                              msg_part: &str| match res {
+            Err(VidError::Argument(msg)) => assert!(msg.contains(msg_part), "{}", msg),
+            res => panic!("expected arg error containing {}, got {:?}", msg_part, res),
+        };
+
+        // mismatched payload_chunk_size
+        let other_chunk_size = advz.payload_chunk_size / 2;
+        let other = Advz::<Bls12_381, Sha256>::new(
+            other_chunk_size,
+            advz.num_storage_nodes,
+            init_srs(other_chunk_size, &mut jf_utils::test_rng()),
+        )
+        .unwrap();
+        expect_arg_err(other.proof_from_bytes(&small_bytes), "payload_chunk_size");
+
+        // mismatched curve
+        let bn254 = Advz::<ark_bn254::Bn254, Sha256>::new(
+            advz.payload_chunk_size,
+            advz.num_storage_nodes,
+            init_srs(advz.payload_chunk_size, &mut jf_utils::test_rng()),
+        )
+        .unwrap();
+        match bn254.proof_from_bytes::<SmallRangeProof<KzgProof<ark_bn254::Bn254>>>(&small_bytes) {
+            Err(VidError::Argument(msg)) => assert!(msg.contains("curve"), "{}", msg),
+            res => panic!("expected arg error, got {:?}", res),
+        }
+
+        // mismatched proof kind
+        expect_arg_err(advz.proof_from_bytes(&large_bytes), "kind");
+
+        // unsupported version and truncated header
+        let mut bad_version = small_bytes.clone();
+        bad_version[0] += 1;
+        expect_arg_err(advz.proof_from_bytes(&bad_version), "version");
+        expect_arg_err(advz.proof_from_bytes(&small_bytes[..5]), "header");
+    }
+
+    #[test]
+    fn element_proof() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let elem_len = elem_byte_capacity::<KzgEval<Bls12_381>>();
+        let elems: Vec<_> = Advz::<Bls12_381, Sha256>::enumerate_elems(&payload)
+            .map(|(_, elem)| elem)
+            .collect();
+        let last = elems.len() - 1;
+        for elem_index in [0, 1, advz.payload_chunk_size + 2, last] {
+            let proof = advz.element_proof(&payload, elem_index).unwrap();
+            advz.verify_element(&d.commit, &d.common, elem_index, elems[elem_index], &proof)
+                .unwrap()
+                .unwrap();
+            advz.verify_element(
+                &d.commit,
+                &d.common,
+                elem_index,
+                elems[elem_index] + KzgEval::<Bls12_381>::from(1u64),
+                &proof,
+            )
+            .unwrap()
+            .unwrap_err();
+
+            // agrees with a small range proof for the same element
+            let range =
+                elem_index * elem_len..usize::min((elem_index + 1) * elem_len, payload.len());
+            let small: SmallRangeProof<_> = advz.payload_proof(&payload, range).unwrap();
+            assert_eq!(small.proofs, vec![proof.proof]);
+            assert!(small.prefix_bytes.is_empty() && small.suffix_bytes.is_empty());
+        }
+
+        assert_arg_err(
+            advz.element_proof(&payload, last + 1),
+            "out of bounds elem index should be arg error",
+        );
+        let proof = advz.element_proof(&payload, 0).unwrap();
+        assert_arg_err(
+            advz.verify_element(&d.commit, &d.common, last + 1, elems[0], &proof),
+            "out of bounds elem index should be arg error",
+        );
+    }
+
+    #[test]
+    fn ext_element_proof() {
+        let (advz, payload) = avdz_init();
+
+        // the final element of `Fr2` has a padding coordinate
+        let payload = &payload[..payload.len() - 20];
+        let d = advz.disperse_ext::<Fr2, _>(payload).unwrap();
+        let elems: Vec<Fr2> = bytes_to_field(payload).collect();
+        let last = elems.len() - 1;
+        assert_eq!(
+            elems[last].to_base_prime_field_elements().nth(1),
+            Some(KzgEval::<Bls12_381>::zero())
+        );
+
+        for ext_index in [0, 1, 2, last] {
+            let proofs = advz
+                .ext_element_proof::<Fr2, _>(payload, ext_index)
+                .unwrap();
+            assert_eq!(proofs.len(), 2);
+            advz.verify_ext_element(&d.commit, &d.common, ext_index, elems[ext_index], &proofs)
+                .unwrap()
+                .unwrap();
+
+            // tampered coordinates
+            for tamper in [
+                Fr2::new(One::one(), Zero::zero()),
+                Fr2::new(Zero::zero(), One::one()),
+            ] {
+                advz.verify_ext_element(
+                    &d.commit,
+                    &d.common,
+                    ext_index,
+                    elems[ext_index] + tamper,
+                    &proofs,
+                )
+                .unwrap()
+                .unwrap_err();
+            }
+
+            // wrong number of proofs
+            assert_arg_err(
+                advz.verify_ext_element(
+                    &d.commit,
+                    &d.common,
+                    ext_index,
+                    elems[ext_index],
+                    &proofs[..1],
+                ),
+                "wrong number of proofs should be arg error",
+            );
+        }
+
+        // proofs for another element
+        let proofs = advz.ext_element_proof::<Fr2, _>(payload, 1).unwrap();
+        advz.verify_ext_element(&d.commit, &d.common, 0, elems[0], &proofs)
+            .unwrap()
+            .unwrap_err();
+
+        assert_arg_err(
+            advz.ext_element_proof::<Fr2, _>(payload, last + 1),
+            "out of bounds ext elem index should be arg error",
+        );
+        assert_arg_err(
+            advz.verify_ext_element(&d.commit, &d.common, last + 1, elems[0], &proofs),
+            "out of bounds ext elem index should be arg error",
+        );
+    }
+
+    fn compressed_bytes<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        value.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn payload_verify_bytes() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let range = 10..40;
+        let stmt_bytes = compressed_bytes(&OwnedStatement {
+            payload_subslice: payload[range.clone()].to_vec(),
+            range: range.clone(),
+            commit: d.commit,
+            common: d.common.clone(),
+        });
+        let mut bad_payload = payload.clone();
+        bad_payload[range.start] ^= 1;
+        let bad_stmt_bytes = compressed_bytes(&OwnedStatement {
+            payload_subslice: bad_payload[range.clone()].to_vec(),
+            range: range.clone(),
+            commit: d.commit,
+            common: d.common.clone(),
+        });
+
+        let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let single: SingleOpeningProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let proofs = [
+            (ProofKind::Small, compressed_bytes(&small)),
+            (ProofKind::Large, compressed_bytes(&large)),
+            (ProofKind::SingleOpening, compressed_bytes(&single)),
+        ];
+        for (kind, proof_bytes) in proofs.iter() {
+            advz.payload_verify_bytes(&stmt_bytes, proof_bytes, *kind)
+                .unwrap()
+                .unwrap();
+            advz.payload_verify_bytes(&bad_stmt_bytes, proof_bytes, *kind)
+                .unwrap()
+                .unwrap_err();
+
+            // mismatched proof kind
+            for (other_kind, _) in proofs.iter().filter(|(k, _)| k != kind) {
+                assert_arg_err(
+                    advz.payload_verify_bytes(&stmt_bytes, proof_bytes, *other_kind),
+                    "mismatched proof kind should be arg error",
+                );
+            }
+
+            // malformed bytes
+            assert_arg_err(
+                advz.payload_verify_bytes(&stmt_bytes, &proof_bytes[1..], *kind),
+                "truncated proof should be arg error",
+            );
+            assert_arg_err(
+                advz.payload_verify_bytes(&stmt_bytes[1..], proof_bytes, *kind),
+                "truncated statement should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn payload_proof_cross() {
+        let (advz, _) = avdz_init();
         let mut rng = jf_utils::test_rng();
-        let srs = init_srs(payload_elems_len, &mut rng);
-        let advz = Advz::<E, H>::new(payload_chunk_size, num_storage_nodes, srs).unwrap();
+        let payloads: Vec<Vec<u8>> = [1000, 0, 800]
+            .into_iter()
+            .map(|len| init_random_payload(len, &mut rng))
+            .collect();
+        let concat = payloads.concat();
+        let (disperses, root) = advz.disperse_multi(&payloads).unwrap();
+        assert_eq!(disperses.len(), payloads.len());
+        for (d, payload) in disperses.iter().zip(payloads.iter()) {
+            assert_eq!(d.commit, advz.commit_only(payload).unwrap());
+        }
+        let commons: Vec<_> = disperses.iter().map(|d| d.common.clone()).collect();
+
+        fn check<PROOF>(
+            advz: &Advz<Bls12_381, Sha256>,
+            payloads: &[Vec<u8>],
+            concat: &[u8],
+            root: &HasherNode<Sha256>,
+            commons: &[Common<Bls12_381, Sha256>],
+        ) where
+            Advz<Bls12_381, Sha256>: PayloadProver<PROOF>,
+            PROOF: Clone + ark_std::fmt::Debug + Eq + Serialize + serde::de::DeserializeOwned,
+        {
+            // straddling payloads 0 and 2 across empty payload 1, and inside
+            // a single payload
+            for range in [994..1010, 0..10, 1790..1800] {
+                let proof: CrossProof<PROOF> =
+                    advz.payload_proof_cross(payloads, range.clone()).unwrap();
+                advz.payload_verify_cross(
+                    &concat[range.clone()],
+                    range.clone(),
+                    root,
+                    commons,
+                    &proof,
+                )
+                .unwrap()
+                .unwrap();
+
+                // tampered subslice
+                let mut bad_subslice = concat[range.clone()].to_vec();
+                let last = bad_subslice.len() - 1;
+                bad_subslice[last] ^= 1;
+                advz.payload_verify_cross(&bad_subslice, range.clone(), root, commons, &proof)
+                    .unwrap()
+                    .unwrap_err();
+            }
+
+            // wrong number of sub-proofs
+            let proof: CrossProof<PROOF> = advz.payload_proof_cross(payloads, 0..10).unwrap();
+            assert_arg_err(
+                advz.payload_verify_cross(&concat[995..1005], 995..1005, root, commons, &proof),
+                "wrong number of sub-proofs should be arg error",
+            );
+
+            // commons inconsistent with root
+            assert_arg_err(
+                advz.payload_verify_cross(&concat[0..10], 0..10, root, &commons[..1], &proof),
+                "commons inconsistent with root should be arg error",
+            );
+
+            for range in [5..5, 1795..1801] {
+                assert_arg_err(
+                    advz.payload_proof_cross::<PROOF, _>(payloads, range),
+                    "empty or out of bounds range should be arg error",
+                );
+            }
+        }
+        check::<SmallRangeProof<_>>(&advz, &payloads, &concat, &root, &commons);
+        check::<LargeRangeProof<_>>(&advz, &payloads, &concat, &root, &commons);
+    }
+
+    #[test]
+    fn small_range_proof_n() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let range = 3..10;
+        let stmt = Statement {
+            payload_subslice: &payload[range.clone()],
+            range: range.clone(),
+            commit: &d.commit,
+            common: &d.common,
+        };
+
+        let proof: SmallRangeProofN<_, 1> = advz.payload_proof(&payload, range.clone()).unwrap();
+        advz.payload_verify(stmt.clone(), &proof).unwrap().unwrap();
+
+        // serde round trip
+        let bytes = bincode::serialize(&proof).unwrap();
+        assert_eq!(
+            bincode::deserialize::<SmallRangeProofN<_, 1>>(&bytes).unwrap(),
+            proof
+        );
+
+        // conversion round trip
+        let dynamic = SmallRangeProof::from(proof.clone());
+        let expected: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        assert_eq!(dynamic, expected);
+        advz.payload_verify(stmt.clone(), &dynamic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(SmallRangeProofN::try_from(dynamic.clone()).unwrap(), proof);
+        assert_arg_err(
+            SmallRangeProofN::<_, 2>::try_from(dynamic),
+            "wrong proof len should be arg error",
+        );
+
+        // corrupt subslice
+        let mut bad_subslice = payload[range.clone()].to_vec();
+        bad_subslice[0] ^= 1;
+        advz.payload_verify(
+            Statement {
+                payload_subslice: &bad_subslice,
+                ..stmt
+            },
+            &proof,
+        )
+        .unwrap()
+        .unwrap_err();
+
+        // range covering a different number of elements
+        let res: VidResult<SmallRangeProofN<_, 1>> = advz.payload_proof(&payload, 3..40);
+        assert_arg_err(res, "range of 2 elems should be arg error");
+    }
+
+    #[test]
+    fn prepare_request() {
+        let (advz, payload) = avdz_init();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+
+        let range = advz.prepare_request(&payload, 3, 40).unwrap();
+        assert_eq!(range, 3..43);
+        let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range).unwrap();
+        assert_eq!(proof.proofs.len(), 2);
+
+        // stale request past the end of the payload is clamped
+        assert_eq!(
+            advz.prepare_request(&payload, payload.len() - 5, 100),
+            Ok(payload.len() - 5..payload.len())
+        );
+        assert_eq!(
+            advz.prepare_request(&payload, payload.len() - 5, usize::MAX),
+            Ok(payload.len() - 5..payload.len())
+        );
+
+        assert_eq!(
+            advz.prepare_request(&payload, 3, 0),
+            Err(InvalidRequest::Empty)
+        );
+        assert_eq!(
+            advz.prepare_request(&payload, payload.len(), 1),
+            Err(InvalidRequest::OutOfBounds {
+                start: payload.len(),
+                payload_len: payload.len()
+            })
+        );
+        assert_eq!(
+            advz.prepare_request(&payload, poly_byte_len - 1, 2 * poly_byte_len),
+            Err(InvalidRequest::SpansMultiplePolys {
+                first_poly: 0,
+                last_poly: 2
+            })
+        );
+        assert_arg_err(
+            advz.prepare_request(&payload, 3, 0).map_err(VidError::from),
+            "invalid request should convert to arg error",
+        );
+    }
+
+    #[test]
+    fn is_element_aligned() {
+        let (advz, payload) = avdz_init();
+        let elem_len = elem_byte_capacity::<KzgEval<Bls12_381>>();
+        for (range, aligned) in [
+            (elem_len..3 * elem_len, (true, true)),
+            (0..elem_len, (true, true)),
+            (elem_len + 1..3 * elem_len, (false, true)),
+            (elem_len..3 * elem_len - 1, (true, false)),
+            (1..2, (false, false)),
+        ] {
+            assert_eq!(
+                advz.is_element_aligned(range.clone()),
+                aligned,
+                "range {:?}",
+                range
+            );
+
+            // aligned ends carry no clamp bytes
+            let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            assert_eq!(
+                proof.prefix_bytes.is_empty(),
+                aligned.0,
+                "range {:?}",
+                range
+            );
+            assert_eq!(
+                proof.suffix_bytes.is_empty(),
+                aligned.1,
+                "range {:?}",
+                range
+            );
+        }
+    }
+
+    #[cfg(feature = "debug-tools")]
+    #[test]
+    fn debug_verify_with_poly() {
+        use crate::vid::advz::payload_prover::Discrepancy;
+
+        let (advz, payload) = avdz_init();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+        let poly = |poly_index: usize| {
+            advz.polynomial(
+                bytes_to_field::<_, KzgEval<Bls12_381>>(
+                    payload[advz.index_poly_to_byte(poly_index)..].iter(),
+                )
+                .take(advz.payload_chunk_size),
+            )
+        };
+        let range = poly_byte_len + 5..poly_byte_len + 100;
+        let mut proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let num_elems = proof.proofs.len();
+        assert!(advz
+            .debug_verify_with_poly(&proof, &poly(1), range.clone())
+            .unwrap()
+            .is_empty());
+
+        // wrong polynomial: every opening fails
+        let discrepancies = advz
+            .debug_verify_with_poly(&proof, &poly(0), range.clone())
+            .unwrap();
+        assert_eq!(discrepancies.len(), num_elems);
+
+        // corrupt one proof element: pinpointed
+        let bad_elem = 2;
+        proof.proofs[bad_elem] = proof.proofs[0].clone();
+        let discrepancies = advz
+            .debug_verify_with_poly(&proof, &poly(1), range.clone())
+            .unwrap();
+        assert_eq!(discrepancies.len(), 1);
+        assert!(matches!(
+            discrepancies[0],
+            Discrepancy::Opening { elem_index, .. } if elem_index == bad_elem
+        ));
+
+        // proof for another range
+        let other_range = poly_byte_len + 40..poly_byte_len + 100;
+        let proof: SmallRangeProof<_> = advz.payload_proof(&payload, other_range.clone()).unwrap();
+        let discrepancies = advz
+            .debug_verify_with_poly(&proof, &poly(1), range.clone())
+            .unwrap();
+        assert_eq!(
+            discrepancies[0],
+            Discrepancy::RangeMismatch {
+                expected: range,
+                actual: other_range
+            }
+        );
+        assert_eq!(
+            discrepancies[1],
+            Discrepancy::ProofCount {
+                expected: num_elems,
+                actual: proof.proofs.len()
+            }
+        );
+    }
+
+    #[test]
+    fn online_proof() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let offline = advz.offline_precompute(&payload).unwrap();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+
+        for range in [
+            0..1,
+            3..50,
+            poly_byte_len + 5..2 * poly_byte_len,
+            payload.len() - 7..payload.len(),
+        ] {
+            let proof = advz.online_proof(&offline, range.clone()).unwrap();
+            let expected: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            assert_eq!(proof, expected);
+            advz.payload_verify(
+                Statement {
+                    payload_subslice: &payload[range.clone()],
+                    range,
+                    commit: &d.commit,
+                    common: &d.common,
+                },
+                &proof,
+            )
+            .unwrap()
+            .unwrap();
+        }
+
+        for range in [
+            3..3,
+            payload.len() - 1..payload.len() + 1,
+            poly_byte_len - 1..poly_byte_len + 1,
+        ] {
+            assert_arg_err(
+                advz.online_proof(&offline, range),
+                "empty, out of bounds or multi-poly range should be arg error",
+            );
+        }
+
+        // offline data from an instance with different parameters
+        let srs = init_srs(8, &mut jf_utils::test_rng());
+        let other_advz = Advz::<Bls12_381, Sha256>::new(8, 8, srs).unwrap();
+        let other_offline = other_advz.offline_precompute(&payload).unwrap();
+        assert_arg_err(
+            advz.online_proof(&other_offline, 3..50),
+            "offline data from other params should be arg error",
+        );
+    }
+
+    #[test]
+    fn recommend_proof_type() {
+        let (advz, _) = avdz_init();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+        assert_eq!(
+            advz.recommend_proof_type(poly_byte_len + 5..poly_byte_len + 6)
+                .unwrap(),
+            ProofKind::Small
+        );
+        assert_eq!(
+            advz.recommend_proof_type(poly_byte_len + 1..2 * poly_byte_len - 1)
+                .unwrap(),
+            ProofKind::Large
+        );
+        assert_arg_err(
+            advz.recommend_proof_type(poly_byte_len - 1..poly_byte_len + 1),
+            "multi-poly range should be arg error",
+        );
+
+        // the large proof size estimate is exact away from the payload end
+        let payload = init_random_payload(3 * poly_byte_len, &mut jf_utils::test_rng());
+        for range in [3..4, 31..poly_byte_len - 5, 0..poly_byte_len] {
+            let proof: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            assert_eq!(
+                advz.estimate_large_proof_size(range).unwrap(),
+                compressed_bytes(&proof).len()
+            );
+        }
+    }
+
+    #[test]
+    fn proofs_per_budget() {
+        let (advz, payload) = avdz_init();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+        for range in [
+            3..4,
+            10..100,
+            poly_byte_len..2 * poly_byte_len,
+            payload.len() - 7..payload.len(),
+        ] {
+            let size = advz.estimate_proof_size(range.clone()).unwrap();
+            let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            assert!(compressed_bytes(&proof).len() <= size);
+
+            for budget in [0, size - 1, size, 10 * size + 3, 1 << 20] {
+                let count = advz.proofs_per_budget(range.clone(), budget).unwrap();
+                assert!(count * size <= budget);
+                assert!((count + 1) * size > budget);
+            }
+        }
+
+        // exact for a range away from the end of the payload
+        let proof: SmallRangeProof<_> = advz.payload_proof(&payload, 10..100).unwrap();
+        assert_eq!(
+            advz.estimate_proof_size(10..100).unwrap(),
+            compressed_bytes(&proof).len()
+        );
+
+        for range in [3..3, poly_byte_len - 1..poly_byte_len + 1] {
+            assert_arg_err(
+                advz.proofs_per_budget(range, 1000),
+                "empty or multi-poly range should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn cost_profile() {
+        let (payload_chunk_size, num_storage_nodes) = (64, 64);
+        let srs = init_srs(payload_chunk_size, &mut jf_utils::test_rng());
+        let advz =
+            Advz::<Bls12_381, Sha256>::new(payload_chunk_size, num_storage_nodes, srs).unwrap();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+
+        // tiny range: converting the polynomial dominates
+        let tiny = advz.cost_profile(3..4, ProofKind::Small).unwrap();
+        assert_eq!(tiny.dominant(), CostComponent::FieldConversions);
+        assert_eq!(tiny.openings, 1);
+
+        // large range: one MSM per element dominates
+        let large = advz
+            .cost_profile(poly_byte_len..2 * poly_byte_len, ProofKind::Small)
+            .unwrap();
+        assert_eq!(large.dominant(), CostComponent::Msm);
+        assert_eq!(large.openings, payload_chunk_size);
+        assert_eq!(large.field_conversions, tiny.field_conversions);
+
+        // single opening proof cost is independent of range len
+        assert_eq!(
+            advz.cost_profile(3..4, ProofKind::SingleOpening).unwrap(),
+            advz.cost_profile(0..poly_byte_len, ProofKind::SingleOpening)
+                .unwrap()
+        );
+        assert_eq!(
+            advz.cost_profile(0..poly_byte_len, ProofKind::Large)
+                .unwrap()
+                .dominant(),
+            CostComponent::FieldConversions
+        );
+
+        for range in [3..3, poly_byte_len - 1..poly_byte_len + 1] {
+            assert_arg_err(
+                advz.cost_profile(range, ProofKind::Small),
+                "empty or multi-poly range should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn single_opening_proof() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+        let elem_len = elem_byte_capacity::<KzgEval<Bls12_381>>();
+        let verify = |range: Range<usize>, subslice: &[u8], proof: &SingleOpeningProof<_>| {
+            let stmt = Statement {
+                payload_subslice: subslice,
+                range,
+                commit: &d.commit,
+                common: &d.common,
+            };
+            advz.payload_verify(stmt, proof)
+        };
+
+        let ranges = [
+            10..40,
+            elem_len..2 * elem_len,
+            0..poly_byte_len,
+            5 * poly_byte_len + 3..6 * poly_byte_len - 1,
+            payload.len() - 20..payload.len(),
+        ];
+        for range in ranges {
+            let proof: SingleOpeningProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            verify(range.clone(), &payload[range.clone()], &proof)
+                .unwrap()
+                .unwrap();
+
+            // tampered subslice
+            let mut bad_subslice = payload[range.clone()].to_vec();
+            bad_subslice[range.len() / 2] ^= 1;
+            verify(range.clone(), &bad_subslice, &proof)
+                .unwrap()
+                .unwrap_err();
+
+            // tampered evals
+            for tamper in [
+                |p: &mut SingleOpeningProof<Bls12_381>| p.poly_eval += KzgEval::<Bls12_381>::one(),
+                |p: &mut SingleOpeningProof<Bls12_381>| {
+                    p.quotient_eval += KzgEval::<Bls12_381>::one()
+                },
+            ] {
+                let mut bad_proof = proof.clone();
+                tamper(&mut bad_proof);
+                verify(range.clone(), &payload[range.clone()], &bad_proof)
+                    .unwrap()
+                    .unwrap_err();
+            }
+        }
+
+        // proof for a different range of the same len
+        let (range, other_range) = (10..40, 41..71);
+        let other_proof: SingleOpeningProof<_> =
+            advz.payload_proof(&payload, other_range.clone()).unwrap();
+        let mut bad_proof = other_proof.clone();
+        bad_proof.chunk_range = range.clone();
+        verify(range.clone(), &payload[range.clone()], &bad_proof)
+            .unwrap()
+            .unwrap_err();
+
+        // shifting the subslice via the prefix is rejected
+        let mut bad_proof: SingleOpeningProof<_> =
+            advz.payload_proof(&payload, range.clone()).unwrap();
+        bad_proof.prefix_bytes.push(payload[range.start]);
+        assert_arg_err(
+            verify(range.clone(), &payload[range.clone()], &bad_proof),
+            "wrong prefix len should be arg error",
+        );
+    }
+
+    #[test]
+    fn full_payload_proof() {
+        let (advz, payload) = avdz_init();
+        let commit = advz.commit_only(&payload).unwrap();
+        let proof = advz.full_payload_proof(&payload).unwrap();
+        assert!(advz.range_byte_to_poly(&(0..payload.len())).len() > 1);
+        advz.full_payload_verify(&payload, &commit, &proof)
+            .unwrap()
+            .unwrap();
+
+        // mutate one byte in each polynomial in turn
+        let poly_byte_len = advz.index_poly_to_byte(1);
+        for offset in (poly_byte_len / 2..payload.len()).step_by(poly_byte_len) {
+            let mut bad_payload = payload.clone();
+            bad_payload[offset] ^= 1;
+            advz.full_payload_verify(&bad_payload, &commit, &proof)
+                .unwrap()
+                .unwrap_err();
+        }
+
+        // payload of another length
+        assert_arg_err(
+            advz.full_payload_verify(&payload[1..], &commit, &proof),
+            "truncated payload should be arg error",
+        );
+        let short_proof = advz.full_payload_proof(&payload[1..]).unwrap();
+        assert_arg_err(
+            advz.full_payload_verify(&payload, &commit, &short_proof),
+            "proof for another payload len should be arg error",
+        );
+
+        // not a full payload proof
+        let range_proof: LargeRangeProof<_> =
+            advz.payload_proof(&payload, 0..poly_byte_len - 1).unwrap();
+        assert_arg_err(
+            advz.full_payload_verify(&payload[..poly_byte_len - 1], &commit, &range_proof),
+            "proof with a suffix should be arg error",
+        );
+
+        assert_arg_err(
+            advz.full_payload_proof([]),
+            "empty payload should be arg error",
+        );
+    }
+
+    #[test]
+    fn payload_proof_with_complement() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+
+        for range in [
+            3..50,
+            poly_byte_len + 3..poly_byte_len + 50,
+            poly_byte_len..2 * poly_byte_len,
+            payload.len() - 10..payload.len(),
+        ] {
+            let stmt = Statement {
+                payload_subslice: &payload[range.clone()],
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+            let proof = advz
+                .payload_proof_with_complement(&payload, range.clone())
+                .unwrap();
+            advz.payload_verify_with_complement(stmt.clone(), &proof)
+                .unwrap()
+                .unwrap();
+
+            // the decomposition holds
+            let poly_index = advz.range_byte_to_poly(&range).start;
+            let (range_commit, complement_commit) = advz
+                .large_range_proof_decomposition(&payload[range.clone()], &proof.proof)
+                .unwrap();
+            assert_eq!(&complement_commit, proof.complement_commit());
+            assert_eq!(
+                (range_commit.0 + complement_commit.0).into_affine(),
+                d.common.poly_commits[poly_index].0
+            );
+
+            // tampered complement
+            let mut bad_proof = proof.clone();
+            bad_proof.complement_commit =
+                KzgCommit::<Bls12_381>::from((complement_commit.0 + range_commit.0).into_affine());
+            advz.payload_verify_with_complement(stmt.clone(), &bad_proof)
+                .unwrap()
+                .unwrap_err();
+
+            // tampered data
+            let mut bad_subslice = stmt.payload_subslice.to_vec();
+            bad_subslice[0] = bad_subslice[0].wrapping_add(1);
+            let bad_stmt = Statement {
+                payload_subslice: &bad_subslice,
+                ..stmt
+            };
+            advz.payload_verify_with_complement(bad_stmt, &proof)
+                .unwrap()
+                .unwrap_err();
+        }
+    }
+
+    #[test]
+    fn prove_common_prefix() {
+        let (advz, payload_a) = avdz_init();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+        let elem_len = elem_byte_capacity::<KzgEval<Bls12_381>>();
+
+        // fork `payload_a` at `fork`, with a different length
+        let fork = poly_byte_len + 3 * elem_len + 7;
+        let mut payload_b = payload_a[..payload_a.len() - 100].to_vec();
+        for byte in payload_b[fork..].iter_mut() {
+            *byte = byte.wrapping_add(1);
+        }
+        let (d_a, d_b) = (
+            advz.disperse(&payload_a).unwrap(),
+            advz.disperse(&payload_b).unwrap(),
+        );
+        let verify = |prefix_len, proof: &CommonPrefixProof<Bls12_381>| {
+            advz.verify_common_prefix(
+                prefix_len,
+                &d_a.commit,
+                &d_a.common,
+                &d_b.commit,
+                &d_b.common,
+                proof,
+            )
+        };
+
+        // equal prefixes, aligned or not to polynomial and element boundaries
+        for prefix_len in [
+            1,
+            elem_len,
+            poly_byte_len,
+            poly_byte_len + 5,
+            poly_byte_len + 3 * elem_len,
+            fork,
+        ] {
+            let proof = advz
+                .prove_common_prefix(&payload_a, &payload_b, prefix_len)
+                .unwrap();
+            verify(prefix_len, &proof).unwrap().unwrap();
+            let bytes = bincode::serialize(&proof).unwrap();
+            assert_eq!(
+                bincode::deserialize::<CommonPrefixProof<Bls12_381>>(&bytes).unwrap(),
+                proof
+            );
+        }
+
+        // differing prefixes
+        for prefix_len in [fork + 1, poly_byte_len + 4 * elem_len, 2 * poly_byte_len] {
+            assert_arg_err(
+                advz.prove_common_prefix(&payload_a, &payload_b, prefix_len),
+                "differing prefixes should be arg error",
+            );
+            let proof = advz
+                .prove_common_prefix(&payload_a, &payload_a, prefix_len)
+                .unwrap();
+            verify(prefix_len, &proof).unwrap().unwrap_err();
+        }
+
+        // bad prefix lens and proof shapes
+        let proof = advz
+            .prove_common_prefix(&payload_a, &payload_b, fork)
+            .unwrap();
+        assert_arg_err(verify(0, &proof), "zero prefix len should be arg error");
+        assert_arg_err(
+            verify(payload_b.len() + 1, &proof),
+            "prefix len out of bounds should be arg error",
+        );
+        assert_arg_err(
+            verify(fork - 7, &proof),
+            "proof for another prefix len should be arg error",
+        );
+    }
+
+    #[test]
+    fn prove_ranges_equal() {
+        let (advz, mut payload) = avdz_init();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+        let elem_len = elem_byte_capacity::<KzgEval<Bls12_381>>();
+
+        // copy the first 2 elems of poly 0 into poly 2
+        let (src, dst) = (
+            0..2 * elem_len,
+            2 * poly_byte_len..2 * poly_byte_len + 2 * elem_len,
+        );
+        payload.copy_within(src, dst.start);
+        let d = advz.disperse(&payload).unwrap();
+
+        let (range_a, range_b) = (10..40, dst.start + 10..dst.start + 40);
+        let proof = advz
+            .prove_ranges_equal(&payload, range_a.clone(), range_b.clone())
+            .unwrap();
+        advz.verify_ranges_equal(
+            range_a.clone(),
+            range_b.clone(),
+            &d.commit,
+            &d.common,
+            &proof,
+        )
+        .unwrap()
+        .unwrap();
+
+        // unequal ranges
+        let mut bad_payload = payload.clone();
+        bad_payload[range_b.start + 5] ^= 1;
+        assert_arg_err(
+            advz.prove_ranges_equal(&bad_payload, range_a.clone(), range_b.clone()),
+            "unequal ranges should be arg error",
+        );
+        let bad_d = advz.disperse(&bad_payload).unwrap();
+        advz.verify_ranges_equal(
+            range_a.clone(),
+            range_b.clone(),
+            &bad_d.commit,
+            &bad_d.common,
+            &proof,
+        )
+        .unwrap()
+        .unwrap_err();
+
+        // proof for other ranges
+        let other_proof = advz
+            .prove_ranges_equal(&payload, range_a.clone(), range_a.clone())
+            .unwrap();
+        advz.verify_ranges_equal(
+            range_a.clone(),
+            range_b.clone(),
+            &d.commit,
+            &d.common,
+            &other_proof,
+        )
+        .unwrap()
+        .unwrap_err();
+
+        // bad ranges
+        for (range_a, range_b) in [
+            (10..40, dst.start + 10..dst.start + 41),
+            (10..40, dst.start + 11..dst.start + 41),
+            (10..10, dst.start + 10..dst.start + 10),
+            (
+                poly_byte_len - 1..poly_byte_len + 1,
+                dst.start - 1..dst.start + 1,
+            ),
+            (10..40, payload.len() + 10..payload.len() + 40),
+        ] {
+            assert_arg_err(
+                advz.prove_ranges_equal(&payload, range_a, range_b),
+                "bad ranges should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn payload_verify_from_compressed_commit() {
+        type A = Advz<Bls12_381, Sha256>;
+        let (advz, payload) = avdz_init_with_commit_version(CommitVersion::V1);
+        let d = advz.disperse(&payload).unwrap();
+        let compact = advz.compact_common(&d.common).unwrap();
+        let poly_commit = d.common.poly_commits[0];
+
+        // round trip
+        let compressed = A::compress_poly_commit(&poly_commit).unwrap();
+        assert_eq!(compressed.len() * 2, poly_commit.uncompressed_size());
+        assert_eq!(A::decompress_poly_commit(&compressed).unwrap(), poly_commit);
+
+        let range = 3..50;
+        let subslice = &payload[range.clone()];
+        let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        advz.payload_verify_from_compressed_commit(
+            subslice,
+            range.clone(),
+            &d.commit,
+            &compact,
+            &compressed,
+            &small,
+        )
+        .unwrap()
+        .unwrap();
+        advz.payload_verify_from_compressed_commit(
+            subslice,
+            range.clone(),
+            &d.commit,
+            &compact,
+            &compressed,
+            &large,
+        )
+        .unwrap()
+        .unwrap();
+
+        // commitment to another polynomial
+        let other = A::compress_poly_commit(&d.common.poly_commits[1]).unwrap();
+        assert_arg_err(
+            advz.payload_verify_from_compressed_commit(
+                subslice,
+                range.clone(),
+                &d.commit,
+                &compact,
+                &other,
+                &small,
+            ),
+            "wrong poly commit should be arg error",
+        );
+
+        // reject an x-coordinate not on the curve
+        let mut found_invalid = false;
+        for i in 0..=u8::MAX {
+            let mut bad = compressed.clone();
+            bad[0] = i;
+            if A::decompress_poly_commit(&bad).is_err() {
+                assert_arg_err(
+                    advz.payload_verify_from_compressed_commit(
+                        subslice,
+                        range.clone(),
+                        &d.commit,
+                        &compact,
+                        &bad,
+                        &small,
+                    ),
+                    "invalid compressed poly commit should be arg error",
+                );
+                found_invalid = true;
+                break;
+            }
+        }
+        assert!(found_invalid);
+
+        // wrong length
+        assert_arg_err(
+            A::decompress_poly_commit(&compressed[1..]),
+            "short compressed poly commit should be arg error",
+        );
+        let mut long = compressed;
+        long.push(0);
+        assert_arg_err(
+            A::decompress_poly_commit(&long),
+            "long compressed poly commit should be arg error",
+        );
+    }
+
+    #[test]
+    fn payload_verify_from_store() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let other_payload: Vec<u8> = payload.iter().map(|b| !b).collect();
+        let other = advz.disperse(&other_payload).unwrap();
+        let mut store = MemCommitStore::<Advz<Bls12_381, Sha256>, u64>::new();
+        store.insert(7, d.commit, d.common.clone());
+        store.insert(8, other.commit, other.common);
+
+        let range = 3..50;
+        let subslice = &payload[range.clone()];
+        let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        advz.payload_verify_from_store(&store, &7, subslice, range.clone(), &small)
+            .unwrap()
+            .unwrap();
+        advz.payload_verify_from_store(&store, &7, subslice, range.clone(), &large)
+            .unwrap()
+            .unwrap();
+
+        // wrong block
+        advz.payload_verify_from_store(&store, &8, subslice, range.clone(), &small)
+            .unwrap()
+            .unwrap_err();
+
+        // missing block
+        assert_arg_err(
+            advz.payload_verify_from_store(&store, &9, subslice, range, &small),
+            "missing block should be arg error",
+        );
+    }
+
+    #[test]
+    fn payload_proof_commit_bound() {
+        let (mut advz, payload) = avdz_init();
+        let header = b"block header";
+
+        // the commit is recomputed from poly commit hashes
+        let d = advz.disperse(&payload).unwrap();
+        assert_arg_err(
+            advz.payload_proof_commit_bound::<SmallRangeProof<_>, _>(
+                &payload,
+                0..1,
+                &d.commit,
+                None,
+            ),
+            "commit bound proof under V0 should be arg error",
+        );
+        advz = avdz_init_with_commit_version(CommitVersion::V1).0;
+
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+        for (header, d) in [
+            (None, advz.disperse(&payload).unwrap()),
+            (
+                Some(&header[..]),
+                advz.disperse_with_header(header, &payload).unwrap(),
+            ),
+        ] {
+            // the prover holds the payload and the trusted commit only
+            for range in [
+                0..1,
+                poly_bytes_len + 5..2 * poly_bytes_len,
+                payload.len() - 7..payload.len(),
+            ] {
+                let subslice = &payload[range.clone()];
+                let small = advz
+                    .payload_proof_commit_bound::<SmallRangeProof<_>, _>(
+                        &payload,
+                        range.clone(),
+                        &d.commit,
+                        header,
+                    )
+                    .unwrap();
+                advz.payload_verify_commit_bound(subslice, range.clone(), &d.commit, &small)
+                    .unwrap()
+                    .unwrap();
+                let large = advz
+                    .payload_proof_commit_bound::<LargeRangeProof<_>, _>(
+                        &payload,
+                        range.clone(),
+                        &d.commit,
+                        header,
+                    )
+                    .unwrap();
+                advz.payload_verify_commit_bound(subslice, range.clone(), &d.commit, &large)
+                    .unwrap()
+                    .unwrap();
+
+                // serde round trip
+                let bytes = bincode::serialize(&small).unwrap();
+                assert_eq!(
+                    bincode::deserialize::<CommitBoundProof<SmallRangeProof<_>, Bls12_381, Sha256>>(
+                        &bytes
+                    )
+                    .unwrap(),
+                    small
+                );
+
+                // corrupt subslice
+                let mut bad_subslice = subslice.to_vec();
+                bad_subslice[0] = bad_subslice[0].wrapping_add(1);
+                advz.payload_verify_commit_bound(&bad_subslice, range.clone(), &d.commit, &small)
+                    .unwrap()
+                    .unwrap_err();
+                advz.payload_verify_commit_bound(&bad_subslice, range.clone(), &d.commit, &large)
+                    .unwrap()
+                    .unwrap_err();
+
+                // wrong commit
+                let bad_commit = advz.commit_only(&payload[1..]).unwrap();
+                advz.payload_verify_commit_bound(subslice, range.clone(), &bad_commit, &small)
+                    .unwrap()
+                    .unwrap_err();
+                advz.payload_verify_commit_bound(subslice, range.clone(), &bad_commit, &large)
+                    .unwrap()
+                    .unwrap_err();
+                assert_arg_err(
+                    advz.payload_proof_commit_bound::<SmallRangeProof<_>, _>(
+                        &payload,
+                        range.clone(),
+                        &bad_commit,
+                        header,
+                    ),
+                    "commit inconsistent with payload should be arg error",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn verify_commit_bound_inclusion() {
+        let header = b"block header";
+        for commit_version in [CommitVersion::V1, CommitVersion::V2] {
+            let (advz, payload) = avdz_init_with_commit_version(commit_version);
+            let range = advz.index_poly_to_byte(1) + 5..advz.index_poly_to_byte(2);
+            for header in [None, Some(&header[..])] {
+                let d = match header {
+                    Some(header) => advz.disperse_with_header(header, &payload),
+                    None => advz.disperse(&payload),
+                }
+                .unwrap();
+                let proof = advz
+                    .payload_proof_commit_bound::<SmallRangeProof<_>, _>(
+                        &payload,
+                        range.clone(),
+                        &d.commit,
+                        header,
+                    )
+                    .unwrap();
+                advz.verify_commit_bound_inclusion(&d.commit, 1, &proof)
+                    .unwrap()
+                    .unwrap();
+
+                // wrong poly index
+                let num_polys = proof.other_poly_commit_hashes.len() + 1;
+                for poly_index in [0, num_polys - 1, num_polys] {
+                    advz.verify_commit_bound_inclusion(&d.commit, poly_index, &proof)
+                        .unwrap()
+                        .unwrap_err();
+                }
 
-        // TEST: different payload byte lengths
-        let payload_byte_len_noise_cases = vec![0, poly_bytes_len / 2, poly_bytes_len - 1];
-        let payload_len_cases = payload_byte_len_noise_cases
-            .into_iter()
-            .map(|l| payload_bytes_base_len - l);
+                // tampered poly commit hash
+                let mut bad_proof = proof.clone();
+                bad_proof.other_poly_commit_hashes[0] = HasherNode::default();
+                advz.verify_commit_bound_inclusion(&d.commit, 1, &bad_proof)
+                    .unwrap()
+                    .unwrap_err();
 
-        // TEST: prove data ranges for this paylaod
-        // it takes too long to test all combos of (polynomial, start, len)
-        // so do some edge cases and random cases
-        let edge_cases = vec![
-            Range { start: 0, end: 1 },
-            Range { start: 0, end: 2 },
-            Range {
-                start: 0,
-                end: poly_bytes_len - 1,
-            },
-            Range {
-                start: 0,
-                end: poly_bytes_len,
-            },
-            Range { start: 1, end: 2 },
-            Range { start: 1, end: 3 },
-            Range {
-                start: 1,
-                end: poly_bytes_len - 1,
-            },
-            Range {
-                start: 1,
-                end: poly_bytes_len,
-            },
-            Range {
-                start: poly_bytes_len - 2,
-                end: poly_bytes_len - 1,
-            },
-            Range {
-                start: poly_bytes_len - 2,
-                end: poly_bytes_len,
-            },
-            Range {
-                start: poly_bytes_len - 1,
-                end: poly_bytes_len,
-            },
-        ];
-        let random_cases = {
-            let num_cases = edge_cases.len();
-            let mut random_cases = Vec::with_capacity(num_cases);
-            for _ in 0..num_cases {
-                let start = rng.gen_range(0..poly_bytes_len - 1);
-                let end = rng.gen_range(start + 1..poly_bytes_len);
-                random_cases.push(Range { start, end });
+                // missing or wrong header digest
+                let mut bad_proof = proof.clone();
+                bad_proof.header_digest = match header {
+                    Some(_) => None,
+                    None => Some(HasherNode::default()),
+                };
+                advz.verify_commit_bound_inclusion(&d.commit, 1, &bad_proof)
+                    .unwrap()
+                    .unwrap_err();
+
+                // only V2 binds the payload byte length
+                let mut bad_proof = proof.clone();
+                bad_proof.bytes_len += 1;
+                assert_eq!(
+                    advz.verify_commit_bound_inclusion(&d.commit, 1, &bad_proof)
+                        .unwrap()
+                        .is_ok(),
+                    commit_version == CommitVersion::V1
+                );
             }
-            random_cases
+        }
+
+        // versions that do not hash the poly commit hashes
+        let (advz, payload) = avdz_init_with_commit_version(CommitVersion::V1);
+        let range = advz.index_poly_to_byte(1) + 5..advz.index_poly_to_byte(2);
+        let d = advz.disperse(&payload).unwrap();
+        let proof = advz
+            .payload_proof_commit_bound::<SmallRangeProof<_>, _>(&payload, range, &d.commit, None)
+            .unwrap();
+        for commit_version in [CommitVersion::V0, CommitVersion::V3] {
+            let (advz, _) = avdz_init_with_commit_version(commit_version);
+            assert_arg_err(
+                advz.verify_commit_bound_inclusion(&d.commit, 1, &proof),
+                "commit bound inclusion should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn payload_verify_in_smt() {
+        use crate::merkle_tree::UniversalMerkleTreeScheme;
+
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let other_commit = advz.commit_only(&payload[1..]).unwrap();
+
+        // height large enough for all u64 keys
+        let (key, other_key, absent_key) = (7u64, u64::MAX, 42u64);
+        let smt = CommitSmt::<Sha256, u64>::from_kv_set(
+            41,
+            [
+                (key, HasherNode::from(d.commit)),
+                (other_key, HasherNode::from(other_commit)),
+            ],
+        )
+        .unwrap();
+        let root = smt.commitment().digest();
+        let (_, smt_proof) = smt.lookup(key).expect_ok().unwrap();
+
+        let range = 3..50;
+        let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let stmt = Statement {
+            payload_subslice: &payload[range.clone()],
+            range: range.clone(),
+            commit: &d.commit,
+            common: &d.common,
         };
-        let all_cases = [(edge_cases, "edge"), (random_cases, "rand")];
+        advz.payload_verify_in_smt(stmt.clone(), &proof, &key, &smt_proof, &root)
+            .unwrap()
+            .unwrap();
 
-        for payload_len_case in payload_len_cases {
-            let payload = init_random_payload(payload_len_case, &mut rng);
-            let d = advz.disperse(&payload).unwrap();
-            println!("payload byte len case: {}", payload.len());
+        // corrupt subslice
+        let mut bad_subslice = payload[range.clone()].to_vec();
+        bad_subslice[0] ^= 1;
+        let bad_stmt = Statement {
+            payload_subslice: &bad_subslice,
+            ..stmt.clone()
+        };
+        advz.payload_verify_in_smt(bad_stmt, &proof, &key, &smt_proof, &root)
+            .unwrap()
+            .unwrap_err();
 
-            for poly in 0..num_polys {
-                let poly_offset = poly * poly_bytes_len;
+        // proof for another block's commit
+        let (_, other_smt_proof) = smt.lookup(other_key).expect_ok().unwrap();
+        for block_key in [key, other_key] {
+            advz.payload_verify_in_smt(stmt.clone(), &proof, &block_key, &other_smt_proof, &root)
+                .unwrap()
+                .unwrap_err();
+        }
 
-                for cases in all_cases.iter() {
-                    for range in cases.0.iter() {
-                        let range = Range {
-                            start: range.start + poly_offset,
-                            end: range.end + poly_offset,
-                        };
-                        print!("poly {} {} case: {:?}", poly, cases.1, range);
+        // non-member commit: block absent from the tree
+        let non_member_proof = smt.universal_lookup(absent_key).expect_not_found().unwrap();
+        advz.payload_verify_in_smt(stmt.clone(), &proof, &absent_key, &non_member_proof, &root)
+            .unwrap()
+            .unwrap_err();
 
-                        // ensure range fits inside payload
-                        let range = if range.start >= payload.len() {
-                            println!(" outside payload len {}, skipping", payload.len());
-                            continue;
-                        } else if range.end > payload.len() {
-                            println!(" clamped to payload len {}", payload.len());
-                            Range {
-                                end: payload.len(),
-                                ..range
-                            }
-                        } else {
-                            println!();
-                            range
-                        };
+        // wrong root
+        let bad_root = HasherNode::<Sha256>::default();
+        advz.payload_verify_in_smt(stmt, &proof, &key, &smt_proof, &bad_root)
+            .unwrap()
+            .unwrap_err();
+    }
 
-                        let stmt = Statement {
-                            payload_subslice: &payload[range.clone()],
+    #[test]
+    fn payload_proof_bundled() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let root = Advz::poly_commits_merkle_root(&d.common).unwrap();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+
+        for range in [
+            0..1,
+            3..50,
+            poly_bytes_len + 5..2 * poly_bytes_len,
+            payload.len() - 7..payload.len(),
+        ] {
+            let stmt = BundledStatement {
+                payload_subslice: &payload[range.clone()],
+                range: range.clone(),
+                root: &root,
+            };
+            let small = advz
+                .payload_proof_bundled::<SmallRangeProof<_>, _>(&payload, range.clone(), &d.common)
+                .unwrap();
+            advz.payload_verify_bundled(stmt.clone(), &small)
+                .unwrap()
+                .unwrap();
+            let large = advz
+                .payload_proof_bundled::<LargeRangeProof<_>, _>(&payload, range.clone(), &d.common)
+                .unwrap();
+            advz.payload_verify_bundled(stmt.clone(), &large)
+                .unwrap()
+                .unwrap();
+
+            // serde round trip
+            let bytes = bincode::serialize(&small).unwrap();
+            assert_eq!(
+                bincode::deserialize::<BundledProof<SmallRangeProof<_>, Bls12_381, Sha256>>(&bytes)
+                    .unwrap(),
+                small
+            );
+
+            // corrupt subslice
+            let mut bad_subslice = payload[range.clone()].to_vec();
+            bad_subslice[0] = bad_subslice[0].wrapping_add(1);
+            let bad_stmt = BundledStatement {
+                payload_subslice: &bad_subslice,
+                ..stmt.clone()
+            };
+            advz.payload_verify_bundled(bad_stmt.clone(), &small)
+                .unwrap()
+                .unwrap_err();
+            advz.payload_verify_bundled(bad_stmt, &large)
+                .unwrap()
+                .unwrap_err();
+
+            // wrong root
+            let bad_root = HasherNode::<Sha256>::default();
+            let bad_stmt = BundledStatement {
+                root: &bad_root,
+                ..stmt
+            };
+            advz.payload_verify_bundled(bad_stmt.clone(), &small)
+                .unwrap()
+                .unwrap_err();
+            advz.payload_verify_bundled(bad_stmt, &large)
+                .unwrap()
+                .unwrap_err();
+        }
+    }
+
+    #[test]
+    fn verify_cache() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let range = 10..40;
+        let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let mut bad_subslice = payload[range.clone()].to_vec();
+        bad_subslice[0] ^= 1;
+        let stmt = Statement {
+            payload_subslice: &payload[range.clone()],
+            range: range.clone(),
+            commit: &d.commit,
+            common: &d.common,
+        };
+        let bad_stmt = Statement {
+            payload_subslice: &bad_subslice,
+            ..stmt.clone()
+        };
+        let metrics = |hits, misses, evictions| VerifyCacheMetrics {
+            hits,
+            misses,
+            evictions,
+        };
+
+        let mut cache = advz.verify_cache(3);
+        cache.payload_verify(stmt.clone(), &small).unwrap().unwrap();
+        cache.payload_verify(stmt.clone(), &small).unwrap().unwrap();
+        assert_eq!(cache.metrics(), metrics(1, 1, 0));
+
+        // a different subslice misses, and its failure is cached too
+        cache
+            .payload_verify(bad_stmt.clone(), &small)
+            .unwrap()
+            .unwrap_err();
+        cache
+            .payload_verify(bad_stmt.clone(), &small)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(cache.metrics(), metrics(2, 2, 0));
+
+        // a different proof type misses
+        cache.payload_verify(stmt.clone(), &large).unwrap().unwrap();
+        assert_eq!(cache.metrics(), metrics(2, 3, 0));
+        assert_eq!(cache.len(), 3);
+
+        // the least recently used entry, the bad subslice, is evicted
+        cache.payload_verify(stmt.clone(), &small).unwrap().unwrap();
+        let other_range = 50..60;
+        let other: SmallRangeProof<_> = advz.payload_proof(&payload, other_range.clone()).unwrap();
+        let other_stmt = Statement {
+            payload_subslice: &payload[other_range.clone()],
+            range: other_range,
+            ..stmt.clone()
+        };
+        cache.payload_verify(other_stmt, &other).unwrap().unwrap();
+        assert_eq!(cache.metrics(), metrics(3, 4, 1));
+        cache
+            .payload_verify(bad_stmt.clone(), &small)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(cache.metrics(), metrics(3, 5, 2));
+
+        // a common with other parameters is rejected, not answered from the
+        // cache
+        let mut other_common = d.common.clone();
+        other_common.num_storage_nodes += 1;
+        assert_arg_err(
+            cache.payload_verify(
+                Statement {
+                    common: &other_common,
+                    ..stmt.clone()
+                },
+                &small,
+            ),
+            "common with other parameters should be arg error",
+        );
+
+        // errors are not cached
+        let short_stmt = Statement {
+            payload_subslice: &payload[range.start..range.end - 1],
+            ..stmt.clone()
+        };
+        assert_arg_err(
+            cache.payload_verify(short_stmt, &small),
+            "bad subslice len should be arg error",
+        );
+        assert_eq!(cache.len(), 3);
+
+        // zero capacity disables caching
+        cache.set_capacity(0);
+        assert!(cache.is_empty());
+        cache.payload_verify(stmt.clone(), &small).unwrap().unwrap();
+        assert!(cache.is_empty());
+        assert_eq!(cache.metrics(), metrics(3, 7, 5));
+    }
+
+    #[test]
+    fn mixed_verifier() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let mut rng = jf_utils::test_rng();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+        let small_ranges = [3..50, 2 * poly_bytes_len + 1..2 * poly_bytes_len + 9];
+        let large_range = poly_bytes_len + 5..2 * poly_bytes_len;
+
+        let small_proofs: Vec<SmallRangeProof<_>> = small_ranges
+            .iter()
+            .map(|r| advz.payload_proof(&payload, r.clone()).unwrap())
+            .collect();
+        let large_proof: LargeRangeProof<_> =
+            advz.payload_proof(&payload, large_range.clone()).unwrap();
+        let mut bad_payload = payload.clone();
+        bad_payload[small_ranges[1].start] ^= 1;
+        bad_payload[large_range.start] ^= 1;
+
+        // (corrupt small, corrupt large, expect valid)
+        for (bad_small, bad_large, valid) in [
+            (false, false, true),
+            (true, false, false),
+            (false, true, false),
+            (true, true, false),
+        ] {
+            let small_payload = if bad_small { &bad_payload } else { &payload };
+            let large_payload = if bad_large { &bad_payload } else { &payload };
+            let mut verifier = advz.mixed_verifier();
+            for (range, proof) in small_ranges.iter().zip(small_proofs.iter()) {
+                verifier
+                    .add_small(
+                        Statement {
+                            payload_subslice: &small_payload[range.clone()],
                             range: range.clone(),
                             commit: &d.commit,
                             common: &d.common,
-                        };
+                        },
+                        proof,
+                    )
+                    .unwrap();
+            }
+            verifier
+                .add_large(
+                    Statement {
+                        payload_subslice: &large_payload[large_range.clone()],
+                        range: large_range.clone(),
+                        commit: &d.commit,
+                        common: &d.common,
+                    },
+                    &large_proof,
+                )
+                .unwrap();
+            assert_eq!(verifier.finalize(&mut rng).unwrap().is_ok(), valid);
+        }
 
-                        let small_range_proof: SmallRangeProof<_> =
-                            advz.payload_proof(&payload, range.clone()).unwrap();
-                        advz.payload_verify(stmt.clone(), &small_range_proof)
-                            .unwrap()
-                            .unwrap();
+        // empty queue
+        advz.mixed_verifier().finalize(&mut rng).unwrap().unwrap();
+    }
 
-                        let large_range_proof: LargeRangeProof<_> =
-                            advz.payload_proof(&payload, range.clone()).unwrap();
-                        advz.payload_verify(stmt, &large_range_proof)
-                            .unwrap()
-                            .unwrap();
-                    }
-                }
+    #[test]
+    fn payload_proof_mmap() {
+        use ark_std::io::Write;
+
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(&payload).unwrap();
+        // SAFETY: the file is private to this test and is not modified while
+        // mapped.
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.unwrap();
+        assert_eq!(&mmap[..], &payload[..]);
+
+        for range in [3..50, poly_bytes_len + 3..poly_bytes_len + 50] {
+            let stmt = Statement {
+                payload_subslice: &mmap[range.clone()],
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+
+            let small: SmallRangeProof<_> = advz.payload_proof(&mmap, range.clone()).unwrap();
+            assert_eq!(
+                small,
+                PayloadProver::<SmallRangeProof<_>>::payload_proof(&advz, &payload, range.clone())
+                    .unwrap()
+            );
+            advz.payload_verify(stmt.clone(), &small).unwrap().unwrap();
+
+            let large: LargeRangeProof<_> = advz.payload_proof(&mmap, range.clone()).unwrap();
+            assert_eq!(
+                large,
+                PayloadProver::<LargeRangeProof<_>>::payload_proof(&advz, &payload, range).unwrap()
+            );
+            advz.payload_verify(stmt, &large).unwrap().unwrap();
+        }
+    }
+
+    #[test]
+    fn payload_verify_returning_commit() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+
+        for range in [3..50, poly_bytes_len + 3..poly_bytes_len + 50] {
+            let range_poly = advz.range_byte_to_poly(&range);
+            let stmt = Statement {
+                payload_subslice: &payload[range.clone()],
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+            let proof: LargeRangeProof<_> = advz.payload_proof(&payload, range).unwrap();
+            let poly_commit = advz
+                .payload_verify_returning_commit(stmt.clone(), &proof)
+                .unwrap()
+                .unwrap();
+            assert_eq!(poly_commit, d.common.poly_commits[range_poly.start]);
+
+            let mut bad_subslice = stmt.payload_subslice.to_vec();
+            bad_subslice[0] = bad_subslice[0].wrapping_add(1);
+            let bad_stmt = Statement {
+                payload_subslice: &bad_subslice,
+                ..stmt
+            };
+            advz.payload_verify_returning_commit(bad_stmt, &proof)
+                .unwrap()
+                .unwrap_err();
+        }
+    }
+
+    #[test]
+    fn payload_proof_points() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+
+        for range in [
+            0..1,
+            3..50,
+            poly_byte_len - 1..poly_byte_len,
+            poly_byte_len + 3..2 * poly_byte_len,
+            payload.len() - 10..payload.len(),
+        ] {
+            let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            let stmt = Statement {
+                payload_subslice: &payload[range.clone()],
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+            let (outcome, log) = advz.payload_verify_with_audit(stmt, &proof).unwrap();
+            outcome.unwrap();
+            let points = advz.payload_proof_points(&range).unwrap();
+            assert_eq!(
+                points,
+                log.entries
+                    .iter()
+                    .map(|entry| entry.point)
+                    .collect::<Vec<_>>()
+            );
+
+            // the proof opens the polynomial at the same points
+            let poly_index = advz.range_byte_to_poly(&range).start;
+            for ((point, entry), pf) in points
+                .iter()
+                .zip(log.entries.iter())
+                .zip(proof.proofs.iter())
+            {
+                assert!(UnivariateKzgPCS::verify(
+                    &advz.vk,
+                    &d.common.poly_commits[poly_index],
+                    point,
+                    &entry.eval,
+                    pf
+                )
+                .unwrap());
             }
         }
     }
 
     #[test]
-    fn correctness() {
-        correctness_generic::<Bls12_381, Sha256>();
+    fn payload_verify_with_points() {
+        let (advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+
+        // ranges at the same offset within different polynomials share points
+        let points = advz.payload_proof_points(&(3..50)).unwrap();
+        assert_eq!(
+            points,
+            advz.payload_proof_points(&(poly_bytes_len + 3..poly_bytes_len + 50))
+                .unwrap()
+        );
+
+        for range in [3..50, poly_bytes_len + 3..poly_bytes_len + 50] {
+            let stmt = Statement {
+                payload_subslice: &payload[range.clone()],
+                range: range.clone(),
+                commit: &d.commit,
+                common: &d.common,
+            };
+            let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range).unwrap();
+            advz.payload_verify_with_points(stmt.clone(), &proof, &points)
+                .unwrap()
+                .unwrap();
+
+            // same result as `payload_verify` for a bad subslice
+            let mut bad_subslice = stmt.payload_subslice.to_vec();
+            bad_subslice[0] = bad_subslice[0].wrapping_add(1);
+            let bad_stmt = Statement {
+                payload_subslice: &bad_subslice,
+                ..stmt.clone()
+            };
+            advz.payload_verify(bad_stmt.clone(), &proof)
+                .unwrap()
+                .unwrap_err();
+            advz.payload_verify_with_points(bad_stmt, &proof, &points)
+                .unwrap()
+                .unwrap_err();
+
+            // wrong-length points
+            assert_arg_err(
+                advz.payload_verify_with_points(stmt.clone(), &proof, &points[1..]),
+                "short points should be arg error",
+            );
+            let mut long_points = points.clone();
+            long_points.push(points[0]);
+            assert_arg_err(
+                advz.payload_verify_with_points(stmt, &proof, &long_points),
+                "long points should be arg error",
+            );
+        }
+
+        assert_arg_err(
+            advz.payload_proof_points(&(0..0)),
+            "empty range should be arg error",
+        );
+        assert_arg_err(
+            advz.payload_proof_points(&(poly_bytes_len - 1..poly_bytes_len + 1)),
+            "multi-poly range should be arg error",
+        );
+    }
+
+    #[test]
+    fn payload_verify_compact() {
+        let (mut advz, payload) = avdz_init();
+        let d = advz.disperse(&payload).unwrap();
+        assert_arg_err(
+            advz.compact_common(&d.common),
+            "compact common under V0 should be arg error",
+        );
+        advz = avdz_init_with_commit_version(CommitVersion::V1).0;
+        let d = advz.disperse(&payload).unwrap();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+
+        // round trip
+        let compact = advz.compact_common(&d.common).unwrap();
+        let mut bytes = Vec::new();
+        compact.serialize_uncompressed(&mut bytes).unwrap();
+        assert_eq!(
+            CompactCommon::deserialize_uncompressed(&bytes[..]).unwrap(),
+            compact
+        );
+        assert!(bytes.len() < d.common.uncompressed_size());
+
+        let range = poly_bytes_len + 3..poly_bytes_len + 50;
+        let poly_index = advz.range_byte_to_poly(&range).start;
+        let stmt = CompactStatement {
+            payload_subslice: &payload[range.clone()],
+            range: range.clone(),
+            commit: &d.commit,
+            common: &compact,
+            poly_commit: &d.common.poly_commits[poly_index],
+        };
+        let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+        advz.payload_verify_compact(stmt.clone(), &small)
+            .unwrap()
+            .unwrap();
+        advz.payload_verify_compact(stmt.clone(), &large)
+            .unwrap()
+            .unwrap();
+
+        // wrong payload data
+        let mut bad_subslice = payload[range.clone()].to_vec();
+        bad_subslice[0] = bad_subslice[0].wrapping_add(1);
+        let bad_stmt = CompactStatement {
+            payload_subslice: &bad_subslice,
+            ..stmt.clone()
+        };
+        advz.payload_verify_compact(bad_stmt.clone(), &small)
+            .unwrap()
+            .unwrap_err();
+        advz.payload_verify_compact(bad_stmt, &large)
+            .unwrap()
+            .unwrap_err();
+
+        // poly commit for the wrong polynomial
+        let bad_stmt = CompactStatement {
+            poly_commit: &d.common.poly_commits[poly_index - 1],
+            ..stmt.clone()
+        };
+        assert_arg_err(
+            advz.payload_verify_compact(bad_stmt.clone(), &small),
+            "wrong poly commit should be arg error",
+        );
+        assert_arg_err(
+            advz.payload_verify_compact(bad_stmt, &large),
+            "wrong poly commit should be arg error",
+        );
+
+        // compact common inconsistent with commit
+        let mut bad_compact = compact.clone();
+        bad_compact.poly_commit_hashes.swap(0, poly_index);
+        let bad_stmt = CompactStatement {
+            common: &bad_compact,
+            ..stmt
+        };
+        assert_arg_err(
+            advz.payload_verify_compact(bad_stmt, &small),
+            "inconsistent compact common should be arg error",
+        );
+    }
+
+    #[test]
+    fn small_range_proof_validate_structure() {
+        let (advz, payload) = avdz_init();
+        let elem_len = elem_byte_capacity::<<Bls12_381 as Pairing>::ScalarField>();
+
+        for range in [0..1, 1..elem_len + 2, elem_len..2 * elem_len] {
+            let proof: SmallRangeProof<_> = advz.payload_proof(&payload, range).unwrap();
+            proof.validate_structure(&advz).unwrap();
+
+            // mismatched proof counts
+            let mut proof_extra = proof.clone();
+            proof_extra.proofs.push(proof.proofs[0].clone());
+            assert_arg_err(
+                proof_extra.validate_structure(&advz),
+                "extra proof should be arg error",
+            );
+            let mut proof_missing = proof.clone();
+            proof_missing.proofs.pop();
+            assert_arg_err(
+                proof_missing.validate_structure(&advz),
+                "missing proof should be arg error",
+            );
+
+            // bad prefix, suffix lengths
+            let mut proof_prefix = proof.clone();
+            proof_prefix.prefix_bytes.push(0);
+            assert_arg_err(
+                proof_prefix.validate_structure(&advz),
+                "bad prefix should be arg error",
+            );
+            let mut proof_suffix = proof.clone();
+            proof_suffix.suffix_bytes.resize(elem_len, 0);
+            assert_arg_err(
+                proof_suffix.validate_structure(&advz),
+                "bad suffix should be arg error",
+            );
+
+            // empty range
+            let mut proof_empty = proof;
+            proof_empty.chunk_range.end = proof_empty.chunk_range.start;
+            assert_arg_err(
+                proof_empty.validate_structure(&advz),
+                "empty range should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn validate_namespace_table() {
+        type A = Advz<Bls12_381, Sha256>;
+        let len = 100;
+
+        // valid, in any order and with empty ranges
+        for table in [
+            vec![(0, 0..30), (1, 30..31), (2, 31..100)],
+            vec![(2, 31..100), (0, 0..30), (1, 30..31)],
+            vec![(0, 0..0), (1, 0..100), (2, 100..100)],
+        ] {
+            A::validate_namespace_table(table, len).unwrap();
+        }
+        A::validate_namespace_table(Vec::<(u32, Range<usize>)>::new(), 0).unwrap();
+
+        for (table, msg) in [
+            (vec![(0, 0..30), (1, 31..100)], "gap"),
+            (vec![(0, 0..30), (1, 30..90)], "gap at end"),
+            (vec![(0, 1..30), (1, 30..100)], "gap at start"),
+            (vec![(0, 0..31), (1, 30..100)], "overlap"),
+            (vec![(0, 0..100), (1, 50..50)], "empty range inside another"),
+            (vec![(0, 0..30), (1, 30..101)], "out of bounds"),
+            (
+                vec![(0, 0..30), (1, 30..100), (2, Range { start: 60, end: 40 })],
+                "reversed",
+            ),
+        ] {
+            assert_arg_err(A::validate_namespace_table(table, len), msg);
+        }
+        assert_arg_err(
+            A::validate_namespace_table(Vec::<(u32, Range<usize>)>::new(), len),
+            "empty table",
+        );
+    }
+
+    #[test]
+    fn dependent_poly_indices() {
+        let (advz, payload) = avdz_init();
+        let poly_bytes_len = advz.index_poly_to_byte(1);
+
+        for (range, poly_index) in [
+            (0..1, 0),
+            (3..poly_bytes_len, 0),
+            (2 * poly_bytes_len + 5..2 * poly_bytes_len + 40, 2),
+            (
+                payload.len() - 3..payload.len(),
+                payload.len() / poly_bytes_len,
+            ),
+        ] {
+            let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            let large: LargeRangeProof<_> = advz.payload_proof(&payload, range).unwrap();
+            assert_eq!(small.dependent_poly_indices(&advz), vec![poly_index]);
+            assert_eq!(large.dependent_poly_indices(&advz), vec![poly_index]);
+        }
+
+        // multi-polynomial and empty ranges
+        let mut small: SmallRangeProof<_> = advz.payload_proof(&payload, 0..1).unwrap();
+        let mut large: LargeRangeProof<_> = advz.payload_proof(&payload, 0..1).unwrap();
+        small.chunk_range = poly_bytes_len - 1..3 * poly_bytes_len + 1;
+        large.chunk_range = small.chunk_range.clone();
+        assert_eq!(small.dependent_poly_indices(&advz), vec![0, 1, 2, 3]);
+        assert_eq!(large.dependent_poly_indices(&advz), vec![0, 1, 2, 3]);
+        small.chunk_range = 5..5;
+        large.chunk_range = 5..5;
+        assert!(small.dependent_poly_indices(&advz).is_empty());
+        assert!(large.dependent_poly_indices(&advz).is_empty());
+    }
+
+    #[test]
+    fn small_from_large() {
+        let (payload_chunk_size, num_storage_nodes) = (4, 6);
+        let mut rng = jf_utils::test_rng();
+        let srs = init_srs(payload_chunk_size, &mut rng);
+        let advz =
+            Advz::<Bls12_381, Sha256>::new(payload_chunk_size, num_storage_nodes, srs).unwrap();
+        let poly_bytes_len =
+            payload_chunk_size * elem_byte_capacity::<<Bls12_381 as Pairing>::ScalarField>();
+
+        // payload ends mid-polynomial to exercise clamping
+        let payload = init_random_payload(2 * poly_bytes_len + poly_bytes_len / 2, &mut rng);
+        let d = advz.disperse(&payload).unwrap();
+
+        let large_ranges = [
+            1..poly_bytes_len - 1,
+            poly_bytes_len..2 * poly_bytes_len,
+            2 * poly_bytes_len + 3..payload.len(),
+        ];
+        for large_range in large_ranges {
+            let large: LargeRangeProof<_> =
+                advz.payload_proof(&payload, large_range.clone()).unwrap();
+            let payload_subslice = &payload[large_range.clone()];
+
+            let sub_ranges = [
+                large_range.start..large_range.start + 1,
+                large_range.start + 2..large_range.end - 2,
+                large_range.end - 1..large_range.end,
+                large_range.clone(),
+            ];
+            for sub_range in sub_ranges {
+                let small = advz
+                    .small_from_large(&large, payload_subslice, sub_range.clone())
+                    .unwrap();
+                let stmt = Statement {
+                    payload_subslice: &payload[sub_range.clone()],
+                    range: sub_range.clone(),
+                    commit: &d.commit,
+                    common: &d.common,
+                };
+                advz.payload_verify(stmt, &small).unwrap().unwrap();
+
+                // derived proof equals a proof made from the full payload
+                let expected: SmallRangeProof<_> = advz.payload_proof(&payload, sub_range).unwrap();
+                assert_eq!(small, expected);
+            }
+
+            // sub range outside the proof range
+            assert_arg_err(
+                advz.small_from_large(
+                    &large,
+                    payload_subslice,
+                    large_range.start..large_range.end + 1,
+                ),
+                "sub range outside proof range should be arg error",
+            );
+            assert_arg_err(
+                advz.small_from_large(&large, &payload_subslice[1..], large_range.clone()),
+                "wrong subslice length should be arg error",
+            );
+        }
+    }
+
+    #[test]
+    fn field_packing_bits() {
+        let (mut advz, mut payload) = avdz_init();
+        advz.set_field_packing(FieldPacking::Bits).unwrap();
+        let poly_byte_len = advz.index_poly_to_byte(1);
+
+        // copy poly 0 into poly 2 for `prove_ranges_equal`
+        payload.copy_within(0..poly_byte_len, 2 * poly_byte_len);
+        let d = advz.disperse(&payload).unwrap();
+        let stmt = |range: Range<usize>| Statement {
+            payload_subslice: &payload[range.clone()],
+            range,
+            commit: &d.commit,
+            common: &d.common,
+        };
+
+        // ranges inside a single polynomial, some of whose ends split an elem
+        let ranges = [
+            0..1,
+            3..5,
+            31..32,
+            30..70,
+            poly_byte_len - 1..poly_byte_len,
+            poly_byte_len + 5..2 * poly_byte_len - 1,
+            payload.len() - 20..payload.len(),
+        ];
+        for range in ranges {
+            let small: SmallRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            advz.payload_verify(stmt(range.clone()), &small)
+                .unwrap()
+                .unwrap();
+            let large: LargeRangeProof<_> = advz.payload_proof(&payload, range.clone()).unwrap();
+            advz.payload_verify(stmt(range.clone()), &large)
+                .unwrap()
+                .unwrap();
+            let single: SingleOpeningProof<_> =
+                advz.payload_proof(&payload, range.clone()).unwrap();
+            advz.payload_verify(stmt(range.clone()), &single)
+                .unwrap()
+                .unwrap();
+
+            // tampered subslice
+            let mut bad_subslice = payload[range.clone()].to_vec();
+            bad_subslice[range.len() / 2] ^= 1;
+            let bad_stmt = Statement {
+                payload_subslice: &bad_subslice,
+                ..stmt(range.clone())
+            };
+            advz.payload_verify(bad_stmt.clone(), &small)
+                .unwrap()
+                .unwrap_err();
+            advz.payload_verify(bad_stmt.clone(), &large)
+                .unwrap()
+                .unwrap_err();
+            advz.payload_verify(bad_stmt, &single).unwrap().unwrap_err();
+
+            // small proof derived from the large proof
+            let derived = advz
+                .small_from_large(&large, &payload[range.clone()], range.clone())
+                .unwrap();
+            assert_eq!(derived, small);
+        }
+
+        // element proofs
+        let elems: Vec<_> = bytes_to_field_packed::<_, KzgEval<Bls12_381>>(&payload).collect();
+        for elem_index in [0, 1, advz.payload_chunk_size + 2, elems.len() - 1] {
+            let proof = advz.element_proof(&payload, elem_index).unwrap();
+            advz.verify_element(&d.commit, &d.common, elem_index, elems[elem_index], &proof)
+                .unwrap()
+                .unwrap();
+        }
+        assert_arg_err(
+            advz.element_proof(&payload, elems.len()),
+            "out of bounds elem index should be arg error",
+        );
+
+        // equal ranges in distinct polynomials
+        let (range_a, range_b) = (10..40, 2 * poly_byte_len + 10..2 * poly_byte_len + 40);
+        let proof = advz
+            .prove_ranges_equal(&payload, range_a.clone(), range_b.clone())
+            .unwrap();
+        advz.verify_ranges_equal(range_a, range_b, &d.commit, &d.common, &proof)
+            .unwrap()
+            .unwrap();
+
+        // byte-only APIs
+        assert_arg_err(
+            advz.payload_proof_strided(&payload, &[0, 1]),
+            "strided proof under bit packing should be arg error",
+        );
+        let large: LargeRangeProof<_> = advz.payload_proof(&payload, 30..70).unwrap();
+        assert_arg_err(
+            advz.payload_verify_stream(30..70, &d.commit, &d.common, &large),
+            "streaming verification under bit packing should be arg error",
+        );
+        assert_arg_err(
+            advz.payload_verify_spec_compliant(&[], &[]),
+            "spec-compliant verification under bit packing should be arg error",
+        );
     }
 }
@@ -11,7 +11,7 @@
 use core::fmt::Debug;
 
 use super::{VidResult, VidScheme};
-use ark_std::ops::Range;
+use ark_std::{collections::BTreeMap, ops::Range};
 use serde::{de::DeserializeOwned, Serialize};
 
 /// Payload proof functionality for [`VidScheme`].
@@ -89,3 +89,77 @@ where
         }
     }
 }
+
+/// Read-only source of VID commitments and common data keyed by block, such
+/// as an archive in an on-disk database.
+///
+/// Lets a verifier check payload proofs without holding every
+/// [`VidScheme::Common`] in memory.
+pub trait CommitStore<V>
+where
+    V: VidScheme + ?Sized,
+{
+    /// Key under which a block's VID data is stored.
+    type BlockId;
+
+    /// Return the payload commitment for `block_id`, or `None` if the store
+    /// has no such block.
+    fn get_commit(&self, block_id: &Self::BlockId) -> VidResult<Option<V::Commit>>;
+
+    /// Return the common data for `block_id`, or `None` if the store has no
+    /// such block.
+    fn get_common(&self, block_id: &Self::BlockId) -> VidResult<Option<V::Common>>;
+}
+
+/// In-memory [`CommitStore`], mainly for tests.
+pub struct MemCommitStore<V, K>
+where
+    V: VidScheme + ?Sized,
+{
+    blocks: BTreeMap<K, (V::Commit, V::Common)>,
+}
+
+impl<V, K> MemCommitStore<V, K>
+where
+    V: VidScheme + ?Sized,
+    K: Ord,
+{
+    /// Return an empty store.
+    pub fn new() -> Self {
+        Self {
+            blocks: BTreeMap::new(),
+        }
+    }
+
+    /// Store `commit` and `common` for `block_id`, replacing any previous
+    /// data for `block_id`.
+    pub fn insert(&mut self, block_id: K, commit: V::Commit, common: V::Common) {
+        self.blocks.insert(block_id, (commit, common));
+    }
+}
+
+impl<V, K> Default for MemCommitStore<V, K>
+where
+    V: VidScheme + ?Sized,
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, K> CommitStore<V> for MemCommitStore<V, K>
+where
+    V: VidScheme + ?Sized,
+    K: Ord,
+{
+    type BlockId = K;
+
+    fn get_commit(&self, block_id: &K) -> VidResult<Option<V::Commit>> {
+        Ok(self.blocks.get(block_id).map(|(commit, _)| commit.clone()))
+    }
+
+    fn get_common(&self, block_id: &K) -> VidResult<Option<V::Common>> {
+        Ok(self.blocks.get(block_id).map(|(_, common)| common.clone()))
+    }
+}
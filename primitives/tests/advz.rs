@@ -1,6 +1,7 @@
 #![cfg(feature = "test-srs")]
 use ark_bls12_381::Bls12_381;
-use ark_ff::{Field, PrimeField};
+use ark_ec::pairing::Pairing;
+use ark_ff::{FftField, Field, PrimeField};
 use jf_primitives::{
     pcs::{checked_fft_size, prelude::UnivariateKzgPCS, PolynomialCommitmentScheme},
     vid::advz::Advz,
@@ -38,4 +39,20 @@ fn round_trip() {
         &payload_byte_lens,
         &mut rng,
     );
+
+    // shares over a coset of the roots of unity
+    vid::round_trip(
+        |payload_chunk_size, num_storage_nodes| {
+            Advz::<Bls12_381, Sha256>::new_coset(
+                payload_chunk_size,
+                num_storage_nodes,
+                <Bls12_381 as Pairing>::ScalarField::GENERATOR,
+                &srs,
+            )
+            .unwrap()
+        },
+        &vid_sizes,
+        &payload_byte_lens,
+        &mut rng,
+    );
 }